@@ -29,6 +29,9 @@ psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" <<-EOSQL
 
   GRANT CONNECT ON DATABASE $HASH_GRAPH_PG_DATABASE TO $HASH_GRAPH_PG_USER;
 
+  -- The change feed and snapshots read the start of transactions of other roles
+  GRANT pg_read_all_stats TO $HASH_GRAPH_PG_USER;
+
   -- Create Realtime user
   CREATE USER $HASH_GRAPH_REALTIME_PG_USER WITH PASSWORD '$HASH_GRAPH_REALTIME_PG_PASSWORD';
 
@@ -5,7 +5,10 @@ use criterion_macro::criterion;
 use graph::{
     store::{query::Filter, AccountStore, EntityStore},
     subgraph::{
-        edges::{EdgeResolveDepths, GraphResolveDepths, OutgoingEdgeResolveDepth},
+        edges::{
+            EdgeResolveDepths, GraphResolveDepths, OntologyEdgeResolveDepth,
+            OutgoingEdgeResolveDepth,
+        },
         query::StructuralQuery,
         temporal_axes::{
             PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
@@ -217,11 +220,11 @@ fn bench_scaling_read_entity_zero_depths(c: &mut Criterion) {
                     store,
                     entity_metadata_list,
                     GraphResolveDepths {
-                        inherits_from: OutgoingEdgeResolveDepth::default(),
-                        constrains_values_on: OutgoingEdgeResolveDepth::default(),
-                        constrains_properties_on: OutgoingEdgeResolveDepth::default(),
-                        constrains_links_on: OutgoingEdgeResolveDepth::default(),
-                        constrains_link_destinations_on: OutgoingEdgeResolveDepth::default(),
+                        inherits_from: OntologyEdgeResolveDepth::default(),
+                        constrains_values_on: OntologyEdgeResolveDepth::default(),
+                        constrains_properties_on: OntologyEdgeResolveDepth::default(),
+                        constrains_links_on: OntologyEdgeResolveDepth::default(),
+                        constrains_link_destinations_on: OntologyEdgeResolveDepth::default(),
                         is_of_type: OutgoingEdgeResolveDepth::default(),
                         has_left_entity: EdgeResolveDepths::default(),
                         has_right_entity: EdgeResolveDepths::default(),
@@ -268,11 +271,11 @@ fn bench_scaling_read_entity_one_depth(c: &mut Criterion) {
                     store,
                     entity_metadata_list,
                     GraphResolveDepths {
-                        inherits_from: OutgoingEdgeResolveDepth::default(),
-                        constrains_values_on: OutgoingEdgeResolveDepth::default(),
-                        constrains_properties_on: OutgoingEdgeResolveDepth::default(),
-                        constrains_links_on: OutgoingEdgeResolveDepth::default(),
-                        constrains_link_destinations_on: OutgoingEdgeResolveDepth::default(),
+                        inherits_from: OntologyEdgeResolveDepth::default(),
+                        constrains_values_on: OntologyEdgeResolveDepth::default(),
+                        constrains_properties_on: OntologyEdgeResolveDepth::default(),
+                        constrains_links_on: OntologyEdgeResolveDepth::default(),
+                        constrains_link_destinations_on: OntologyEdgeResolveDepth::default(),
                         is_of_type: OutgoingEdgeResolveDepth::default(),
                         has_left_entity: EdgeResolveDepths {
                             incoming: 1,
@@ -35,7 +35,10 @@ mod seed;
 
 use criterion::{BenchmarkId, Criterion, SamplingMode};
 use criterion_macro::criterion;
-use graph::subgraph::edges::{EdgeResolveDepths, GraphResolveDepths, OutgoingEdgeResolveDepth};
+use graph::subgraph::edges::{
+    EdgeResolveDepths, GraphResolveDepths, OntologyEdgeResolveDepth, OntologyResolveDepth,
+    OutgoingEdgeResolveDepth,
+};
 
 use self::seed::setup_and_extract_samples;
 use crate::util::setup;
@@ -77,24 +80,24 @@ fn bench_representative_read_multiple_entities(c: &mut Criterion) {
     let graph_resolve_depths = [
         GraphResolveDepths::default(),
         GraphResolveDepths {
-            inherits_from: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            inherits_from: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_values_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_values_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_properties_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_properties_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_links_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_links_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_link_destinations_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_link_destinations_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
             is_of_type: OutgoingEdgeResolveDepth {
@@ -111,24 +114,24 @@ fn bench_representative_read_multiple_entities(c: &mut Criterion) {
             },
         },
         GraphResolveDepths {
-            inherits_from: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            inherits_from: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_values_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_values_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_properties_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_properties_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_links_on: OutgoingEdgeResolveDepth {
-                outgoing: 1,
+            constrains_links_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(1),
                 incoming: 0,
             },
-            constrains_link_destinations_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_link_destinations_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
             is_of_type: OutgoingEdgeResolveDepth {
@@ -145,24 +148,24 @@ fn bench_representative_read_multiple_entities(c: &mut Criterion) {
             },
         },
         GraphResolveDepths {
-            inherits_from: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            inherits_from: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_values_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_values_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_properties_on: OutgoingEdgeResolveDepth {
-                outgoing: 2,
+            constrains_properties_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(2),
                 incoming: 0,
             },
-            constrains_links_on: OutgoingEdgeResolveDepth {
-                outgoing: 1,
+            constrains_links_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(1),
                 incoming: 0,
             },
-            constrains_link_destinations_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_link_destinations_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
             is_of_type: OutgoingEdgeResolveDepth {
@@ -179,24 +182,24 @@ fn bench_representative_read_multiple_entities(c: &mut Criterion) {
             },
         },
         GraphResolveDepths {
-            inherits_from: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            inherits_from: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
-            constrains_values_on: OutgoingEdgeResolveDepth {
-                outgoing: 2,
+            constrains_values_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(2),
                 incoming: 0,
             },
-            constrains_properties_on: OutgoingEdgeResolveDepth {
-                outgoing: 2,
+            constrains_properties_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(2),
                 incoming: 0,
             },
-            constrains_links_on: OutgoingEdgeResolveDepth {
-                outgoing: 1,
+            constrains_links_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(1),
                 incoming: 0,
             },
-            constrains_link_destinations_on: OutgoingEdgeResolveDepth {
-                outgoing: 0,
+            constrains_link_destinations_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(0),
                 incoming: 0,
             },
             is_of_type: OutgoingEdgeResolveDepth {
@@ -213,24 +216,24 @@ fn bench_representative_read_multiple_entities(c: &mut Criterion) {
             },
         },
         GraphResolveDepths {
-            inherits_from: OutgoingEdgeResolveDepth {
-                outgoing: 1,
+            inherits_from: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(1),
                 incoming: 0,
             },
-            constrains_values_on: OutgoingEdgeResolveDepth {
-                outgoing: 255,
+            constrains_values_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(255),
                 incoming: 0,
             },
-            constrains_properties_on: OutgoingEdgeResolveDepth {
-                outgoing: 255,
+            constrains_properties_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(255),
                 incoming: 0,
             },
-            constrains_links_on: OutgoingEdgeResolveDepth {
-                outgoing: 127,
+            constrains_links_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(127),
                 incoming: 0,
             },
-            constrains_link_destinations_on: OutgoingEdgeResolveDepth {
-                outgoing: 126,
+            constrains_link_destinations_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(126),
                 incoming: 0,
             },
             is_of_type: OutgoingEdgeResolveDepth {
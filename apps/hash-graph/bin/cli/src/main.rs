@@ -22,6 +22,9 @@ async fn main() -> Result<(), GraphError> {
             Ok(())
         }
         Subcommand::Snapshot(args) => subcommand::snapshot(args).await,
+        Subcommand::OntologyGraph(args) => subcommand::ontology_graph(args).await,
+        Subcommand::OntologyImport(args) => subcommand::ontology_import(args).await,
+        Subcommand::OntologyCodegen(args) => subcommand::ontology_codegen(args).await,
         #[cfg(all(hash_graph_test_environment, feature = "test-server"))]
         Subcommand::TestServer(args) => subcommand::test_server(args).await,
     }
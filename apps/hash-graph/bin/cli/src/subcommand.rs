@@ -1,5 +1,8 @@
 mod completions;
 mod migrate;
+mod ontology_codegen;
+mod ontology_graph;
+mod ontology_import;
 mod server;
 mod snapshot;
 #[cfg(all(hash_graph_test_environment, feature = "test-server"))]
@@ -11,6 +14,9 @@ pub use self::test_server::{test_server, TestServerArgs};
 pub use self::{
     completions::{completions, CompletionsArgs},
     migrate::{migrate, MigrateArgs},
+    ontology_codegen::{ontology_codegen, OntologyCodegenArgs},
+    ontology_graph::{ontology_graph, OntologyGraphArgs},
+    ontology_import::{ontology_import, OntologyImportArgs},
     server::{server, ServerArgs},
     snapshot::{snapshot, SnapshotArgs},
     type_fetcher::{type_fetcher, TypeFetcherArgs},
@@ -29,6 +35,12 @@ pub enum Subcommand {
     Completions(CompletionsArgs),
     /// Snapshot API for the database.
     Snapshot(SnapshotArgs),
+    /// Export the dependencies between ontology types as Graphviz DOT or Mermaid.
+    OntologyGraph(OntologyGraphArgs),
+    /// Import the ontology types of a directory of JSON Schema files in a single transaction.
+    OntologyImport(OntologyImportArgs),
+    /// Generate Rust types for entity types, which convert from and to entity properties.
+    OntologyCodegen(OntologyCodegenArgs),
     /// Test server
     #[cfg(all(hash_graph_test_environment, feature = "test-server"))]
     TestServer(TestServerArgs),
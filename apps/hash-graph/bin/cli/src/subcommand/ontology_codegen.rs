@@ -0,0 +1,117 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use error_stack::{Report, Result, ResultExt};
+use graph::{
+    logging::{init_logger, LoggingArgs},
+    ontology::{link_entity_types, OntologyTypeImport, ResolvedEntityType, RustCodeGenerator},
+    store::{DatabaseConnectionInfo, EntityTypeStore, PostgresStorePool, StorePool},
+};
+use tokio::io::{self, AsyncWriteExt};
+use tokio_postgres::NoTls;
+use type_system::url::VersionedUrl;
+
+use crate::error::GraphError;
+
+#[derive(Debug, Parser)]
+#[clap(version, author, about, long_about = None)]
+pub struct OntologyCodegenArgs {
+    /// The entity types to generate Rust types for.
+    ///
+    /// The link entity types they may link with are generated as well.
+    #[clap(required = true, value_name = "VERSIONED_URL")]
+    pub entity_types: Vec<VersionedUrl>,
+
+    /// A directory containing the JSON Schemas of the ontology types to read the entity types
+    /// from instead of the database.
+    ///
+    /// All `.json` files in the directory and its subdirectories are read.
+    #[clap(long)]
+    pub path: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub log_config: LoggingArgs,
+
+    #[clap(flatten)]
+    pub db_info: DatabaseConnectionInfo,
+}
+
+/// Generates the entity type and queues the link entity types it may link with.
+fn generate(
+    generator: &mut RustCodeGenerator,
+    pending: &mut VecDeque<VersionedUrl>,
+    visited: &mut HashSet<VersionedUrl>,
+    entity_type: &ResolvedEntityType,
+) {
+    generator.add_entity_type(entity_type);
+    for link_entity_type_id in link_entity_types(entity_type) {
+        if visited.insert(link_entity_type_id.clone()) {
+            pending.push_back(link_entity_type_id);
+        }
+    }
+}
+
+pub async fn ontology_codegen(args: OntologyCodegenArgs) -> Result<(), GraphError> {
+    let _log_guard = init_logger(&args.log_config);
+
+    let mut generator = RustCodeGenerator::default();
+    let mut visited = args.entity_types.iter().cloned().collect::<HashSet<_>>();
+    let mut pending = VecDeque::from(args.entity_types);
+
+    if let Some(path) = &args.path {
+        let import = OntologyTypeImport::read_directory(path)
+            .await
+            .change_context(GraphError)
+            .attach_printable("Failed to read the ontology types")?;
+
+        while let Some(entity_type_id) = pending.pop_front() {
+            let entity_type = import.resolve_entity_type(&entity_type_id).ok_or_else(|| {
+                Report::new(GraphError).attach_printable(format!(
+                    "The entity type `{entity_type_id}` is not contained in `{}`",
+                    path.display()
+                ))
+            })?;
+            generate(&mut generator, &mut pending, &mut visited, &entity_type);
+        }
+    } else {
+        let pool = PostgresStorePool::new(&args.db_info, NoTls)
+            .await
+            .change_context(GraphError)
+            .map_err(|report| {
+                tracing::error!(error = ?report, "Failed to connect to database");
+                report
+            })?;
+
+        let store = pool
+            .acquire()
+            .await
+            .change_context(GraphError)
+            .map_err(|report| {
+                tracing::error!(error = ?report, "Failed to acquire database connection");
+                report
+            })?;
+
+        while let Some(entity_type_id) = pending.pop_front() {
+            let entity_type = store
+                .get_resolved_entity_type(&entity_type_id)
+                .await
+                .change_context(GraphError)
+                .attach_printable_lazy(|| {
+                    format!("Failed to resolve the entity type `{entity_type_id}`")
+                })?;
+            generate(&mut generator, &mut pending, &mut visited, &entity_type);
+        }
+    }
+
+    let mut output = io::stdout();
+    output
+        .write_all(generator.finish().as_bytes())
+        .await
+        .change_context(GraphError)?;
+    output.flush().await.change_context(GraphError)?;
+
+    Ok(())
+}
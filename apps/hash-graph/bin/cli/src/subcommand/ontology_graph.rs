@@ -0,0 +1,65 @@
+use clap::Parser;
+use error_stack::{Result, ResultExt};
+use graph::{
+    logging::{init_logger, LoggingArgs},
+    ontology::{OntologyDependencyGraph, OntologyGraphFormat},
+    store::{DatabaseConnectionInfo, PostgresStorePool, StorePool},
+};
+use tokio::io::{self, AsyncWriteExt};
+use tokio_postgres::NoTls;
+use type_system::url::VersionedUrl;
+
+use crate::error::GraphError;
+
+#[derive(Debug, Parser)]
+#[clap(version, author, about, long_about = None)]
+pub struct OntologyGraphArgs {
+    /// The data types, property types, or entity types to start the graph from.
+    #[clap(required = true, value_name = "VERSIONED_URL")]
+    pub roots: Vec<VersionedUrl>,
+
+    /// The format to render the graph in.
+    #[clap(long, value_enum, default_value_t = OntologyGraphFormat::Dot)]
+    pub format: OntologyGraphFormat,
+
+    #[clap(flatten)]
+    pub log_config: LoggingArgs,
+
+    #[clap(flatten)]
+    pub db_info: DatabaseConnectionInfo,
+}
+
+pub async fn ontology_graph(args: OntologyGraphArgs) -> Result<(), GraphError> {
+    let _log_guard = init_logger(&args.log_config);
+
+    let pool = PostgresStorePool::new(&args.db_info, NoTls)
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to connect to database");
+            report
+        })?;
+
+    let store = pool
+        .acquire()
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to acquire database connection");
+            report
+        })?;
+
+    let graph = OntologyDependencyGraph::read(&store, &args.roots)
+        .await
+        .change_context(GraphError)
+        .attach_printable("Failed to read the ontology dependency graph")?;
+
+    let mut output = io::stdout();
+    output
+        .write_all(graph.render(args.format).as_bytes())
+        .await
+        .change_context(GraphError)?;
+    output.flush().await.change_context(GraphError)?;
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use error_stack::{Result, ResultExt};
+use graph::{
+    logging::{init_logger, LoggingArgs},
+    ontology::{domain_validator::DomainValidator, OntologyTypeImport},
+    store::{DatabaseConnectionInfo, PostgresStorePool, StorePool},
+};
+use graph_types::{
+    account::AccountId,
+    provenance::{OwnedById, RecordCreatedById},
+};
+use regex::Regex;
+use tokio::io::{self, AsyncWriteExt};
+use tokio_postgres::NoTls;
+use uuid::Uuid;
+
+use crate::error::GraphError;
+
+#[derive(Debug, Parser)]
+#[clap(version, author, about, long_about = None)]
+pub struct OntologyImportArgs {
+    /// The directory containing the JSON Schemas of the ontology types.
+    ///
+    /// All `.json` files in the directory and its subdirectories are imported.
+    pub path: PathBuf,
+
+    /// The account owning the created ontology types.
+    #[clap(long, value_name = "ACCOUNT_ID")]
+    pub owned_by_id: Uuid,
+
+    /// The account creating the ontology types.
+    #[clap(long, value_name = "ACCOUNT_ID")]
+    pub actor_id: Uuid,
+
+    /// A regex which *new* Type System URLs are checked against. Trying to create new Types with
+    /// a domain that doesn't satisfy the pattern will error.
+    ///
+    /// See the `server` subcommand for the requirements of the pattern.
+    #[clap(
+        long,
+        default_value_t = Regex::new(r"http://localhost:3000/@(?P<shortname>[\w-]+)/types/(?P<kind>(?:data-type)|(?:property-type)|(?:entity-type)|(?:link-type))/[\w\-_%]+/").unwrap(),
+        env = "HASH_GRAPH_ALLOWED_URL_DOMAIN_PATTERN",
+    )]
+    pub allowed_url_domain: Regex,
+
+    #[clap(flatten)]
+    pub log_config: LoggingArgs,
+
+    #[clap(flatten)]
+    pub db_info: DatabaseConnectionInfo,
+}
+
+pub async fn ontology_import(args: OntologyImportArgs) -> Result<(), GraphError> {
+    let _log_guard = init_logger(&args.log_config);
+
+    let import = OntologyTypeImport::read_directory(&args.path)
+        .await
+        .change_context(GraphError)
+        .attach_printable("Failed to read the ontology types")?;
+    import
+        .validate(&DomainValidator::new(args.allowed_url_domain))
+        .change_context(GraphError)
+        .attach_printable("Ontology type URLs failed to validate")?;
+
+    let pool = PostgresStorePool::new(&args.db_info, NoTls)
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to connect to database");
+            report
+        })?;
+
+    let mut store = pool
+        .acquire()
+        .await
+        .change_context(GraphError)
+        .map_err(|report| {
+            tracing::error!(error = ?report, "Failed to acquire database connection");
+            report
+        })?;
+
+    let summary = store
+        .import_ontology_types(
+            import,
+            OwnedById::new(AccountId::new(args.owned_by_id)),
+            RecordCreatedById::new(AccountId::new(args.actor_id)),
+        )
+        .await
+        .change_context(GraphError)
+        .attach_printable("Failed to import the ontology types")?;
+
+    let mut output = io::BufWriter::new(io::stdout());
+    for (status, ids) in [
+        ("created", &summary.created),
+        ("updated", &summary.updated),
+        ("unchanged", &summary.unchanged),
+    ] {
+        for id in ids {
+            output
+                .write_all(format!("{status} {id}\n").as_bytes())
+                .await
+                .change_context(GraphError)?;
+        }
+    }
+    output.flush().await.change_context(GraphError)?;
+
+    tracing::info!("Ontology imported successfully: {summary}");
+    Ok(())
+}
@@ -11,7 +11,7 @@ use error_stack::{Report, Result, ResultExt};
 use graph::{
     api::rest::{rest_api_router, OpenApiDocumentation, RestRouterDependencies},
     logging::{init_logger, LoggingArgs},
-    ontology::domain_validator::DomainValidator,
+    ontology::{compatibility::OntologyUpdatePolicy, domain_validator::DomainValidator},
     store::{
         error::VersionedUrlAlreadyExists, AccountStore, DataTypeStore, DatabaseConnectionInfo,
         EntityTypeStore, FetchingPool, PostgresStorePool, StorePool,
@@ -117,6 +117,15 @@ pub struct ServerArgs {
     /// Starts a server without connecting to the type fetcher
     #[clap(long, default_value_t = false, conflicts_with_all = ["type_fetcher_host", "type_fetcher_port"])]
     pub offline: bool,
+
+    /// Rejects updates of ontology types, which contain breaking changes or would invalidate
+    /// existing entities, unless the update is explicitly forced.
+    #[clap(
+        long,
+        default_value_t = false,
+        env = "HASH_GRAPH_REJECT_BREAKING_ONTOLOGY_UPDATES"
+    )]
+    pub reject_breaking_ontology_updates: bool,
 }
 
 // TODO: Consider making this a refinery migration
@@ -379,6 +388,11 @@ pub async fn server(args: ServerArgs) -> Result<(), GraphError> {
     let router = rest_api_router(RestRouterDependencies {
         store: Arc::new(pool),
         domain_regex: DomainValidator::new(args.allowed_url_domain),
+        update_policy: if args.reject_breaking_ontology_updates {
+            OntologyUpdatePolicy::RejectBreaking
+        } else {
+            OntologyUpdatePolicy::AllowBreaking
+        },
     });
 
     tracing::info!("Listening on {}", args.api_address);
@@ -1,27 +1,260 @@
+use std::{
+    io::Write,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
 use clap::Parser;
-use error_stack::{Result, ResultExt};
-use futures::{SinkExt, StreamExt, TryStreamExt};
+use error_stack::{Report, Result, ResultExt};
+use futures::{SinkExt, Stream, StreamExt, TryStreamExt};
 use graph::{
     logging::{init_logger, LoggingArgs},
-    snapshot::{codec, SnapshotEntry, SnapshotStore},
-    store::{DatabaseConnectionInfo, PostgresStorePool, StorePool},
+    snapshot::{
+        diff_snapshots, SnapshotConflictBehavior, SnapshotDiffSettings, SnapshotDiffSummary,
+        SnapshotDumpFilter, SnapshotDumpSettings, SnapshotEntry, SnapshotFormat,
+        SnapshotRestoreError, SnapshotRestoreMode, SnapshotRestoreProgress,
+        SnapshotRestoreSettings, SnapshotStore, SnapshotVerifier, SNAPSHOT_VERSION,
+    },
+    store::{DatabaseConnectionInfo, PostgresStorePool},
+};
+use temporal_versioning::{DecisionTime, Timestamp, TransactionTime};
+use tokio::{
+    fs,
+    io::{self, AsyncWriteExt},
 };
-use tokio::io;
 use tokio_postgres::NoTls;
-use tokio_util::codec::{FramedRead, FramedWrite};
+use type_system::url::VersionedUrl;
+use uuid::Uuid;
 
 use crate::error::GraphError;
 
 #[derive(Debug, Parser)]
-pub struct SnapshotDumpArgs;
+pub struct SnapshotDumpArgs {
+    /// The format to write the snapshot in.
+    #[clap(long, value_enum, default_value_t = SnapshotFormat::JsonLines)]
+    pub format: SnapshotFormat,
+
+    /// Only dump the records as they were stored at the given transaction time.
+    ///
+    /// The timestamp is expected in ISO 8601 format, e.g. `2023-09-01T12:00:00Z`.
+    #[clap(long)]
+    pub transaction_time: Option<Timestamp<TransactionTime>>,
+
+    /// Only dump the records which were valid at the given decision time.
+    ///
+    /// The timestamp is expected in ISO 8601 format, e.g. `2023-09-01T12:00:00Z`.
+    #[clap(long)]
+    pub decision_time: Option<Timestamp<DecisionTime>>,
+
+    /// Only dump the changes since a previous snapshot to create an incremental snapshot.
+    ///
+    /// Either the path to the previous snapshot or a transaction time in ISO 8601 format is
+    /// expected. The incremental snapshot contains all records created or closed at or after that
+    /// transaction time.
+    #[clap(long, conflicts_with_all = ["transaction_time", "decision_time"])]
+    pub since: Option<SnapshotSince>,
+
+    /// Only dump the entities and the owned ontology types owned by one of the given accounts.
+    #[clap(long = "owned-by-id", value_name = "ACCOUNT_ID")]
+    pub owned_by_ids: Vec<Uuid>,
+
+    /// Only dump the given entity types and the entities of one of these types.
+    #[clap(long = "entity-type", value_name = "VERSIONED_URL")]
+    pub entity_types: Vec<VersionedUrl>,
+
+    /// Also dump the records the selected records depend on.
+    ///
+    /// This includes the ontology types the selected types and entities refer to and the entities
+    /// the selected links point to, so the snapshot can be restored into an empty database.
+    #[clap(long, default_value_t = false)]
+    pub include_dependencies: bool,
+
+    /// The number of database connections used to read records concurrently.
+    ///
+    /// Defaults to the number of available cores, but at most 8.
+    #[clap(long)]
+    pub parallelism: Option<NonZeroUsize>,
+}
+
+/// The point in time an incremental snapshot is dumped from.
+#[derive(Debug, Clone)]
+pub enum SnapshotSince {
+    /// The transaction time since which the changes are dumped.
+    TransactionTime(Timestamp<TransactionTime>),
+    /// The path to a previous snapshot, which changes are dumped on top of.
+    Snapshot(PathBuf),
+}
+
+impl FromStr for SnapshotSince {
+    type Err = <PathBuf as FromStr>::Err;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if let Ok(transaction_time) = s.parse() {
+            Ok(Self::TransactionTime(transaction_time))
+        } else {
+            s.parse().map(Self::Snapshot)
+        }
+    }
+}
+
+impl SnapshotSince {
+    /// Returns the transaction time since which the changes are dumped.
+    ///
+    /// For a previous snapshot, this is the transaction time up to which the snapshot contains
+    /// all changes, which is read from its metadata.
+    async fn transaction_time(&self) -> Result<Timestamp<TransactionTime>, GraphError> {
+        let path = match self {
+            Self::TransactionTime(transaction_time) => return Ok(*transaction_time),
+            Self::Snapshot(path) => path,
+        };
+
+        let mut snapshot =
+            io::BufReader::new(fs::File::open(path).await.change_context(GraphError)?);
+        let format = SnapshotFormat::detect(&mut snapshot)
+            .await
+            .change_context(GraphError)?;
+
+        if let Some(SnapshotEntry::Snapshot(metadata)) = format
+            .decode(snapshot)
+            .next()
+            .await
+            .transpose()
+            .change_context(GraphError)?
+        {
+            metadata.custom.transaction_time.ok_or_else(|| {
+                Report::new(GraphError).attach_printable(
+                    "the previous snapshot does not record the transaction time it was dumped at",
+                )
+            })
+        } else {
+            Err(Report::new(GraphError)
+                .attach_printable("the previous snapshot does not start with its metadata"))
+        }
+    }
+}
 
 #[derive(Debug, Parser)]
-pub struct SnapshotRestoreArgs;
+pub struct SnapshotRestoreArgs {
+    /// The format to read the snapshot in.
+    ///
+    /// If not specified, the format is detected from the start of the snapshot.
+    #[clap(long, value_enum)]
+    pub format: Option<SnapshotFormat>,
+
+    /// Restore an incremental snapshot on top of the snapshot it was dumped since.
+    #[clap(long, default_value_t = false, conflicts_with = "merge")]
+    pub incremental: bool,
+
+    /// Merge the snapshot into a database, which already contains records.
+    ///
+    /// Records already present in the database are skipped.
+    #[clap(long, default_value_t = false)]
+    pub merge: bool,
+
+    /// How to handle records, which conflict with records already present in the database.
+    ///
+    /// A record conflicts if the database contains a different ontology type schema for the same
+    /// versioned URL, a different entity edition with the same ID, or an overlapping edition of
+    /// the same entity.
+    #[clap(long, value_enum, default_value_t = SnapshotConflictBehavior::Fail, requires = "merge")]
+    pub on_conflict: SnapshotConflictBehavior,
+
+    /// Commit the restore in checkpoints of the given number of entries.
+    ///
+    /// The records are written into staging tables and are only moved into the database after
+    /// the whole snapshot was read. An interrupted restore can be continued from the last
+    /// checkpoint with `--resume`.
+    #[clap(long)]
+    pub checkpoint_interval: Option<NonZeroUsize>,
+
+    /// Continue an interrupted restore from its last checkpoint.
+    ///
+    /// The same snapshot has to be passed again. If `--checkpoint-interval` is not specified,
+    /// checkpoints are committed every 100000 entries.
+    #[clap(long, default_value_t = false)]
+    pub resume: bool,
+
+    /// Discard the checkpoint of an interrupted restore and restore from the start.
+    ///
+    /// Without `--resume` or this flag, the restore fails if an interrupted restore exists.
+    #[clap(long, default_value_t = false, conflicts_with = "resume")]
+    pub discard_checkpoint: bool,
+
+    /// Print the number of records read so far to stderr while restoring.
+    #[clap(long, default_value_t = false)]
+    pub progress: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct SnapshotVerifyArgs {
+    /// The format to read the snapshot in.
+    ///
+    /// If not specified, the format is detected from the start of the snapshot.
+    #[clap(long, value_enum)]
+    pub format: Option<SnapshotFormat>,
+}
+
+#[derive(Debug, Parser)]
+pub struct SnapshotUpgradeArgs {
+    /// The format to read the snapshot in.
+    ///
+    /// If not specified, the format is detected from the start of the snapshot.
+    #[clap(long, value_enum)]
+    pub format: Option<SnapshotFormat>,
+
+    /// The format to write the upgraded snapshot in.
+    ///
+    /// If not specified, the snapshot is written in the format it was read in.
+    #[clap(long, value_enum)]
+    pub output_format: Option<SnapshotFormat>,
+}
+
+#[derive(Debug, Parser)]
+pub struct SnapshotDiffArgs {
+    /// The path to the old snapshot.
+    pub old: PathBuf,
+
+    /// The path to the new snapshot.
+    pub new: PathBuf,
+
+    /// Only print the number of records added, removed, or changed for each record kind.
+    #[clap(long, default_value_t = false)]
+    pub summary: bool,
+
+    /// The format to print the differences in.
+    #[clap(long, value_enum, default_value_t = SnapshotDiffOutput::Text)]
+    pub output: SnapshotDiffOutput,
+
+    /// The number of records of a snapshot, which are sorted in memory.
+    ///
+    /// Larger snapshots are sorted in multiple runs, which are written to the temporary
+    /// directory.
+    #[clap(long, default_value_t = SnapshotDiffSettings::default().run_size)]
+    pub run_size: usize,
+}
+
+/// The format the differences between two snapshots are printed in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum SnapshotDiffOutput {
+    /// One line per record, followed by the changed values of the record.
+    Text,
+    /// One JSON object per record, or a single JSON object for the summary.
+    Json,
+}
 
 #[derive(Debug, Parser)]
 pub enum SnapshotCommand {
     Dump(SnapshotDumpArgs),
     Restore(SnapshotRestoreArgs),
+    /// Checks a snapshot for problems, which would prevent restoring it, without a database.
+    Verify(SnapshotVerifyArgs),
+    /// Rewrites a snapshot in the latest snapshot version, without a database.
+    Upgrade(SnapshotUpgradeArgs),
+    /// Compares two snapshots and prints the records added, removed, or changed, without a
+    /// database.
+    Diff(SnapshotDiffArgs),
 }
 
 #[derive(Debug, Parser)]
@@ -41,6 +274,13 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), GraphError> {
     let _log_guard = init_logger(&args.log_config);
     SnapshotEntry::install_error_stack_hook();
 
+    match &args.command {
+        SnapshotCommand::Verify(verify_args) => return verify(verify_args).await,
+        SnapshotCommand::Upgrade(upgrade_args) => return upgrade(upgrade_args).await,
+        SnapshotCommand::Diff(diff_args) => return diff(diff_args).await,
+        SnapshotCommand::Dump(_) | SnapshotCommand::Restore(_) => {}
+    }
+
     let pool = PostgresStorePool::new(&args.db_info, NoTls)
         .await
         .change_context(GraphError)
@@ -49,53 +289,282 @@ pub async fn snapshot(args: SnapshotArgs) -> Result<(), GraphError> {
             report
         })?;
 
-    let mut store = SnapshotStore::new(pool.acquire().await.change_context(GraphError).map_err(
-        |report| {
-            tracing::error!(error = ?report, "Failed to acquire database connection");
-            report
-        },
-    )?);
+    let store = SnapshotStore::new(&pool);
 
     match args.command {
-        SnapshotCommand::Dump(_) => {
+        SnapshotCommand::Dump(dump_args) => {
+            let since = if let Some(since) = &dump_args.since {
+                Some(
+                    since
+                        .transaction_time()
+                        .await
+                        .attach_printable("Failed to read the previous snapshot")?,
+                )
+            } else {
+                None
+            };
+
+            let mut filter = SnapshotDumpFilter {
+                include_dependencies: dump_args.include_dependencies,
+                ..SnapshotDumpFilter::default()
+            };
+            if !dump_args.owned_by_ids.is_empty() {
+                filter = filter.owned_by(&dump_args.owned_by_ids);
+            }
+            if !dump_args.entity_types.is_empty() {
+                filter = filter.of_entity_types(&dump_args.entity_types);
+            }
+
             store
-                .dump_snapshot()
+                .dump_snapshot(
+                    SnapshotDumpSettings {
+                        transaction_time: dump_args.transaction_time,
+                        decision_time: dump_args.decision_time,
+                        since,
+                        parallelism: dump_args.parallelism,
+                    },
+                    filter,
+                )
                 .map_err(|report| {
                     report
                         .change_context(GraphError)
                         .attach_printable("Failed to produce snapshot dump")
                 })
                 .forward(
-                    FramedWrite::new(
-                        io::BufWriter::new(io::stdout()),
-                        codec::JsonLinesEncoder::default(),
-                    )
-                    .sink_map_err(|report| {
-                        report
-                            .change_context(GraphError)
-                            .attach_printable("Failed to write snapshot dump")
-                    }),
+                    dump_args
+                        .format
+                        .encode(io::BufWriter::new(io::stdout()))
+                        .sink_map_err(|report| {
+                            report
+                                .change_context(GraphError)
+                                .attach_printable("Failed to write snapshot dump")
+                        }),
                 )
                 .await?;
 
             tracing::info!("Snapshot dumped successfully");
         }
-        SnapshotCommand::Restore(_) => {
-            store
-                .restore_snapshot(
-                    FramedRead::new(
-                        io::BufReader::new(io::stdin()),
-                        codec::JsonLinesDecoder::default(),
-                    ),
-                    10_000,
-                )
+        SnapshotCommand::Restore(restore_args) => {
+            let mut snapshot = io::BufReader::new(io::stdin());
+            let format = match restore_args.format {
+                Some(format) => format,
+                None => SnapshotFormat::detect(&mut snapshot)
+                    .await
+                    .change_context(GraphError)
+                    .attach_printable("Failed to detect the snapshot format")?,
+            };
+
+            let mode = if restore_args.incremental {
+                SnapshotRestoreMode::Incremental
+            } else if restore_args.merge {
+                SnapshotRestoreMode::Merge(restore_args.on_conflict)
+            } else {
+                SnapshotRestoreMode::Empty
+            };
+
+            let show_progress = restore_args.progress;
+            let mut progress = SnapshotRestoreProgress::default();
+            let entries = format.decode(snapshot).inspect_ok(move |entry| {
+                progress.add(entry);
+                if show_progress && progress.is_due(Duration::from_millis(250)) {
+                    // The progress is only informational, so failing to print it is not an error
+                    let _ = write!(std::io::stderr(), "\r{progress}");
+                }
+            });
+
+            let report = store
+                .restore_snapshot(entries, SnapshotRestoreSettings {
+                    chunk_size: 10_000,
+                    mode,
+                    checkpoint_interval: restore_args
+                        .checkpoint_interval
+                        .or((restore_args.resume || restore_args.discard_checkpoint)
+                            .then_some(100_000)
+                            .and_then(NonZeroUsize::new)),
+                    resume: restore_args.resume,
+                    discard_checkpoint: restore_args.discard_checkpoint,
+                })
                 .await
+                .map_err(|report| {
+                    if matches!(
+                        report.current_context(),
+                        SnapshotRestoreError::InterruptedRestore
+                    ) {
+                        report.attach_printable(
+                            "pass `--resume` to continue the interrupted restore or \
+                             `--discard-checkpoint` to start over",
+                        )
+                    } else {
+                        report
+                    }
+                })
+                .change_context(GraphError)
+                .attach_printable("Failed to restore snapshot");
+            if show_progress {
+                let _ = writeln!(std::io::stderr());
+            }
+            let report = report?;
+
+            tracing::info!(
+                accounts = %report.accounts,
+                ontology_types = %report.ontology_types,
+                entity_editions = %report.entity_editions,
+                ownership_transfers = %report.ownership_transfers,
+                "Snapshot restored successfully"
+            );
+        }
+        SnapshotCommand::Verify(_) | SnapshotCommand::Upgrade(_) | SnapshotCommand::Diff(_) => {
+            unreachable!("the command does not require a database")
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a snapshot from stdin and writes every problem found in it to stdout.
+async fn verify(args: &SnapshotVerifyArgs) -> Result<(), GraphError> {
+    let mut snapshot = io::BufReader::new(io::stdin());
+    let format = match args.format {
+        Some(format) => format,
+        None => SnapshotFormat::detect(&mut snapshot)
+            .await
+            .change_context(GraphError)
+            .attach_printable("Failed to detect the snapshot format")?,
+    };
+
+    let mut verifier = SnapshotVerifier::default();
+    let mut entries = format.decode(snapshot);
+    while let Some(entry) = entries
+        .try_next()
+        .await
+        .change_context(GraphError)
+        .attach_printable("Failed to read snapshot")?
+    {
+        verifier.verify(entry);
+    }
+
+    let problems = verifier.finish();
+    let mut report = io::BufWriter::new(io::stdout());
+    for problem in &problems {
+        report
+            .write_all(format!("{problem}\n").as_bytes())
+            .await
+            .change_context(GraphError)?;
+    }
+    report.flush().await.change_context(GraphError)?;
+
+    if problems.is_empty() {
+        tracing::info!("Snapshot verified successfully");
+        Ok(())
+    } else {
+        Err(Report::new(GraphError)
+            .attach_printable(format!("The snapshot contains {} problems", problems.len())))
+    }
+}
+
+/// Reads a snapshot from stdin and writes it in the latest snapshot version to stdout.
+async fn upgrade(args: &SnapshotUpgradeArgs) -> Result<(), GraphError> {
+    let mut snapshot = io::BufReader::new(io::stdin());
+    let format = match args.format {
+        Some(format) => format,
+        None => SnapshotFormat::detect(&mut snapshot)
+            .await
+            .change_context(GraphError)
+            .attach_printable("Failed to detect the snapshot format")?,
+    };
+
+    format
+        .decode(snapshot)
+        .map_err(|report| {
+            report
                 .change_context(GraphError)
-                .attach_printable("Failed to restore snapshot")?;
+                .attach_printable("Failed to read snapshot")
+        })
+        .forward(
+            args.output_format
+                .unwrap_or(format)
+                .encode(io::BufWriter::new(io::stdout()))
+                .sink_map_err(|report| {
+                    report
+                        .change_context(GraphError)
+                        .attach_printable("Failed to write upgraded snapshot")
+                }),
+        )
+        .await?;
+
+    tracing::info!("Snapshot upgraded to version {SNAPSHOT_VERSION}");
+    Ok(())
+}
 
-            tracing::info!("Snapshot restored successfully");
+/// Opens the snapshot at `path` and detects its format.
+async fn read_snapshot(
+    path: &Path,
+) -> Result<impl Stream<Item = Result<SnapshotEntry, io::Error>> + Send, GraphError> {
+    let mut snapshot = io::BufReader::new(
+        fs::File::open(path)
+            .await
+            .change_context(GraphError)
+            .attach_printable_lazy(|| path.display().to_string())?,
+    );
+    let format = SnapshotFormat::detect(&mut snapshot)
+        .await
+        .change_context(GraphError)
+        .attach_printable("Failed to detect the snapshot format")?;
+    Ok(format.decode(snapshot))
+}
+
+/// Compares two snapshots and writes the differences to stdout.
+async fn diff(args: &SnapshotDiffArgs) -> Result<(), GraphError> {
+    let mut changes = diff_snapshots(
+        read_snapshot(&args.old).await?,
+        read_snapshot(&args.new).await?,
+        &SnapshotDiffSettings {
+            run_size: args.run_size,
+            ..SnapshotDiffSettings::default()
+        },
+    )
+    .await
+    .change_context(GraphError)
+    .attach_printable("Failed to compare snapshots")?;
+
+    let mut output = io::BufWriter::new(io::stdout());
+    let mut summary = SnapshotDiffSummary::default();
+    while let Some(change) = changes
+        .try_next()
+        .await
+        .change_context(GraphError)
+        .attach_printable("Failed to compare snapshots")?
+    {
+        summary.add(&change);
+        if args.summary {
+            continue;
         }
+
+        let line = match args.output {
+            SnapshotDiffOutput::Text => change.to_string(),
+            SnapshotDiffOutput::Json => {
+                serde_json::to_string(&change).change_context(GraphError)?
+            }
+        };
+        output
+            .write_all(format!("{line}\n").as_bytes())
+            .await
+            .change_context(GraphError)?;
+    }
+
+    if args.summary {
+        let summary = match args.output {
+            SnapshotDiffOutput::Text => summary.to_string(),
+            SnapshotDiffOutput::Json => {
+                serde_json::to_string(&summary).change_context(GraphError)?
+            }
+        };
+        output
+            .write_all(format!("{summary}\n").as_bytes())
+            .await
+            .change_context(GraphError)?;
     }
+    output.flush().await.change_context(GraphError)?;
 
     Ok(())
 }
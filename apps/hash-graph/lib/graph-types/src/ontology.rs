@@ -27,7 +27,7 @@ pub use self::{
     },
     property_type::PropertyTypeWithMetadata,
 };
-use crate::provenance::{OwnedById, ProvenanceMetadata};
+use crate::provenance::{DeprecatedById, OwnedById, ProvenanceMetadata};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -110,6 +110,23 @@ pub struct OntologyTemporalMetadata {
     pub transaction_time: LeftClosedTemporalInterval<TransactionTime>,
 }
 
+/// Marks an ontology type as deprecated.
+///
+/// Deprecated ontology types can still be read and used, but should be replaced by their
+/// `successor` if one is specified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OntologyTypeDeprecation {
+    pub deprecated_by_id: DeprecatedById,
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    #[serde(with = "temporal_versioning::serde::time")]
+    pub deprecated_at: OffsetDateTime,
+    #[cfg_attr(feature = "utoipa", schema(value_type = String))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor: Option<VersionedUrl>,
+}
+
 /// A [`CustomOntologyMetadata`] that has not yet been fully resolved.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PartialCustomOntologyMetadata {
@@ -138,6 +155,8 @@ impl PartialCustomOntologyMetadata {
 pub struct OntologyElementMetadata {
     pub record_id: OntologyTypeRecordId,
     pub custom: CustomOntologyMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<OntologyTypeDeprecation>,
 }
 
 // TODO: Restrict mutable access when `#[feature(mut_restriction)]` is available.
@@ -171,6 +190,22 @@ pub enum CustomOntologyMetadata {
     },
 }
 
+impl CustomOntologyMetadata {
+    #[must_use]
+    pub const fn temporal_versioning(&self) -> &OntologyTemporalMetadata {
+        match self {
+            Self::Owned {
+                temporal_versioning,
+                ..
+            }
+            | Self::External {
+                temporal_versioning,
+                ..
+            } => temporal_versioning,
+        }
+    }
+}
+
 /// An [`OntologyElementMetadata`] that has not yet been fully resolved.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PartialOntologyElementMetadata {
@@ -204,6 +239,7 @@ impl OntologyElementMetadata {
                     fetched_at,
                 },
             },
+            deprecation: None,
         }
     }
 }
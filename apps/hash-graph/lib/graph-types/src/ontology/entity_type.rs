@@ -15,7 +15,7 @@ use utoipa::{
 
 use crate::ontology::{
     CustomOntologyMetadata, OntologyElementMetadata, OntologyTemporalMetadata, OntologyType,
-    OntologyTypeRecordId, OntologyTypeReference, OntologyTypeWithMetadata,
+    OntologyTypeDeprecation, OntologyTypeRecordId, OntologyTypeReference, OntologyTypeWithMetadata,
     PartialCustomOntologyMetadata,
 };
 #[cfg(feature = "utoipa")]
@@ -105,6 +105,8 @@ pub struct PartialEntityTypeMetadata {
 pub struct EntityTypeMetadata {
     pub record_id: OntologyTypeRecordId,
     pub custom: CustomEntityTypeMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<OntologyTypeDeprecation>,
 }
 
 impl EntityTypeMetadata {
@@ -147,6 +149,7 @@ impl EntityTypeMetadata {
                     },
                 },
             },
+            deprecation: None,
         }
     }
 }
@@ -156,6 +159,7 @@ impl From<EntityTypeMetadata> for OntologyElementMetadata {
         Self {
             record_id: value.record_id,
             custom: value.custom.common,
+            deprecation: value.deprecation,
         }
     }
 }
@@ -53,6 +53,8 @@ macro_rules! define_provenance_id {
 define_provenance_id!(OwnedById);
 define_provenance_id!(RecordCreatedById);
 define_provenance_id!(RecordArchivedById);
+define_provenance_id!(DeprecatedById);
+define_provenance_id!(TransferredById);
 
 // TODO: Restrict field mutation when `#[feature(mut_restriction)]` is available.
 //   see https://github.com/rust-lang/rust/issues/105077
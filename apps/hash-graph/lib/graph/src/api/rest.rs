@@ -13,9 +13,11 @@ mod status;
 mod utoipa_typedef;
 
 mod account;
+mod change_feed;
 mod data_type;
 mod entity;
 mod entity_type;
+mod ontology_graph;
 mod property_type;
 
 use std::{fs, io, sync::Arc};
@@ -23,7 +25,7 @@ use std::{fs, io, sync::Arc};
 use async_trait::async_trait;
 use axum::{
     extract::Path,
-    http::StatusCode,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     routing::get,
     Extension, Json, Router,
@@ -32,15 +34,18 @@ use error_stack::{Report, ResultExt};
 use graph_types::{
     ontology::{
         CustomEntityTypeMetadata, CustomOntologyMetadata, EntityTypeMetadata,
-        OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeRecordId,
-        OntologyTypeReference, OntologyTypeVersion,
+        OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeDeprecation,
+        OntologyTypeRecordId, OntologyTypeReference, OntologyTypeVersion,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use include_dir::{include_dir, Dir};
 use temporal_versioning::{
-    ClosedTemporalBound, DecisionTime, LeftClosedTemporalInterval, LimitedTemporalBound,
-    OpenTemporalBound, RightBoundedTemporalInterval, TemporalBound, Timestamp, TransactionTime,
+    BitemporalRectangle, BitemporalRegion, ClosedTemporalBound, DecisionTime,
+    LeftClosedTemporalInterval, LimitedTemporalBound, OpenTemporalBound,
+    RightBoundedTemporalInterval, TemporalBound, TemporalInterval, Timestamp, TransactionTime,
 };
 use utoipa::{
     openapi::{
@@ -63,12 +68,16 @@ use crate::{
             MaybeListOfEntityTypeMetadata, MaybeListOfOntologyElementMetadata,
         },
     },
-    ontology::{domain_validator::DomainValidator, Selector},
+    ontology::{
+        compatibility::OntologyUpdatePolicy, domain_validator::DomainValidator, EntityTypeUsage,
+        OntologyTypeUsage, OwnerUsage, PropertyTypeUsage, Selector,
+    },
     store::{error::VersionedUrlAlreadyExists, QueryError, Store, StorePool, TypeFetcher},
     subgraph::{
         edges::{
             EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind, OntologyEdgeKind,
-            OutgoingEdgeResolveDepth, SharedEdgeKind,
+            OntologyEdgeResolveDepth, OntologyResolveDepth, OutgoingEdgeResolveDepth,
+            SharedEdgeKind,
         },
         identifier::{
             DataTypeVertexId, EntityIdWithInterval, EntityTypeVertexId, EntityVertexId,
@@ -136,6 +145,8 @@ where
         property_type::PropertyTypeResource::routes::<P>(),
         entity_type::EntityTypeResource::routes::<P>(),
         entity::EntityResource::routes::<P>(),
+        change_feed::ChangeFeedResource::routes::<P>(),
+        ontology_graph::OntologyGraphResource::routes::<P>(),
     ]
 }
 
@@ -146,6 +157,8 @@ fn api_documentation() -> Vec<openapi::OpenApi> {
         property_type::PropertyTypeResource::documentation(),
         entity_type::EntityTypeResource::documentation(),
         entity::EntityResource::documentation(),
+        change_feed::ChangeFeedResource::documentation(),
+        ontology_graph::OntologyGraphResource::documentation(),
     ]
 }
 
@@ -159,9 +172,29 @@ fn report_to_status_code<C>(report: &Report<C>) -> StatusCode {
     status_code
 }
 
+/// Returns the `warnings` as `Warning` headers.
+///
+/// Warnings are informational only, so failing to collect them does not fail the request.
+fn warning_headers(warnings: error_stack::Result<Vec<String>, QueryError>) -> HeaderMap {
+    let warnings = warnings.unwrap_or_else(|report| {
+        tracing::warn!(error=?report, "Could not collect warnings");
+        Vec::new()
+    });
+
+    let mut headers = HeaderMap::new();
+    for warning in warnings {
+        tracing::warn!("{warning}");
+        if let Ok(value) = HeaderValue::from_str(&format!(r#"299 - "{warning}""#)) {
+            headers.append(header::WARNING, value);
+        }
+    }
+    headers
+}
+
 pub struct RestRouterDependencies<P: StorePool + Send + 'static> {
     pub store: Arc<P>,
     pub domain_regex: DomainValidator,
+    pub update_policy: OntologyUpdatePolicy,
 }
 
 /// A [`Router`] that only serves the `OpenAPI` specification (JSON, and necessary subschemas) for
@@ -195,6 +228,7 @@ where
     merged_routes
         .layer(Extension(dependencies.store))
         .layer(Extension(dependencies.domain_regex))
+        .layer(Extension(dependencies.update_policy))
         .layer(axum::middleware::from_fn(log_request_and_response))
         .layer(span_trace_layer())
         .merge(openapi_only_router())
@@ -234,10 +268,16 @@ async fn serve_static_schema(Path(path): Path<String>) -> Result<Response, Statu
             OwnedById,
             RecordCreatedById,
             RecordArchivedById,
+            DeprecatedById,
             ProvenanceMetadata,
             OntologyTypeRecordId,
             OntologyElementMetadata,
             OntologyTemporalMetadata,
+            OntologyTypeDeprecation,
+            OntologyTypeUsage,
+            PropertyTypeUsage,
+            EntityTypeUsage,
+            OwnerUsage,
             CustomOntologyMetadata,
             EntityTypeMetadata,
             CustomEntityTypeMetadata,
@@ -268,6 +308,8 @@ async fn serve_static_schema(Path(path): Path<String>) -> Result<Response, Statu
             GraphResolveDepths,
             EdgeResolveDepths,
             OutgoingEdgeResolveDepth,
+            OntologyEdgeResolveDepth,
+            OntologyResolveDepth,
             Subgraph,
             SubgraphTemporalAxes,
 
@@ -665,6 +707,10 @@ impl Modify for TimeSchemaAddon {
                 ClosedTemporalBound::<()>::schema().0.to_owned(),
                 ClosedTemporalBound::<()>::schema().1,
             );
+            components.schemas.insert(
+                "TemporalInterval".to_owned(),
+                TemporalInterval::<()>::schema().1,
+            );
             components.schemas.insert(
                 "LeftClosedTemporalInterval".to_owned(),
                 LeftClosedTemporalInterval::<()>::schema().1,
@@ -679,6 +725,14 @@ impl Modify for TimeSchemaAddon {
                     .to_owned(),
                 RightBoundedTemporalIntervalUnresolved::<()>::schema().1,
             );
+            components.schemas.insert(
+                BitemporalRectangle::schema().0.to_owned(),
+                BitemporalRectangle::schema().1,
+            );
+            components.schemas.insert(
+                BitemporalRegion::schema().0.to_owned(),
+                BitemporalRegion::schema().1,
+            );
         }
     }
 }
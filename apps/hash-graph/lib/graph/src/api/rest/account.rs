@@ -3,23 +3,39 @@
 use std::sync::Arc;
 
 use axum::{http::StatusCode, routing::post, Extension, Router};
-use graph_types::account::AccountId;
-use utoipa::OpenApi;
+use graph_types::{
+    account::AccountId,
+    provenance::{OwnedById, TransferredById},
+};
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
 use uuid::Uuid;
 
 use super::api_resource::RoutedResource;
 use crate::{
     api::rest::json::Json,
-    store::{AccountStore, StorePool},
+    store::{
+        error::EntityIdAlreadyExists, AccountStore, OwnershipTransfer, OwnershipTransferRecord,
+        StorePool,
+    },
 };
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         create_account_id,
+        transfer_ownership,
+        get_ownership_transfers,
     ),
     components(
-        schemas(AccountId),
+        schemas(
+            AccountId,
+            TransferredById,
+            TransferOwnershipRequest,
+            OwnershipTransfer,
+            OwnershipTransfersRequest,
+            OwnershipTransferRecord,
+        ),
     ),
     tags(
         (name = "Account", description = "Account management API")
@@ -33,7 +49,10 @@ impl RoutedResource for AccountResource {
         // TODO: The URL format here is preliminary and will have to change.
         Router::new().nest(
             "/accounts",
-            Router::new().route("/", post(create_account_id::<P>)),
+            Router::new()
+                .route("/", post(create_account_id::<P>))
+                .route("/transfer-ownership", post(transfer_ownership::<P>))
+                .route("/ownership-transfers", post(get_ownership_transfers::<P>)),
         )
     }
 }
@@ -69,3 +88,88 @@ async fn create_account_id<P: StorePool + Send>(
 
     Ok(Json(account_id))
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct TransferOwnershipRequest {
+    from_owned_by_id: OwnedById,
+    to_owned_by_id: OwnedById,
+    actor_id: TransferredById,
+}
+
+#[utoipa::path(
+    post,
+    path = "/accounts/transfer-ownership",
+    request_body = TransferOwnershipRequest,
+    tag = "Account",
+    responses(
+        (status = 200, content_type = "application/json", description = "The summary of the ownership transfer", body = OwnershipTransfer),
+
+        (status = 409, description = "An entity of the receiving account has the same entity UUID as an entity to transfer"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+async fn transfer_ownership<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    Json(request): Json<TransferOwnershipRequest>,
+) -> Result<Json<OwnershipTransfer>, StatusCode> {
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .transfer_ownership(
+            request.from_owned_by_id,
+            request.to_owned_by_id,
+            request.actor_id,
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not transfer ownership");
+
+            if report.contains::<EntityIdAlreadyExists>() {
+                return StatusCode::CONFLICT;
+            }
+
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct OwnershipTransfersRequest {
+    owned_by_id: OwnedById,
+}
+
+#[utoipa::path(
+    post,
+    path = "/accounts/ownership-transfers",
+    request_body = OwnershipTransfersRequest,
+    tag = "Account",
+    responses(
+        (status = 200, content_type = "application/json", description = "The ownership transfers from or to the account", body = [OwnershipTransferRecord]),
+
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+async fn get_ownership_transfers<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    Json(request): Json<OwnershipTransfersRequest>,
+) -> Result<Json<Vec<OwnershipTransferRecord>>, StatusCode> {
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_ownership_transfers(request.owned_by_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read ownership transfers");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
@@ -0,0 +1,77 @@
+//! Web routes for reading changes of the store.
+
+use std::sync::Arc;
+
+use axum::{http::StatusCode, routing::post, Extension, Router};
+use futures::TryFutureExt;
+use utoipa::OpenApi;
+
+use crate::{
+    api::rest::{api_resource::RoutedResource, json::Json, report_to_status_code},
+    store::{
+        ChangeEvent, ChangeFeedPage, ChangeFeedQuery, ChangeFeedResumeToken, ChangeFeedStore,
+        ChangeKind, ChangedRecord, StorePool,
+    },
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_changes,
+    ),
+    components(
+        schemas(
+            ChangeFeedQuery,
+            ChangeFeedPage,
+            ChangeFeedResumeToken,
+            ChangeEvent,
+            ChangeKind,
+            ChangedRecord,
+        )
+    ),
+    tags(
+        (name = "ChangeFeed", description = "Change feed API")
+    )
+)]
+pub struct ChangeFeedResource;
+
+impl RoutedResource for ChangeFeedResource {
+    /// Create routes for reading changes of the store.
+    fn routes<P: StorePool + Send + 'static>() -> Router {
+        Router::new().nest(
+            "/changes",
+            Router::new().route("/query", post(get_changes::<P>)),
+        )
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/changes/query",
+    request_body = ChangeFeedQuery,
+    tag = "ChangeFeed",
+    responses(
+        (status = 200, content_type = "application/json", body = ChangeFeedPage, description = "The changes of entities and ontology types in the requested transaction time range, ordered by transaction time."),
+        (status = 422, content_type = "text/plain", description = "Provided query is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn get_changes<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    Json(query): Json<ChangeFeedQuery>,
+) -> Result<Json<ChangeFeedPage>, StatusCode> {
+    pool.acquire()
+        .map_err(|error| {
+            tracing::error!(?error, "Could not acquire access to the store");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .and_then(|store| async move {
+            store.get_changes(&query).await.map_err(|report| {
+                tracing::error!(error=?report, ?query, "Could not read changes from the store");
+                report_to_status_code(&report)
+            })
+        })
+        .await
+        .map(Json)
+}
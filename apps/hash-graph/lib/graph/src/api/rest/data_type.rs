@@ -11,9 +11,12 @@ use futures::TryFutureExt;
 use graph_types::{
     ontology::{
         DataTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
-        OntologyTypeReference, PartialCustomOntologyMetadata, PartialOntologyElementMetadata,
+        OntologyTypeDeprecation, OntologyTypeReference, PartialCustomOntologyMetadata,
+        PartialOntologyElementMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use serde::{Deserialize, Serialize};
 use type_system::{url::VersionedUrl, DataType};
@@ -29,7 +32,7 @@ use crate::{
     },
     ontology::{
         domain_validator::{DomainValidator, ValidateOntologyType},
-        patch_id_and_parse, DataTypeQueryToken,
+        patch_id_and_parse, DataTypeQueryToken, OntologyTypeUsage,
     },
     store::{
         error::VersionedUrlAlreadyExists, BaseUrlAlreadyExists, ConflictBehavior, DataTypeStore,
@@ -43,9 +46,11 @@ use crate::{
         create_data_type,
         load_external_data_type,
         get_data_types_by_query,
+        get_data_type_usage,
         update_data_type,
         archive_data_type,
         unarchive_data_type,
+        deprecate_data_type,
     ),
     components(
         schemas(
@@ -56,8 +61,10 @@ use crate::{
             UpdateDataTypeRequest,
             DataTypeQueryToken,
             DataTypeStructuralQuery,
+            DataTypeUsageRequest,
             ArchiveDataTypeRequest,
             UnarchiveDataTypeRequest,
+            DeprecateDataTypeRequest,
         )
     ),
     tags(
@@ -78,9 +85,11 @@ impl RoutedResource for DataTypeResource {
             Router::new()
                 .route("/", post(create_data_type::<P>).put(update_data_type::<P>))
                 .route("/query", post(get_data_types_by_query::<P>))
+                .route("/usage", post(get_data_type_usage::<P>))
                 .route("/load", post(load_external_data_type::<P>))
                 .route("/archive", put(archive_data_type::<P>))
-                .route("/unarchive", put(unarchive_data_type::<P>)),
+                .route("/unarchive", put(unarchive_data_type::<P>))
+                .route("/deprecate", put(deprecate_data_type::<P>)),
         )
     }
 }
@@ -278,6 +287,46 @@ async fn get_data_types_by_query<P: StorePool + Send>(
         .map(|subgraph| Json(subgraph.into()))
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DataTypeUsageRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    data_type_id: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/data-types/usage",
+    request_body = DataTypeUsageRequest,
+    tag = "DataType",
+    responses(
+        (status = 200, content_type = "application/json", body = OntologyTypeUsage, description = "The property types, entity types, and entities using the data type"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or the data type does not exist"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn get_data_type_usage<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<DataTypeUsageRequest>,
+) -> Result<Json<OntologyTypeUsage>, StatusCode> {
+    let Json(DataTypeUsageRequest { data_type_id }) = body;
+
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_data_type_usage(&data_type_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read data type usage");
+            report_to_status_code(&report)
+        })
+        .map(Json)
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct UpdateDataTypeRequest {
@@ -453,3 +502,59 @@ async fn unarchive_data_type<P: StorePool + Send>(
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DeprecateDataTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_to_deprecate: VersionedUrl,
+    #[schema(value_type = Option<SHARED_VersionedUrl>)]
+    #[serde(default)]
+    successor: Option<VersionedUrl>,
+    actor_id: DeprecatedById,
+}
+
+#[utoipa::path(
+    put,
+    path = "/data-types/deprecate",
+    tag = "DataType",
+    responses(
+        (status = 200, content_type = "application/json", description = "The deprecation of the data type", body = OntologyTypeDeprecation),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Data type ID or successor was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = DeprecateDataTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn deprecate_data_type<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<DeprecateDataTypeRequest>,
+) -> Result<Json<OntologyTypeDeprecation>, StatusCode> {
+    let Json(DeprecateDataTypeRequest {
+        type_to_deprecate,
+        successor,
+        actor_id,
+    }) = body;
+
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .deprecate_data_type(&type_to_deprecate, successor.as_ref(), actor_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not deprecate data type");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
@@ -2,7 +2,11 @@
 
 use std::sync::Arc;
 
-use axum::{http::StatusCode, routing::post, Extension, Router};
+use axum::{
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Extension, Router,
+};
 use futures::TryFutureExt;
 use graph_types::{
     knowledge::{
@@ -21,9 +25,10 @@ use utoipa::{OpenApi, ToSchema};
 use crate::{
     api::rest::{
         api_resource::RoutedResource, json::Json, report_to_status_code,
-        utoipa_typedef::subgraph::Subgraph,
+        utoipa_typedef::subgraph::Subgraph, warning_headers,
     },
     knowledge::EntityQueryToken,
+    ontology::entity_type_deprecation_warnings,
     store::{
         error::{EntityDoesNotExist, RaceConditionOnUpdate},
         EntityStore, StorePool,
@@ -101,7 +106,9 @@ struct CreateEntityRequest {
     request_body = CreateEntityRequest,
     tag = "Entity",
     responses(
-        (status = 200, content_type = "application/json", description = "The metadata of the created entity", body = EntityMetadata),
+        (status = 200, content_type = "application/json", description = "The metadata of the created entity", body = EntityMetadata, headers(
+            ("Warning" = String, description = "Set if the entity type is deprecated"),
+        )),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
         (status = 404, description = "Entity Type URL was not found"),
@@ -112,7 +119,7 @@ struct CreateEntityRequest {
 async fn create_entity<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     body: Json<CreateEntityRequest>,
-) -> Result<Json<EntityMetadata>, StatusCode> {
+) -> Result<(HeaderMap, Json<EntityMetadata>), StatusCode> {
     let Json(CreateEntityRequest {
         properties,
         entity_type_id,
@@ -127,6 +134,8 @@ async fn create_entity<P: StorePool + Send>(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    let headers = warning_headers(entity_type_deprecation_warnings(&store, &entity_type_id).await);
+
     store
         .create_entity(
             owned_by_id,
@@ -145,7 +154,7 @@ async fn create_entity<P: StorePool + Send>(
             // Insertion/update errors are considered internal server errors.
             StatusCode::INTERNAL_SERVER_ERROR
         })
-        .map(Json)
+        .map(|metadata| (headers, Json(metadata)))
 }
 
 #[utoipa::path(
@@ -205,7 +214,9 @@ struct UpdateEntityRequest {
     path = "/entities",
     tag = "Entity",
     responses(
-        (status = 200, content_type = "application/json", description = "The metadata of the updated entity", body = EntityMetadata),
+        (status = 200, content_type = "application/json", description = "The metadata of the updated entity", body = EntityMetadata, headers(
+            ("Warning" = String, description = "Set if the entity type is deprecated"),
+        )),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
         (status = 423, content_type = "text/plain", description = "The entity that should be updated was unexpectedly updated at the same time"),
 
@@ -218,7 +229,7 @@ struct UpdateEntityRequest {
 async fn update_entity<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     body: Json<UpdateEntityRequest>,
-) -> Result<Json<EntityMetadata>, StatusCode> {
+) -> Result<(HeaderMap, Json<EntityMetadata>), StatusCode> {
     let Json(UpdateEntityRequest {
         properties,
         entity_id,
@@ -233,6 +244,8 @@ async fn update_entity<P: StorePool + Send>(
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
+    let headers = warning_headers(entity_type_deprecation_warnings(&store, &entity_type_id).await);
+
     store
         .update_entity(
             entity_id,
@@ -256,5 +269,5 @@ async fn update_entity<P: StorePool + Send>(
                 StatusCode::INTERNAL_SERVER_ERROR
             }
         })
-        .map(Json)
+        .map(|metadata| (headers, Json(metadata)))
 }
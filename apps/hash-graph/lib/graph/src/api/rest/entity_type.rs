@@ -1,10 +1,10 @@
 //! Web routes for CRU operations on Entity types.
 
-use std::{collections::hash_map, sync::Arc};
+use std::{collections::hash_map, slice, sync::Arc};
 
 use axum::{
-    http::StatusCode,
-    response::Response,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{post, put},
     Extension, Router,
 };
@@ -12,10 +12,12 @@ use futures::TryFutureExt;
 use graph_types::{
     ontology::{
         EntityTypeMetadata, EntityTypeWithMetadata, OntologyElementMetadata,
-        OntologyTemporalMetadata, OntologyTypeReference, PartialCustomEntityTypeMetadata,
-        PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
+        OntologyTemporalMetadata, OntologyTypeDeprecation, OntologyTypeReference,
+        PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use hash_map::HashMap;
 use serde::{Deserialize, Serialize};
@@ -34,12 +36,17 @@ use crate::{
             report_to_status_code,
             status::status_to_response,
             utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfEntityType},
-            RestApiStore,
+            warning_headers, RestApiStore,
         },
     },
     ontology::{
+        compatibility::{
+            self, OntologyTypeChange, OntologyTypeChangeKind, OntologyTypeCompatibility,
+            OntologyUpdatePolicy,
+        },
         domain_validator::{DomainValidator, ValidateOntologyType},
-        patch_id_and_parse, EntityTypeQueryToken,
+        patch_id_and_parse, reference_deprecation_warnings, EntityTypeQueryToken,
+        ResolvedEntityType,
     },
     store::{
         error::{BaseUrlAlreadyExists, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
@@ -54,9 +61,12 @@ use crate::{
         create_entity_type,
         load_external_entity_type,
         get_entity_types_by_query,
+        get_resolved_entity_type,
         update_entity_type,
+        check_entity_type_update,
         archive_entity_type,
         unarchive_entity_type,
+        deprecate_entity_type,
     ),
     components(
         schemas(
@@ -65,10 +75,17 @@ use crate::{
             CreateEntityTypeRequest,
             LoadExternalEntityTypeRequest,
             UpdateEntityTypeRequest,
+            CheckEntityTypeUpdateRequest,
+            OntologyTypeCompatibility,
+            OntologyTypeChange,
+            OntologyTypeChangeKind,
             EntityTypeQueryToken,
             EntityTypeStructuralQuery,
+            ResolveEntityTypeRequest,
+            ResolvedEntityType,
             ArchiveEntityTypeRequest,
             UnarchiveEntityTypeRequest,
+            DeprecateEntityTypeRequest,
         )
     ),
     tags(
@@ -91,10 +108,13 @@ impl RoutedResource for EntityTypeResource {
                     "/",
                     post(create_entity_type::<P>).put(update_entity_type::<P>),
                 )
+                .route("/check-update", post(check_entity_type_update::<P>))
                 .route("/query", post(get_entity_types_by_query::<P>))
+                .route("/resolve", post(get_resolved_entity_type::<P>))
                 .route("/load", post(load_external_entity_type::<P>))
                 .route("/archive", put(archive_entity_type::<P>))
-                .route("/unarchive", put(unarchive_entity_type::<P>)),
+                .route("/unarchive", put(unarchive_entity_type::<P>))
+                .route("/deprecate", put(deprecate_entity_type::<P>)),
         )
     }
 }
@@ -117,7 +137,9 @@ struct CreateEntityTypeRequest {
     request_body = CreateEntityTypeRequest,
     tag = "EntityType",
     responses(
-        (status = 200, content_type = "application/json", description = "The metadata of the created entity type", body = MaybeListOfEntityTypeMetadata),
+        (status = 200, content_type = "application/json", description = "The metadata of the created entity type", body = MaybeListOfEntityTypeMetadata, headers(
+            ("Warning" = String, description = "Set if a referenced ontology type is deprecated"),
+        )),
         (status = 400, content_type = "application/json", description = "Provided request body is invalid", body = VAR_STATUS),
 
         (status = 409, content_type = "application/json", description = "Unable to create entity type in the datastore as the base entity type ID already exists", body = VAR_STATUS),
@@ -131,7 +153,7 @@ async fn create_entity_type<P: StorePool + Send>(
     body: Json<CreateEntityTypeRequest>,
     // TODO: We want to be able to return `Status` here we should try and create a general way to
     //  call `status_to_response` for our routes that return Status
-) -> Result<Json<ListOrValue<EntityTypeMetadata>>, Response>
+) -> Result<(HeaderMap, Json<ListOrValue<EntityTypeMetadata>>), Response>
 where
     for<'pool> P::Store<'pool>: RestApiStore,
 {
@@ -228,6 +250,8 @@ where
         entity_types.push(entity_type);
     }
 
+    let headers = warning_headers(reference_deprecation_warnings(&store, &entity_types).await);
+
     let mut metadata = store
         .create_entity_types(
             entity_types.into_iter().zip(partial_metadata),
@@ -287,11 +311,14 @@ where
         })?;
 
     if is_list {
-        Ok(Json(ListOrValue::List(metadata)))
+        Ok((headers, Json(ListOrValue::List(metadata))))
     } else {
-        Ok(Json(ListOrValue::Value(
-            metadata.pop().expect("metadata does not contain a value"),
-        )))
+        Ok((
+            headers,
+            Json(ListOrValue::Value(
+                metadata.pop().expect("metadata does not contain a value"),
+            )),
+        ))
     }
 }
 
@@ -424,6 +451,46 @@ async fn get_entity_types_by_query<P: StorePool + Send>(
         .map(|subgraph| Json(subgraph.into()))
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct ResolveEntityTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    entity_type_id: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-types/resolve",
+    request_body = ResolveEntityTypeRequest,
+    tag = "EntityType",
+    responses(
+        (status = 200, content_type = "application/json", body = ResolvedEntityType, description = "The entity type with its inheritance merged and all property types and data types inlined"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn get_resolved_entity_type<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<ResolveEntityTypeRequest>,
+) -> Result<Json<ResolvedEntityType>, StatusCode> {
+    let Json(ResolveEntityTypeRequest { entity_type_id }) = body;
+
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_resolved_entity_type(&entity_type_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not resolve entity type");
+            report_to_status_code(&report)
+        })
+        .map(Json)
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct UpdateEntityTypeRequest {
@@ -435,6 +502,9 @@ struct UpdateEntityTypeRequest {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schema(value_type = SHARED_BaseUrl)]
     label_property: Option<BaseUrl>,
+    /// Applies the update even if it is breaking and breaking updates are rejected.
+    #[serde(default)]
+    force: bool,
 }
 
 #[utoipa::path(
@@ -442,10 +512,13 @@ struct UpdateEntityTypeRequest {
     path = "/entity-types",
     tag = "EntityType",
     responses(
-        (status = 200, content_type = "application/json", description = "The metadata of the updated entity type", body = OntologyElementMetadata),
+        (status = 200, content_type = "application/json", description = "The metadata of the updated entity type", body = OntologyElementMetadata, headers(
+            ("Warning" = String, description = "Set if a referenced ontology type is deprecated"),
+        )),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
         (status = 404, description = "Base entity type ID was not found"),
+        (status = 409, content_type = "application/json", description = "The update is breaking and breaking updates are rejected", body = OntologyTypeCompatibility),
         (status = 500, description = "Store error occurred"),
     ),
     request_body = UpdateEntityTypeRequest,
@@ -453,30 +526,59 @@ struct UpdateEntityTypeRequest {
 #[tracing::instrument(level = "info", skip(pool))]
 async fn update_entity_type<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
+    update_policy: Extension<OntologyUpdatePolicy>,
     body: Json<UpdateEntityTypeRequest>,
-) -> Result<Json<EntityTypeMetadata>, StatusCode> {
+) -> Result<(HeaderMap, Json<EntityTypeMetadata>), Response> {
     let Json(UpdateEntityTypeRequest {
         schema,
         mut type_to_update,
         actor_id,
         label_property,
+        force,
     }) = body;
 
+    let previous_type = type_to_update.clone();
     type_to_update.version += 1;
 
     let entity_type = patch_id_and_parse(&type_to_update, schema).map_err(|report| {
         tracing::error!(error=?report, "Couldn't convert schema to Entity Type");
         // Shame there isn't an UNPROCESSABLE_ENTITY_TYPE code :D
-        StatusCode::UNPROCESSABLE_ENTITY
+        StatusCode::UNPROCESSABLE_ENTITY.into_response()
         // TODO - We should probably return more information to the client
         //  https://app.asana.com/0/1201095311341924/1202574350052904/f
     })?;
 
     let mut store = pool.acquire().await.map_err(|report| {
         tracing::error!(error=?report, "Could not acquire store");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
+    if *update_policy == OntologyUpdatePolicy::RejectBreaking && !force {
+        let compatibility = compatibility::check_entity_type_update(
+            &store,
+            &previous_type,
+            &entity_type,
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not check compatibility of entity type update");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND.into_response();
+            }
+
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })?;
+        if compatibility.is_breaking() {
+            tracing::warn!(?compatibility, "Rejected breaking entity type update");
+            return Err((StatusCode::CONFLICT, Json(compatibility)).into_response());
+        }
+    }
+
+    let headers = warning_headers(
+        reference_deprecation_warnings(&store, slice::from_ref(&entity_type)).await,
+    );
+
     store
         .update_entity_type(entity_type, actor_id, label_property)
         .await
@@ -484,10 +586,70 @@ async fn update_entity_type<P: StorePool + Send>(
             tracing::error!(error=?report, "Could not update entity type");
 
             if report.contains::<OntologyVersionDoesNotExist>() {
-                return StatusCode::NOT_FOUND;
+                return StatusCode::NOT_FOUND.into_response();
             }
 
             // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })
+        .map(|metadata| (headers, Json(metadata)))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct CheckEntityTypeUpdateRequest {
+    #[schema(value_type = VAR_UPDATE_ENTITY_TYPE)]
+    schema: serde_json::Value,
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_to_update: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/entity-types/check-update",
+    tag = "EntityType",
+    responses(
+        (status = 200, content_type = "application/json", description = "The changes of the update and the number of entities it would invalidate", body = OntologyTypeCompatibility),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Base entity type ID was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = CheckEntityTypeUpdateRequest,
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn check_entity_type_update<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<CheckEntityTypeUpdateRequest>,
+) -> Result<Json<OntologyTypeCompatibility>, StatusCode> {
+    let Json(CheckEntityTypeUpdateRequest {
+        schema,
+        type_to_update,
+    }) = body;
+
+    let updated_type = VersionedUrl {
+        base_url: type_to_update.base_url.clone(),
+        version: type_to_update.version + 1,
+    };
+    let entity_type = patch_id_and_parse(&updated_type, schema).map_err(|report| {
+        tracing::error!(error=?report, "Couldn't convert schema to Entity Type");
+        StatusCode::UNPROCESSABLE_ENTITY
+    })?;
+
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    compatibility::check_entity_type_update(&store, &type_to_update, &entity_type)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not check compatibility of entity type update");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
             StatusCode::INTERNAL_SERVER_ERROR
         })
         .map(Json)
@@ -604,3 +766,59 @@ async fn unarchive_entity_type<P: StorePool + Send>(
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DeprecateEntityTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_to_deprecate: VersionedUrl,
+    #[schema(value_type = Option<SHARED_VersionedUrl>)]
+    #[serde(default)]
+    successor: Option<VersionedUrl>,
+    actor_id: DeprecatedById,
+}
+
+#[utoipa::path(
+    put,
+    path = "/entity-types/deprecate",
+    tag = "EntityType",
+    responses(
+        (status = 200, content_type = "application/json", description = "The deprecation of the entity type", body = OntologyTypeDeprecation),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Entity type ID or successor was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = DeprecateEntityTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn deprecate_entity_type<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<DeprecateEntityTypeRequest>,
+) -> Result<Json<OntologyTypeDeprecation>, StatusCode> {
+    let Json(DeprecateEntityTypeRequest {
+        type_to_deprecate,
+        successor,
+        actor_id,
+    }) = body;
+
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .deprecate_entity_type(&type_to_deprecate, successor.as_ref(), actor_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not deprecate entity type");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
@@ -0,0 +1,99 @@
+//! Web routes for exporting the dependencies between ontology types.
+
+use std::sync::Arc;
+
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Router,
+};
+use serde::Deserialize;
+use type_system::url::VersionedUrl;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::rest::{api_resource::RoutedResource, json::Json, report_to_status_code},
+    ontology::{OntologyDependencyGraph, OntologyGraphFormat},
+    store::StorePool,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_ontology_dependency_graph,
+    ),
+    components(
+        schemas(
+            OntologyDependencyGraphRequest,
+            OntologyGraphFormat,
+        )
+    ),
+    tags(
+        (name = "Ontology", description = "Ontology API")
+    )
+)]
+pub struct OntologyGraphResource;
+
+impl RoutedResource for OntologyGraphResource {
+    /// Create routes for exporting the dependencies between ontology types.
+    fn routes<P: StorePool + Send + 'static>() -> Router {
+        Router::new().nest(
+            "/ontology-types",
+            Router::new().route(
+                "/dependency-graph",
+                post(get_ontology_dependency_graph::<P>),
+            ),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct OntologyDependencyGraphRequest {
+    /// The data types, property types, or entity types to start the graph from.
+    #[schema(value_type = Vec<SHARED_VersionedUrl>)]
+    roots: Vec<VersionedUrl>,
+    #[serde(default)]
+    format: OntologyGraphFormat,
+}
+
+#[utoipa::path(
+    post,
+    path = "/ontology-types/dependency-graph",
+    request_body = OntologyDependencyGraphRequest,
+    tag = "Ontology",
+    responses(
+        (status = 200, content_type = "text/plain", body = String, description = "The ontology types reachable from the roots and the edges between them, rendered as Graphviz DOT or Mermaid"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or one of the roots does not exist"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn get_ontology_dependency_graph<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<OntologyDependencyGraphRequest>,
+) -> Result<Response, StatusCode> {
+    let Json(OntologyDependencyGraphRequest { roots, format }) = body;
+
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let graph = OntologyDependencyGraph::read(&store, &roots)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read ontology dependency graph");
+            report_to_status_code(&report)
+        })?;
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(format.content_type()),
+        )],
+        graph.render(format),
+    )
+        .into_response())
+}
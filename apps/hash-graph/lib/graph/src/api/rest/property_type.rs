@@ -1,19 +1,23 @@
 //! Web routes for CRU operations on Property types.
 
-use std::sync::Arc;
+use std::{slice, sync::Arc};
 
 use axum::{
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     routing::{post, put},
     Extension, Router,
 };
 use futures::TryFutureExt;
 use graph_types::{
     ontology::{
-        OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeReference,
-        PartialCustomOntologyMetadata, PartialOntologyElementMetadata, PropertyTypeWithMetadata,
+        OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeDeprecation,
+        OntologyTypeReference, PartialCustomOntologyMetadata, PartialOntologyElementMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use serde::{Deserialize, Serialize};
 use type_system::{url::VersionedUrl, PropertyType};
@@ -25,11 +29,16 @@ use crate::{
         json::Json,
         report_to_status_code,
         utoipa_typedef::{subgraph::Subgraph, ListOrValue, MaybeListOfPropertyType},
-        RestApiStore,
+        warning_headers, RestApiStore,
     },
     ontology::{
+        compatibility::{
+            self, OntologyTypeChange, OntologyTypeChangeKind, OntologyTypeCompatibility,
+            OntologyUpdatePolicy,
+        },
         domain_validator::{DomainValidator, ValidateOntologyType},
-        patch_id_and_parse, PropertyTypeQueryToken,
+        patch_id_and_parse, reference_deprecation_warnings, OntologyTypeUsage,
+        PropertyTypeQueryToken,
     },
     store::{
         error::VersionedUrlAlreadyExists, BaseUrlAlreadyExists, ConflictBehavior,
@@ -44,9 +53,12 @@ use crate::{
         create_property_type,
         load_external_property_type,
         get_property_types_by_query,
+        get_property_type_usage,
         update_property_type,
+        check_property_type_update,
         archive_property_type,
         unarchive_property_type,
+        deprecate_property_type,
     ),
     components(
         schemas(
@@ -55,10 +67,16 @@ use crate::{
             CreatePropertyTypeRequest,
             LoadExternalPropertyTypeRequest,
             UpdatePropertyTypeRequest,
+            CheckPropertyTypeUpdateRequest,
+            OntologyTypeCompatibility,
+            OntologyTypeChange,
+            OntologyTypeChangeKind,
             PropertyTypeQueryToken,
             PropertyTypeStructuralQuery,
+            PropertyTypeUsageRequest,
             ArchivePropertyTypeRequest,
             UnarchivePropertyTypeRequest,
+            DeprecatePropertyTypeRequest,
         )
     ),
     tags(
@@ -81,10 +99,13 @@ impl RoutedResource for PropertyTypeResource {
                     "/",
                     post(create_property_type::<P>).put(update_property_type::<P>),
                 )
+                .route("/check-update", post(check_property_type_update::<P>))
                 .route("/query", post(get_property_types_by_query::<P>))
+                .route("/usage", post(get_property_type_usage::<P>))
                 .route("/load", post(load_external_property_type::<P>))
                 .route("/archive", put(archive_property_type::<P>))
-                .route("/unarchive", put(unarchive_property_type::<P>)),
+                .route("/unarchive", put(unarchive_property_type::<P>))
+                .route("/deprecate", put(deprecate_property_type::<P>)),
         )
     }
 }
@@ -104,7 +125,9 @@ struct CreatePropertyTypeRequest {
     request_body = CreatePropertyTypeRequest,
     tag = "PropertyType",
     responses(
-        (status = 200, content_type = "application/json", description = "The metadata of the created property type", body = MaybeListOfOntologyElementMetadata),
+        (status = 200, content_type = "application/json", description = "The metadata of the created property type", body = MaybeListOfOntologyElementMetadata, headers(
+            ("Warning" = String, description = "Set if a referenced ontology type is deprecated"),
+        )),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
         (status = 409, description = "Unable to create property type in the store as the base property type ID already exists"),
@@ -116,7 +139,7 @@ async fn create_property_type<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
     domain_validator: Extension<DomainValidator>,
     body: Json<CreatePropertyTypeRequest>,
-) -> Result<Json<ListOrValue<OntologyElementMetadata>>, StatusCode>
+) -> Result<(HeaderMap, Json<ListOrValue<OntologyElementMetadata>>), StatusCode>
 where
     for<'pool> P::Store<'pool>: RestApiStore,
 {
@@ -166,6 +189,8 @@ where
         property_types.push(property_type);
     }
 
+    let headers = warning_headers(reference_deprecation_warnings(&store, &property_types).await);
+
     let mut metadata = store
         .create_property_types(
             property_types.into_iter().zip(partial_metadata),
@@ -184,11 +209,14 @@ where
         })?;
 
     if is_list {
-        Ok(Json(ListOrValue::List(metadata)))
+        Ok((headers, Json(ListOrValue::List(metadata))))
     } else {
-        Ok(Json(ListOrValue::Value(
-            metadata.pop().expect("metadata does not contain a value"),
-        )))
+        Ok((
+            headers,
+            Json(ListOrValue::Value(
+                metadata.pop().expect("metadata does not contain a value"),
+            )),
+        ))
     }
 }
 
@@ -286,6 +314,46 @@ async fn get_property_types_by_query<P: StorePool + Send>(
         .map(|subgraph| Json(subgraph.into()))
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct PropertyTypeUsageRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    property_type_id: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/property-types/usage",
+    request_body = PropertyTypeUsageRequest,
+    tag = "PropertyType",
+    responses(
+        (status = 200, content_type = "application/json", body = OntologyTypeUsage, description = "The property types, entity types, and entities using the property type"),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid or the property type does not exist"),
+        (status = 500, description = "Store error occurred"),
+    )
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn get_property_type_usage<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<PropertyTypeUsageRequest>,
+) -> Result<Json<OntologyTypeUsage>, StatusCode> {
+    let Json(PropertyTypeUsageRequest { property_type_id }) = body;
+
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .get_property_type_usage(&property_type_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not read property type usage");
+            report_to_status_code(&report)
+        })
+        .map(Json)
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 struct UpdatePropertyTypeRequest {
@@ -294,6 +362,9 @@ struct UpdatePropertyTypeRequest {
     #[schema(value_type = SHARED_VersionedUrl)]
     type_to_update: VersionedUrl,
     actor_id: RecordCreatedById,
+    /// Applies the update even if it is breaking and breaking updates are rejected.
+    #[serde(default)]
+    force: bool,
 }
 
 #[utoipa::path(
@@ -301,10 +372,13 @@ struct UpdatePropertyTypeRequest {
     path = "/property-types",
     tag = "PropertyType",
     responses(
-        (status = 200, content_type = "application/json", description = "The metadata of the updated property type", body = OntologyElementMetadata),
+        (status = 200, content_type = "application/json", description = "The metadata of the updated property type", body = OntologyElementMetadata, headers(
+            ("Warning" = String, description = "Set if a referenced ontology type is deprecated"),
+        )),
         (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
 
         (status = 404, description = "Base property type ID was not found"),
+        (status = 409, content_type = "application/json", description = "The update is breaking and breaking updates are rejected", body = OntologyTypeCompatibility),
         (status = 500, description = "Store error occurred"),
     ),
     request_body = UpdatePropertyTypeRequest,
@@ -312,28 +386,57 @@ struct UpdatePropertyTypeRequest {
 #[tracing::instrument(level = "info", skip(pool))]
 async fn update_property_type<P: StorePool + Send>(
     pool: Extension<Arc<P>>,
+    update_policy: Extension<OntologyUpdatePolicy>,
     body: Json<UpdatePropertyTypeRequest>,
-) -> Result<Json<OntologyElementMetadata>, StatusCode> {
+) -> Result<(HeaderMap, Json<OntologyElementMetadata>), Response> {
     let Json(UpdatePropertyTypeRequest {
         schema,
         mut type_to_update,
         actor_id,
+        force,
     }) = body;
 
+    let previous_type = type_to_update.clone();
     type_to_update.version += 1;
 
     let property_type = patch_id_and_parse(&type_to_update, schema).map_err(|report| {
         tracing::error!(error=?report, "Couldn't patch schema and convert to Property Type");
-        StatusCode::UNPROCESSABLE_ENTITY
+        StatusCode::UNPROCESSABLE_ENTITY.into_response()
         // TODO - We should probably return more information to the client
         //  https://app.asana.com/0/1201095311341924/1202574350052904/f
     })?;
 
     let mut store = pool.acquire().await.map_err(|report| {
         tracing::error!(error=?report, "Could not acquire store");
-        StatusCode::INTERNAL_SERVER_ERROR
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
     })?;
 
+    if *update_policy == OntologyUpdatePolicy::RejectBreaking && !force {
+        let compatibility = compatibility::check_property_type_update(
+            &store,
+            &previous_type,
+            &property_type,
+        )
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not check compatibility of property type update");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND.into_response();
+            }
+
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })?;
+        if compatibility.is_breaking() {
+            tracing::warn!(?compatibility, "Rejected breaking property type update");
+            return Err((StatusCode::CONFLICT, Json(compatibility)).into_response());
+        }
+    }
+
+    let headers = warning_headers(
+        reference_deprecation_warnings(&store, slice::from_ref(&property_type)).await,
+    );
+
     store
         .update_property_type(property_type, actor_id)
         .await
@@ -341,10 +444,70 @@ async fn update_property_type<P: StorePool + Send>(
             tracing::error!(error=?report, "Could not update property type");
 
             if report.contains::<OntologyVersionDoesNotExist>() {
-                return StatusCode::NOT_FOUND;
+                return StatusCode::NOT_FOUND.into_response();
             }
 
             // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        })
+        .map(|metadata| (headers, Json(metadata)))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct CheckPropertyTypeUpdateRequest {
+    #[schema(value_type = VAR_UPDATE_PROPERTY_TYPE)]
+    schema: serde_json::Value,
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_to_update: VersionedUrl,
+}
+
+#[utoipa::path(
+    post,
+    path = "/property-types/check-update",
+    tag = "PropertyType",
+    responses(
+        (status = 200, content_type = "application/json", description = "The changes of the update and the number of entities it would invalidate", body = OntologyTypeCompatibility),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Base property type ID was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = CheckPropertyTypeUpdateRequest,
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn check_property_type_update<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<CheckPropertyTypeUpdateRequest>,
+) -> Result<Json<OntologyTypeCompatibility>, StatusCode> {
+    let Json(CheckPropertyTypeUpdateRequest {
+        schema,
+        type_to_update,
+    }) = body;
+
+    let updated_type = VersionedUrl {
+        base_url: type_to_update.base_url.clone(),
+        version: type_to_update.version + 1,
+    };
+    let property_type = patch_id_and_parse(&updated_type, schema).map_err(|report| {
+        tracing::error!(error=?report, "Couldn't patch schema and convert to Property Type");
+        StatusCode::UNPROCESSABLE_ENTITY
+    })?;
+
+    let store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    compatibility::check_property_type_update(&store, &type_to_update, &property_type)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not check compatibility of property type update");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
             StatusCode::INTERNAL_SERVER_ERROR
         })
         .map(Json)
@@ -461,3 +624,59 @@ async fn unarchive_property_type<P: StorePool + Send>(
         })
         .map(Json)
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+struct DeprecatePropertyTypeRequest {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    type_to_deprecate: VersionedUrl,
+    #[schema(value_type = Option<SHARED_VersionedUrl>)]
+    #[serde(default)]
+    successor: Option<VersionedUrl>,
+    actor_id: DeprecatedById,
+}
+
+#[utoipa::path(
+    put,
+    path = "/property-types/deprecate",
+    tag = "PropertyType",
+    responses(
+        (status = 200, content_type = "application/json", description = "The deprecation of the property type", body = OntologyTypeDeprecation),
+        (status = 422, content_type = "text/plain", description = "Provided request body is invalid"),
+
+        (status = 404, description = "Property type ID or successor was not found"),
+        (status = 500, description = "Store error occurred"),
+    ),
+    request_body = DeprecatePropertyTypeRequest,
+)]
+#[tracing::instrument(level = "info", skip(pool))]
+async fn deprecate_property_type<P: StorePool + Send>(
+    pool: Extension<Arc<P>>,
+    body: Json<DeprecatePropertyTypeRequest>,
+) -> Result<Json<OntologyTypeDeprecation>, StatusCode> {
+    let Json(DeprecatePropertyTypeRequest {
+        type_to_deprecate,
+        successor,
+        actor_id,
+    }) = body;
+
+    let mut store = pool.acquire().await.map_err(|report| {
+        tracing::error!(error=?report, "Could not acquire store");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    store
+        .deprecate_property_type(&type_to_deprecate, successor.as_ref(), actor_id)
+        .await
+        .map_err(|report| {
+            tracing::error!(error=?report, "Could not deprecate property type");
+
+            if report.contains::<OntologyVersionDoesNotExist>() {
+                return StatusCode::NOT_FOUND;
+            }
+
+            // Insertion/update errors are considered internal server errors.
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+        .map(Json)
+}
@@ -11,7 +11,7 @@ use futures::TryStreamExt;
 use hash_status::{Status, StatusCode};
 use tokio::io;
 use tokio_postgres::NoTls;
-use tokio_util::{codec::FramedRead, io::StreamReader};
+use tokio_util::io::StreamReader;
 
 use crate::{
     api::{
@@ -21,7 +21,7 @@ use crate::{
             status::status_to_response,
         },
     },
-    snapshot::{codec, SnapshotStore},
+    snapshot::{SnapshotFormat, SnapshotRestoreSettings, SnapshotStore},
     store::{PostgresStorePool, StorePool},
 };
 
@@ -67,17 +67,12 @@ async fn restore_snapshot(
     pool: Extension<Arc<PostgresStorePool<NoTls>>>,
     snapshot: BodyStream,
 ) -> Result<Response, Response> {
-    let store = pool.acquire().await.map_err(store_acquisition_error)?;
-
-    SnapshotStore::new(store)
+    SnapshotStore::new(&**pool)
         .restore_snapshot(
-            FramedRead::new(
-                StreamReader::new(
-                    snapshot.map_err(|err| io::Error::new(io::ErrorKind::Other, err)),
-                ),
-                codec::JsonLinesDecoder::default(),
-            ),
-            10_000,
+            SnapshotFormat::JsonLines.decode(StreamReader::new(
+                snapshot.map_err(|err| io::Error::new(io::ErrorKind::Other, err)),
+            )),
+            SnapshotRestoreSettings::default(),
         )
         .await
         .map_err(|report| {
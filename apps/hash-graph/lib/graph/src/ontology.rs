@@ -1,9 +1,17 @@
 //! TODO: DOC
 
+mod codegen;
+pub mod compatibility;
 mod data_type;
+mod dependency_graph;
+mod deprecation;
 pub mod domain_validator;
 mod entity_type;
+mod import;
 mod property_type;
+mod resolved;
+mod usage;
+pub(crate) mod validation;
 
 use core::fmt;
 
@@ -18,9 +26,17 @@ use type_system::url::VersionedUrl;
 use utoipa::ToSchema;
 
 pub use self::{
+    codegen::{link_entity_types, RustCodeGenerator},
     data_type::{DataTypeQueryPath, DataTypeQueryPathVisitor, DataTypeQueryToken},
+    dependency_graph::{OntologyDependencyGraph, OntologyGraphFormat},
+    deprecation::{entity_type_deprecation_warnings, reference_deprecation_warnings},
     entity_type::{EntityTypeQueryPath, EntityTypeQueryPathVisitor, EntityTypeQueryToken},
+    import::{
+        ImportedOntologyType, OntologyImportError, OntologyImportSummary, OntologyTypeImport,
+    },
     property_type::{PropertyTypeQueryPath, PropertyTypeQueryPathVisitor, PropertyTypeQueryToken},
+    resolved::ResolvedEntityType,
+    usage::{EntityTypeUsage, OntologyTypeUsage, OwnerUsage, PropertyTypeUsage},
 };
 use crate::{
     store::Record,
@@ -0,0 +1,644 @@
+use std::{collections::HashSet, fmt::Write};
+
+use serde_json::{Map, Value};
+use type_system::url::VersionedUrl;
+
+use crate::ontology::{validation::reference, ResolvedEntityType};
+
+/// Keywords, which cannot be used as identifiers without escaping them.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// Type names, which are used by the generated module, so no generated type may be named like
+/// them.
+const RESERVED_TYPE_NAMES: &[&str] = &[
+    "EntityProperties",
+    "Serialize",
+    "Deserialize",
+    "Value",
+    "Self",
+    "Option",
+    "Result",
+    "String",
+    "Vec",
+    "TryFrom",
+    "Debug",
+    "Clone",
+    "PartialEq",
+];
+
+/// Generates Rust types mirroring the properties of entity types.
+///
+/// Every entity type is turned into a struct, whose fields are keyed by the [`BaseUrl`] of their
+/// property types, so it can be converted from and to [`EntityProperties`]. Property objects are
+/// turned into nested structs and property types with multiple possible values into untagged
+/// enums.
+///
+/// [`BaseUrl`]: type_system::url::BaseUrl
+/// [`EntityProperties`]: graph_types::knowledge::entity::EntityProperties
+#[derive(Debug)]
+pub struct RustCodeGenerator {
+    items: Vec<String>,
+    type_names: HashSet<String>,
+    entity_types: HashSet<VersionedUrl>,
+}
+
+impl Default for RustCodeGenerator {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            type_names: RESERVED_TYPE_NAMES
+                .iter()
+                .map(|name| (*name).to_owned())
+                .collect(),
+            entity_types: HashSet::new(),
+        }
+    }
+}
+
+impl RustCodeGenerator {
+    /// Adds a struct for the `entity_type`.
+    ///
+    /// Entity types, which were already added, are skipped.
+    pub fn add_entity_type(&mut self, entity_type: &ResolvedEntityType) {
+        if !self.entity_types.insert(entity_type.entity_type_id.clone()) {
+            return;
+        }
+
+        let schema = &entity_type.schema;
+        let name = self.type_name(
+            schema
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or("EntityType"),
+        );
+
+        let fields = self.fields(
+            schema.get("properties").and_then(Value::as_object),
+            schema.get("required"),
+            &name,
+        );
+
+        let mut links = String::new();
+        for (link_entity_type_id, destinations) in self::links(schema) {
+            let destinations = destinations
+                .iter()
+                .map(|destination| string_literal(&destination.to_string()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                links,
+                "        ({}, &[{destinations}]),",
+                string_literal(&link_entity_type_id.to_string())
+            );
+        }
+
+        let mut item = doc_comment(schema.get("description").and_then(Value::as_str), "");
+        if !item.is_empty() {
+            item.push_str("///\n");
+        }
+        let entity_type_id = string_literal(&entity_type.entity_type_id.to_string());
+        let _ = writeln!(item, "/// Generated from `{}`.", entity_type.entity_type_id);
+        let _ = writeln!(
+            item,
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct {name} \
+             {{\n{fields}}}\n"
+        );
+        let _ = writeln!(item, "impl {name} {{");
+        let _ = writeln!(
+            item,
+            "    /// The ID of the entity type this struct was generated from.\n    pub const \
+             ENTITY_TYPE_ID: &'static str = {entity_type_id};"
+        );
+        let _ = writeln!(
+            item,
+            "    /// The link entity types an entity of this type may link with, each with the \
+             entity types\n    /// the link may point to. An empty list allows any destination."
+        );
+        let _ = writeln!(
+            item,
+            "    pub const LINKS: &'static [(&'static str, &'static [&'static str])] = \
+             &[\n{links}    ];\n}}\n"
+        );
+        for (source, target) in [
+            ("EntityProperties", name.as_str()),
+            (&name, "EntityProperties"),
+        ] {
+            let _ = writeln!(
+                    item,
+                    "impl TryFrom<{source}> for {target} {{\n    type Error = \
+                     serde_json::Error;\n\n    fn try_from(properties: {source}) -> Result<Self, \
+                     Self::Error> {{\n        \
+                     serde_json::from_value(serde_json::to_value(properties)?)\n    }}\n}}\n"
+                );
+        }
+        item.pop();
+        self.items.push(item);
+    }
+
+    /// Returns the generated Rust module.
+    #[must_use]
+    pub fn finish(self) -> String {
+        let mut output = String::from(
+            "// This file is generated from entity types. Do not edit it manually.\n\nuse \
+             graph_types::knowledge::entity::EntityProperties;\nuse serde::{Deserialize, \
+             Serialize};\n",
+        );
+        for item in self.items {
+            output.push('\n');
+            output.push_str(&item);
+        }
+        output
+    }
+
+    /// Returns the fields of a struct with the given `properties`.
+    ///
+    /// Properties, which are not `required`, are optional.
+    fn fields(
+        &mut self,
+        properties: Option<&Map<String, Value>>,
+        required: Option<&Value>,
+        struct_name: &str,
+    ) -> String {
+        let required = required
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect::<HashSet<_>>();
+
+        let mut field_names = HashSet::new();
+        let mut fields = String::new();
+        for (base_url, property) in properties.into_iter().flatten() {
+            // The title of a property type array is the title of its items. Titles without any
+            // ASCII alphanumeric characters can't be turned into a name, so the base URL is used.
+            let name = property
+                .get("title")
+                .or_else(|| property.pointer("/items/title"))
+                .and_then(Value::as_str)
+                .filter(|title| !words(title).is_empty())
+                .unwrap_or_else(|| last_segment(base_url));
+            let field_name = unique(&mut field_names, field_name(name));
+
+            let mut field_type =
+                self.property_type(property, &format!("{struct_name}{}", pascal_case(name)));
+            let rename = string_literal(base_url);
+            let serde = if required.contains(base_url.as_str()) {
+                format!("#[serde(rename = {rename})]")
+            } else {
+                field_type = format!("Option<{field_type}>");
+                format!(
+                    "#[serde(rename = {rename}, default, skip_serializing_if = \
+                     \"Option::is_none\")]"
+                )
+            };
+
+            fields.push_str(&doc_comment(
+                property
+                    .get("description")
+                    .or_else(|| property.pointer("/items/description"))
+                    .and_then(Value::as_str),
+                "    ",
+            ));
+            let _ = writeln!(fields, "    {serde}\n    pub {field_name}: {field_type},");
+        }
+        fields
+    }
+
+    /// Returns the Rust type of an inlined property type or an array of property types.
+    fn property_type(&mut self, schema: &Value, name_hint: &str) -> String {
+        if reference(schema).is_some() {
+            // Recursive property types are not inlined
+            return "serde_json::Value".to_owned();
+        }
+
+        if let Some(items) = schema.get("items") {
+            return format!("Vec<{}>", self.property_type(items, name_hint));
+        }
+
+        schema.get("oneOf").and_then(Value::as_array).map_or_else(
+            || "serde_json::Value".to_owned(),
+            |values| self.one_of_type(values, name_hint),
+        )
+    }
+
+    /// Returns the Rust type of one of the given property values.
+    ///
+    /// If there is more than one possible value, an untagged enum is generated.
+    fn one_of_type(&mut self, values: &[Value], name_hint: &str) -> String {
+        if let [value] = values {
+            return self.value_type(value, name_hint);
+        }
+
+        let name = self.type_name(name_hint);
+        let mut variant_names = HashSet::new();
+        let mut variants = String::new();
+        for value in values {
+            let title = value
+                .get("title")
+                .and_then(Value::as_str)
+                .unwrap_or_else(|| match value.get("type").and_then(Value::as_str) {
+                    Some("object") => "Object",
+                    Some("array") => "Array",
+                    _ => "Value",
+                });
+            let variant_name = unique(&mut variant_names, identifier(title, "Variant"));
+            let variant_type = self.value_type(value, &format!("{name}{variant_name}"));
+            let _ = writeln!(variants, "    {variant_name}({variant_type}),");
+        }
+
+        self.items.push(format!(
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n#[serde(untagged)]\npub \
+             enum {name} {{\n{variants}}}\n"
+        ));
+        name
+    }
+
+    /// Returns the Rust type of a data type, a property object, or an array of property values.
+    fn value_type(&mut self, value: &Value, name_hint: &str) -> String {
+        if reference(value).is_some() {
+            return "serde_json::Value".to_owned();
+        }
+
+        match value.get("type").and_then(Value::as_str) {
+            Some("object") if value.get("properties").is_some() => {
+                let name = self.type_name(name_hint);
+                let fields = self.fields(
+                    value.get("properties").and_then(Value::as_object),
+                    value.get("required"),
+                    &name,
+                );
+                self.items.push(format!(
+                    "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct \
+                     {name} {{\n{fields}}}\n"
+                ));
+                name
+            }
+            Some("array") => value
+                .pointer("/items/oneOf")
+                .and_then(Value::as_array)
+                .map_or_else(
+                    || "Vec<serde_json::Value>".to_owned(),
+                    |values| format!("Vec<{}>", self.one_of_type(values, name_hint)),
+                ),
+            Some("string") => "String".to_owned(),
+            Some("number") => "f64".to_owned(),
+            Some("integer") => "i64".to_owned(),
+            Some("boolean") => "bool".to_owned(),
+            Some("null") => "()".to_owned(),
+            _ => "serde_json::Value".to_owned(),
+        }
+    }
+
+    /// Returns a type name derived from `name`, which is not used by any other generated type.
+    fn type_name(&mut self, name: &str) -> String {
+        unique(&mut self.type_names, identifier(name, "Type"))
+    }
+}
+
+/// Returns the link entity types of an entity type schema with their allowed destinations.
+fn links(schema: &Value) -> Vec<(VersionedUrl, Vec<VersionedUrl>)> {
+    schema
+        .get("links")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(link_entity_type_id, link)| {
+            Some((
+                link_entity_type_id.parse().ok()?,
+                link.pointer("/items/oneOf")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(reference)
+                    .collect(),
+            ))
+        })
+        .collect()
+}
+
+/// Returns the link entity types, which an entity type may link with.
+#[must_use]
+pub fn link_entity_types(entity_type: &ResolvedEntityType) -> Vec<VersionedUrl> {
+    links(&entity_type.schema)
+        .into_iter()
+        .map(|(link_entity_type_id, _)| link_entity_type_id)
+        .collect()
+}
+
+/// Returns `name` or, if it's already contained in `names`, `name` with the lowest numeric suffix
+/// not contained.
+fn unique(names: &mut HashSet<String>, name: String) -> String {
+    if names.insert(name.clone()) {
+        return name;
+    }
+    let mut unique_name = name.clone();
+    let mut suffix = 2;
+    while names.contains(&unique_name) {
+        unique_name = format!("{name}{suffix}");
+        suffix += 1;
+    }
+    names.insert(unique_name.clone());
+    unique_name
+}
+
+/// Splits `name` into words at non-alphanumeric characters and at lowercase to uppercase
+/// transitions.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lowercase = false;
+    for char in name.chars() {
+        if !char.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            previous_lowercase = false;
+            continue;
+        }
+        if char.is_ascii_uppercase() && previous_lowercase && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_lowercase = char.is_ascii_lowercase() || char.is_ascii_digit();
+        word.push(char);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn pascal_case(name: &str) -> String {
+    words(name)
+        .into_iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                let mut word = first.to_ascii_uppercase().to_string();
+                word.push_str(&chars.as_str().to_ascii_lowercase());
+                word
+            })
+        })
+        .collect()
+}
+
+/// Returns `name` in pascal case, prefixed with `prefix` if it's not a valid identifier on its own.
+fn identifier(name: &str, prefix: &str) -> String {
+    let mut identifier = pascal_case(name);
+    if identifier.is_empty()
+        || identifier == "Self"
+        || identifier.starts_with(|char: char| char.is_ascii_digit())
+    {
+        identifier.insert_str(0, prefix);
+    }
+    identifier
+}
+
+fn field_name(name: &str) -> String {
+    let mut field_name = words(name)
+        .into_iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if field_name.is_empty() {
+        field_name.push_str("property");
+    } else if field_name.starts_with(|char: char| char.is_ascii_digit()) {
+        field_name.insert(0, '_');
+    }
+    if matches!(field_name.as_str(), "self" | "super" | "crate") {
+        field_name.push('_');
+    } else if KEYWORDS.contains(&field_name.as_str()) {
+        field_name.insert_str(0, "r#");
+    }
+    field_name
+}
+
+/// Returns the last path segment of a [`BaseUrl`].
+///
+/// [`BaseUrl`]: type_system::url::BaseUrl
+fn last_segment(base_url: &str) -> &str {
+    base_url
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(base_url)
+}
+
+/// Returns `value` as a Rust string literal.
+///
+/// The `Debug` implementation of `str` escapes it as required by Rust.
+fn string_literal(value: &str) -> String {
+    format!("{value:?}")
+}
+
+/// Returns a doc comment containing `description` indented by `indentation`.
+fn doc_comment(description: Option<&str>, indentation: &str) -> String {
+    description
+        .into_iter()
+        .flat_map(str::lines)
+        .fold(String::new(), |mut doc_comment, line| {
+            let _ = writeln!(
+                doc_comment,
+                "{}",
+                format!("{indentation}/// {line}").trim_end()
+            );
+            doc_comment
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use graph_test_data::{data_type, entity_type, property_type};
+    use serde_json::json;
+
+    use super::*;
+    use crate::ontology::{ImportedOntologyType, OntologyTypeImport};
+
+    #[test]
+    fn names() {
+        assert_eq!(words("userID"), ["user", "ID"]);
+        assert_eq!(pascal_case("favorite-film"), "FavoriteFilm");
+        assert_eq!(pascal_case("userID"), "UserId");
+        assert_eq!(field_name("Favorite Film"), "favorite_film");
+        assert_eq!(field_name("Type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("2nd Name"), "_2nd_name");
+        assert_eq!(field_name("日本語"), "property");
+        assert_eq!(identifier("2D", "Variant"), "Variant2D");
+        assert_eq!(identifier("★", "Variant"), "Variant");
+        assert_eq!(identifier("self", "Variant"), "VariantSelf");
+        assert_eq!(
+            last_segment("https://example.com/property-type/name/"),
+            "name"
+        );
+
+        let mut names = HashSet::from(["Name".to_owned()]);
+        assert_eq!(unique(&mut names, "Name".to_owned()), "Name2");
+        assert_eq!(unique(&mut names, "Name".to_owned()), "Name3");
+        assert_eq!(unique(&mut names, "Other".to_owned()), "Other");
+    }
+
+    #[test]
+    fn imported_entity_type() {
+        let import = OntologyTypeImport::new(
+            [
+                data_type::TEXT_V1,
+                property_type::NAME_V1,
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ]
+            .map(|schema| {
+                ImportedOntologyType::from_value(
+                    serde_json::from_str(schema).expect("could not parse schema"),
+                )
+                .expect("could not parse ontology type")
+            }),
+        )
+        .expect("could not order ontology types");
+        let person = import
+            .resolve_entity_type(
+                &"https://blockprotocol.org/@alice/types/entity-type/person/v/1"
+                    .parse()
+                    .expect("invalid versioned URL"),
+            )
+            .expect("could not resolve entity type");
+        assert_eq!(link_entity_types(&person), [
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1"
+                .parse::<VersionedUrl>()
+                .expect("invalid versioned URL")
+        ]);
+
+        let mut generator = RustCodeGenerator::default();
+        generator.add_entity_type(&person);
+        generator.add_entity_type(&person);
+        let output = generator.finish();
+
+        assert_eq!(output.matches("pub struct Person {").count(), 1);
+        for expected in [
+            "    #[serde(rename = \"https://blockprotocol.org/@alice/types/property-type/name/\", \
+             default, skip_serializing_if = \"Option::is_none\")]\n    pub name: Option<String>,\n",
+            "    pub const ENTITY_TYPE_ID: &'static str = \
+             \"https://blockprotocol.org/@alice/types/entity-type/person/v/1\";\n",
+            "        (\"https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1\", \
+             &[\"https://blockprotocol.org/@alice/types/entity-type/person/v/1\"]),\n",
+            "impl TryFrom<EntityProperties> for Person {\n",
+            "impl TryFrom<Person> for EntityProperties {\n",
+        ] {
+            assert!(output.contains(expected), "`{expected}` is missing in:\n{output}");
+        }
+    }
+
+    #[test]
+    fn nested_properties_and_enums() {
+        let book = ResolvedEntityType {
+            entity_type_id: "https://example.com/entity-type/book/v/1"
+                .parse()
+                .expect("invalid versioned URL"),
+            inherits_from: Vec::new(),
+            schema: json!({
+                "title": "Book",
+                "properties": {
+                    "https://example.com/property-type/type/": {
+                        "title": "Type",
+                        "oneOf": [
+                            { "title": "Text", "type": "string" },
+                            { "title": "Number", "type": "number" },
+                        ],
+                    },
+                    "https://example.com/property-type/author/": {
+                        "type": "array",
+                        "items": {
+                            "title": "Author",
+                            "oneOf": [{
+                                "type": "object",
+                                "properties": {
+                                    "https://example.com/property-type/name/": {
+                                        "title": "Name",
+                                        "oneOf": [{ "type": "string" }],
+                                    },
+                                },
+                                "required": ["https://example.com/property-type/name/"],
+                            }],
+                        },
+                    },
+                },
+                "required": ["https://example.com/property-type/type/"],
+                "links": {},
+            }),
+        };
+
+        let mut generator = RustCodeGenerator::default();
+        generator.add_entity_type(&book);
+        let output = generator.finish();
+
+        for expected in [
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n#[serde(untagged)]\npub \
+             enum BookType {\n    Text(String),\n    Number(f64),\n}\n",
+            "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct BookAuthor \
+             {\n    #[serde(rename = \"https://example.com/property-type/name/\")]\n    pub name: \
+             String,\n}\n",
+            "    #[serde(rename = \"https://example.com/property-type/type/\")]\n    pub r#type: \
+             BookType,\n",
+            "    pub author: Option<Vec<BookAuthor>>,\n",
+            "    pub const LINKS: &'static [(&'static str, &'static [&'static str])] = &[\n    ];\n",
+        ] {
+            assert!(output.contains(expected), "`{expected}` is missing in:\n{output}");
+        }
+    }
+
+    #[test]
+    fn names_without_valid_identifiers() {
+        let entity_type = ResolvedEntityType {
+            entity_type_id: "https://example.com/entity-type/entity-properties/v/1"
+                .parse()
+                .expect("invalid versioned URL"),
+            inherits_from: Vec::new(),
+            schema: json!({
+                "title": "Entity Properties",
+                "properties": {
+                    "https://example.com/property-type/nihongo/": {
+                        "title": "日本語",
+                        "oneOf": [
+                            { "title": "2D", "type": "string" },
+                            { "title": "★", "type": "number" },
+                            { "title": "Self", "type": "boolean" },
+                        ],
+                    },
+                    "https://example.com/property-type/★/": {
+                        "title": "★",
+                        "oneOf": [{ "type": "string" }],
+                    },
+                },
+                "required": [
+                    "https://example.com/property-type/nihongo/",
+                    "https://example.com/property-type/★/",
+                ],
+                "links": {},
+            }),
+        };
+
+        let mut generator = RustCodeGenerator::default();
+        generator.add_entity_type(&entity_type);
+        let output = generator.finish();
+
+        for expected in [
+            "pub struct EntityProperties2 {\n",
+            "impl TryFrom<EntityProperties> for EntityProperties2 {\n",
+            "    pub nihongo: EntityProperties2Nihongo,\n",
+            "    pub property: String,\n",
+            "pub enum EntityProperties2Nihongo {\n    Variant2D(String),\n    Variant(f64),\n    \
+             VariantSelf(bool),\n}\n",
+        ] {
+            assert!(
+                output.contains(expected),
+                "`{expected}` is missing in:\n{output}"
+            );
+        }
+    }
+}
@@ -0,0 +1,797 @@
+//! Compatibility analysis between two versions of an ontology type.
+//!
+//! Updating an ontology type creates a new version of it. Every change between the two versions is
+//! classified as [`Additive`], if anything valid against the old version is still valid against
+//! the new version, or as [`Breaking`] otherwise.
+//!
+//! [`Additive`]: OntologyTypeChangeKind::Additive
+//! [`Breaking`]: OntologyTypeChangeKind::Breaking
+
+use std::{borrow::Cow, collections::BTreeSet};
+
+use error_stack::{Report, Result};
+use futures::TryStreamExt;
+use graph_types::{
+    knowledge::entity::Entity,
+    ontology::{EntityTypeWithMetadata, OntologyTypeVersion, PropertyTypeWithMetadata},
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use type_system::{url::VersionedUrl, EntityType, PropertyType};
+use utoipa::ToSchema;
+
+use crate::{
+    knowledge::EntityQueryPath,
+    ontology::{
+        validation::{references, schema_value, OntologySchemas, SchemaReference},
+        EntityTypeQueryPath, PropertyTypeQueryPath,
+    },
+    store::{
+        crud::Read,
+        error::OntologyVersionDoesNotExist,
+        query::{Filter, FilterExpression, Parameter},
+        QueryError, Store,
+    },
+    subgraph::{
+        edges::{EdgeDirection, OntologyEdgeKind, SharedEdgeKind},
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxesUnresolved,
+            VariableTemporalAxisUnresolved,
+        },
+    },
+};
+
+/// Describes if data, which is valid against the old version of an ontology type, stays valid
+/// against the new version.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum OntologyTypeChangeKind {
+    /// Anything valid against the old version is valid against the new version as well.
+    Additive,
+    /// Data valid against the old version may be invalid against the new version.
+    Breaking,
+}
+
+/// A single change between two versions of an ontology type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyTypeChange {
+    /// The [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) to the changed value in the
+    /// schema.
+    pub path: String,
+    pub kind: OntologyTypeChangeKind,
+    pub description: String,
+}
+
+/// The result of comparing an ontology type with the update of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyTypeCompatibility {
+    pub changes: Vec<OntologyTypeChange>,
+    /// The number of current entities, which are valid against the old version of the ontology
+    /// type but would fail to validate against the new version.
+    pub invalid_entities: usize,
+}
+
+impl OntologyTypeCompatibility {
+    /// Returns if any change is breaking or any existing entity would become invalid.
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.invalid_entities > 0
+            || self
+                .changes
+                .iter()
+                .any(|change| change.kind == OntologyTypeChangeKind::Breaking)
+    }
+}
+
+/// Decides how the REST API handles updates of ontology types, which contain breaking changes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OntologyUpdatePolicy {
+    /// Breaking updates are accepted.
+    #[default]
+    AllowBreaking,
+    /// Breaking updates are rejected unless they are explicitly forced.
+    RejectBreaking,
+}
+
+/// Compares two versions of an [`EntityType`] and classifies each change.
+#[must_use]
+pub fn compare_entity_types(old: &EntityType, new: &EntityType) -> Vec<OntologyTypeChange> {
+    let mut changes = Changes::default();
+    changes.compare_entity_types(&schema_value(old), &schema_value(new));
+    changes.0
+}
+
+/// Compares two versions of a [`PropertyType`] and classifies each change.
+#[must_use]
+pub fn compare_property_types(old: &PropertyType, new: &PropertyType) -> Vec<OntologyTypeChange> {
+    let mut changes = Changes::default();
+    changes.compare_property_types(&schema_value(old), &schema_value(new));
+    changes.0
+}
+
+/// Checks the compatibility of updating the entity type `type_to_update` to `entity_type`.
+///
+/// The number of invalid entities is determined by validating the current entities of
+/// `type_to_update` and of the entity types inheriting from it against `entity_type`.
+///
+/// # Errors
+///
+/// - [`OntologyVersionDoesNotExist`], if `type_to_update` does not exist
+/// - if any ontology type referenced by `entity_type` does not exist
+/// - if reading from the store fails
+pub async fn check_entity_type_update<S: Store>(
+    store: &S,
+    type_to_update: &VersionedUrl,
+    entity_type: &EntityType,
+) -> Result<OntologyTypeCompatibility, QueryError> {
+    let old_entity_type = Read::<EntityTypeWithMetadata>::read_vec(
+        store,
+        &Filter::for_versioned_url(type_to_update),
+        None,
+    )
+    .await?
+    .pop()
+    .ok_or_else(|| {
+        Report::new(OntologyVersionDoesNotExist)
+            .attach_printable(type_to_update.clone())
+            .change_context(QueryError)
+    })?;
+    let changes = compare_entity_types(&old_entity_type.schema, entity_type);
+
+    let mut updated = OntologySchemas::default();
+    updated
+        .entity_types
+        .insert(type_to_update.clone(), schema_value(entity_type));
+    updated.read(store, references(entity_type)).await?;
+
+    let invalid_entities = count_invalid_entities(
+        store,
+        &Filter::All(vec![
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                    edge_kind: SharedEdgeKind::IsOfType,
+                    path: EntityTypeQueryPath::BaseUrl,
+                    inheritance_depth: None,
+                })),
+                Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                    type_to_update.base_url.as_str(),
+                )))),
+            ),
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                    edge_kind: SharedEdgeKind::IsOfType,
+                    path: EntityTypeQueryPath::Version,
+                    inheritance_depth: None,
+                })),
+                Some(FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                    OntologyTypeVersion::new(type_to_update.version),
+                ))),
+            ),
+        ]),
+        updated,
+    )
+    .await?;
+
+    Ok(OntologyTypeCompatibility {
+        changes,
+        invalid_entities,
+    })
+}
+
+/// Checks the compatibility of updating the property type `type_to_update` to `property_type`.
+///
+/// The number of invalid entities is determined by validating the current entities, whose entity
+/// type is constrained by `type_to_update`, as if their entity type referred to `property_type`
+/// instead. This includes entity types, which inherit the property type from a parent, and entity
+/// types constrained by a property type, which contains `type_to_update` as nested property type.
+///
+/// # Errors
+///
+/// - [`OntologyVersionDoesNotExist`], if `type_to_update` does not exist
+/// - if any ontology type referenced by `property_type` does not exist
+/// - if reading from the store fails
+pub async fn check_property_type_update<S: Store>(
+    store: &S,
+    type_to_update: &VersionedUrl,
+    property_type: &PropertyType,
+) -> Result<OntologyTypeCompatibility, QueryError> {
+    let old_property_type = Read::<PropertyTypeWithMetadata>::read_vec(
+        store,
+        &Filter::for_versioned_url(type_to_update),
+        None,
+    )
+    .await?
+    .pop()
+    .ok_or_else(|| {
+        Report::new(OntologyVersionDoesNotExist)
+            .attach_printable(type_to_update.clone())
+            .change_context(QueryError)
+    })?;
+    let changes = compare_property_types(&old_property_type.schema, property_type);
+
+    let mut updated = OntologySchemas::default();
+    updated
+        .property_types
+        .insert(type_to_update.clone(), schema_value(property_type));
+    updated.read(store, references(property_type)).await?;
+
+    let property_types = nesting_property_types(store, type_to_update).await?;
+    let invalid_entities = count_invalid_entities(
+        store,
+        &Filter::Any(
+            property_types
+                .iter()
+                .map(|property_type| {
+                    Filter::All(vec![
+                        Filter::Equal(
+                            Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                                edge_kind: SharedEdgeKind::IsOfType,
+                                path: EntityTypeQueryPath::PropertyTypeEdge {
+                                    edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                                    path: PropertyTypeQueryPath::BaseUrl,
+                                    inheritance_depth: None,
+                                },
+                                inheritance_depth: Some(0),
+                            })),
+                            Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                                property_type.base_url.as_str(),
+                            )))),
+                        ),
+                        Filter::Equal(
+                            Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                                edge_kind: SharedEdgeKind::IsOfType,
+                                path: EntityTypeQueryPath::PropertyTypeEdge {
+                                    edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                                    path: PropertyTypeQueryPath::Version,
+                                    inheritance_depth: None,
+                                },
+                                inheritance_depth: Some(0),
+                            })),
+                            Some(FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                                OntologyTypeVersion::new(property_type.version),
+                            ))),
+                        ),
+                    ])
+                })
+                .collect(),
+        ),
+        updated,
+    )
+    .await?;
+
+    Ok(OntologyTypeCompatibility {
+        changes,
+        invalid_entities,
+    })
+}
+
+/// Returns `property_type` and all property types, which contain it as a nested property type at
+/// any depth.
+async fn nesting_property_types<S: Store>(
+    store: &S,
+    property_type: &VersionedUrl,
+) -> Result<Vec<VersionedUrl>, QueryError> {
+    let mut property_types = vec![property_type.clone()];
+    let mut next = 0;
+    while let Some(nested) = property_types.get(next).cloned() {
+        next += 1;
+
+        let nesting = Read::<PropertyTypeWithMetadata>::read_vec(
+            store,
+            &Filter::All(vec![
+                Filter::Equal(
+                    Some(FilterExpression::Path(
+                        PropertyTypeQueryPath::PropertyTypeEdge {
+                            edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                            path: Box::new(PropertyTypeQueryPath::BaseUrl),
+                            direction: EdgeDirection::Outgoing,
+                        },
+                    )),
+                    Some(FilterExpression::Parameter(Parameter::Text(Cow::Borrowed(
+                        nested.base_url.as_str(),
+                    )))),
+                ),
+                Filter::Equal(
+                    Some(FilterExpression::Path(
+                        PropertyTypeQueryPath::PropertyTypeEdge {
+                            edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
+                            path: Box::new(PropertyTypeQueryPath::Version),
+                            direction: EdgeDirection::Outgoing,
+                        },
+                    )),
+                    Some(FilterExpression::Parameter(Parameter::OntologyTypeVersion(
+                        OntologyTypeVersion::new(nested.version),
+                    ))),
+                ),
+            ]),
+            None,
+        )
+        .await?;
+        for property_type in nesting {
+            if !property_types.contains(property_type.schema.id()) {
+                property_types.push(property_type.schema.id().clone());
+            }
+        }
+    }
+
+    Ok(property_types)
+}
+
+/// Counts the current entities matching `filter`, which are valid against the ontology types in
+/// the store but invalid against the `updated` schemas.
+///
+/// `updated` contains the schema of the updated ontology type under the URL of the type to be
+/// updated, so it replaces the old version wherever it is referenced.
+async fn count_invalid_entities<S: Store>(
+    store: &S,
+    filter: &Filter<'_, Entity>,
+    mut updated: OntologySchemas,
+) -> Result<usize, QueryError> {
+    let temporal_axes = QueryTemporalAxesUnresolved::DecisionTime {
+        pinned: PinnedTemporalAxisUnresolved::new(None),
+        variable: VariableTemporalAxisUnresolved::new(None, None),
+    }
+    .resolve();
+
+    let entities = Read::<Entity>::read(store, filter, Some(&temporal_axes)).await?;
+    futures::pin_mut!(entities);
+
+    let mut current = OntologySchemas::default();
+    let mut invalid_entities = 0;
+    while let Some(entity) = entities.try_next().await? {
+        let entity_type_id = entity.metadata.entity_type_id();
+        let pending = vec![SchemaReference::EntityType(entity_type_id.clone())];
+        current.read(store, pending.clone()).await?;
+        updated.read(store, pending).await?;
+
+        let properties = serde_json::to_value(&entity.properties).unwrap_or(Value::Null);
+        if current
+            .validate_entity(entity_type_id, &properties)
+            .is_empty()
+            && !updated
+                .validate_entity(entity_type_id, &properties)
+                .is_empty()
+        {
+            invalid_entities += 1;
+        }
+    }
+
+    Ok(invalid_entities)
+}
+
+/// Collects the changes between two schemas.
+#[derive(Default)]
+struct Changes(Vec<OntologyTypeChange>);
+
+impl Changes {
+    fn push(&mut self, kind: OntologyTypeChangeKind, path: &[&str], description: String) {
+        let path = path.iter().fold(String::new(), |mut pointer, segment| {
+            pointer.push('/');
+            pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+            pointer
+        });
+        self.0.push(OntologyTypeChange {
+            path,
+            kind,
+            description,
+        });
+    }
+
+    fn additive(&mut self, path: &[&str], description: String) {
+        self.push(OntologyTypeChangeKind::Additive, path, description);
+    }
+
+    fn breaking(&mut self, path: &[&str], description: String) {
+        self.push(OntologyTypeChangeKind::Breaking, path, description);
+    }
+
+    fn compare_entity_types(&mut self, old: &Value, new: &Value) {
+        let old_required = required(old);
+        let new_required = required(new);
+
+        for key in keys(Some(old), Some(new)) {
+            let (old_value, new_value) = (old.get(key), new.get(key));
+            if old_value == new_value {
+                continue;
+            }
+            match key {
+                "$id" | "required" => {}
+                "title" | "description" | "examples" => {
+                    self.additive(&[key], format!("`{key}` was changed"));
+                }
+                "allOf" => {
+                    let (old_parents, new_parents) =
+                        (references_in(old_value), references_in(new_value));
+                    for parent in old_parents.difference(&new_parents) {
+                        self.breaking(&[key], format!("no longer inherits from `{parent}`"));
+                    }
+                    for parent in new_parents.difference(&old_parents) {
+                        self.breaking(&[key], format!("inherits from `{parent}`"));
+                    }
+                }
+                "properties" => {
+                    for base_url in keys(old_value, new_value) {
+                        let path = [key, base_url];
+                        match (
+                            old_value.and_then(|properties| properties.get(base_url)),
+                            new_value.and_then(|properties| properties.get(base_url)),
+                        ) {
+                            (Some(_), None) => {
+                                self.breaking(&path, format!("`{base_url}` was removed"));
+                            }
+                            (None, Some(_)) if new_required.contains(base_url) => {
+                                self.breaking(&path, format!("`{base_url}` was added as required"));
+                            }
+                            (None, Some(_)) => {
+                                self.additive(&path, format!("`{base_url}` was added"));
+                            }
+                            (Some(old), Some(new)) if old != new => {
+                                self.compare_property_references(&path, old, new);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                "links" => {
+                    for link_type in keys(old_value, new_value) {
+                        let path = [key, link_type];
+                        match (
+                            old_value.and_then(|links| links.get(link_type)),
+                            new_value.and_then(|links| links.get(link_type)),
+                        ) {
+                            (Some(_), None) => {
+                                self.breaking(&path, format!("`{link_type}` was removed"));
+                            }
+                            (None, Some(new)) if min_items(new) > 0 => {
+                                self.breaking(
+                                    &path,
+                                    format!("`{link_type}` was added as required"),
+                                );
+                            }
+                            (None, Some(_)) => {
+                                self.additive(&path, format!("`{link_type}` was added"));
+                            }
+                            (Some(old), Some(new)) if old != new => {
+                                self.compare_arrays(&path, old, new, Self::compare_destinations);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => self.breaking(&[key], format!("`{key}` was changed")),
+            }
+        }
+
+        let has_property = |schema: &Value, base_url: &str| {
+            schema
+                .get("properties")
+                .and_then(|properties| properties.get(base_url))
+                .is_some()
+        };
+        for base_url in new_required.difference(&old_required) {
+            if has_property(old, base_url) {
+                self.breaking(&["required"], format!("`{base_url}` became required"));
+            }
+        }
+        for base_url in old_required.difference(&new_required) {
+            if has_property(new, base_url) {
+                self.additive(&["required"], format!("`{base_url}` is no longer required"));
+            }
+        }
+    }
+
+    /// Compares the values of a property, which are either a reference to a property type or an
+    /// array of them.
+    fn compare_property_references(&mut self, path: &[&str], old: &Value, new: &Value) {
+        match (old.get("$ref"), new.get("$ref")) {
+            (Some(old), Some(new)) => {
+                self.breaking(path, format!("{old} was replaced by {new}"));
+            }
+            (Some(_), None) => self.breaking(path, "the value became an array".to_owned()),
+            (None, Some(_)) => self.breaking(path, "the value is no longer an array".to_owned()),
+            (None, None) => self.compare_arrays(path, old, new, Self::compare_property_references),
+        }
+    }
+
+    /// Compares the allowed destinations of a link.
+    ///
+    /// A link without `oneOf` may point to any entity.
+    fn compare_destinations(&mut self, path: &[&str], old: &Value, new: &Value) {
+        let mut path = path.to_vec();
+        path.push("oneOf");
+        match (old.get("oneOf"), new.get("oneOf")) {
+            (None, Some(_)) => self.breaking(&path, "the destinations were restricted".to_owned()),
+            (Some(_), None) => {
+                self.additive(
+                    &path,
+                    "the destinations are no longer restricted".to_owned(),
+                );
+            }
+            (old, new) => {
+                let (old, new) = (references_in(old), references_in(new));
+                for destination in old.difference(&new) {
+                    self.breaking(&path, format!("`{destination}` is no longer a destination"));
+                }
+                for destination in new.difference(&old) {
+                    self.additive(&path, format!("`{destination}` became a destination"));
+                }
+            }
+        }
+    }
+
+    /// Compares two array schemas and their items using `compare_items`.
+    fn compare_arrays(
+        &mut self,
+        path: &[&str],
+        old: &Value,
+        new: &Value,
+        compare_items: fn(&mut Self, &[&str], &Value, &Value),
+    ) {
+        for key in keys(Some(old), Some(new)) {
+            let (old_value, new_value) = (old.get(key), new.get(key));
+            if old_value == new_value {
+                continue;
+            }
+            let mut path = path.to_vec();
+            path.push(key);
+            match (key, old_value, new_value) {
+                ("items", Some(old), Some(new)) => compare_items(self, &path, old, new),
+                ("minItems", ..) => {
+                    let (old, new) = (min_items(old), min_items(new));
+                    if new > old {
+                        self.breaking(&path, format!("the minimum was raised from {old} to {new}"));
+                    } else {
+                        self.additive(
+                            &path,
+                            format!("the minimum was lowered from {old} to {new}"),
+                        );
+                    }
+                }
+                ("maxItems", ..) => match (max_items(old), max_items(new)) {
+                    (_, None) => self.additive(&path, "the maximum was removed".to_owned()),
+                    (None, Some(new)) => {
+                        self.breaking(&path, format!("the maximum was set to {new}"));
+                    }
+                    (Some(old), Some(new)) if new < old => {
+                        self.breaking(
+                            &path,
+                            format!("the maximum was lowered from {old} to {new}"),
+                        );
+                    }
+                    (Some(old), Some(new)) => {
+                        self.additive(&path, format!("the maximum was raised from {old} to {new}"));
+                    }
+                },
+                _ => self.breaking(&path, format!("`{key}` was changed")),
+            }
+        }
+    }
+
+    fn compare_property_types(&mut self, old: &Value, new: &Value) {
+        for key in keys(Some(old), Some(new)) {
+            let (old_value, new_value) = (old.get(key), new.get(key));
+            if old_value == new_value {
+                continue;
+            }
+            match key {
+                "$id" => {}
+                "title" | "description" => {
+                    self.additive(&[key], format!("`{key}` was changed"));
+                }
+                "oneOf" => {
+                    let variants = |value: Option<&Value>| {
+                        value
+                            .and_then(Value::as_array)
+                            .map_or(&[][..], Vec::as_slice)
+                            .to_vec()
+                    };
+                    let (old_variants, new_variants) = (variants(old_value), variants(new_value));
+                    for variant in &old_variants {
+                        if !new_variants.contains(variant) {
+                            self.breaking(
+                                &[key],
+                                format!("{variant} is no longer a possible value"),
+                            );
+                        }
+                    }
+                    for variant in &new_variants {
+                        if !old_variants.contains(variant) {
+                            self.additive(&[key], format!("{variant} became a possible value"));
+                        }
+                    }
+                }
+                _ => self.breaking(&[key], format!("`{key}` was changed")),
+            }
+        }
+    }
+}
+
+/// Returns the keys of both objects in order.
+fn keys<'a>(old: Option<&'a Value>, new: Option<&'a Value>) -> BTreeSet<&'a str> {
+    [old, new]
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+        .flat_map(Map::keys)
+        .map(String::as_str)
+        .collect()
+}
+
+/// Returns the property types, which are required by the entity type schema.
+fn required(schema: &Value) -> BTreeSet<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect()
+}
+
+/// Returns the `$ref`s of the objects in the array `value`.
+fn references_in(value: Option<&Value>) -> BTreeSet<&str> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|reference| reference.get("$ref").and_then(Value::as_str))
+        .collect()
+}
+
+fn min_items(schema: &Value) -> u64 {
+    schema.get("minItems").and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn max_items(schema: &Value) -> Option<u64> {
+    schema.get("maxItems").and_then(Value::as_u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use type_system::repr;
+
+    use super::*;
+
+    const NAME: &str = "https://blockprotocol.org/@alice/types/property-type/name/";
+    const BLURB: &str = "https://blockprotocol.org/@alice/types/property-type/blurb/";
+    const WRITTEN_BY: &str = "https://blockprotocol.org/@alice/types/entity-type/written-by/v/1";
+
+    fn book(update: impl FnOnce(&mut Value)) -> EntityType {
+        let mut schema: Value =
+            serde_json::from_str(graph_test_data::entity_type::BOOK_V1).expect("invalid JSON");
+        update(&mut schema);
+        EntityType::try_from(
+            serde_json::from_value::<repr::EntityType>(schema).expect("invalid representation"),
+        )
+        .expect("invalid entity type")
+    }
+
+    fn name(update: impl FnOnce(&mut Value)) -> PropertyType {
+        let mut schema: Value =
+            serde_json::from_str(graph_test_data::property_type::NAME_V1).expect("invalid JSON");
+        update(&mut schema);
+        PropertyType::try_from(
+            serde_json::from_value::<repr::PropertyType>(schema).expect("invalid representation"),
+        )
+        .expect("invalid property type")
+    }
+
+    /// Returns the kinds of the changes between the original and the updated book.
+    fn book_changes(update: impl FnOnce(&mut Value)) -> Vec<(String, OntologyTypeChangeKind)> {
+        compare_entity_types(&book(|_| {}), &book(update))
+            .into_iter()
+            .map(|change| (change.path, change.kind))
+            .collect()
+    }
+
+    fn name_changes(update: impl FnOnce(&mut Value)) -> Vec<(String, OntologyTypeChangeKind)> {
+        compare_property_types(&name(|_| {}), &name(update))
+            .into_iter()
+            .map(|change| (change.path, change.kind))
+            .collect()
+    }
+
+    #[test]
+    fn unchanged_entity_type() {
+        assert!(book_changes(|_| {}).is_empty());
+    }
+
+    #[test]
+    fn changed_title_is_additive() {
+        assert_eq!(book_changes(|book| book["title"] = json!("Novel")), [(
+            "/title".to_owned(),
+            OntologyTypeChangeKind::Additive
+        )]);
+    }
+
+    #[test]
+    fn removed_property_is_breaking() {
+        assert_eq!(
+            book_changes(|book| {
+                book["properties"]
+                    .as_object_mut()
+                    .expect("should be an object")
+                    .remove(BLURB);
+            }),
+            [(
+                "/properties/https:~1~1blockprotocol.org~1@alice~1types~1property-type~1blurb~1"
+                    .to_owned(),
+                OntologyTypeChangeKind::Breaking
+            )]
+        );
+    }
+
+    #[test]
+    fn required_properties() {
+        assert_eq!(
+            book_changes(|book| book["required"] = json!([NAME, BLURB])),
+            [("/required".to_owned(), OntologyTypeChangeKind::Breaking)]
+        );
+        assert_eq!(
+            book_changes(|book| {
+                book.as_object_mut()
+                    .expect("should be an object")
+                    .remove("required");
+            }),
+            [("/required".to_owned(), OntologyTypeChangeKind::Additive)]
+        );
+    }
+
+    #[test]
+    fn link_destinations() {
+        let destinations = format!("/links/{}/items/oneOf", WRITTEN_BY.replace('/', "~1"));
+        assert_eq!(
+            book_changes(|book| {
+                book["links"][WRITTEN_BY]["items"]
+                    .as_object_mut()
+                    .expect("should be an object")
+                    .remove("oneOf");
+            }),
+            [(destinations.clone(), OntologyTypeChangeKind::Additive)]
+        );
+        assert_eq!(
+            book_changes(|book| {
+                book["links"][WRITTEN_BY]["items"]["oneOf"] = json!([{
+                    "$ref": "https://blockprotocol.org/@alice/types/entity-type/organization/v/1"
+                }]);
+            }),
+            [
+                (destinations.clone(), OntologyTypeChangeKind::Breaking),
+                (destinations, OntologyTypeChangeKind::Additive)
+            ]
+        );
+    }
+
+    #[test]
+    fn added_value_is_additive() {
+        assert_eq!(
+            name_changes(|name| {
+                name["oneOf"]
+                    .as_array_mut()
+                    .expect("should be an array")
+                    .push(json!({
+                        "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/number/v/1"
+                    }));
+            }),
+            [("/oneOf".to_owned(), OntologyTypeChangeKind::Additive)]
+        );
+    }
+
+    #[test]
+    fn replaced_value_is_breaking() {
+        assert_eq!(
+            name_changes(|name| {
+                name["oneOf"] = json!([{
+                    "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/number/v/1"
+                }]);
+            }),
+            [
+                ("/oneOf".to_owned(), OntologyTypeChangeKind::Breaking),
+                ("/oneOf".to_owned(), OntologyTypeChangeKind::Additive)
+            ]
+        );
+    }
+}
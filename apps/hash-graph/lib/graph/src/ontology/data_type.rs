@@ -21,7 +21,7 @@ use crate::{
 /// [`DataType`]: type_system::DataType
 // TODO: Adjust enum and docs when adding non-primitive data types
 //   see https://app.asana.com/0/1200211978612931/1202464168422955/f
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DataTypeQueryPath<'p> {
     /// The [`BaseUrl`] of the [`DataType`].
     ///
@@ -137,6 +137,24 @@ pub enum DataTypeQueryPath<'p> {
     /// [`RecordArchivedById`]: graph_types::provenance::RecordArchivedById
     /// [`ProvenanceMetadata`]: graph_types::provenance::ProvenanceMetadata
     RecordArchivedById,
+    /// The [`DeprecatedById`] of the [`OntologyTypeDeprecation`] belonging to the [`DataType`].
+    ///
+    /// The value is `null` if the [`DataType`] is not deprecated, so deprecated types can be
+    /// filtered by comparing this path with `null`:
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # use graph::ontology::DataTypeQueryPath;
+    /// let path = DataTypeQueryPath::deserialize(json!(["deprecatedById"]))?;
+    /// assert_eq!(path, DataTypeQueryPath::DeprecatedById);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`DataType`]: type_system::DataType
+    /// [`DeprecatedById`]: graph_types::provenance::DeprecatedById
+    /// [`OntologyTypeDeprecation`]: graph_types::ontology::OntologyTypeDeprecation
+    DeprecatedById,
     /// Corresponds to [`DataType::title()`].
     ///
     /// ```rust
@@ -198,6 +216,12 @@ pub enum DataTypeQueryPath<'p> {
     },
     /// Only used internally and not available for deserialization.
     AdditionalMetadata,
+    /// Only used internally and not available for deserialization.
+    DeprecatedAt,
+    /// Only used internally and not available for deserialization.
+    SuccessorBaseUrl,
+    /// Only used internally and not available for deserialization.
+    SuccessorVersion,
 }
 
 impl OntologyQueryPath for DataTypeQueryPath<'_> {
@@ -244,12 +268,14 @@ impl QueryPath for DataTypeQueryPath<'_> {
             Self::OntologyId
             | Self::OwnedById
             | Self::RecordCreatedById
-            | Self::RecordArchivedById => ParameterType::Uuid,
+            | Self::RecordArchivedById
+            | Self::DeprecatedById => ParameterType::Uuid,
             Self::Schema(_) | Self::AdditionalMetadata => ParameterType::Object,
-            Self::BaseUrl => ParameterType::BaseUrl,
+            Self::BaseUrl | Self::SuccessorBaseUrl => ParameterType::BaseUrl,
             Self::VersionedUrl => ParameterType::VersionedUrl,
             Self::TransactionTime => ParameterType::TimeInterval,
-            Self::Version => ParameterType::OntologyTypeVersion,
+            Self::DeprecatedAt => ParameterType::Timestamp,
+            Self::Version | Self::SuccessorVersion => ParameterType::OntologyTypeVersion,
             Self::Description | Self::Title | Self::Type => ParameterType::Text,
             Self::PropertyTypeEdge { path, .. } => path.expected_type(),
         }
@@ -267,12 +293,16 @@ impl fmt::Display for DataTypeQueryPath<'_> {
             Self::OwnedById => fmt.write_str("ownedById"),
             Self::RecordCreatedById => fmt.write_str("recordCreatedById"),
             Self::RecordArchivedById => fmt.write_str("recordArchivedById"),
+            Self::DeprecatedById => fmt.write_str("deprecatedById"),
             Self::Schema(Some(path)) => write!(fmt, "schema.{path}"),
             Self::Schema(None) => fmt.write_str("schema"),
             Self::Title => fmt.write_str("title"),
             Self::Description => fmt.write_str("description"),
             Self::Type => fmt.write_str("type"),
             Self::AdditionalMetadata => fmt.write_str("additionalMetadata"),
+            Self::DeprecatedAt => fmt.write_str("deprecatedAt"),
+            Self::SuccessorBaseUrl => fmt.write_str("successorBaseUrl"),
+            Self::SuccessorVersion => fmt.write_str("successorVersion"),
             #[expect(
                 clippy::use_debug,
                 reason = "We don't have a `Display` impl for `OntologyEdgeKind` and this should \
@@ -296,6 +326,7 @@ pub enum DataTypeQueryToken {
     OwnedById,
     RecordCreatedById,
     RecordArchivedById,
+    DeprecatedById,
     Title,
     Description,
     Type,
@@ -312,7 +343,7 @@ pub struct DataTypeQueryPathVisitor {
 impl DataTypeQueryPathVisitor {
     pub const EXPECTING: &'static str = "one of `baseUrl`, `version`, `versionedUrl`, \
                                          `ownedById`, `recordCreatedById`, `recordArchivedById`, \
-                                         `title`, `description`, `type`";
+                                         `deprecatedById`, `title`, `description`, `type`";
 
     #[must_use]
     pub const fn new(position: usize) -> Self {
@@ -340,6 +371,7 @@ impl<'de> Visitor<'de> for DataTypeQueryPathVisitor {
             DataTypeQueryToken::OwnedById => DataTypeQueryPath::OwnedById,
             DataTypeQueryToken::RecordCreatedById => DataTypeQueryPath::RecordCreatedById,
             DataTypeQueryToken::RecordArchivedById => DataTypeQueryPath::RecordArchivedById,
+            DataTypeQueryToken::DeprecatedById => DataTypeQueryPath::DeprecatedById,
             DataTypeQueryToken::BaseUrl => DataTypeQueryPath::BaseUrl,
             DataTypeQueryToken::VersionedUrl => DataTypeQueryPath::VersionedUrl,
             DataTypeQueryToken::Version => DataTypeQueryPath::Version,
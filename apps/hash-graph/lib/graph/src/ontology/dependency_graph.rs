@@ -0,0 +1,552 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use error_stack::{Report, Result};
+use graph_types::ontology::{CustomOntologyMetadata, OntologyType, OntologyTypeVersion};
+use serde::Deserialize;
+use serde_json::Value;
+use type_system::url::{BaseUrl, VersionedUrl};
+use utoipa::ToSchema;
+
+use crate::{
+    ontology::validation::schema_value,
+    store::{query::Filter, DataTypeStore, EntityTypeStore, PropertyTypeStore, QueryError},
+    subgraph::{
+        edges::{
+            EdgeDirection, GraphResolveDepths, OntologyEdgeKind, OntologyEdgeResolveDepth,
+            OutwardEdge,
+        },
+        identifier::{DataTypeVertexId, EdgeEndpoint, EntityTypeVertexId, PropertyTypeVertexId},
+        query::StructuralQuery,
+        temporal_axes::QueryTemporalAxesUnresolved,
+        Subgraph,
+    },
+};
+
+/// The base URL of the entity type every link entity type inherits from.
+const LINK_ENTITY_TYPE_BASE_URL: &str =
+    "https://blockprotocol.org/@blockprotocol/types/entity-type/link/";
+
+/// The language an [`OntologyDependencyGraph`] is rendered in.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "camelCase")]
+pub enum OntologyGraphFormat {
+    /// A Graphviz DOT digraph.
+    #[default]
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+}
+
+impl OntologyGraphFormat {
+    /// Returns the media type of a graph rendered in this format.
+    #[must_use]
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Dot => "text/vnd.graphviz",
+            Self::Mermaid => "text/plain",
+        }
+    }
+}
+
+/// The kind of an ontology type in an [`OntologyDependencyGraph`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum OntologyTypeKind {
+    DataType,
+    PropertyType,
+    EntityType,
+    LinkType,
+}
+
+impl OntologyTypeKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::DataType => "data type",
+            Self::PropertyType => "property type",
+            Self::EntityType => "entity type",
+            Self::LinkType => "link type",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct OntologyGraphNode {
+    kind: OntologyTypeKind,
+    title: Option<String>,
+    owned: bool,
+}
+
+impl OntologyGraphNode {
+    fn new<T: OntologyType + Clone>(
+        kind: OntologyTypeKind,
+        schema: &T,
+        custom: &CustomOntologyMetadata,
+    ) -> Self {
+        Self {
+            kind,
+            title: schema_value(schema)
+                .get("title")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+            owned: matches!(custom, CustomOntologyMetadata::Owned { .. }),
+        }
+    }
+
+    /// Returns the lines of the label of the node.
+    fn label(&self, url: &VersionedUrl) -> [String; 3] {
+        [
+            self.title.clone().unwrap_or_else(|| url.to_string()),
+            url.to_string(),
+            format!(
+                "{}, {}",
+                self.kind.as_str(),
+                if self.owned { "owned" } else { "external" }
+            ),
+        ]
+    }
+}
+
+/// The ontology types reachable from a set of root types and the edges between them.
+///
+/// All edges of the [`OntologyEdgeKind`]s are followed until no new ontology types are
+/// discovered, so the graph contains everything the root types depend on.
+#[derive(Debug, Default)]
+pub struct OntologyDependencyGraph {
+    nodes: HashMap<VersionedUrl, OntologyGraphNode>,
+    edges: HashSet<(VersionedUrl, OntologyEdgeKind, VersionedUrl)>,
+}
+
+impl OntologyDependencyGraph {
+    /// Reads the ontology types reachable from `roots` from the `store`.
+    ///
+    /// The roots may be data types, property types, or entity types.
+    ///
+    /// # Errors
+    ///
+    /// - if any of the `roots` does not exist
+    /// - if reading from the `store` fails
+    pub async fn read<S>(store: &S, roots: &[VersionedUrl]) -> Result<Self, QueryError>
+    where
+        S: DataTypeStore + PropertyTypeStore + EntityTypeStore,
+    {
+        let mut graph = Self::default();
+        if roots.is_empty() {
+            return Ok(graph);
+        }
+
+        let graph_resolve_depths = GraphResolveDepths {
+            inherits_from: OntologyEdgeResolveDepth::fixpoint(),
+            constrains_values_on: OntologyEdgeResolveDepth::fixpoint(),
+            constrains_properties_on: OntologyEdgeResolveDepth::fixpoint(),
+            constrains_links_on: OntologyEdgeResolveDepth::fixpoint(),
+            constrains_link_destinations_on: OntologyEdgeResolveDepth::fixpoint(),
+            ..GraphResolveDepths::default()
+        };
+
+        graph.extend(
+            &store
+                .get_data_type(&StructuralQuery {
+                    filter: Filter::Any(roots.iter().map(Filter::for_versioned_url).collect()),
+                    graph_resolve_depths,
+                    temporal_axes: QueryTemporalAxesUnresolved::default(),
+                })
+                .await?,
+        );
+        graph.extend(
+            &store
+                .get_property_type(&StructuralQuery {
+                    filter: Filter::Any(roots.iter().map(Filter::for_versioned_url).collect()),
+                    graph_resolve_depths,
+                    temporal_axes: QueryTemporalAxesUnresolved::default(),
+                })
+                .await?,
+        );
+        graph.extend(
+            &store
+                .get_entity_type(&StructuralQuery {
+                    filter: Filter::Any(roots.iter().map(Filter::for_versioned_url).collect()),
+                    graph_resolve_depths,
+                    temporal_axes: QueryTemporalAxesUnresolved::default(),
+                })
+                .await?,
+        );
+
+        if let Some(root) = roots.iter().find(|root| !graph.nodes.contains_key(root)) {
+            return Err(Report::new(QueryError)
+                .attach_printable(format!("ontology type `{root}` does not exist")));
+        }
+
+        graph.mark_link_types();
+        Ok(graph)
+    }
+
+    /// Adds the ontology types and the edges between them contained in the `subgraph`.
+    fn extend(&mut self, subgraph: &Subgraph) {
+        for (vertex_id, data_type) in subgraph.iter_vertices::<DataTypeVertexId>() {
+            self.nodes.insert(
+                versioned_url(&vertex_id.base_id, vertex_id.revision_id),
+                OntologyGraphNode::new(
+                    OntologyTypeKind::DataType,
+                    &data_type.schema,
+                    &data_type.metadata.custom,
+                ),
+            );
+        }
+        for (vertex_id, property_type) in subgraph.iter_vertices::<PropertyTypeVertexId>() {
+            self.nodes.insert(
+                versioned_url(&vertex_id.base_id, vertex_id.revision_id),
+                OntologyGraphNode::new(
+                    OntologyTypeKind::PropertyType,
+                    &property_type.schema,
+                    &property_type.metadata.custom,
+                ),
+            );
+        }
+        for (vertex_id, entity_type) in subgraph.iter_vertices::<EntityTypeVertexId>() {
+            self.nodes.insert(
+                versioned_url(&vertex_id.base_id, vertex_id.revision_id),
+                OntologyGraphNode::new(
+                    OntologyTypeKind::EntityType,
+                    &entity_type.schema,
+                    &entity_type.metadata.custom.common,
+                ),
+            );
+        }
+
+        let edges = &subgraph.edges;
+        self.insert_edges(edges.entity_type_to_entity_type.iter());
+        self.insert_edges(edges.entity_type_to_property_type.iter());
+        self.insert_edges(edges.property_type_to_property_type.iter());
+        self.insert_edges(edges.property_type_to_data_type.iter());
+    }
+
+    fn insert_edges<'s, R>(
+        &mut self,
+        edges: impl Iterator<
+            Item = (
+                &'s BaseUrl,
+                &'s OntologyTypeVersion,
+                OutwardEdge<OntologyEdgeKind, &'s R>,
+            ),
+        >,
+    ) where
+        R: EdgeEndpoint<BaseId = BaseUrl, RevisionId = OntologyTypeVersion> + 's,
+    {
+        self.edges.extend(
+            edges
+                .filter(|(_, _, edge)| edge.direction == EdgeDirection::Outgoing)
+                .map(|(base_url, version, edge)| {
+                    (
+                        versioned_url(base_url, *version),
+                        edge.kind,
+                        versioned_url(
+                            edge.right_endpoint.base_id(),
+                            edge.right_endpoint.revision_id(),
+                        ),
+                    )
+                }),
+        );
+    }
+
+    /// Marks every entity type, which directly or indirectly inherits from the link entity type,
+    /// as a link type.
+    fn mark_link_types(&mut self) {
+        let mut link_types = self
+            .nodes
+            .keys()
+            .filter(|url| url.base_url.as_str() == LINK_ENTITY_TYPE_BASE_URL)
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let mut changed = !link_types.is_empty();
+        while changed {
+            changed = false;
+            for (source, kind, target) in &self.edges {
+                if *kind == OntologyEdgeKind::InheritsFrom
+                    && link_types.contains(target)
+                    && link_types.insert(source.clone())
+                {
+                    changed = true;
+                }
+            }
+        }
+
+        for url in &link_types {
+            if let Some(node) = self.nodes.get_mut(url) {
+                node.kind = OntologyTypeKind::LinkType;
+            }
+        }
+    }
+
+    /// Returns the nodes and the edges between them ordered by their URLs.
+    ///
+    /// Edges pointing to ontology types, which are not contained in the graph, are skipped.
+    fn sorted(
+        &self,
+    ) -> (
+        Vec<(&VersionedUrl, &OntologyGraphNode)>,
+        Vec<(&VersionedUrl, OntologyEdgeKind, &VersionedUrl)>,
+    ) {
+        let mut nodes = self.nodes.iter().collect::<Vec<_>>();
+        nodes.sort_by_cached_key(|(url, _)| url.to_string());
+
+        let mut edges = self
+            .edges
+            .iter()
+            .filter(|(source, _, target)| {
+                self.nodes.contains_key(source) && self.nodes.contains_key(target)
+            })
+            .map(|(source, kind, target)| (source, *kind, target))
+            .collect::<Vec<_>>();
+        edges.sort_by_cached_key(|(source, kind, target)| {
+            (source.to_string(), edge_label(*kind), target.to_string())
+        });
+
+        (nodes, edges)
+    }
+
+    /// Renders the graph in the given `format`.
+    #[must_use]
+    pub fn render(&self, format: OntologyGraphFormat) -> String {
+        match format {
+            OntologyGraphFormat::Dot => self.render_dot(),
+            OntologyGraphFormat::Mermaid => self.render_mermaid(),
+        }
+    }
+
+    fn render_dot(&self) -> String {
+        let (nodes, edges) = self.sorted();
+
+        let mut output = String::from("digraph ontology {\n    rankdir=LR;\n");
+        for (url, node) in nodes {
+            let label = node.label(url).map(|line| escape_dot(&line)).join("\\n");
+            let shape = match node.kind {
+                OntologyTypeKind::DataType => "ellipse",
+                OntologyTypeKind::PropertyType | OntologyTypeKind::EntityType => "box",
+                OntologyTypeKind::LinkType => "hexagon",
+            };
+            let style = match (node.kind, node.owned) {
+                (OntologyTypeKind::PropertyType, true) => "rounded",
+                (OntologyTypeKind::PropertyType, false) => "\"rounded,dashed\"",
+                (_, true) => "solid",
+                (_, false) => "dashed",
+            };
+            // Writing to a `String` cannot fail
+            let _ = writeln!(
+                output,
+                "    \"{}\" [label=\"{label}\", shape={shape}, style={style}];",
+                escape_dot(&url.to_string()),
+            );
+        }
+        for (source, kind, target) in edges {
+            let _ = writeln!(
+                output,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(&source.to_string()),
+                escape_dot(&target.to_string()),
+                edge_label(kind),
+            );
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    fn render_mermaid(&self) -> String {
+        let (nodes, edges) = self.sorted();
+
+        // Mermaid identifiers cannot contain arbitrary characters, so the nodes are numbered
+        let ids = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, (url, _))| (*url, format!("n{index}")))
+            .collect::<HashMap<_, _>>();
+
+        let mut output = String::from("flowchart LR\n");
+        let mut external = Vec::new();
+        for (url, node) in &nodes {
+            let id = &ids[url];
+            let label = node
+                .label(url)
+                .map(|line| escape_mermaid(&line))
+                .join("<br/>");
+            let _ = match node.kind {
+                OntologyTypeKind::DataType => writeln!(output, "    {id}([\"{label}\"])"),
+                OntologyTypeKind::PropertyType => writeln!(output, "    {id}(\"{label}\")"),
+                OntologyTypeKind::EntityType => writeln!(output, "    {id}[\"{label}\"]"),
+                OntologyTypeKind::LinkType => writeln!(output, "    {id}{{{{\"{label}\"}}}}"),
+            };
+            if !node.owned {
+                external.push(id.as_str());
+            }
+        }
+        for (source, kind, target) in edges {
+            let _ = writeln!(
+                output,
+                "    {} -- {} --> {}",
+                ids[&source],
+                edge_label(kind),
+                ids[&target],
+            );
+        }
+        if !external.is_empty() {
+            output.push_str("    classDef external stroke-dasharray: 5 5\n");
+            let _ = writeln!(output, "    class {} external", external.join(","));
+        }
+        output
+    }
+}
+
+fn versioned_url(base_url: &BaseUrl, version: OntologyTypeVersion) -> VersionedUrl {
+    VersionedUrl {
+        base_url: base_url.clone(),
+        version: version.inner(),
+    }
+}
+
+/// Returns the label of an edge, which matches the name of the edge kind in the API.
+const fn edge_label(kind: OntologyEdgeKind) -> &'static str {
+    match kind {
+        OntologyEdgeKind::InheritsFrom => "INHERITS_FROM",
+        OntologyEdgeKind::ConstrainsValuesOn => "CONSTRAINS_VALUES_ON",
+        OntologyEdgeKind::ConstrainsPropertiesOn => "CONSTRAINS_PROPERTIES_ON",
+        OntologyEdgeKind::ConstrainsLinksOn => "CONSTRAINS_LINKS_ON",
+        OntologyEdgeKind::ConstrainsLinkDestinationsOn => "CONSTRAINS_LINK_DESTINATIONS_ON",
+    }
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn url(url: &str) -> VersionedUrl {
+        VersionedUrl::from_str(url).expect("invalid versioned URL")
+    }
+
+    fn node(kind: OntologyTypeKind, title: &str, owned: bool) -> OntologyGraphNode {
+        OntologyGraphNode {
+            kind,
+            title: Some(title.to_owned()),
+            owned,
+        }
+    }
+
+    const TEXT: &str = "https://example.com/data-type/text/v/1";
+    const NAME: &str = "https://example.com/property-type/name/v/1";
+    const LINK: &str = "https://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1";
+    const FRIEND_OF: &str = "https://example.com/entity-type/friend-of/v/1";
+    const PERSON: &str = "https://example.com/entity-type/person/v/1";
+
+    /// A person with a name, which is linked to other persons.
+    fn graph() -> OntologyDependencyGraph {
+        let mut graph = OntologyDependencyGraph::default();
+        graph.nodes.extend([
+            (url(TEXT), node(OntologyTypeKind::DataType, "Text", false)),
+            (
+                url(NAME),
+                node(OntologyTypeKind::PropertyType, "Name", true),
+            ),
+            (url(LINK), node(OntologyTypeKind::EntityType, "Link", false)),
+            (
+                url(FRIEND_OF),
+                node(OntologyTypeKind::EntityType, "Friend \"Of\"", true),
+            ),
+            (
+                url(PERSON),
+                node(OntologyTypeKind::EntityType, "Person", true),
+            ),
+        ]);
+        graph.edges.extend([
+            (
+                url(PERSON),
+                OntologyEdgeKind::ConstrainsPropertiesOn,
+                url(NAME),
+            ),
+            (url(NAME), OntologyEdgeKind::ConstrainsValuesOn, url(TEXT)),
+            (
+                url(PERSON),
+                OntologyEdgeKind::ConstrainsLinksOn,
+                url(FRIEND_OF),
+            ),
+            (
+                url(PERSON),
+                OntologyEdgeKind::ConstrainsLinkDestinationsOn,
+                url(PERSON),
+            ),
+            (url(FRIEND_OF), OntologyEdgeKind::InheritsFrom, url(LINK)),
+            // Points to an ontology type, which is not contained in the graph
+            (
+                url(PERSON),
+                OntologyEdgeKind::InheritsFrom,
+                url("https://example.com/entity-type/thing/v/1"),
+            ),
+        ]);
+        graph.mark_link_types();
+        graph
+    }
+
+    #[test]
+    fn link_types_are_marked() {
+        let graph = graph();
+        assert_eq!(graph.nodes[&url(LINK)].kind, OntologyTypeKind::LinkType);
+        assert_eq!(
+            graph.nodes[&url(FRIEND_OF)].kind,
+            OntologyTypeKind::LinkType
+        );
+        assert_eq!(graph.nodes[&url(PERSON)].kind, OntologyTypeKind::EntityType);
+    }
+
+    #[test]
+    fn render_dot() {
+        assert_eq!(
+            graph().render(OntologyGraphFormat::Dot),
+            r#"digraph ontology {
+    rankdir=LR;
+    "https://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1" [label="Link\nhttps://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1\nlink type, external", shape=hexagon, style=dashed];
+    "https://example.com/data-type/text/v/1" [label="Text\nhttps://example.com/data-type/text/v/1\ndata type, external", shape=ellipse, style=dashed];
+    "https://example.com/entity-type/friend-of/v/1" [label="Friend \"Of\"\nhttps://example.com/entity-type/friend-of/v/1\nlink type, owned", shape=hexagon, style=solid];
+    "https://example.com/entity-type/person/v/1" [label="Person\nhttps://example.com/entity-type/person/v/1\nentity type, owned", shape=box, style=solid];
+    "https://example.com/property-type/name/v/1" [label="Name\nhttps://example.com/property-type/name/v/1\nproperty type, owned", shape=box, style=rounded];
+    "https://example.com/entity-type/friend-of/v/1" -> "https://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1" [label="INHERITS_FROM"];
+    "https://example.com/entity-type/person/v/1" -> "https://example.com/entity-type/friend-of/v/1" [label="CONSTRAINS_LINKS_ON"];
+    "https://example.com/entity-type/person/v/1" -> "https://example.com/entity-type/person/v/1" [label="CONSTRAINS_LINK_DESTINATIONS_ON"];
+    "https://example.com/entity-type/person/v/1" -> "https://example.com/property-type/name/v/1" [label="CONSTRAINS_PROPERTIES_ON"];
+    "https://example.com/property-type/name/v/1" -> "https://example.com/data-type/text/v/1" [label="CONSTRAINS_VALUES_ON"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn render_mermaid() {
+        assert_eq!(
+            graph().render(OntologyGraphFormat::Mermaid),
+            r#"flowchart LR
+    n0{{"Link<br/>https://blockprotocol.org/@blockprotocol/types/entity-type/link/v/1<br/>link type, external"}}
+    n1(["Text<br/>https://example.com/data-type/text/v/1<br/>data type, external"])
+    n2{{"Friend #quot;Of#quot;<br/>https://example.com/entity-type/friend-of/v/1<br/>link type, owned"}}
+    n3["Person<br/>https://example.com/entity-type/person/v/1<br/>entity type, owned"]
+    n4("Name<br/>https://example.com/property-type/name/v/1<br/>property type, owned")
+    n2 -- INHERITS_FROM --> n0
+    n3 -- CONSTRAINS_LINKS_ON --> n2
+    n3 -- CONSTRAINS_LINK_DESTINATIONS_ON --> n3
+    n3 -- CONSTRAINS_PROPERTIES_ON --> n4
+    n4 -- CONSTRAINS_VALUES_ON --> n1
+    classDef external stroke-dasharray: 5 5
+    class n0,n1 external
+"#
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use error_stack::Result;
+use graph_types::ontology::{
+    DataTypeWithMetadata, EntityTypeWithMetadata, OntologyType, OntologyTypeDeprecation,
+    PropertyTypeWithMetadata,
+};
+use type_system::url::VersionedUrl;
+
+use crate::{
+    ontology::validation::{references, SchemaReference},
+    store::{
+        crud::Read, query::Filter, DataTypeStore, EntityTypeStore, PropertyTypeStore, QueryError,
+    },
+};
+
+/// Returns a warning if the entity type, an entity is created or updated with, is deprecated.
+///
+/// # Errors
+///
+/// - if reading from the `store` fails
+pub async fn entity_type_deprecation_warnings<S>(
+    store: &S,
+    entity_type_id: &VersionedUrl,
+) -> Result<Vec<String>, QueryError>
+where
+    S: DataTypeStore + PropertyTypeStore + EntityTypeStore,
+{
+    deprecation_warnings(store, vec![SchemaReference::EntityType(
+        entity_type_id.clone(),
+    )])
+    .await
+}
+
+/// Returns a warning for every deprecated ontology type referenced by the `ontology_types`.
+///
+/// Referenced property types are followed to the data types and property types they reference,
+/// so an entity type using a property type, which only accepts a deprecated data type, is reported
+/// as well. Referenced entity types are reported but not followed.
+///
+/// Ontology types, which do not exist (yet), are skipped.
+///
+/// # Errors
+///
+/// - if reading from the `store` fails
+pub async fn reference_deprecation_warnings<S, T>(
+    store: &S,
+    ontology_types: &[T],
+) -> Result<Vec<String>, QueryError>
+where
+    S: DataTypeStore + PropertyTypeStore + EntityTypeStore,
+    T: OntologyType,
+{
+    deprecation_warnings(store, ontology_types.iter().flat_map(references).collect()).await
+}
+
+async fn deprecation_warnings<S>(
+    store: &S,
+    mut pending: Vec<SchemaReference>,
+) -> Result<Vec<String>, QueryError>
+where
+    S: DataTypeStore + PropertyTypeStore + EntityTypeStore,
+{
+    let mut visited = HashSet::new();
+    let mut warnings = Vec::new();
+    while let Some(reference) = pending.pop() {
+        let (kind, url, deprecation) = match reference {
+            SchemaReference::DataType(url) => {
+                if !visited.insert(url.clone()) {
+                    continue;
+                }
+                let Some(data_type) = Read::<DataTypeWithMetadata>::read_vec(
+                    store,
+                    &Filter::for_versioned_url(&url),
+                    None,
+                )
+                .await?
+                .pop() else {
+                    continue;
+                };
+                ("data type", url, data_type.metadata.deprecation)
+            }
+            SchemaReference::PropertyType(url) => {
+                if !visited.insert(url.clone()) {
+                    continue;
+                }
+                let Some(property_type) = Read::<PropertyTypeWithMetadata>::read_vec(
+                    store,
+                    &Filter::for_versioned_url(&url),
+                    None,
+                )
+                .await?
+                .pop() else {
+                    continue;
+                };
+                pending.extend(references(&property_type.schema));
+                ("property type", url, property_type.metadata.deprecation)
+            }
+            SchemaReference::EntityType(url) => {
+                if !visited.insert(url.clone()) {
+                    continue;
+                }
+                let Some(entity_type) = Read::<EntityTypeWithMetadata>::read_vec(
+                    store,
+                    &Filter::for_versioned_url(&url),
+                    None,
+                )
+                .await?
+                .pop() else {
+                    continue;
+                };
+                ("entity type", url, entity_type.metadata.deprecation)
+            }
+        };
+
+        if let Some(deprecation) = deprecation {
+            warnings.push(deprecation_warning(kind, &url, &deprecation));
+        }
+    }
+
+    Ok(warnings)
+}
+
+fn deprecation_warning(
+    kind: &str,
+    url: &VersionedUrl,
+    deprecation: &OntologyTypeDeprecation,
+) -> String {
+    match &deprecation.successor {
+        Some(successor) => format!("The {kind} {url} is deprecated, use {successor} instead"),
+        None => format!("The {kind} {url} is deprecated"),
+    }
+}
@@ -18,7 +18,7 @@ use crate::{
 /// A path to a [`EntityType`] field.
 ///
 /// [`EntityType`]: type_system::EntityType
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EntityTypeQueryPath<'p> {
     /// The [`BaseUrl`] of the [`EntityType`].
     ///
@@ -134,6 +134,24 @@ pub enum EntityTypeQueryPath<'p> {
     /// [`RecordArchivedById`]: graph_types::provenance::RecordArchivedById
     /// [`ProvenanceMetadata`]: graph_types::provenance::ProvenanceMetadata
     RecordArchivedById,
+    /// The [`DeprecatedById`] of the [`OntologyTypeDeprecation`] belonging to the [`EntityType`].
+    ///
+    /// The value is `null` if the [`EntityType`] is not deprecated, so deprecated types can be
+    /// filtered by comparing this path with `null`:
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # use graph::ontology::EntityTypeQueryPath;
+    /// let path = EntityTypeQueryPath::deserialize(json!(["deprecatedById"]))?;
+    /// assert_eq!(path, EntityTypeQueryPath::DeprecatedById);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`EntityType`]: type_system::EntityType
+    /// [`DeprecatedById`]: graph_types::provenance::DeprecatedById
+    /// [`OntologyTypeDeprecation`]: graph_types::ontology::OntologyTypeDeprecation
+    DeprecatedById,
     /// Corresponds to [`EntityType::title()`].
     ///
     /// ```rust
@@ -435,6 +453,12 @@ pub enum EntityTypeQueryPath<'p> {
     Schema(Option<JsonPath<'p>>),
     /// Only used internally and not available for deserialization.
     AdditionalMetadata,
+    /// Only used internally and not available for deserialization.
+    DeprecatedAt,
+    /// Only used internally and not available for deserialization.
+    SuccessorBaseUrl,
+    /// Only used internally and not available for deserialization.
+    SuccessorVersion,
 }
 
 impl OntologyQueryPath for EntityTypeQueryPath<'_> {
@@ -481,13 +505,15 @@ impl QueryPath for EntityTypeQueryPath<'_> {
             Self::OntologyId
             | Self::OwnedById
             | Self::RecordCreatedById
-            | Self::RecordArchivedById => ParameterType::Uuid,
+            | Self::RecordArchivedById
+            | Self::DeprecatedById => ParameterType::Uuid,
             Self::Schema(_) | Self::AdditionalMetadata => ParameterType::Object,
             Self::Examples | Self::Required => ParameterType::Any,
-            Self::BaseUrl | Self::LabelProperty => ParameterType::BaseUrl,
+            Self::BaseUrl | Self::LabelProperty | Self::SuccessorBaseUrl => ParameterType::BaseUrl,
             Self::VersionedUrl => ParameterType::VersionedUrl,
-            Self::Version => ParameterType::OntologyTypeVersion,
+            Self::Version | Self::SuccessorVersion => ParameterType::OntologyTypeVersion,
             Self::TransactionTime => ParameterType::TimeInterval,
+            Self::DeprecatedAt => ParameterType::Timestamp,
             Self::Title | Self::Description => ParameterType::Text,
             Self::PropertyTypeEdge { path, .. } => path.expected_type(),
             Self::EntityTypeEdge { path, .. } => path.expected_type(),
@@ -507,6 +533,7 @@ impl fmt::Display for EntityTypeQueryPath<'_> {
             Self::OwnedById => fmt.write_str("ownedById"),
             Self::RecordCreatedById => fmt.write_str("recordCreatedById"),
             Self::RecordArchivedById => fmt.write_str("recordArchivedById"),
+            Self::DeprecatedById => fmt.write_str("deprecatedById"),
             Self::Schema(Some(path)) => write!(fmt, "schema.{path}"),
             Self::Schema(None) => fmt.write_str("schema"),
             Self::Title => fmt.write_str("title"),
@@ -589,6 +616,9 @@ impl fmt::Display for EntityTypeQueryPath<'_> {
                 inheritance_depth: None,
             } => write!(fmt, "isTypeOf.{path}"),
             Self::AdditionalMetadata => fmt.write_str("additionalMetadata"),
+            Self::DeprecatedAt => fmt.write_str("deprecatedAt"),
+            Self::SuccessorBaseUrl => fmt.write_str("successorBaseUrl"),
+            Self::SuccessorVersion => fmt.write_str("successorVersion"),
         }
     }
 }
@@ -603,6 +633,7 @@ pub enum EntityTypeQueryToken {
     OwnedById,
     RecordCreatedById,
     RecordArchivedById,
+    DeprecatedById,
     Title,
     Description,
     Examples,
@@ -625,8 +656,8 @@ pub struct EntityTypeQueryPathVisitor {
 impl EntityTypeQueryPathVisitor {
     pub const EXPECTING: &'static str =
         "one of `baseUrl`, `version`, `versionedUrl`, `ownedById`, `recordCreatedById`, \
-         `recordArchivedById`, `title`, `description`, `examples`, `properties`, `required`, \
-         `labelProperty`, `links`, `inheritsFrom`, `children`";
+         `recordArchivedById`, `deprecatedById`, `title`, `description`, `examples`, \
+         `properties`, `required`, `labelProperty`, `links`, `inheritsFrom`, `children`";
 
     #[must_use]
     pub const fn new(position: usize) -> Self {
@@ -655,6 +686,7 @@ impl<'de> Visitor<'de> for EntityTypeQueryPathVisitor {
             EntityTypeQueryToken::OwnedById => EntityTypeQueryPath::OwnedById,
             EntityTypeQueryToken::RecordCreatedById => EntityTypeQueryPath::RecordCreatedById,
             EntityTypeQueryToken::RecordArchivedById => EntityTypeQueryPath::RecordArchivedById,
+            EntityTypeQueryToken::DeprecatedById => EntityTypeQueryPath::DeprecatedById,
             EntityTypeQueryToken::BaseUrl => EntityTypeQueryPath::BaseUrl,
             EntityTypeQueryToken::VersionedUrl => EntityTypeQueryPath::VersionedUrl,
             EntityTypeQueryToken::Version => EntityTypeQueryPath::Version,
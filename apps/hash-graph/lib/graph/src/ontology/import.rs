@@ -0,0 +1,458 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+};
+
+use error_stack::{Context, Report, Result, ResultExt};
+use graph_types::ontology::OntologyType;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::fs;
+use type_system::{url::VersionedUrl, DataType, EntityType, PropertyType};
+
+use crate::ontology::{
+    domain_validator::{DomainValidationError, DomainValidator, ValidateOntologyType},
+    validation::{schema_value, OntologySchemas},
+    ResolvedEntityType,
+};
+
+#[derive(Debug)]
+pub struct OntologyImportError;
+
+impl Context for OntologyImportError {}
+
+impl fmt::Display for OntologyImportError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("could not read the ontology types to import")
+    }
+}
+
+/// An ontology type read from a JSON Schema file.
+#[derive(Debug, Clone)]
+pub enum ImportedOntologyType {
+    DataType(DataType),
+    PropertyType(PropertyType),
+    EntityType(EntityType),
+}
+
+impl ImportedOntologyType {
+    /// Parses an ontology type from its JSON Schema.
+    ///
+    /// The kind of the ontology type is determined by the `kind` keyword of the schema.
+    ///
+    /// # Errors
+    ///
+    /// - if the `kind` keyword is missing or unknown
+    /// - if the schema is not a valid ontology type of that kind
+    pub fn from_value(value: Value) -> Result<Self, OntologyImportError> {
+        match value.get("kind").and_then(Value::as_str) {
+            Some("dataType") => parse(value).map(Self::DataType),
+            Some("propertyType") => parse(value).map(Self::PropertyType),
+            Some("entityType") => parse(value).map(Self::EntityType),
+            Some(kind) => Err(Report::new(OntologyImportError)
+                .attach_printable(format!("unknown ontology type kind `{kind}`"))),
+            None => Err(Report::new(OntologyImportError)
+                .attach_printable("the schema does not specify the kind of the ontology type")),
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> &VersionedUrl {
+        match self {
+            Self::DataType(data_type) => data_type.id(),
+            Self::PropertyType(property_type) => property_type.id(),
+            Self::EntityType(entity_type) => entity_type.id(),
+        }
+    }
+
+    /// Returns the ontology types directly referenced by this ontology type.
+    #[must_use]
+    pub fn references(&self) -> Vec<VersionedUrl> {
+        let references = match self {
+            Self::DataType(data_type) => data_type.traverse_references(),
+            Self::PropertyType(property_type) => property_type.traverse_references(),
+            Self::EntityType(entity_type) => entity_type.traverse_references(),
+        };
+        references
+            .into_iter()
+            .map(|reference| reference.url().clone())
+            .collect()
+    }
+
+    /// Returns the JSON representation of the schema.
+    #[must_use]
+    pub fn schema(&self) -> Value {
+        match self {
+            Self::DataType(data_type) => schema_value(data_type),
+            Self::PropertyType(property_type) => schema_value(property_type),
+            Self::EntityType(entity_type) => schema_value(entity_type),
+        }
+    }
+
+    /// Validates the URL of the ontology type against the `domain_validator`.
+    ///
+    /// # Errors
+    ///
+    /// - [`DomainValidationError`], if the URL does not match or the kind is invalid
+    pub fn validate(
+        &self,
+        domain_validator: &DomainValidator,
+    ) -> Result<(), DomainValidationError> {
+        match self {
+            Self::DataType(data_type) => domain_validator.validate(data_type),
+            Self::PropertyType(property_type) => domain_validator.validate(property_type),
+            Self::EntityType(entity_type) => domain_validator.validate(entity_type),
+        }
+    }
+
+    /// The position of the kind when ordering ontology types, which don't reference each other.
+    const fn kind_order(&self) -> u8 {
+        match self {
+            Self::DataType(_) => 0,
+            Self::PropertyType(_) => 1,
+            Self::EntityType(_) => 2,
+        }
+    }
+}
+
+fn parse<T: OntologyType>(value: Value) -> Result<T, OntologyImportError> {
+    let ontology_type_repr: T::Representation =
+        serde_json::from_value(value).change_context(OntologyImportError)?;
+    T::try_from(ontology_type_repr).change_context(OntologyImportError)
+}
+
+/// A set of ontology types to import, ordered by their references.
+///
+/// Every ontology type is ordered after the ontology types it references and after the lower
+/// versions of the same base URL. For cyclic references, e.g. entity types linking to each other,
+/// the order within the cycle is arbitrary.
+#[derive(Debug, Default)]
+pub struct OntologyTypeImport {
+    ontology_types: Vec<ImportedOntologyType>,
+}
+
+impl OntologyTypeImport {
+    /// Orders the `ontology_types` by their references.
+    ///
+    /// # Errors
+    ///
+    /// - if the same ontology type is contained twice with different schemas
+    pub fn new(
+        ontology_types: impl IntoIterator<Item = ImportedOntologyType>,
+    ) -> Result<Self, OntologyImportError> {
+        let mut by_id = HashMap::<VersionedUrl, ImportedOntologyType>::new();
+        for ontology_type in ontology_types {
+            if let Some(existing) = by_id.get(ontology_type.id()) {
+                if existing.schema() != ontology_type.schema() {
+                    return Err(Report::new(OntologyImportError).attach_printable(format!(
+                        "the ontology type `{}` is defined multiple times with different schemas",
+                        ontology_type.id()
+                    )));
+                }
+            } else {
+                by_id.insert(ontology_type.id().clone(), ontology_type);
+            }
+        }
+
+        let mut roots = by_id.values().collect::<Vec<_>>();
+        roots.sort_by_cached_key(|ontology_type| {
+            let id = ontology_type.id();
+            (
+                ontology_type.kind_order(),
+                id.base_url.to_string(),
+                id.version,
+            )
+        });
+
+        // Every version is ordered after the previous imported version of the same base URL, so it
+        // can be imported as an update of it.
+        let mut previous_versions = HashMap::<&VersionedUrl, VersionedUrl>::new();
+        let mut ids = by_id.keys().collect::<Vec<_>>();
+        ids.sort_by_cached_key(|id| (id.base_url.to_string(), id.version));
+        for window in ids.windows(2) {
+            if window[0].base_url == window[1].base_url {
+                previous_versions.insert(window[1], window[0].clone());
+            }
+        }
+
+        // Depth-first search, which emits an ontology type after all of its references. The
+        // boolean marks whether the references of the ontology type were already pushed.
+        let mut visited = HashSet::new();
+        let mut ordered = Vec::with_capacity(by_id.len());
+        for root in roots {
+            let mut stack = vec![(root, false)];
+            while let Some((ontology_type, expanded)) = stack.pop() {
+                if expanded {
+                    ordered.push(ontology_type.id().clone());
+                } else if visited.insert(ontology_type.id()) {
+                    stack.push((ontology_type, true));
+                    let mut references = ontology_type
+                        .references()
+                        .into_iter()
+                        .chain(previous_versions.get(ontology_type.id()).cloned())
+                        .filter_map(|reference| by_id.get(&reference))
+                        .collect::<Vec<_>>();
+                    references.sort_by_cached_key(|reference| {
+                        let id = reference.id();
+                        (id.base_url.to_string(), id.version)
+                    });
+                    stack.extend(
+                        references
+                            .into_iter()
+                            .rev()
+                            .map(|reference| (reference, false)),
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            ontology_types: ordered
+                .into_iter()
+                .filter_map(|id| by_id.remove(&id))
+                .collect(),
+        })
+    }
+
+    /// Reads all `.json` files in `path` and its subdirectories.
+    ///
+    /// # Errors
+    ///
+    /// - if the directory cannot be read
+    /// - if any file does not contain a valid ontology type
+    /// - if the same ontology type is contained twice with different schemas
+    pub async fn read_directory(
+        path: impl AsRef<Path> + Send,
+    ) -> Result<Self, OntologyImportError> {
+        let mut files = Vec::new();
+        let mut directories = vec![path.as_ref().to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = fs::read_dir(&directory)
+                .await
+                .change_context(OntologyImportError)
+                .attach_printable_lazy(|| directory.display().to_string())?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .change_context(OntologyImportError)
+                .attach_printable_lazy(|| directory.display().to_string())?
+            {
+                let path = entry.path();
+                if entry
+                    .file_type()
+                    .await
+                    .change_context(OntologyImportError)?
+                    .is_dir()
+                {
+                    directories.push(path);
+                } else if path
+                    .extension()
+                    .is_some_and(|extension| extension == "json")
+                {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+
+        let mut ontology_types = Vec::with_capacity(files.len());
+        for file in files {
+            ontology_types.push(read_file(&file).await?);
+        }
+        Self::new(ontology_types)
+    }
+
+    /// Validates the URLs of all ontology types against the `domain_validator`.
+    ///
+    /// # Errors
+    ///
+    /// - [`DomainValidationError`], if the URL of any ontology type does not match or its kind is
+    ///   invalid
+    pub fn validate(
+        &self,
+        domain_validator: &DomainValidator,
+    ) -> Result<(), DomainValidationError> {
+        let mut result: Result<(), DomainValidationError> = Ok(());
+        for ontology_type in &self.ontology_types {
+            if let Err(report) = ontology_type.validate(domain_validator) {
+                let report = report.attach_printable(ontology_type.id().clone());
+                match &mut result {
+                    Ok(()) => result = Err(report),
+                    Err(previous) => previous.extend_one(report),
+                }
+            }
+        }
+        result
+    }
+
+    /// Resolves the entity type `entity_type_id` from the imported ontology types.
+    ///
+    /// Returns `None` if the entity type is not imported. Referenced ontology types, which are not
+    /// imported, are not inlined.
+    #[must_use]
+    pub fn resolve_entity_type(&self, entity_type_id: &VersionedUrl) -> Option<ResolvedEntityType> {
+        let mut schemas = OntologySchemas::default();
+        for ontology_type in &self.ontology_types {
+            let ontology_types = match ontology_type {
+                ImportedOntologyType::DataType(_) => &mut schemas.data_types,
+                ImportedOntologyType::PropertyType(_) => &mut schemas.property_types,
+                ImportedOntologyType::EntityType(_) => &mut schemas.entity_types,
+            };
+            ontology_types.insert(ontology_type.id().clone(), ontology_type.schema());
+        }
+        ResolvedEntityType::resolve(&schemas, entity_type_id)
+    }
+
+    /// Returns the ontology types ordered by their references.
+    pub fn iter(&self) -> impl Iterator<Item = &ImportedOntologyType> {
+        self.ontology_types.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ontology_types.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ontology_types.is_empty()
+    }
+}
+
+impl IntoIterator for OntologyTypeImport {
+    type IntoIter = std::vec::IntoIter<ImportedOntologyType>;
+    type Item = ImportedOntologyType;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ontology_types.into_iter()
+    }
+}
+
+async fn read_file(path: &Path) -> Result<ImportedOntologyType, OntologyImportError> {
+    let content = fs::read(path)
+        .await
+        .change_context(OntologyImportError)
+        .attach_printable_lazy(|| path.display().to_string())?;
+    let value = serde_json::from_slice(&content)
+        .change_context(OntologyImportError)
+        .attach_printable_lazy(|| path.display().to_string())?;
+    ImportedOntologyType::from_value(value).attach_printable_lazy(|| path.display().to_string())
+}
+
+/// The result of importing a set of ontology types.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyImportSummary {
+    /// The ontology types, which did not exist before.
+    pub created: Vec<VersionedUrl>,
+    /// The ontology types, which were created as a new version of an existing ontology type.
+    pub updated: Vec<VersionedUrl>,
+    /// The ontology types, which already existed with the same schema.
+    pub unchanged: Vec<VersionedUrl>,
+}
+
+impl fmt::Display for OntologyImportSummary {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{} created, {} updated, {} unchanged",
+            self.created.len(),
+            self.updated.len(),
+            self.unchanged.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn data_type(id: &str) -> ImportedOntologyType {
+        ImportedOntologyType::from_value(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": id,
+            "title": "Object",
+            "type": "object"
+        }))
+        .expect("could not parse data type")
+    }
+
+    fn property_type(id: &str, data_type_id: &str) -> ImportedOntologyType {
+        ImportedOntologyType::from_value(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/property-type",
+            "kind": "propertyType",
+            "$id": id,
+            "title": "Value",
+            "oneOf": [{ "$ref": data_type_id }]
+        }))
+        .expect("could not parse property type")
+    }
+
+    fn ordered_ids(import: &OntologyTypeImport) -> Vec<String> {
+        import
+            .iter()
+            .map(|ontology_type| ontology_type.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn orders_versions_of_the_same_base_url() {
+        let import = OntologyTypeImport::new([
+            data_type("https://example.com/data-type/object/v/10"),
+            data_type("https://example.com/data-type/object/v/2"),
+            data_type("https://example.com/data-type/object/v/1"),
+        ])
+        .expect("could not order ontology types");
+
+        assert_eq!(ordered_ids(&import), [
+            "https://example.com/data-type/object/v/1",
+            "https://example.com/data-type/object/v/2",
+            "https://example.com/data-type/object/v/10",
+        ]);
+    }
+
+    #[test]
+    fn orders_versions_after_their_references() {
+        let import = OntologyTypeImport::new([
+            property_type(
+                "https://example.com/property-type/value/v/2",
+                "https://example.com/data-type/object/v/1",
+            ),
+            property_type(
+                "https://example.com/property-type/value/v/1",
+                "https://example.com/data-type/object/v/2",
+            ),
+            data_type("https://example.com/data-type/object/v/2"),
+            data_type("https://example.com/data-type/object/v/1"),
+        ])
+        .expect("could not order ontology types");
+
+        assert_eq!(ordered_ids(&import), [
+            "https://example.com/data-type/object/v/1",
+            "https://example.com/data-type/object/v/2",
+            "https://example.com/property-type/value/v/1",
+            "https://example.com/property-type/value/v/2",
+        ]);
+    }
+
+    #[test]
+    fn rejects_conflicting_schemas() {
+        let conflicting = json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+            "kind": "dataType",
+            "$id": "https://example.com/data-type/object/v/1",
+            "title": "Other Object",
+            "type": "object"
+        });
+
+        OntologyTypeImport::new([
+            data_type("https://example.com/data-type/object/v/1"),
+            ImportedOntologyType::from_value(conflicting).expect("could not parse data type"),
+        ])
+        .expect_err("could order conflicting ontology types");
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
 /// A path to a [`PropertyType`] field.
 ///
 /// [`PropertyType`]: type_system::PropertyType
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PropertyTypeQueryPath<'p> {
     /// The [`BaseUrl`] of the [`PropertyType`].
     ///
@@ -117,6 +117,25 @@ pub enum PropertyTypeQueryPath<'p> {
     /// [`RecordArchivedById`]: graph_types::provenance::RecordArchivedById
     /// [`ProvenanceMetadata`]: graph_types::provenance::ProvenanceMetadata
     RecordArchivedById,
+    /// The [`DeprecatedById`] of the [`OntologyTypeDeprecation`] belonging to the
+    /// [`PropertyType`].
+    ///
+    /// The value is `null` if the [`PropertyType`] is not deprecated, so deprecated types can be
+    /// filtered by comparing this path with `null`:
+    ///
+    /// ```rust
+    /// # use serde::Deserialize;
+    /// # use serde_json::json;
+    /// # use graph::ontology::PropertyTypeQueryPath;
+    /// let path = PropertyTypeQueryPath::deserialize(json!(["deprecatedById"]))?;
+    /// assert_eq!(path, PropertyTypeQueryPath::DeprecatedById);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    ///
+    /// [`PropertyType`]: type_system::PropertyType
+    /// [`DeprecatedById`]: graph_types::provenance::DeprecatedById
+    /// [`OntologyTypeDeprecation`]: graph_types::ontology::OntologyTypeDeprecation
+    DeprecatedById,
     /// Corresponds to [`PropertyType::title()`].
     ///
     /// [`PropertyType::title()`]: type_system::PropertyType::title
@@ -236,6 +255,12 @@ pub enum PropertyTypeQueryPath<'p> {
     Schema(Option<JsonPath<'p>>),
     /// Only used internally and not available for deserialization.
     AdditionalMetadata,
+    /// Only used internally and not available for deserialization.
+    DeprecatedAt,
+    /// Only used internally and not available for deserialization.
+    SuccessorBaseUrl,
+    /// Only used internally and not available for deserialization.
+    SuccessorVersion,
 }
 
 impl OntologyQueryPath for PropertyTypeQueryPath<'_> {
@@ -282,12 +307,14 @@ impl QueryPath for PropertyTypeQueryPath<'_> {
             Self::OntologyId
             | Self::OwnedById
             | Self::RecordCreatedById
-            | Self::RecordArchivedById => ParameterType::Uuid,
+            | Self::RecordArchivedById
+            | Self::DeprecatedById => ParameterType::Uuid,
             Self::Schema(_) | Self::AdditionalMetadata => ParameterType::Object,
-            Self::BaseUrl => ParameterType::BaseUrl,
+            Self::BaseUrl | Self::SuccessorBaseUrl => ParameterType::BaseUrl,
             Self::VersionedUrl => ParameterType::VersionedUrl,
-            Self::Version => ParameterType::OntologyTypeVersion,
+            Self::Version | Self::SuccessorVersion => ParameterType::OntologyTypeVersion,
             Self::TransactionTime => ParameterType::TimeInterval,
+            Self::DeprecatedAt => ParameterType::Timestamp,
             Self::Title | Self::Description => ParameterType::Text,
             Self::DataTypeEdge { path, .. } => path.expected_type(),
             Self::PropertyTypeEdge { path, .. } => path.expected_type(),
@@ -307,6 +334,7 @@ impl fmt::Display for PropertyTypeQueryPath<'_> {
             Self::OwnedById => fmt.write_str("ownedById"),
             Self::RecordCreatedById => fmt.write_str("recordCreatedById"),
             Self::RecordArchivedById => fmt.write_str("recordArchivedById"),
+            Self::DeprecatedById => fmt.write_str("deprecatedById"),
             Self::Schema(Some(path)) => write!(fmt, "schema.{path}"),
             Self::Schema(None) => fmt.write_str("schema"),
             Self::Title => fmt.write_str("title"),
@@ -346,6 +374,9 @@ impl fmt::Display for PropertyTypeQueryPath<'_> {
                 edge_kind, path, ..
             } => write!(fmt, "<{edge_kind:?}>.{path}"),
             Self::AdditionalMetadata => fmt.write_str("additionalMetadata"),
+            Self::DeprecatedAt => fmt.write_str("deprecatedAt"),
+            Self::SuccessorBaseUrl => fmt.write_str("successorBaseUrl"),
+            Self::SuccessorVersion => fmt.write_str("successorVersion"),
         }
     }
 }
@@ -360,6 +391,7 @@ pub enum PropertyTypeQueryToken {
     OwnedById,
     RecordCreatedById,
     RecordArchivedById,
+    DeprecatedById,
     Title,
     Description,
     DataTypes,
@@ -377,7 +409,8 @@ pub struct PropertyTypeQueryPathVisitor {
 impl PropertyTypeQueryPathVisitor {
     pub const EXPECTING: &'static str = "one of `baseUrl`, `version`, `versionedUrl`, \
                                          `ownedById`, `recordCreatedById`, `recordArchivedById`, \
-                                         `title`, `description`, `dataTypes`, `propertyTypes`";
+                                         `deprecatedById`, `title`, `description`, `dataTypes`, \
+                                         `propertyTypes`";
 
     #[must_use]
     pub const fn new(position: usize) -> Self {
@@ -405,6 +438,7 @@ impl<'de> Visitor<'de> for PropertyTypeQueryPathVisitor {
             PropertyTypeQueryToken::OwnedById => PropertyTypeQueryPath::OwnedById,
             PropertyTypeQueryToken::RecordCreatedById => PropertyTypeQueryPath::RecordCreatedById,
             PropertyTypeQueryToken::RecordArchivedById => PropertyTypeQueryPath::RecordArchivedById,
+            PropertyTypeQueryToken::DeprecatedById => PropertyTypeQueryPath::DeprecatedById,
             PropertyTypeQueryToken::BaseUrl => PropertyTypeQueryPath::BaseUrl,
             PropertyTypeQueryToken::VersionedUrl => PropertyTypeQueryPath::VersionedUrl,
             PropertyTypeQueryToken::Version => PropertyTypeQueryPath::Version,
@@ -0,0 +1,340 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use type_system::url::VersionedUrl;
+use utoipa::ToSchema;
+
+use crate::ontology::validation::{reference, OntologySchemas};
+
+/// An [`EntityType`] with its inheritance merged and all referenced property types and data types
+/// inlined.
+///
+/// The schema is self-contained, so it can be used to generate forms or to validate entities
+/// without resolving any further ontology types. Link destinations are not inlined and still refer
+/// to their entity types.
+///
+/// [`EntityType`]: type_system::EntityType
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedEntityType {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    pub entity_type_id: VersionedUrl,
+    /// The entity types inherited from, directly or indirectly, ordered by their distance.
+    #[schema(value_type = Vec<SHARED_VersionedUrl>)]
+    pub inherits_from: Vec<VersionedUrl>,
+    /// The resolved schema containing the effective `properties`, `required` properties, and
+    /// `links`.
+    #[schema(value_type = Object)]
+    pub schema: Value,
+}
+
+impl ResolvedEntityType {
+    /// Resolves the entity type `entity_type_id` from the `schemas`.
+    ///
+    /// Properties and links of an entity type take precedence over the ones of the entity types
+    /// it inherits from. Returns `None` if the entity type is not contained in `schemas`.
+    pub(crate) fn resolve(
+        schemas: &OntologySchemas,
+        entity_type_id: &VersionedUrl,
+    ) -> Option<Self> {
+        let entity_type = schemas.entity_types.get(entity_type_id)?;
+
+        let mut inherits_from = Vec::new();
+        let mut visited = HashSet::from([entity_type_id.clone()]);
+        let mut pending = VecDeque::from([entity_type]);
+        while let Some(schema) = pending.pop_front() {
+            for parent in schema
+                .get("allOf")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(reference)
+            {
+                if visited.insert(parent.clone()) {
+                    pending.extend(schemas.entity_types.get(&parent));
+                    inherits_from.push(parent);
+                }
+            }
+        }
+
+        let resolver = Resolver { schemas };
+        let mut properties = Map::new();
+        let mut required = BTreeSet::new();
+        let mut links = Map::new();
+        for schema in inherits_from
+            .iter()
+            .rev()
+            .filter_map(|parent| schemas.entity_types.get(parent))
+            .chain([entity_type])
+        {
+            for (base_url, property) in schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                properties.insert(
+                    base_url.clone(),
+                    resolver.property(property, &mut Vec::new()),
+                );
+            }
+            required.extend(
+                schema
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str),
+            );
+            if let Some(entity_type_links) = schema.get("links").and_then(Value::as_object) {
+                links.extend(entity_type_links.clone());
+            }
+        }
+
+        let mut schema = Map::new();
+        schema.insert("$id".to_owned(), Value::from(entity_type_id.to_string()));
+        for key in ["title", "description", "examples"] {
+            if let Some(value) = entity_type.get(key) {
+                schema.insert(key.to_owned(), value.clone());
+            }
+        }
+        schema.insert("type".to_owned(), Value::from("object"));
+        schema.insert("properties".to_owned(), Value::Object(properties));
+        schema.insert(
+            "required".to_owned(),
+            required.into_iter().map(Value::from).collect(),
+        );
+        schema.insert("links".to_owned(), Value::Object(links));
+
+        Some(Self {
+            entity_type_id: entity_type_id.clone(),
+            inherits_from,
+            schema: Value::Object(schema),
+        })
+    }
+}
+
+/// Inlines the property types and data types referenced in a schema.
+struct Resolver<'s> {
+    schemas: &'s OntologySchemas,
+}
+
+impl Resolver<'_> {
+    /// Resolves a reference to a property type or an array of them.
+    ///
+    /// `resolving` contains the property types currently being inlined. References to them are
+    /// kept, so recursive property types do not recurse infinitely.
+    fn property(&self, schema: &Value, resolving: &mut Vec<VersionedUrl>) -> Value {
+        if let Some(property_type_id) = reference(schema) {
+            let Some(property_type) = self.schemas.property_types.get(&property_type_id) else {
+                return schema.clone();
+            };
+            if resolving.contains(&property_type_id) {
+                return schema.clone();
+            }
+
+            resolving.push(property_type_id);
+            let mut property_type = without_meta_schema(property_type);
+            if let Some(one_of) = property_type.get_mut("oneOf").and_then(Value::as_array_mut) {
+                for property_values in one_of {
+                    *property_values = self.property_values(property_values, resolving);
+                }
+            }
+            resolving.pop();
+            property_type
+        } else if let Some(items) = schema.get("items") {
+            let mut schema = schema.clone();
+            schema["items"] = self.property(items, resolving);
+            schema
+        } else {
+            schema.clone()
+        }
+    }
+
+    /// Resolves the possible values of a property type.
+    fn property_values(&self, schema: &Value, resolving: &mut Vec<VersionedUrl>) -> Value {
+        if let Some(data_type_id) = reference(schema) {
+            return self
+                .schemas
+                .data_types
+                .get(&data_type_id)
+                .map_or_else(|| schema.clone(), without_meta_schema);
+        }
+
+        let mut schema = schema.clone();
+        match schema.get("type").and_then(Value::as_str) {
+            Some("object") => {
+                if let Some(properties) =
+                    schema.get_mut("properties").and_then(Value::as_object_mut)
+                {
+                    for property in properties.values_mut() {
+                        *property = self.property(property, resolving);
+                    }
+                }
+            }
+            Some("array") => {
+                if let Some(one_of) = schema
+                    .pointer_mut("/items/oneOf")
+                    .and_then(Value::as_array_mut)
+                {
+                    for property_values in one_of {
+                        *property_values = self.property_values(property_values, resolving);
+                    }
+                }
+            }
+            _ => {}
+        }
+        schema
+    }
+}
+
+/// Returns the schema of an ontology type without the keys describing the meta schema.
+fn without_meta_schema(schema: &Value) -> Value {
+    let mut schema = schema.clone();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("$schema");
+        object.remove("kind");
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn url(url: &str) -> VersionedUrl {
+        VersionedUrl::from_str(url).expect("invalid versioned URL")
+    }
+
+    const TEXT: &str = "https://example.com/data-type/text/v/1";
+    const NAME: &str = "https://example.com/property-type/name/v/1";
+    const NESTED: &str = "https://example.com/property-type/nested/v/1";
+    const THING: &str = "https://example.com/entity-type/thing/v/1";
+    const PERSON: &str = "https://example.com/entity-type/person/v/1";
+    const FRIEND_OF: &str = "https://example.com/entity-type/friend-of/v/1";
+
+    fn schemas() -> OntologySchemas {
+        let mut schemas = OntologySchemas::default();
+        schemas.data_types.insert(
+            url(TEXT),
+            json!({
+                "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/data-type",
+                "kind": "dataType",
+                "$id": TEXT,
+                "title": "Text",
+                "type": "string",
+            }),
+        );
+        schemas.property_types.insert(
+            url(NAME),
+            json!({
+                "kind": "propertyType",
+                "$id": NAME,
+                "title": "Name",
+                "oneOf": [{ "$ref": TEXT }],
+            }),
+        );
+        schemas.property_types.insert(
+            url(NESTED),
+            json!({
+                "kind": "propertyType",
+                "$id": NESTED,
+                "title": "Nested",
+                "oneOf": [{
+                    "type": "object",
+                    "properties": {
+                        "https://example.com/property-type/nested/": { "$ref": NESTED },
+                    },
+                }],
+            }),
+        );
+        schemas.entity_types.insert(
+            url(THING),
+            json!({
+                "$id": THING,
+                "title": "Thing",
+                "properties": {
+                    "https://example.com/property-type/name/": { "$ref": NAME },
+                },
+                "required": ["https://example.com/property-type/name/"],
+            }),
+        );
+        schemas.entity_types.insert(
+            url(PERSON),
+            json!({
+                "$id": PERSON,
+                "title": "Person",
+                "allOf": [{ "$ref": THING }],
+                "properties": {
+                    "https://example.com/property-type/nested/": {
+                        "type": "array",
+                        "items": { "$ref": NESTED },
+                    },
+                },
+                "links": {
+                    FRIEND_OF: { "type": "array", "items": {} },
+                },
+            }),
+        );
+        schemas
+    }
+
+    #[test]
+    fn inheritance_is_merged() {
+        let resolved =
+            ResolvedEntityType::resolve(&schemas(), &url(PERSON)).expect("could not resolve");
+
+        assert_eq!(resolved.inherits_from, [url(THING)]);
+        assert_eq!(resolved.schema["title"], "Person");
+        assert_eq!(
+            resolved.schema["required"],
+            json!(["https://example.com/property-type/name/"])
+        );
+        assert_eq!(
+            resolved.schema["links"],
+            json!({ FRIEND_OF: { "type": "array", "items": {} } })
+        );
+    }
+
+    #[test]
+    fn referenced_types_are_inlined() {
+        let resolved =
+            ResolvedEntityType::resolve(&schemas(), &url(PERSON)).expect("could not resolve");
+
+        assert_eq!(
+            resolved.schema["properties"]["https://example.com/property-type/name/"],
+            json!({
+                "$id": NAME,
+                "title": "Name",
+                "oneOf": [{ "$id": TEXT, "title": "Text", "type": "string" }],
+            })
+        );
+        // The recursive reference of the nested property type is not inlined again.
+        assert_eq!(
+            resolved.schema["properties"]["https://example.com/property-type/nested/"],
+            json!({
+                "type": "array",
+                "items": {
+                    "$id": NESTED,
+                    "title": "Nested",
+                    "oneOf": [{
+                        "type": "object",
+                        "properties": {
+                            "https://example.com/property-type/nested/": { "$ref": NESTED },
+                        },
+                    }],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn missing_entity_type_is_not_resolved() {
+        assert!(ResolvedEntityType::resolve(&schemas(), &url(FRIEND_OF)).is_none());
+    }
+}
@@ -0,0 +1,74 @@
+use graph_types::provenance::OwnedById;
+use serde::Serialize;
+use type_system::url::VersionedUrl;
+use utoipa::ToSchema;
+
+/// Describes where a [`DataType`] or [`PropertyType`] is used.
+///
+/// Before changing an ontology type, this can be used to estimate the impact of the change: the
+/// ontology types referencing it and the entities currently having a value for it.
+///
+/// [`DataType`]: type_system::DataType
+/// [`PropertyType`]: type_system::PropertyType
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyTypeUsage {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    pub ontology_type_id: VersionedUrl,
+    /// The property types referencing the ontology type, directly or via nested property types.
+    pub property_types: Vec<PropertyTypeUsage>,
+    /// The entity types referencing the ontology type, directly, via nested property types, or
+    /// via an entity type they inherit from.
+    pub entity_types: Vec<EntityTypeUsage>,
+    /// The number of entities having a value for the ontology type per owner of the entities.
+    ///
+    /// Every entity is counted once, even if multiple of its entity types reference the ontology
+    /// type.
+    pub owners: Vec<OwnerUsage>,
+}
+
+/// A property type referencing the ontology type a usage report was requested for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyTypeUsage {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    pub property_type_id: VersionedUrl,
+    /// The owner of the property type, `None` for external property types.
+    #[schema(nullable = false)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owned_by_id: Option<OwnedById>,
+    /// The number of property types traversed to reach the ontology type.
+    ///
+    /// A depth of `1` means the property type references the ontology type directly.
+    pub depth: u32,
+}
+
+/// An entity type referencing the ontology type a usage report was requested for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityTypeUsage {
+    #[schema(value_type = SHARED_VersionedUrl)]
+    pub entity_type_id: VersionedUrl,
+    /// The owner of the entity type, `None` for external entity types.
+    #[schema(nullable = false)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owned_by_id: Option<OwnedById>,
+    /// Whether one of the properties of the entity type references the ontology type directly,
+    /// i.e. not only via nested property types.
+    pub direct: bool,
+    /// Whether the reference is only inherited from another entity type.
+    pub inherited: bool,
+    /// The number of entities of this entity type having a value for the ontology type.
+    pub entities: u64,
+    /// The number of entities of this entity type having a value for the ontology type per owner
+    /// of the entities.
+    pub owners: Vec<OwnerUsage>,
+}
+
+/// The number of entities owned by an account, which have a value for an ontology type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerUsage {
+    pub owned_by_id: OwnedById,
+    pub entities: u64,
+}
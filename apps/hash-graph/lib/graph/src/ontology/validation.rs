@@ -0,0 +1,343 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use error_stack::Result;
+use graph_types::ontology::{
+    DataTypeWithMetadata, EntityTypeWithMetadata, OntologyType, OntologyTypeReference,
+    PropertyTypeWithMetadata,
+};
+use serde_json::Value;
+use type_system::url::VersionedUrl;
+
+use crate::store::{
+    crud::Read, query::Filter, DataTypeStore, EntityTypeStore, PropertyTypeStore, QueryError,
+};
+
+/// The JSON schemas of ontology types, which entity properties are validated against.
+///
+/// Ontology types, which are not contained, are not validated, so values referring to them are
+/// considered valid.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct OntologySchemas {
+    pub(crate) data_types: HashMap<VersionedUrl, Value>,
+    pub(crate) property_types: HashMap<VersionedUrl, Value>,
+    pub(crate) entity_types: HashMap<VersionedUrl, Value>,
+}
+
+impl OntologySchemas {
+    /// Validates the properties of an entity against the entity type and its parents.
+    ///
+    /// Returns a description of every problem found in the properties.
+    pub(crate) fn validate_entity(
+        &self,
+        entity_type_id: &VersionedUrl,
+        properties: &Value,
+    ) -> Vec<String> {
+        let mut validator = PropertyValidator {
+            schemas: self,
+            errors: Vec::new(),
+        };
+        validator.validate_entity(entity_type_id, properties);
+        validator.errors
+    }
+
+    /// Reads the `pending` ontology types and the ontology types they depend on from the `store`.
+    ///
+    /// Ontology types already contained are neither read again nor are their dependencies
+    /// followed.
+    ///
+    /// # Errors
+    ///
+    /// - if any of the ontology types does not exist
+    /// - if reading from the `store` fails
+    pub(crate) async fn read<S>(
+        &mut self,
+        store: &S,
+        mut pending: Vec<SchemaReference>,
+    ) -> Result<(), QueryError>
+    where
+        S: DataTypeStore + PropertyTypeStore + EntityTypeStore,
+    {
+        while let Some(reference) = pending.pop() {
+            match reference {
+                SchemaReference::DataType(url) => {
+                    if !self.data_types.contains_key(&url) {
+                        let data_type = Read::<DataTypeWithMetadata>::read_one(
+                            store,
+                            &Filter::for_versioned_url(&url),
+                            None,
+                        )
+                        .await?;
+                        self.data_types.insert(url, schema_value(&data_type.schema));
+                    }
+                }
+                SchemaReference::PropertyType(url) => {
+                    if !self.property_types.contains_key(&url) {
+                        let property_type = Read::<PropertyTypeWithMetadata>::read_one(
+                            store,
+                            &Filter::for_versioned_url(&url),
+                            None,
+                        )
+                        .await?;
+                        pending.extend(references(&property_type.schema));
+                        self.property_types
+                            .insert(url, schema_value(&property_type.schema));
+                    }
+                }
+                SchemaReference::EntityType(url) => {
+                    if !self.entity_types.contains_key(&url) {
+                        let entity_type = Read::<EntityTypeWithMetadata>::read_one(
+                            store,
+                            &Filter::for_versioned_url(&url),
+                            None,
+                        )
+                        .await?;
+                        pending.extend(references(&entity_type.schema));
+                        self.entity_types
+                            .insert(url, schema_value(&entity_type.schema));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An owned reference to an ontology type.
+#[derive(Debug, Clone)]
+pub(crate) enum SchemaReference {
+    DataType(VersionedUrl),
+    PropertyType(VersionedUrl),
+    EntityType(VersionedUrl),
+}
+
+impl From<OntologyTypeReference<'_>> for SchemaReference {
+    fn from(reference: OntologyTypeReference<'_>) -> Self {
+        let url = reference.url().clone();
+        match reference {
+            OntologyTypeReference::DataTypeReference(_) => Self::DataType(url),
+            OntologyTypeReference::PropertyTypeReference(_) => Self::PropertyType(url),
+            OntologyTypeReference::EntityTypeReference(_) => Self::EntityType(url),
+        }
+    }
+}
+
+/// Returns the ontology types directly referenced by `ontology_type`.
+pub(crate) fn references<T: OntologyType>(ontology_type: &T) -> Vec<SchemaReference> {
+    ontology_type
+        .traverse_references()
+        .into_iter()
+        .map(SchemaReference::from)
+        .collect()
+}
+
+/// Returns the JSON representation of the schema of an ontology type.
+pub(crate) fn schema_value<T: OntologyType + Clone>(ontology_type: &T) -> Value {
+    serde_json::to_value(T::Representation::from(ontology_type.clone())).unwrap_or(Value::Null)
+}
+
+/// Validates entity properties against [`OntologySchemas`].
+struct PropertyValidator<'s> {
+    schemas: &'s OntologySchemas,
+    errors: Vec<String>,
+}
+
+impl PropertyValidator<'_> {
+    /// Validates the properties of an entity against the entity type and its parents.
+    fn validate_entity(&mut self, entity_type_id: &VersionedUrl, properties: &Value) {
+        let schemas = self.schemas;
+        let mut allowed_properties = HashMap::new();
+        let mut required_properties = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![entity_type_id.clone()];
+        while let Some(entity_type_id) = pending.pop() {
+            if !visited.insert(entity_type_id.clone()) {
+                continue;
+            }
+            let Some(entity_type) = schemas.entity_types.get(&entity_type_id) else {
+                // Values referring to missing ontology types are considered valid.
+                return;
+            };
+
+            if let Some(properties) = entity_type.get("properties").and_then(Value::as_object) {
+                allowed_properties.extend(properties.iter());
+            }
+            if let Some(required) = entity_type.get("required").and_then(Value::as_array) {
+                required_properties.extend(required.iter().filter_map(Value::as_str));
+            }
+            pending.extend(
+                entity_type
+                    .get("allOf")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(reference),
+            );
+        }
+
+        let Some(properties) = properties.as_object() else {
+            self.errors
+                .push("the properties are not an object".to_owned());
+            return;
+        };
+
+        for (base_url, value) in properties {
+            if let Some(schema) = allowed_properties.get(base_url) {
+                self.validate_property(base_url, schema, value);
+            } else {
+                self.errors
+                    .push(format!("`{base_url}` is not allowed by {entity_type_id}"));
+            }
+        }
+        for base_url in required_properties {
+            if !properties.contains_key(base_url) {
+                self.errors
+                    .push(format!("`{base_url}` is required by {entity_type_id}"));
+            }
+        }
+    }
+
+    /// Validates a value against a property type reference or an array of them.
+    fn validate_property(&mut self, path: &str, schema: &Value, value: &Value) {
+        if let Some(property_type_id) = reference(schema) {
+            let Some(property_type) = self.schemas.property_types.get(&property_type_id) else {
+                return;
+            };
+            let one_of = property_type
+                .get("oneOf")
+                .and_then(Value::as_array)
+                .map_or(&[][..], Vec::as_slice);
+            if !one_of
+                .iter()
+                .any(|property_values| self.matches(path, property_values, value))
+            {
+                self.errors.push(format!(
+                    "`{path}` does not match any possible value of {property_type_id}"
+                ));
+            }
+        } else if let Some(items) = self.validate_array(path, schema, value) {
+            for (index, item) in items.iter().enumerate() {
+                self.validate_property(&format!("{path}[{index}]"), &schema["items"], item);
+            }
+        }
+    }
+
+    /// Validates a value against the possible values of a property type.
+    fn validate_property_values(&mut self, path: &str, property_values: &Value, value: &Value) {
+        if let Some(data_type_id) = reference(property_values) {
+            let Some(data_type) = self.schemas.data_types.get(&data_type_id) else {
+                return;
+            };
+            let matches_type = match data_type.get("type").and_then(Value::as_str) {
+                Some("string") => value.is_string(),
+                Some("number") => value.is_number(),
+                Some("boolean") => value.is_boolean(),
+                Some("null") => value.is_null(),
+                Some("object") => value.is_object(),
+                Some("array") => value.is_array(),
+                _ => true,
+            };
+            if !matches_type {
+                self.errors
+                    .push(format!("`{path}` is not a valid {data_type_id}"));
+            }
+            return;
+        }
+
+        match property_values.get("type").and_then(Value::as_str) {
+            Some("object") => {
+                let Some(object) = value.as_object() else {
+                    self.errors.push(format!("`{path}` is not an object"));
+                    return;
+                };
+                let properties = property_values.get("properties");
+                for (base_url, value) in object {
+                    if let Some(schema) = properties.and_then(|properties| properties.get(base_url))
+                    {
+                        self.validate_property(&format!("{path}.{base_url}"), schema, value);
+                    } else {
+                        self.errors
+                            .push(format!("`{path}.{base_url}` is not allowed"));
+                    }
+                }
+                for base_url in property_values
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    if !object.contains_key(base_url) {
+                        self.errors.push(format!("`{path}.{base_url}` is required"));
+                    }
+                }
+            }
+            Some("array") => {
+                if let Some(items) = self.validate_array(path, property_values, value) {
+                    let one_of = property_values["items"]
+                        .get("oneOf")
+                        .and_then(Value::as_array)
+                        .map_or(&[][..], Vec::as_slice);
+                    for (index, item) in items.iter().enumerate() {
+                        let path = format!("{path}[{index}]");
+                        if !one_of
+                            .iter()
+                            .any(|property_values| self.matches(&path, property_values, item))
+                        {
+                            self.errors
+                                .push(format!("`{path}` does not match any possible value"));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns if the value validates against the possible values of a property type.
+    fn matches(&self, path: &str, property_values: &Value, value: &Value) -> bool {
+        let mut validator = PropertyValidator {
+            schemas: self.schemas,
+            errors: Vec::new(),
+        };
+        validator.validate_property_values(path, property_values, value);
+        validator.errors.is_empty()
+    }
+
+    /// Validates that the value is an array within the bounds of the schema.
+    ///
+    /// Returns the items of the array if the value is an array.
+    fn validate_array<'a>(
+        &mut self,
+        path: &str,
+        schema: &Value,
+        value: &'a Value,
+    ) -> Option<&'a Vec<Value>> {
+        let Some(items) = value.as_array() else {
+            self.errors.push(format!("`{path}` is not an array"));
+            return None;
+        };
+        let len = items.len() as u64;
+        if let Some(min_items) = schema.get("minItems").and_then(Value::as_u64) {
+            if len < min_items {
+                self.errors
+                    .push(format!("`{path}` has less than {min_items} items"));
+            }
+        }
+        if let Some(max_items) = schema.get("maxItems").and_then(Value::as_u64) {
+            if len > max_items {
+                self.errors
+                    .push(format!("`{path}` has more than {max_items} items"));
+            }
+        }
+        Some(items)
+    }
+}
+
+/// Returns the URL referenced by a `$ref` in the schema.
+pub(crate) fn reference(schema: &Value) -> Option<VersionedUrl> {
+    VersionedUrl::from_str(schema.get("$ref")?.as_str()?).ok()
+}
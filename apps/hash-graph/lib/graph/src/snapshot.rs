@@ -2,29 +2,68 @@ pub mod account;
 pub mod codec;
 pub mod entity;
 
+mod diff;
+mod dump;
 mod error;
+mod format;
+mod manifest;
 mod metadata;
 mod ontology;
+mod ownership_transfer;
 mod restore;
+mod upgrade;
+mod verify;
+
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use error_stack::{ensure, Context, Report, Result, ResultExt};
-use futures::{stream, SinkExt, Stream, StreamExt, TryFutureExt, TryStreamExt};
-use graph_types::knowledge::entity::Entity;
+use futures::{
+    future, stream, stream::BoxStream, SinkExt, Stream, StreamExt, TryFutureExt, TryStreamExt,
+};
+use graph_types::{
+    knowledge::entity::Entity,
+    ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
+};
 use hash_status::StatusCode;
 use serde::{Deserialize, Serialize};
+use temporal_versioning::{Timestamp, TransactionTime};
 use tokio_postgres::error::SqlState;
 use type_system::{DataType, EntityType, PropertyType};
+use uuid::Uuid;
 
 pub use self::{
-    error::{SnapshotDumpError, SnapshotRestoreError},
+    diff::{
+        diff_snapshots, SnapshotChange, SnapshotChangeKind, SnapshotDiffCounts,
+        SnapshotDiffSettings, SnapshotDiffSummary, ValueChange,
+    },
+    dump::{SnapshotDumpFilter, SnapshotDumpSettings, DEFAULT_MAX_PARALLELISM},
+    error::{SnapshotDiffError, SnapshotDumpError, SnapshotRestoreError, SnapshotUpgradeError},
+    format::{SnapshotFormat, SnapshotSink},
+    manifest::{SnapshotManifest, SnapshotRecordCounts},
     metadata::{BlockProtocolModuleVersions, CustomGlobalMetadata},
     ontology::OntologyTypeSnapshotRecord,
+    restore::{
+        SnapshotConflictBehavior, SnapshotRestoreCounts, SnapshotRestoreMode,
+        SnapshotRestoreProgress, SnapshotRestoreReport, SnapshotRestoreSettings,
+    },
+    upgrade::SNAPSHOT_VERSION,
+    verify::{SnapshotProblem, SnapshotProblemKind, SnapshotRecordId, SnapshotVerifier},
 };
 pub use crate::snapshot::metadata::SnapshotMetadata;
 use crate::{
-    snapshot::{entity::EntitySnapshotRecord, restore::SnapshotRecordBatch},
-    store::{crud::Read, query::Filter, AsClient, InsertionError, PostgresStore},
+    knowledge::EntityQueryPath,
+    ontology::{DataTypeQueryPath, EntityTypeQueryPath, PropertyTypeQueryPath},
+    snapshot::{
+        dump::SnapshotDependencies, entity::EntitySnapshotRecord, manifest::SnapshotContent,
+        restore::SnapshotRecordBatch,
+    },
+    store::{
+        crud::Read,
+        query::{Filter, FilterExpression, ParameterList},
+        AsClient, InsertionError, OwnershipTransferRecord, PostgresStore, StorePool,
+    },
+    subgraph::temporal_axes::QueryTemporalAxes,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -35,6 +74,8 @@ pub enum SnapshotEntry {
     PropertyType(OntologyTypeSnapshotRecord<PropertyType>),
     EntityType(OntologyTypeSnapshotRecord<EntityType>),
     Entity(EntitySnapshotRecord),
+    OwnershipTransfer(OwnershipTransferRecord),
+    Manifest(SnapshotManifest),
 }
 
 impl SnapshotEntry {
@@ -88,37 +129,215 @@ impl SnapshotEntry {
                     }
                 }
             }
+            Self::OwnershipTransfer(transfer) => {
+                context.push_body(format!("ownership transfer: {}", transfer.transfer_id));
+                if context.alternate() {
+                    if let Ok(json) = serde_json::to_string_pretty(transfer) {
+                        context.push_appendix(format!("{}:\n{json}", transfer.transfer_id));
+                    }
+                }
+            }
+            Self::Manifest(manifest) => {
+                context.push_body(format!("manifest checksum: {}", manifest.checksum));
+            }
         });
     }
 }
 
+/// The transaction snapshot shared by all connections used for dumping a snapshot.
+struct ExportedSnapshot<'f, C> {
+    /// The connection, which exported the transaction snapshot.
+    store: PostgresStore<C>,
+    snapshot_id: String,
+    metadata: SnapshotMetadata,
+    /// The owners of all entities, which are used to partition the entities.
+    owners: Vec<Uuid>,
+    filter: SnapshotDumpFilter<'f>,
+    dependencies: Option<SnapshotDependencies>,
+    /// The name of the database the snapshot is dumped from.
+    database: String,
+    /// The point in time the snapshot is dumped at.
+    dumped_at: Timestamp<TransactionTime>,
+}
+
+/// Selects the entities read by one partition of a snapshot dump.
+enum EntityPartition<'f> {
+    /// All entities owned by one of the accounts.
+    Owners(Vec<Uuid>),
+    /// The entities with one of the UUIDs, which are owned by one of the accounts.
+    Dependencies {
+        owners: Vec<Uuid>,
+        entity_uuids: Arc<[Uuid]>,
+    },
+    /// All entities matching the filter.
+    Filter(Filter<'f, Entity>),
+}
+
 #[async_trait]
 trait WriteBatch<C> {
     async fn begin(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError>;
     async fn write(&self, postgres_client: &PostgresStore<C>) -> Result<(), InsertionError>;
+    /// Prepares the written records to be merged into a store, which already contains records.
+    ///
+    /// Records already present in the store are not inserted again and records in the store,
+    /// which are superseded by the written records, are removed from the store.
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError>;
+    /// Prepares the written records to be merged into a store, which already contains records.
+    ///
+    /// Records already present in the store are not inserted again and records conflicting with
+    /// records in the store are handled as specified by `on_conflict`. The number of skipped and
+    /// conflicting records is added to the `report`.
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        on_conflict: SnapshotConflictBehavior,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError>;
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError>;
 }
 
-pub struct SnapshotStore<C>(PostgresStore<C>);
+/// Dumps and restores snapshots of the store.
+///
+/// Dumping a snapshot reads the records over multiple connections acquired from the pool, so the
+/// pool has to provide at least one more connection than the [`parallelism`] the snapshot is
+/// dumped with.
+///
+/// [`parallelism`]: SnapshotDumpSettings::parallelism
+pub struct SnapshotStore<'pool, P>(&'pool P);
 
-impl<C> SnapshotStore<C> {
-    pub const fn new(store: PostgresStore<C>) -> Self {
-        Self(store)
+impl<'pool, P> SnapshotStore<'pool, P> {
+    pub const fn new(pool: &'pool P) -> Self {
+        Self(pool)
     }
 }
 
-impl<C: AsClient> SnapshotStore<C> {
+impl<P, C> SnapshotStore<'_, P>
+where
+    P: StorePool<Store<'static> = PostgresStore<C>, Error: Context>,
+    C: AsClient + 'static,
+{
+    /// Acquires a connection, which reads inside of the exported transaction snapshot.
+    async fn acquire_snapshot_connection(
+        &self,
+        snapshot_id: &str,
+    ) -> Result<PostgresStore<C>, SnapshotDumpError> {
+        let store = self
+            .0
+            .acquire_owned()
+            .await
+            .change_context(SnapshotDumpError::Connection)?;
+
+        store
+            .as_client()
+            .client()
+            .simple_query(&format!(
+                "
+                    BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY;
+                    SET TRANSACTION SNAPSHOT '{snapshot_id}';
+                "
+            ))
+            .await
+            .change_context(SnapshotDumpError::Query)?;
+
+        Ok(store)
+    }
+
     /// Convenience function to create a stream of snapshot entries.
+    ///
+    /// The records are read over a separate connection inside of the exported transaction
+    /// snapshot. The transaction is committed after the last record was read.
     async fn create_dump_stream<T>(
         &self,
-    ) -> Result<impl Stream<Item = Result<T, SnapshotDumpError>> + Send, SnapshotDumpError>
+        snapshot_id: &str,
+        filter: &Filter<'_, <PostgresStore<C> as Read<T>>::Record>,
+        temporal_axes: Option<QueryTemporalAxes>,
+    ) -> Result<BoxStream<'static, Result<T, SnapshotDumpError>>, SnapshotDumpError>
     where
         PostgresStore<C>: Read<T>,
+        T: Send + 'static,
     {
-        Ok(Read::<T>::read(&self.0, &Filter::All(vec![]), None)
+        let store = self.acquire_snapshot_connection(snapshot_id).await?;
+
+        let records = Read::<T>::read(&store, filter, temporal_axes.as_ref())
             .await
             .map_err(|future_error| future_error.change_context(SnapshotDumpError::Query))?
-            .map_err(|stream_error| stream_error.change_context(SnapshotDumpError::Read)))
+            .map_err(|stream_error| stream_error.change_context(SnapshotDumpError::Read));
+
+        Ok(records
+            .chain(
+                stream::once(async move {
+                    store
+                        .as_client()
+                        .client()
+                        .simple_query("COMMIT;")
+                        .await
+                        .change_context(SnapshotDumpError::Query)
+                })
+                .try_filter_map(|_| future::ready(Ok(None))),
+            )
+            .boxed())
+    }
+
+    /// Starts the transaction, which snapshot is shared by all connections used for dumping.
+    ///
+    /// The connection has to be kept alive until all other connections have imported the snapshot.
+    async fn export_snapshot<'f>(
+        &self,
+        settings: SnapshotDumpSettings,
+        filter: SnapshotDumpFilter<'f>,
+    ) -> Result<ExportedSnapshot<'f, C>, SnapshotDumpError> {
+        let store = self
+            .0
+            .acquire_owned()
+            .await
+            .change_context(SnapshotDumpError::Connection)?;
+
+        store
+            .as_client()
+            .client()
+            .simple_query("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY;")
+            .await
+            .change_context(SnapshotDumpError::Query)?;
+
+        // The transaction snapshot is taken by the first query, so all changes up to the
+        // transaction time horizon read here are visible in the snapshot.
+        let metadata = settings.metadata(&store).await?;
+
+        let snapshot_id = store
+            .as_client()
+            .query_one("SELECT pg_export_snapshot();", &[])
+            .await
+            .change_context(SnapshotDumpError::Query)?
+            .get(0);
+
+        let source = store
+            .as_client()
+            .query_one("SELECT current_database(), now();", &[])
+            .await
+            .change_context(SnapshotDumpError::Query)?;
+
+        let owners = store
+            .as_client()
+            .query("SELECT DISTINCT owned_by_id FROM entity_ids;", &[])
+            .await
+            .change_context(SnapshotDumpError::Query)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect();
+
+        let dependencies = filter
+            .dependencies(&store, settings.temporal_axes().as_ref())
+            .await?;
+
+        Ok(ExportedSnapshot {
+            store,
+            snapshot_id,
+            metadata,
+            owners,
+            filter,
+            dependencies,
+            database: source.get(0),
+            dumped_at: Timestamp::from_anonymous(source.get(1)),
+        })
     }
 
     /// Reads the snapshot from the store into the given sink.
@@ -126,60 +345,392 @@ impl<C: AsClient> SnapshotStore<C> {
     /// The sink is expected to be a `futures::Sink` that can be used to write the snapshot entries
     /// into.
     ///
+    /// By default, the full history of the store is dumped. If a transaction time or a decision
+    /// time is pinned in the `settings`, only the editions visible at that point in time are
+    /// dumped. Their intervals on the pinned axes are left open-ended, so the restored store
+    /// looks as the store did at that point in time.
+    ///
+    /// If `since` is specified in the `settings`, an incremental snapshot is dumped, which only
+    /// contains the editions created or closed at or after that transaction time. The metadata of
+    /// each snapshot records the transaction time up to which all changes are contained, which is
+    /// the point the next incremental snapshot is dumped from.
+    ///
+    /// Only the records selected by the `filter` are dumped. If the filter includes dependencies,
+    /// the records the selected records depend on are resolved first and dumped as well.
+    ///
+    /// Postgres does not allow to have multiple queries open on the same connection, so each
+    /// record kind and each partition of the entities is read over a separate connection. All
+    /// connections read inside of the same exported repeatable read snapshot, so the dump is
+    /// consistent. Entities are only partitioned if all entities or their dependencies are
+    /// dumped. The metadata is always the first entry and the [`SnapshotManifest`] is always the
+    /// last entry, the order of the other entries is not specified.
+    ///
     /// # Errors
     ///
+    /// - If the settings combine `since` with a pinned timestamp
+    /// - If acquiring a connection from the pool fails
+    /// - If resolving the dependencies of the selected records fails
     /// - If reading a record from the datastore fails
     /// - If writing a record into the sink fails
-    pub fn dump_snapshot(
+    pub fn dump_snapshot<'f>(
+        &'f self,
+        settings: SnapshotDumpSettings,
+        filter: SnapshotDumpFilter<'f>,
+    ) -> impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + 'f {
+        stream::once(self.export_snapshot(settings, filter))
+            .map_ok(move |exported| {
+                let ExportedSnapshot {
+                    store,
+                    snapshot_id,
+                    metadata,
+                    owners,
+                    filter,
+                    dependencies,
+                    database,
+                    dumped_at,
+                } = exported;
+                let parallelism = settings.parallelism();
+                let temporal_axes = settings.temporal_axes();
+                let partition_entities = dependencies.is_some() || filter.selects_all_entities();
+                // Transfers are not associated with the selected records, so they are only dumped
+                // if all records are selected.
+                let ownership_transfers = filter.selects_all().then(|| {
+                    self.dump_ownership_transfers(snapshot_id.clone(), settings)
+                        .boxed()
+                });
+                let ontology_ids = dependencies
+                    .as_ref()
+                    .map(|dependencies| Arc::clone(&dependencies.ontology_ids));
+
+                let ontology_partitions = [
+                    self.dump_data_types(
+                        snapshot_id.clone(),
+                        filter.data_types,
+                        ontology_ids.clone(),
+                        temporal_axes.clone(),
+                        settings,
+                    )
+                    .boxed(),
+                    self.dump_property_types(
+                        snapshot_id.clone(),
+                        filter.property_types,
+                        ontology_ids.clone(),
+                        temporal_axes.clone(),
+                        settings,
+                    )
+                    .boxed(),
+                    self.dump_entity_types(
+                        snapshot_id.clone(),
+                        filter.entity_types,
+                        ontology_ids,
+                        temporal_axes.clone(),
+                        settings,
+                    )
+                    .boxed(),
+                ];
+
+                let entity_partitions = if partition_entities {
+                    owners
+                        .chunks(owners.len().div_ceil(parallelism).max(1))
+                        .map(|owners| {
+                            if let Some(dependencies) = &dependencies {
+                                EntityPartition::Dependencies {
+                                    owners: owners.to_vec(),
+                                    entity_uuids: Arc::clone(&dependencies.entity_uuids),
+                                }
+                            } else {
+                                EntityPartition::Owners(owners.to_vec())
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                } else {
+                    vec![EntityPartition::Filter(filter.entities)]
+                }
+                .into_iter()
+                .map(|partition| {
+                    self.dump_entities(
+                        snapshot_id.clone(),
+                        partition,
+                        temporal_axes.clone(),
+                        settings,
+                    )
+                    .boxed()
+                })
+                .collect::<Vec<_>>();
+
+                let entries = stream::once(future::ready(Ok(SnapshotEntry::Snapshot(metadata))))
+                    .chain(
+                        stream::iter(ontology_partitions)
+                            .chain(stream::iter(entity_partitions))
+                            .chain(stream::iter(ownership_transfers))
+                            .map(TryFutureExt::try_flatten_stream)
+                            .flatten_unordered(parallelism),
+                    )
+                    .chain(
+                        stream::once(async move {
+                            store
+                                .as_client()
+                                .client()
+                                .simple_query("COMMIT;")
+                                .await
+                                .change_context(SnapshotDumpError::Query)
+                        })
+                        .try_filter_map(|_| future::ready(Ok(None))),
+                    );
+
+                // The manifest is appended after all other entries were read successfully.
+                stream::unfold(
+                    (entries.boxed(), Some(SnapshotContent::default())),
+                    move |(mut entries, content)| {
+                        let database = database.clone();
+                        async move {
+                            let mut content = content?;
+                            match entries.next().await {
+                                Some(Ok(entry)) => {
+                                    content.add(&entry);
+                                    Some((Ok(entry), (entries, Some(content))))
+                                }
+                                Some(Err(error)) => Some((Err(error), (entries, None))),
+                                None => Some((
+                                    Ok(SnapshotEntry::Manifest(
+                                        content.manifest(database, dumped_at),
+                                    )),
+                                    (entries, None),
+                                )),
+                            }
+                        }
+                    },
+                )
+            })
+            .try_flatten()
+    }
+
+    /// Dumps the data types matching the `filter` or, if specified, with one of the
+    /// `ontology_ids`.
+    async fn dump_data_types(
         &self,
-    ) -> impl Stream<Item = Result<SnapshotEntry, SnapshotDumpError>> + '_ {
-        // TODO: Postgres does not allow to have multiple queries open at the same time. This means
-        //       that each stream needs to be fully processed before the next one can be created.
-        //       We might want to work around this by using a single stream that yields all the
-        //       entries or even use multiple connections to the database.
-        //   see https://app.asana.com/0/0/1204347352251098/f
-
-        stream::once(async {
-            SnapshotEntry::Snapshot(SnapshotMetadata {
-                block_protocol_module_versions: BlockProtocolModuleVersions {
-                    graph: semver::Version::new(0, 3, 0),
-                },
-                custom: CustomGlobalMetadata,
+        snapshot_id: String,
+        filter: Filter<'_, DataTypeWithMetadata>,
+        ontology_ids: Option<Arc<[Uuid]>>,
+        temporal_axes: Option<QueryTemporalAxes>,
+        settings: SnapshotDumpSettings,
+    ) -> Result<BoxStream<'static, Result<SnapshotEntry, SnapshotDumpError>>, SnapshotDumpError>
+    {
+        let records = if let Some(ontology_ids) = ontology_ids {
+            self.create_dump_stream::<OntologyTypeSnapshotRecord<DataType>>(
+                &snapshot_id,
+                &settings.restrict_to_changes(
+                    Filter::In(
+                        FilterExpression::Path(DataTypeQueryPath::OntologyId),
+                        ParameterList::Uuid(&ontology_ids),
+                    ),
+                    DataTypeQueryPath::TransactionTime,
+                ),
+                temporal_axes,
+            )
+            .await?
+        } else {
+            self.create_dump_stream::<OntologyTypeSnapshotRecord<DataType>>(
+                &snapshot_id,
+                &settings.restrict_to_changes(filter, DataTypeQueryPath::TransactionTime),
+                temporal_axes,
+            )
+            .await?
+        };
+
+        Ok(records
+            .map_ok(move |mut data_type| {
+                settings.pin_ontology_type(&mut data_type.metadata.custom);
+                SnapshotEntry::DataType(data_type)
             })
-        })
-        .map(Ok)
-        .chain(
-            self.create_dump_stream::<OntologyTypeSnapshotRecord<DataType>>()
-                .try_flatten_stream()
-                .map_ok(SnapshotEntry::DataType),
-        )
-        .chain(
-            self.create_dump_stream::<OntologyTypeSnapshotRecord<PropertyType>>()
-                .try_flatten_stream()
-                .map_ok(SnapshotEntry::PropertyType),
-        )
-        .chain(
-            self.create_dump_stream::<OntologyTypeSnapshotRecord<EntityType>>()
-                .try_flatten_stream()
-                .map_ok(SnapshotEntry::EntityType),
-        )
-        .chain(
-            self.create_dump_stream::<Entity>()
-                .try_flatten_stream()
-                .map_ok(|entity| SnapshotEntry::Entity(entity.into())),
-        )
+            .boxed())
+    }
+
+    /// Dumps the property types matching the `filter` or, if specified, with one of the
+    /// `ontology_ids`.
+    async fn dump_property_types(
+        &self,
+        snapshot_id: String,
+        filter: Filter<'_, PropertyTypeWithMetadata>,
+        ontology_ids: Option<Arc<[Uuid]>>,
+        temporal_axes: Option<QueryTemporalAxes>,
+        settings: SnapshotDumpSettings,
+    ) -> Result<BoxStream<'static, Result<SnapshotEntry, SnapshotDumpError>>, SnapshotDumpError>
+    {
+        let records = if let Some(ontology_ids) = ontology_ids {
+            self.create_dump_stream::<OntologyTypeSnapshotRecord<PropertyType>>(
+                &snapshot_id,
+                &settings.restrict_to_changes(
+                    Filter::In(
+                        FilterExpression::Path(PropertyTypeQueryPath::OntologyId),
+                        ParameterList::Uuid(&ontology_ids),
+                    ),
+                    PropertyTypeQueryPath::TransactionTime,
+                ),
+                temporal_axes,
+            )
+            .await?
+        } else {
+            self.create_dump_stream::<OntologyTypeSnapshotRecord<PropertyType>>(
+                &snapshot_id,
+                &settings.restrict_to_changes(filter, PropertyTypeQueryPath::TransactionTime),
+                temporal_axes,
+            )
+            .await?
+        };
+
+        Ok(records
+            .map_ok(move |mut property_type| {
+                settings.pin_ontology_type(&mut property_type.metadata.custom);
+                SnapshotEntry::PropertyType(property_type)
+            })
+            .boxed())
+    }
+
+    /// Dumps the entity types matching the `filter` or, if specified, with one of the
+    /// `ontology_ids`.
+    async fn dump_entity_types(
+        &self,
+        snapshot_id: String,
+        filter: Filter<'_, EntityTypeWithMetadata>,
+        ontology_ids: Option<Arc<[Uuid]>>,
+        temporal_axes: Option<QueryTemporalAxes>,
+        settings: SnapshotDumpSettings,
+    ) -> Result<BoxStream<'static, Result<SnapshotEntry, SnapshotDumpError>>, SnapshotDumpError>
+    {
+        let records = if let Some(ontology_ids) = ontology_ids {
+            self.create_dump_stream::<OntologyTypeSnapshotRecord<EntityType>>(
+                &snapshot_id,
+                &settings.restrict_to_changes(
+                    Filter::In(
+                        FilterExpression::Path(EntityTypeQueryPath::OntologyId),
+                        ParameterList::Uuid(&ontology_ids),
+                    ),
+                    EntityTypeQueryPath::TransactionTime,
+                ),
+                temporal_axes,
+            )
+            .await?
+        } else {
+            self.create_dump_stream::<OntologyTypeSnapshotRecord<EntityType>>(
+                &snapshot_id,
+                &settings.restrict_to_changes(filter, EntityTypeQueryPath::TransactionTime),
+                temporal_axes,
+            )
+            .await?
+        };
+
+        Ok(records
+            .map_ok(move |mut entity_type| {
+                settings.pin_ontology_type(&mut entity_type.metadata.custom.common);
+                SnapshotEntry::EntityType(entity_type)
+            })
+            .boxed())
+    }
+
+    /// Dumps the entities selected by the `partition`.
+    async fn dump_entities(
+        &self,
+        snapshot_id: String,
+        partition: EntityPartition<'_>,
+        temporal_axes: Option<QueryTemporalAxes>,
+        settings: SnapshotDumpSettings,
+    ) -> Result<BoxStream<'static, Result<SnapshotEntry, SnapshotDumpError>>, SnapshotDumpError>
+    {
+        let records = match partition {
+            EntityPartition::Owners(owners) => {
+                self.create_dump_stream::<Entity>(
+                    &snapshot_id,
+                    &settings.restrict_to_changes(
+                        Filter::In(
+                            FilterExpression::Path(EntityQueryPath::OwnedById),
+                            ParameterList::Uuid(&owners),
+                        ),
+                        EntityQueryPath::TransactionTime,
+                    ),
+                    temporal_axes,
+                )
+                .await?
+            }
+            EntityPartition::Dependencies {
+                owners,
+                entity_uuids,
+            } => {
+                self.create_dump_stream::<Entity>(
+                    &snapshot_id,
+                    &settings.restrict_to_changes(
+                        Filter::All(vec![
+                            Filter::In(
+                                FilterExpression::Path(EntityQueryPath::OwnedById),
+                                ParameterList::Uuid(&owners),
+                            ),
+                            Filter::In(
+                                FilterExpression::Path(EntityQueryPath::Uuid),
+                                ParameterList::Uuid(&entity_uuids),
+                            ),
+                        ]),
+                        EntityQueryPath::TransactionTime,
+                    ),
+                    temporal_axes,
+                )
+                .await?
+            }
+            EntityPartition::Filter(filter) => {
+                self.create_dump_stream::<Entity>(
+                    &snapshot_id,
+                    &settings.restrict_to_changes(filter, EntityQueryPath::TransactionTime),
+                    temporal_axes,
+                )
+                .await?
+            }
+        };
+
+        Ok(records
+            .map_ok(EntitySnapshotRecord::from)
+            .map_ok(move |mut entity| {
+                settings.pin_entity(&mut entity);
+                SnapshotEntry::Entity(entity)
+            })
+            .boxed())
+    }
+
+    /// Dumps the ownership transfers, which were recorded in the transaction time range of the
+    /// snapshot.
+    async fn dump_ownership_transfers(
+        &self,
+        snapshot_id: String,
+        settings: SnapshotDumpSettings,
+    ) -> Result<BoxStream<'static, Result<SnapshotEntry, SnapshotDumpError>>, SnapshotDumpError>
+    {
+        let store = self.acquire_snapshot_connection(&snapshot_id).await?;
+
+        let transfers = store
+            .read_ownership_transfers(None, settings.since, settings.transaction_time)
+            .await
+            .change_context(SnapshotDumpError::Query)?;
+
+        store
+            .as_client()
+            .client()
+            .simple_query("COMMIT;")
+            .await
+            .change_context(SnapshotDumpError::Query)?;
+
+        Ok(stream::iter(transfers)
+            .map(|transfer| Ok(SnapshotEntry::OwnershipTransfer(transfer)))
+            .boxed())
     }
 
     /// Reads the snapshot from from the stream into the store.
     ///
-    /// The data emitted by the stream is read in a separate thread and is sent to different
-    /// channels for each record type. Each channel holds a buffer of `chunk_size` entries. The
-    /// receivers of the channels are then used to insert the records into the store. When a write
-    /// operation to the store succeeds, the next entry is read from the channel, even if the
-    /// buffer of the channel is not full yet. This ensures, that the store is continuously writing
-    /// to the database and does not wait for the buffer to be full.
+    /// The data emitted by the stream is sent to different channels for each record type. Each
+    /// channel holds a buffer of `chunk_size` entries. The receivers of the channels are then used
+    /// to insert the records into the store. When a write operation to the store succeeds, the
+    /// next entry is read from the channel, even if the buffer of the channel is not full yet. This
+    /// ensures, that the store is continuously writing to the database and does not wait for the
+    /// buffer to be full.
     ///
-    /// Writing to the store happens in three stages:
+    /// Writing to the store happens in these stages:
     ///   1. The first stage is the `begin` stage. This stage is executed before any records are
     ///      read from the stream. It is used to create a transaction, so a possible rollback is
     ///      possible. For each data, which is inserted, a temporary table is created. This table is
@@ -188,109 +739,350 @@ impl<C: AsClient> SnapshotStore<C> {
     ///   2. The second stage is the `write` stage. This stage is executed for each record type. It
     ///      reads the batch of records from the channels and inserts them into the temporary
     ///      tables, which were created above.
-    ///   3. The third stage is the `commit` stage. This stage is executed after all records have
+    ///   3. Depending on the [`SnapshotRestoreMode`], the records are prepared to be merged into
+    ///      the store. For an incremental snapshot, records already present in the store are
+    ///      skipped and records in the store, which are superseded by the snapshot, e.g. because an
+    ///      edition was closed since the base snapshot, are replaced. When merging a snapshot into
+    ///      a non-empty store, records already present in the store are skipped and conflicting
+    ///      records are handled as specified by the [`SnapshotConflictBehavior`].
+    ///   4. The last stage is the `commit` stage. This stage is executed after all records have
     ///      been read from the stream. It is used to insert the data from the temporary tables into
     ///      the store and to drop the temporary tables. As foreign key constraints are now enabled,
     ///      this stage might fail. In this case, the transaction is rolled back and the error is
     ///      returned.
     ///
+    /// By default, all stages are executed in a single transaction. If a `checkpoint_interval` is
+    /// specified in the [`SnapshotRestoreSettings`], the `write` stage is split into multiple
+    /// transactions instead, each writing up to `checkpoint_interval` entries into staging tables
+    /// in the `snapshot_restore` schema and recording the number of entries written so far. If the
+    /// restore is interrupted, it can be continued by passing the same snapshot again with
+    /// `resume` set, which skips the entries up to the last checkpoint. The records are only moved
+    /// into the store in the last transaction, so the store is never left partially restored. The
+    /// staging tables of an interrupted restore are only replaced if `discard_checkpoint` is set,
+    /// and an advisory lock prevents concurrent restores from sharing them.
+    ///
+    /// The progress of the restore is reported through `tracing` periodically.
+    ///
     /// If the input stream contains an `Err` value, the snapshot restore is aborted and the error
     /// is returned.
     ///
+    /// An incremental snapshot can only be restored with [`SnapshotRestoreMode::Incremental`], on
+    /// top of a store containing all changes up to the transaction time the incremental snapshot
+    /// was dumped since.
+    ///
+    /// Returns a report of how many records were inserted, skipped or conflicting.
+    ///
     /// # Errors
     ///
     /// - If reading a record from the provided stream fails
+    /// - If the snapshot metadata is missing or not supported
+    /// - If the snapshot announces a manifest, but is truncated or its checksum does not match
+    /// - If the snapshot is restored incrementally but is not incremental or vice versa
+    /// - If the snapshot conflicts with the store and [`SnapshotConflictBehavior::Fail`] is used
+    /// - If a restore is resumed, but the snapshot ends before the last checkpoint
+    /// - If an interrupted restore exists, which is neither resumed nor its checkpoint discarded
+    /// - If another restore with a `checkpoint_interval` is in progress
     /// - If writing a record into the datastore fails
     pub async fn restore_snapshot(
-        &mut self,
-        snapshot: impl Stream<Item = Result<SnapshotEntry, impl Context>> + Send + 'static,
-        chunk_size: usize,
-    ) -> Result<(), SnapshotRestoreError> {
+        &self,
+        snapshot: impl Stream<Item = Result<SnapshotEntry, impl Context>> + Send,
+        settings: SnapshotRestoreSettings,
+    ) -> Result<SnapshotRestoreReport, SnapshotRestoreError> {
         tracing::info!("snapshot restore started");
 
-        let (snapshot_record_tx, snapshot_record_rx, metadata_rx) = restore::channel(chunk_size);
-
-        let read_thread = tokio::spawn(
-            snapshot
-                .map_err(|report| report.change_context(SnapshotRestoreError::Read))
-                .forward(
-                    snapshot_record_tx
-                        .sink_map_err(|report| report.change_context(SnapshotRestoreError::Buffer)),
-                ),
-        );
+        let mut entries = snapshot
+            .map_err(|report| report.change_context(SnapshotRestoreError::Read))
+            .and_then(|entry| async move {
+                if let SnapshotEntry::Snapshot(metadata) = &entry {
+                    let version = &metadata.block_protocol_module_versions.graph;
+                    ensure!(
+                        *version == SNAPSHOT_VERSION,
+                        Report::new(SnapshotRestoreError::Unsupported).attach_printable(format!(
+                            "the snapshot has version {version}, but only version \
+                             {SNAPSHOT_VERSION} can be restored"
+                        ))
+                    );
+                }
+                Ok(entry)
+            })
+            .boxed();
+        let mut read = SnapshotRead::default();
 
-        let client = self
+        let mut store = self
             .0
-            .transaction()
+            .acquire_owned()
             .await
-            .change_context(SnapshotRestoreError::Write)?;
+            .change_context(SnapshotRestoreError::Write)
+            .attach_printable("unable to acquire a connection to the store")?;
 
-        SnapshotRecordBatch::begin(&client)
-            .await
-            .change_context(SnapshotRestoreError::Write)?;
+        let report = if let Some(checkpoint_interval) = settings.checkpoint_interval {
+            // The staging tables are shared, so only one checkpointed restore may run at a time
+            restore::lock_staging(&store).await?;
+            let report = restore_with_checkpoints(
+                &mut store,
+                &mut entries,
+                &mut read,
+                checkpoint_interval,
+                settings,
+            )
+            .await;
+            let unlocked = restore::unlock_staging(&store).await;
+            let report = report?;
+            unlocked?;
+            report
+        } else {
+            let client = store
+                .transaction()
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
 
-        let client = snapshot_record_rx
-            .map(Ok::<_, Report<SnapshotRestoreError>>)
-            .try_fold(client, |client, records: SnapshotRecordBatch| async move {
-                records
-                    .write(&client)
-                    .await
-                    .change_context(SnapshotRestoreError::Write)?;
-                Ok(client)
-            })
-            .await?;
+            SnapshotRecordBatch::begin(&client)
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+            write_entries(&client, &mut entries, settings.chunk_size, &mut read).await?;
+            let report = finish_restore(&client, &read, settings.mode).await?;
+
+            client
+                .commit()
+                .await
+                .change_context(SnapshotRestoreError::Write)
+                .attach_printable("unable to commit snapshot to the store")?;
+            report
+        };
+
+        tracing::info!(%report, "snapshot restore finished");
+
+        Ok(report)
+    }
+}
 
-        tracing::info!("snapshot reading finished, committing...");
+/// The state accumulated while reading the entries of a snapshot.
+#[derive(Debug, Default)]
+struct SnapshotRead {
+    content: SnapshotContent,
+    manifest: Option<SnapshotManifest>,
+    metadata: Vec<SnapshotMetadata>,
+    progress: SnapshotRestoreProgress,
+}
+
+impl SnapshotRead {
+    /// Adds the `entry` to the content of the snapshot, the metadata is collected separately.
+    fn add(&mut self, entry: &SnapshotEntry) {
+        if let SnapshotEntry::Manifest(manifest) = entry {
+            self.manifest = Some(manifest.clone());
+        } else {
+            self.content.add(entry);
+        }
+
+        self.progress.add(entry);
+        if self.progress.is_due(Duration::from_secs(10)) {
+            tracing::info!(progress = %self.progress, "restoring snapshot");
+        }
+    }
+}
+
+/// Restores the `entries` in transactions of up to `checkpoint_interval` entries, which are written
+/// into the staging tables, and moves them into the store after the whole snapshot was read.
+///
+/// If the restore is resumed, the entries up to the last checkpoint are skipped.
+async fn restore_with_checkpoints<C: AsClient>(
+    store: &mut PostgresStore<C>,
+    entries: &mut BoxStream<'_, Result<SnapshotEntry, SnapshotRestoreError>>,
+    read: &mut SnapshotRead,
+    checkpoint_interval: NonZeroUsize,
+    settings: SnapshotRestoreSettings,
+) -> Result<SnapshotRestoreReport, SnapshotRestoreError> {
+    let checkpoint =
+        restore::prepare_staging(store, settings.resume, settings.discard_checkpoint).await?;
+    if checkpoint > 0 {
+        tracing::info!(entries = checkpoint, "resuming snapshot restore");
+    }
+    for _ in 0..checkpoint {
+        let entry = entries.try_next().await?.ok_or_else(|| {
+            Report::new(SnapshotRestoreError::Read).attach_printable(
+                "the snapshot ends before the checkpoint of the interrupted restore",
+            )
+        })?;
+        read.add(&entry);
+        if let SnapshotEntry::Snapshot(metadata) = entry {
+            read.metadata.push(metadata);
+        }
+    }
 
-        SnapshotRecordBatch::commit(&client)
+    let mut processed = checkpoint;
+    loop {
+        let client = store
+            .transaction()
             .await
-            .change_context(SnapshotRestoreError::Write)
-            .map_err(|report| {
-                if let Some(error) = report
-                    .downcast_ref()
-                    .and_then(tokio_postgres::Error::as_db_error)
-                {
-                    match *error.code() {
-                        SqlState::FOREIGN_KEY_VIOLATION => {
-                            report.attach_printable(StatusCode::NotFound)
-                        }
-                        SqlState::UNIQUE_VIOLATION => {
-                            report.attach_printable(StatusCode::AlreadyExists)
-                        }
-                        _ => report,
-                    }
-                } else {
-                    report
-                }
-            })?;
+            .change_context(SnapshotRestoreError::Write)?;
+        restore::use_staging(&client).await?;
 
+        let written = write_entries(
+            &client,
+            (&mut *entries).take(checkpoint_interval.get()),
+            settings.chunk_size,
+            read,
+        )
+        .await?;
+        processed += written;
+
+        restore::update_checkpoint(&client, processed).await?;
         client
             .commit()
             .await
             .change_context(SnapshotRestoreError::Write)
-            .attach_printable("unable to commit snapshot to the store")?;
+            .attach_printable("unable to commit checkpoint")?;
+        tracing::info!(entries = processed, "snapshot restore checkpoint committed");
 
-        read_thread
-            .await
-            .change_context(SnapshotRestoreError::Read)??;
+        if written < checkpoint_interval.get() {
+            break;
+        }
+    }
 
-        let mut found_metadata = false;
-        for metadata in metadata_rx.collect::<Vec<SnapshotMetadata>>().await {
-            if found_metadata {
-                tracing::warn!("found more than one metadata record in the snapshot");
-            }
-            found_metadata = true;
+    let client = store
+        .transaction()
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+    restore::use_staging(&client).await?;
+    let report = finish_restore(&client, read, settings.mode).await?;
+    restore::drop_staging(&client).await?;
+    client
+        .commit()
+        .await
+        .change_context(SnapshotRestoreError::Write)
+        .attach_printable("unable to commit snapshot to the store")?;
+    Ok(report)
+}
+
+/// Writes the `entries` into the temporary tables and returns the number of entries read.
+async fn write_entries<C: AsClient>(
+    client: &PostgresStore<C>,
+    entries: impl Stream<Item = Result<SnapshotEntry, SnapshotRestoreError>> + Send,
+    chunk_size: usize,
+    read: &mut SnapshotRead,
+) -> Result<usize, SnapshotRestoreError> {
+    let (snapshot_record_tx, snapshot_record_rx, metadata_rx) = restore::channel(chunk_size);
 
+    let mut num_entries = 0;
+    let read_entries = entries
+        .inspect_ok(|entry| {
+            num_entries += 1;
+            read.add(entry);
+        })
+        .forward(
+            snapshot_record_tx
+                .sink_map_err(|report| report.change_context(SnapshotRestoreError::Buffer)),
+        );
+    let write_records = snapshot_record_rx
+        .map(Ok::<_, Report<SnapshotRestoreError>>)
+        .try_for_each(|records: SnapshotRecordBatch| async move {
+            records
+                .write(client)
+                .await
+                .change_context(SnapshotRestoreError::Write)
+        });
+    future::try_join(read_entries, write_records).await?;
+
+    read.metadata
+        .extend(metadata_rx.collect::<Vec<SnapshotMetadata>>().await);
+
+    Ok(num_entries)
+}
+
+/// Checks the read snapshot and moves the records from the temporary tables into the store.
+async fn finish_restore<C: AsClient>(
+    client: &PostgresStore<C>,
+    read: &SnapshotRead,
+    mode: SnapshotRestoreMode,
+) -> Result<SnapshotRestoreReport, SnapshotRestoreError> {
+    tracing::info!(progress = %read.progress, "snapshot reading finished, committing...");
+
+    let mut found_metadata = false;
+    for metadata in &read.metadata {
+        if found_metadata {
+            tracing::warn!("found more than one metadata record in the snapshot");
+        }
+        found_metadata = true;
+
+        if metadata.custom.has_manifest {
+            let manifest = read
+                .manifest
+                .as_ref()
+                .ok_or_else(|| Report::new(SnapshotRestoreError::Truncated))
+                .attach_printable("the snapshot does not end with a manifest")?;
+            ensure!(
+                manifest.records == read.content.records(),
+                Report::new(SnapshotRestoreError::Truncated).attach_printable(format!(
+                    "the snapshot contains {:?} but the manifest expects {:?}",
+                    read.content.records(),
+                    manifest.records
+                ))
+            );
+            ensure!(
+                manifest.checksum == read.content.checksum(),
+                SnapshotRestoreError::Corrupted
+            );
+        }
+        if mode == SnapshotRestoreMode::Incremental {
+            ensure!(
+                metadata.custom.since.is_some(),
+                SnapshotRestoreError::NotIncremental
+            );
+        } else {
             ensure!(
-                metadata.block_protocol_module_versions.graph == semver::Version::new(0, 3, 0),
-                SnapshotRestoreError::Unsupported
+                metadata.custom.since.is_none(),
+                SnapshotRestoreError::Incremental
             );
         }
+    }
 
-        ensure!(found_metadata, SnapshotRestoreError::MissingMetadata);
+    ensure!(found_metadata, SnapshotRestoreError::MissingMetadata);
 
-        tracing::info!("snapshot restore finished");
+    let mut report = SnapshotRestoreReport::default();
+    match mode {
+        SnapshotRestoreMode::Empty => {}
+        SnapshotRestoreMode::Incremental => {
+            SnapshotRecordBatch::prepare_incremental(client)
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
+        }
+        SnapshotRestoreMode::Merge(on_conflict) => {
+            SnapshotRecordBatch::prepare_merge(client, on_conflict, &mut report)
+                .await
+                .change_context(SnapshotRestoreError::Write)?;
 
-        Ok(())
+            if on_conflict == SnapshotConflictBehavior::Fail && report.conflicting() > 0 {
+                return Err(Report::new(SnapshotRestoreError::Conflict)
+                    .attach_printable(report)
+                    .attach_printable(StatusCode::AlreadyExists));
+            }
+        }
     }
+    SnapshotRecordBatch::count_inserted(client, &mut report)
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+
+    SnapshotRecordBatch::commit(client)
+        .await
+        .change_context(SnapshotRestoreError::Write)
+        .map_err(|report| {
+            if let Some(error) = report
+                .downcast_ref()
+                .and_then(tokio_postgres::Error::as_db_error)
+            {
+                match *error.code() {
+                    SqlState::FOREIGN_KEY_VIOLATION => {
+                        report.attach_printable(StatusCode::NotFound)
+                    }
+                    SqlState::UNIQUE_VIOLATION => {
+                        report.attach_printable(StatusCode::AlreadyExists)
+                    }
+                    _ => report,
+                }
+            } else {
+                report
+            }
+        })?;
+
+    Ok(report)
 }
@@ -3,7 +3,7 @@ use error_stack::{Result, ResultExt};
 use tokio_postgres::GenericClient;
 
 use crate::{
-    snapshot::{account::AccountRow, WriteBatch},
+    snapshot::{account::AccountRow, SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch},
     store::{AsClient, InsertionError, PostgresStore},
 };
 
@@ -54,6 +54,43 @@ impl<C: AsClient> WriteBatch<C> for AccountRowBatch {
         Ok(())
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM accounts_tmp
+                        WHERE account_id IN (SELECT account_id FROM accounts);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        _on_conflict: SnapshotConflictBehavior,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let skipped = postgres_client
+            .as_client()
+            .client()
+            .query(
+                r"
+                    DELETE FROM accounts_tmp
+                        WHERE account_id IN (SELECT account_id FROM accounts)
+                        RETURNING 1;
+                ",
+                &[],
+            )
+            .await
+            .change_context(InsertionError)?;
+        report.accounts.skipped += skipped.len();
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         postgres_client
             .as_client()
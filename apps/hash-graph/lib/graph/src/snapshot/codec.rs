@@ -3,11 +3,16 @@ use std::{
     marker::PhantomData,
 };
 
-use bytes::{BufMut, BytesMut};
+use bytes::{Buf, BufMut, BytesMut};
 use derivative::Derivative;
 use error_stack::{Report, ResultExt};
 use serde::{de::DeserializeOwned, Serialize};
-use tokio_util::codec::{Decoder, Encoder, LinesCodec};
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec, LinesCodec};
+
+/// The self-describe tag of CBOR, which is written at the start of a CBOR snapshot.
+///
+/// See [RFC 8949, section 3.4.6](https://www.rfc-editor.org/rfc/rfc8949#section-3.4.6).
+pub const CBOR_MAGIC: [u8; 3] = [0xD9, 0xD9, 0xF7];
 
 #[derive(Derivative)]
 #[derivative(
@@ -76,41 +81,153 @@ impl<T> JsonLinesDecoder<T> {
     }
 }
 
+impl<T> JsonLinesDecoder<T> {
+    /// Returns the number of the line, which was read last.
+    pub(crate) const fn current_line(&self) -> usize {
+        self.current_line
+    }
+
+    /// Reads the next non-empty line without deserializing it.
+    pub(crate) fn decode_line(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> Result<Option<String>, Report<io::Error>> {
+        let line = self
+            .lines
+            .decode(buf)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        Ok(self.next_line(line))
+    }
+
+    /// Reads the last non-empty line without deserializing it.
+    pub(crate) fn decode_line_eof(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> Result<Option<String>, Report<io::Error>> {
+        let line = self
+            .lines
+            .decode_eof(buf)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        Ok(self.next_line(line))
+    }
+
+    fn next_line(&mut self, line: Option<String>) -> Option<String> {
+        if line.is_some() {
+            self.current_line += 1;
+        }
+        line.filter(|line| !line.is_empty())
+    }
+}
+
+impl<T: DeserializeOwned> JsonLinesDecoder<T> {
+    fn deserialize(&self, line: &str) -> Result<T, Report<io::Error>> {
+        serde_json::from_str(line)
+            .map_err(io::Error::from)
+            .attach_printable_lazy(|| format!("line in input: {}", self.current_line))
+    }
+}
+
 impl<T: DeserializeOwned> Decoder for JsonLinesDecoder<T> {
     // `Decoder::Error` requires `From<io::Error>` so we need to use `Report<io::Error>` here.
     type Error = Report<io::Error>;
     type Item = T;
 
     fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, Self::Error> {
-        self.lines
-            .decode(buf)
-            .map(|line| {
-                self.current_line += 1;
-                line
-            })
-            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
-            .filter(|line| !line.is_empty())
-            .map(|line| {
-                serde_json::from_str(&line)
-                    .map_err(io::Error::from)
-                    .attach_printable_lazy(|| format!("line in input: {}", self.current_line))
-            })
+        self.decode_line(buf)?
+            .map(|line| self.deserialize(&line))
             .transpose()
     }
 
     fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        self.lines
-            .decode_eof(buf)
-            .map(|line| {
-                self.current_line += 1;
-                line
-            })
-            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
-            .filter(|line| !line.is_empty())
-            .map(|line| {
-                serde_json::from_str(&line)
-                    .map_err(io::Error::from)
-                    .attach_printable_lazy(|| format!("line in input: {}", self.current_line))
+        self.decode_line_eof(buf)?
+            .map(|line| self.deserialize(&line))
+            .transpose()
+    }
+}
+
+/// Encodes items as CBOR, each prefixed by its length.
+///
+/// Before the first item, [`CBOR_MAGIC`] is written to allow detecting the format.
+#[derive(Derivative)]
+#[derivative(Debug(bound = ""), Default(bound = ""))]
+pub struct CborEncoder<T> {
+    frames: LengthDelimitedCodec,
+    header_written: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize + Send + Sync + 'static> Encoder<T> for CborEncoder<T> {
+    type Error = Report<io::Error>;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        if !self.header_written {
+            dst.put_slice(&CBOR_MAGIC);
+            self.header_written = true;
+        }
+
+        let mut frame = BytesMut::new().writer();
+        ciborium::ser::into_writer(&item, &mut frame)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+            .attach(item)?;
+        self.frames.encode(frame.into_inner().freeze(), dst)?;
+        Ok(())
+    }
+}
+
+/// Decodes items encoded by [`CborEncoder`].
+#[derive(Derivative)]
+#[derivative(Debug(bound = ""), Default(bound = ""))]
+pub struct CborDecoder<T> {
+    frames: LengthDelimitedCodec,
+    header_read: bool,
+    current_item: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> CborDecoder<T> {
+    /// Returns the number of the item, which was read last.
+    pub(crate) const fn current_item(&self) -> usize {
+        self.current_item
+    }
+
+    /// Reads the next item without deserializing it.
+    pub(crate) fn decode_frame(
+        &mut self,
+        buf: &mut BytesMut,
+    ) -> Result<Option<BytesMut>, Report<io::Error>> {
+        if !self.header_read {
+            if buf.len() < CBOR_MAGIC.len() {
+                return Ok(None);
+            }
+            if buf[..CBOR_MAGIC.len()] != CBOR_MAGIC {
+                return Err(Report::new(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "input does not start with the CBOR self-describe tag",
+                )));
+            }
+            buf.advance(CBOR_MAGIC.len());
+            self.header_read = true;
+        }
+
+        let frame = self.frames.decode(buf)?;
+        if frame.is_some() {
+            self.current_item += 1;
+        }
+        Ok(frame)
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for CborDecoder<T> {
+    // `Decoder::Error` requires `From<io::Error>` so we need to use `Report<io::Error>` here.
+    type Error = Report<io::Error>;
+    type Item = T;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<T>, Self::Error> {
+        self.decode_frame(buf)?
+            .map(|frame| {
+                ciborium::de::from_reader(frame.reader())
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                    .attach_printable_lazy(|| format!("item in input: {}", self.current_item))
             })
             .transpose()
     }
@@ -0,0 +1,511 @@
+mod sort;
+mod value;
+
+use std::{cmp::Ordering, env, fmt, path::PathBuf};
+
+use error_stack::{Context, Result, ResultExt};
+use futures::{stream, stream::BoxStream, Stream, StreamExt, TryStreamExt};
+use serde::Serialize;
+
+pub use self::value::ValueChange;
+use crate::snapshot::{
+    diff::{
+        sort::{sort_records, SortedRecord},
+        value::diff_values,
+    },
+    SnapshotDiffError, SnapshotEntry, SnapshotRecordId,
+};
+
+/// Specifies how two snapshots are compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotDiffSettings {
+    /// The number of records of a snapshot, which are sorted in memory.
+    ///
+    /// Larger snapshots are sorted in multiple runs, which are written to `directory`.
+    pub run_size: usize,
+    /// The directory to write the sorted runs of large snapshots into.
+    pub directory: PathBuf,
+}
+
+impl Default for SnapshotDiffSettings {
+    fn default() -> Self {
+        Self {
+            run_size: 100_000,
+            directory: env::temp_dir(),
+        }
+    }
+}
+
+/// How a record differs between two snapshots.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SnapshotChangeKind {
+    /// The record is only contained in the new snapshot.
+    Added,
+    /// The record is only contained in the old snapshot.
+    Removed,
+    /// The record is contained in both snapshots, but its content differs.
+    Changed,
+}
+
+/// A record, which differs between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotChange {
+    pub record: SnapshotRecordId,
+    pub kind: SnapshotChangeKind,
+    /// The values, which changed inside of the record.
+    ///
+    /// The paths are relative to the snapshot entry, so changed properties of an entity are
+    /// reported below `/properties`. This is only populated for changed records.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<ValueChange>,
+}
+
+impl fmt::Display for SnapshotChange {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.kind {
+            SnapshotChangeKind::Added => '+',
+            SnapshotChangeKind::Removed => '-',
+            SnapshotChangeKind::Changed => '~',
+        };
+        write!(fmt, "{marker} {}", self.record)?;
+        for change in &self.changes {
+            write!(fmt, "\n    {change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The number of records added, removed, or changed between two snapshots.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiffCounts {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl fmt::Display for SnapshotDiffCounts {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{} added, {} removed, {} changed",
+            self.added, self.removed, self.changed
+        )
+    }
+}
+
+/// Summarizes the differences between two snapshots by record kind.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiffSummary {
+    pub data_types: SnapshotDiffCounts,
+    pub property_types: SnapshotDiffCounts,
+    pub entity_types: SnapshotDiffCounts,
+    pub entities: SnapshotDiffCounts,
+    pub ownership_transfers: SnapshotDiffCounts,
+}
+
+impl SnapshotDiffSummary {
+    pub fn add(&mut self, change: &SnapshotChange) {
+        let counts = match change.record {
+            SnapshotRecordId::Metadata | SnapshotRecordId::Manifest => return,
+            SnapshotRecordId::DataType(_) => &mut self.data_types,
+            SnapshotRecordId::PropertyType(_) => &mut self.property_types,
+            SnapshotRecordId::EntityType(_) => &mut self.entity_types,
+            SnapshotRecordId::Entity(_) => &mut self.entities,
+            SnapshotRecordId::OwnershipTransfer(_) => &mut self.ownership_transfers,
+        };
+        match change.kind {
+            SnapshotChangeKind::Added => counts.added += 1,
+            SnapshotChangeKind::Removed => counts.removed += 1,
+            SnapshotChangeKind::Changed => counts.changed += 1,
+        }
+    }
+}
+
+impl fmt::Display for SnapshotDiffSummary {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(fmt, "data types: {}", self.data_types)?;
+        writeln!(fmt, "property types: {}", self.property_types)?;
+        writeln!(fmt, "entity types: {}", self.entity_types)?;
+        writeln!(fmt, "entities: {}", self.entities)?;
+        write!(fmt, "ownership transfers: {}", self.ownership_transfers)
+    }
+}
+
+/// Aligns the sorted records of two snapshots.
+struct SnapshotMerge {
+    old: stream::Fuse<BoxStream<'static, Result<SortedRecord, SnapshotDiffError>>>,
+    new: stream::Fuse<BoxStream<'static, Result<SortedRecord, SnapshotDiffError>>>,
+    old_head: Option<SortedRecord>,
+    new_head: Option<SortedRecord>,
+}
+
+impl SnapshotMerge {
+    async fn next_change(&mut self) -> Result<Option<SnapshotChange>, SnapshotDiffError> {
+        loop {
+            if self.old_head.is_none() {
+                self.old_head = self.old.try_next().await?;
+            }
+            if self.new_head.is_none() {
+                self.new_head = self.new.try_next().await?;
+            }
+
+            let (old, new) = match (self.old_head.take(), self.new_head.take()) {
+                (None, None) => return Ok(None),
+                (Some(old), None) => return Ok(Some(removed(old))),
+                (None, Some(new)) => return Ok(Some(added(new))),
+                (Some(old), Some(new)) => (old, new),
+            };
+
+            match old.cmp_key(&new) {
+                Ordering::Less => {
+                    self.new_head = Some(new);
+                    return Ok(Some(removed(old)));
+                }
+                Ordering::Greater => {
+                    self.old_head = Some(old);
+                    return Ok(Some(added(new)));
+                }
+                Ordering::Equal if old.entry != new.entry => {
+                    return changed(old, &new).map(Some);
+                }
+                Ordering::Equal => {}
+            }
+        }
+    }
+}
+
+fn added(record: SortedRecord) -> SnapshotChange {
+    SnapshotChange {
+        record: record.record,
+        kind: SnapshotChangeKind::Added,
+        changes: Vec::new(),
+    }
+}
+
+fn removed(record: SortedRecord) -> SnapshotChange {
+    SnapshotChange {
+        record: record.record,
+        kind: SnapshotChangeKind::Removed,
+        changes: Vec::new(),
+    }
+}
+
+fn changed(old: SortedRecord, new: &SortedRecord) -> Result<SnapshotChange, SnapshotDiffError> {
+    let to_value = |entry: &SnapshotEntry| {
+        serde_json::to_value(entry)
+            .change_context(SnapshotDiffError::Compare)
+            .attach_printable_lazy(|| old.record.clone())
+    };
+
+    let mut changes = Vec::new();
+    diff_values(
+        &mut String::new(),
+        &to_value(&old.entry)?,
+        &to_value(&new.entry)?,
+        &mut changes,
+    );
+    Ok(SnapshotChange {
+        record: old.record,
+        kind: SnapshotChangeKind::Changed,
+        changes,
+    })
+}
+
+/// Compares the records of two snapshots.
+///
+/// The records are matched by their ID, i.e. the versioned URL of an ontology type, the entity
+/// ID and edition ID of an entity, and the ID of an ownership transfer. For records contained in
+/// both snapshots, the values, which differ, are reported as [`ValueChange`]s. The snapshot
+/// metadata and the manifest are not compared.
+///
+/// Both snapshots are sorted before they are compared, which requires reading them completely.
+/// The memory usage is bounded by [`SnapshotDiffSettings::run_size`], larger snapshots are sorted
+/// on disk. The changes are emitted in an unspecified, but deterministic order.
+///
+/// # Errors
+///
+/// - If reading a snapshot fails
+/// - If writing or reading the sorted runs of a snapshot fails
+pub async fn diff_snapshots(
+    old: impl Stream<Item = Result<SnapshotEntry, impl Context>> + Send,
+    new: impl Stream<Item = Result<SnapshotEntry, impl Context>> + Send,
+    settings: &SnapshotDiffSettings,
+) -> Result<BoxStream<'static, Result<SnapshotChange, SnapshotDiffError>>, SnapshotDiffError> {
+    let old = sort_records(
+        old.map_err(|report| {
+            report
+                .change_context(SnapshotDiffError::Read)
+                .attach_printable("could not read the old snapshot")
+        }),
+        settings,
+    )
+    .await?;
+    let new = sort_records(
+        new.map_err(|report| {
+            report
+                .change_context(SnapshotDiffError::Read)
+                .attach_printable("could not read the new snapshot")
+        }),
+        settings,
+    )
+    .await?;
+
+    let merge = SnapshotMerge {
+        old: old.fuse(),
+        new: new.fuse(),
+        old_head: None,
+        new_head: None,
+    };
+    Ok(stream::try_unfold(merge, |mut merge| async move {
+        Ok(merge.next_change().await?.map(|change| (change, merge)))
+    })
+    .boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io};
+
+    use error_stack::Report;
+    use graph_types::{
+        account::AccountId,
+        knowledge::entity::{
+            EntityEditionId, EntityId, EntityProperties, EntityRecordId, EntityTemporalMetadata,
+            EntityUuid,
+        },
+        provenance::OwnedById,
+    };
+    use serde_json::Value;
+    use temporal_versioning::{
+        ClosedTemporalBound, DecisionTime, LeftClosedTemporalInterval, OpenTemporalBound,
+        TransactionTime,
+    };
+    use type_system::url::{BaseUrl, VersionedUrl};
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::snapshot::entity::{CustomEntityMetadata, EntityMetadata, EntitySnapshotRecord};
+
+    fn interval<A>(start: &str, end: Option<&str>) -> LeftClosedTemporalInterval<A> {
+        LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(start.parse().expect("should be a valid timestamp")),
+            end.map_or(OpenTemporalBound::Unbounded, |end| {
+                OpenTemporalBound::Exclusive(end.parse().expect("should be a valid timestamp"))
+            }),
+        )
+    }
+
+    fn entity(
+        record_id: EntityRecordId,
+        decision_time: LeftClosedTemporalInterval<DecisionTime>,
+        transaction_time: LeftClosedTemporalInterval<TransactionTime>,
+    ) -> SnapshotEntry {
+        SnapshotEntry::Entity(EntitySnapshotRecord {
+            properties: EntityProperties::empty(),
+            metadata: EntityMetadata {
+                record_id,
+                entity_type_id: VersionedUrl {
+                    base_url: BaseUrl::new(
+                        "https://example.com/types/entity-type/thing/".to_owned(),
+                    )
+                    .expect("should be a valid base URL"),
+                    version: 1,
+                },
+                temporal_versioning: Some(EntityTemporalMetadata {
+                    decision_time,
+                    transaction_time,
+                }),
+                custom: CustomEntityMetadata {
+                    provenance: None,
+                    archived: Some(false),
+                },
+            },
+            link_data: None,
+        })
+    }
+
+    /// Creates a single-row edition of a new entity.
+    fn new_entity() -> SnapshotEntry {
+        entity(
+            EntityRecordId {
+                entity_id: EntityId {
+                    owned_by_id: OwnedById::new(AccountId::new(Uuid::new_v4())),
+                    entity_uuid: EntityUuid::new(Uuid::new_v4()),
+                },
+                edition_id: EntityEditionId::new(Uuid::new_v4()),
+            },
+            interval("2000-01-01T00:00:00Z", None),
+            interval("2000-01-01T00:00:00Z", None),
+        )
+    }
+
+    fn archive(mut entry: SnapshotEntry) -> SnapshotEntry {
+        if let SnapshotEntry::Entity(entity) = &mut entry {
+            entity.metadata.custom.archived = Some(true);
+        }
+        entry
+    }
+
+    async fn diff(
+        old: Vec<SnapshotEntry>,
+        new: Vec<SnapshotEntry>,
+        settings: &SnapshotDiffSettings,
+    ) -> Vec<SnapshotChange> {
+        diff_snapshots(
+            stream::iter(old.into_iter().map(Ok::<_, Report<io::Error>>)),
+            stream::iter(new.into_iter().map(Ok::<_, Report<io::Error>>)),
+            settings,
+        )
+        .await
+        .expect("should be able to sort the snapshots")
+        .try_collect()
+        .await
+        .expect("should be able to compare the snapshots")
+    }
+
+    #[tokio::test]
+    async fn aligns_records_of_both_snapshots() {
+        let removed = new_entity();
+        let changed = new_entity();
+        let added = new_entity();
+
+        let changes = diff(
+            vec![removed.clone(), changed.clone()],
+            vec![archive(changed.clone()), added.clone()],
+            &SnapshotDiffSettings::default(),
+        )
+        .await;
+
+        assert_eq!(changes.len(), 3, "unexpected changes: {changes:#?}");
+        for (entry, kind) in [
+            (&removed, SnapshotChangeKind::Removed),
+            (&added, SnapshotChangeKind::Added),
+            (&changed, SnapshotChangeKind::Changed),
+        ] {
+            assert!(
+                changes
+                    .iter()
+                    .any(|change| change.kind == kind
+                        && change.record == SnapshotRecordId::from(entry)),
+                "expected {kind:?} record in {changes:#?}"
+            );
+        }
+
+        let change = changes
+            .iter()
+            .find(|change| change.kind == SnapshotChangeKind::Changed)
+            .expect("should contain a changed record");
+        assert_eq!(change.changes, [ValueChange::Changed {
+            path: "/metadata/custom/archived".to_owned(),
+            old: Value::Bool(false),
+            new: Value::Bool(true),
+        }]);
+    }
+
+    #[tokio::test]
+    async fn aligns_rows_of_the_same_edition() {
+        let SnapshotEntry::Entity(created) = new_entity() else {
+            unreachable!("should be an entity");
+        };
+        let record_id = created.metadata.record_id;
+
+        // An edition, which was updated with a later decision time, consists of two rows with the
+        // same record ID.
+        let before_update = entity(
+            record_id,
+            interval("2000-01-01T00:00:00Z", None),
+            interval("2000-01-01T00:00:00Z", Some("2000-01-03T00:00:00Z")),
+        );
+        let after_update = entity(
+            record_id,
+            interval("2000-01-01T00:00:00Z", Some("2000-01-02T00:00:00Z")),
+            interval("2000-01-03T00:00:00Z", None),
+        );
+
+        let changes = diff(
+            vec![before_update.clone(), after_update.clone()],
+            vec![after_update.clone(), before_update.clone()],
+            &SnapshotDiffSettings::default(),
+        )
+        .await;
+        assert!(changes.is_empty(), "unexpected changes: {changes:#?}");
+
+        // Closing the transaction time of a row keeps it aligned with its previous state.
+        let closed = entity(
+            record_id,
+            interval("2000-01-01T00:00:00Z", Some("2000-01-02T00:00:00Z")),
+            interval("2000-01-03T00:00:00Z", Some("2000-01-04T00:00:00Z")),
+        );
+        let changes = diff(
+            vec![before_update.clone(), after_update],
+            vec![before_update, closed],
+            &SnapshotDiffSettings::default(),
+        )
+        .await;
+        assert_eq!(changes.len(), 1, "unexpected changes: {changes:#?}");
+        assert_eq!(changes[0].kind, SnapshotChangeKind::Changed);
+        assert_eq!(changes[0].record, SnapshotRecordId::Entity(record_id));
+    }
+
+    #[tokio::test]
+    async fn sorts_large_snapshots_on_disk() {
+        let directory = env::temp_dir().join(format!("snapshot-diff-test-{}", Uuid::new_v4()));
+        fs::create_dir(&directory).expect("should be able to create the directory");
+        let spilling = SnapshotDiffSettings {
+            run_size: 2,
+            directory: directory.clone(),
+        };
+
+        let old = (0..7).map(|_| new_entity()).collect::<Vec<_>>();
+        let mut new = old[2..].iter().cloned().map(archive).collect::<Vec<_>>();
+        new.extend((0..3).map(|_| new_entity()));
+        new.reverse();
+
+        let in_memory = diff(old.clone(), new.clone(), &SnapshotDiffSettings::default()).await;
+        assert_eq!(in_memory.len(), 10, "unexpected changes: {in_memory:#?}");
+
+        let on_disk = diff(old, new, &spilling).await;
+        assert_eq!(on_disk, in_memory);
+        assert_eq!(
+            fs::read_dir(&directory)
+                .expect("should be able to read the directory")
+                .count(),
+            0,
+            "the sorted runs should be removed"
+        );
+
+        fs::remove_dir(&directory).expect("should be able to remove the directory");
+    }
+
+    #[tokio::test]
+    async fn sorted_runs_are_merged_in_order() {
+        let entries = (0..9).map(|_| new_entity()).collect::<Vec<_>>();
+        let sort = |run_size: usize| {
+            let entries = entries.clone();
+            async move {
+                sort_records(
+                    stream::iter(entries.into_iter().map(Ok)),
+                    &SnapshotDiffSettings {
+                        run_size,
+                        ..SnapshotDiffSettings::default()
+                    },
+                )
+                .await
+                .expect("should be able to sort the records")
+                .map_ok(|record| record.record)
+                .try_collect::<Vec<_>>()
+                .await
+                .expect("should be able to read the sorted records")
+            }
+        };
+
+        let sorted = sort(100).await;
+        assert_eq!(sorted.len(), entries.len());
+        assert_eq!(sort(1).await, sorted);
+        assert_eq!(sort(4).await, sorted);
+    }
+}
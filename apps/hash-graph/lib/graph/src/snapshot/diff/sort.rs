@@ -0,0 +1,204 @@
+use std::{cmp::Ordering, fmt::Write, fs, path::PathBuf};
+
+use error_stack::{Report, Result, ResultExt};
+use futures::{stream, stream::BoxStream, SinkExt, Stream, StreamExt, TryStreamExt};
+use tokio::io;
+use tokio_util::codec::{FramedRead, FramedWrite};
+use uuid::Uuid;
+
+use crate::snapshot::{
+    codec::{JsonLinesDecoder, JsonLinesEncoder},
+    SnapshotDiffError, SnapshotDiffSettings, SnapshotEntry, SnapshotRecordId,
+};
+
+/// A record of a snapshot together with the key the records are sorted by.
+pub(super) struct SortedRecord {
+    key: (u8, String),
+    pub(super) record: SnapshotRecordId,
+    pub(super) entry: SnapshotEntry,
+}
+
+impl SortedRecord {
+    /// Returns `None` for entries, which are not records, i.e. the metadata and the manifest.
+    ///
+    /// An edition of an entity may consist of multiple rows, so the key of an entity additionally
+    /// contains the start of its decision time and transaction time. As the rows of an entity
+    /// don't overlap, these are unique for each row, while an edition, which was closed between
+    /// two snapshots, is still aligned with its previous state.
+    fn new(entry: SnapshotEntry) -> Option<Self> {
+        let record = SnapshotRecordId::from(&entry);
+        let key = match &record {
+            SnapshotRecordId::Metadata | SnapshotRecordId::Manifest => return None,
+            SnapshotRecordId::DataType(url) => (0, url.to_string()),
+            SnapshotRecordId::PropertyType(url) => (1, url.to_string()),
+            SnapshotRecordId::EntityType(url) => (2, url.to_string()),
+            SnapshotRecordId::Entity(record_id) => {
+                let mut key = format!("{}/{}", record_id.entity_id, record_id.edition_id.as_uuid());
+                if let SnapshotEntry::Entity(entity) = &entry {
+                    if let Some(temporal_versioning) = &entity.metadata.temporal_versioning {
+                        // Writing into a `String` cannot fail
+                        let _ = write!(
+                            key,
+                            "/{}/{}",
+                            temporal_versioning.decision_time.start(),
+                            temporal_versioning.transaction_time.start()
+                        );
+                    }
+                }
+                (3, key)
+            }
+            SnapshotRecordId::OwnershipTransfer(transfer_id) => (4, transfer_id.to_string()),
+        };
+        Some(Self { key, record, entry })
+    }
+
+    /// Compares the keys of the records.
+    ///
+    /// The order is only used to align two snapshots, so it does not have a meaning on its own.
+    pub(super) fn cmp_key(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// A file containing sorted records of a snapshot in JSON Lines format.
+///
+/// The file is removed when the run is dropped.
+struct SortedRun {
+    path: PathBuf,
+}
+
+impl SortedRun {
+    /// Sorts the `records` and writes them into a new run, leaving `records` empty.
+    async fn write(
+        records: &mut Vec<SortedRecord>,
+        settings: &SnapshotDiffSettings,
+    ) -> Result<Self, SnapshotDiffError> {
+        records.sort_unstable_by(SortedRecord::cmp_key);
+
+        let run = Self {
+            path: settings
+                .directory
+                .join(format!("snapshot-diff-{}.jsonl", Uuid::new_v4())),
+        };
+        let file = tokio::fs::File::create(&run.path)
+            .await
+            .change_context(SnapshotDiffError::Spill)
+            .attach_printable_lazy(|| run.path.display().to_string())?;
+
+        let mut sink = FramedWrite::new(io::BufWriter::new(file), JsonLinesEncoder::default());
+        sink.send_all(&mut stream::iter(
+            records.drain(..).map(|record| Ok(record.entry)),
+        ))
+        .await
+        .change_context(SnapshotDiffError::Spill)?;
+        sink.close()
+            .await
+            .change_context(SnapshotDiffError::Spill)?;
+
+        Ok(run)
+    }
+
+    async fn read(self) -> Result<SortedRunReader, SnapshotDiffError> {
+        let file = tokio::fs::File::open(&self.path)
+            .await
+            .change_context(SnapshotDiffError::Spill)
+            .attach_printable_lazy(|| self.path.display().to_string())?;
+
+        let mut reader = SortedRunReader {
+            entries: FramedRead::new(
+                io::BufReader::new(file),
+                JsonLinesDecoder::<SnapshotEntry>::default(),
+            ),
+            head: None,
+            _run: self,
+        };
+        reader.advance().await?;
+        Ok(reader)
+    }
+}
+
+impl Drop for SortedRun {
+    fn drop(&mut self) {
+        if let Err(error) = fs::remove_file(&self.path) {
+            tracing::warn!(path = %self.path.display(), %error, "could not remove sorted run");
+        }
+    }
+}
+
+/// Reads the records of a [`SortedRun`] one by one.
+struct SortedRunReader {
+    entries: FramedRead<io::BufReader<tokio::fs::File>, JsonLinesDecoder<SnapshotEntry>>,
+    /// The next record of the run, which has not been emitted yet.
+    head: Option<SortedRecord>,
+    _run: SortedRun,
+}
+
+impl SortedRunReader {
+    async fn advance(&mut self) -> Result<(), SnapshotDiffError> {
+        self.head = self
+            .entries
+            .try_next()
+            .await
+            .change_context(SnapshotDiffError::Spill)?
+            .and_then(SortedRecord::new);
+        Ok(())
+    }
+}
+
+/// Sorts the records of a snapshot, so two snapshots can be compared by merging them.
+///
+/// Up to [`run_size`] records are sorted in memory. Larger snapshots are split into sorted runs,
+/// which are written to [`directory`] and merged while the returned stream is read, so the memory
+/// usage does not depend on the size of the snapshot.
+///
+/// [`run_size`]: SnapshotDiffSettings::run_size
+/// [`directory`]: SnapshotDiffSettings::directory
+pub(super) async fn sort_records(
+    entries: impl Stream<Item = Result<SnapshotEntry, SnapshotDiffError>> + Send,
+    settings: &SnapshotDiffSettings,
+) -> Result<BoxStream<'static, Result<SortedRecord, SnapshotDiffError>>, SnapshotDiffError> {
+    let mut entries = entries.boxed();
+    let mut records = Vec::new();
+    let mut runs = Vec::new();
+    while let Some(entry) = entries.try_next().await? {
+        records.extend(SortedRecord::new(entry));
+        if records.len() >= settings.run_size.max(1) {
+            runs.push(SortedRun::write(&mut records, settings).await?);
+        }
+    }
+
+    if runs.is_empty() {
+        records.sort_unstable_by(SortedRecord::cmp_key);
+        return Ok(stream::iter(records.into_iter().map(Ok)).boxed());
+    }
+    if !records.is_empty() {
+        runs.push(SortedRun::write(&mut records, settings).await?);
+    }
+    tracing::debug!(runs = runs.len(), "merging sorted snapshot runs");
+
+    let mut readers = Vec::with_capacity(runs.len());
+    for run in runs {
+        readers.push(run.read().await?);
+    }
+
+    Ok(stream::try_unfold(readers, |mut readers| async move {
+        let Some(reader) = readers
+            .iter_mut()
+            .filter(|reader| reader.head.is_some())
+            .min_by(|lhs, rhs| match (&lhs.head, &rhs.head) {
+                (Some(lhs), Some(rhs)) => lhs.cmp_key(rhs),
+                _ => Ordering::Equal,
+            })
+        else {
+            return Ok(None);
+        };
+
+        let record = reader
+            .head
+            .take()
+            .ok_or_else(|| Report::new(SnapshotDiffError::Spill))?;
+        reader.advance().await?;
+        Ok(Some((record, readers)))
+    })
+    .boxed())
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// A change of a single value inside of a record.
+///
+/// The location of the value is described by a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "op")]
+pub enum ValueChange {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl fmt::Display for ValueChange {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { path, value } => write!(fmt, "{path}: added {value}"),
+            Self::Removed { path, value } => write!(fmt, "{path}: removed {value}"),
+            Self::Changed { path, old, new } => write!(fmt, "{path}: {old} -> {new}"),
+        }
+    }
+}
+
+/// Appends the changes from `old` to `new` to `changes`.
+///
+/// Objects and arrays are compared element-wise, so only the values, which actually changed,
+/// are reported. `path` is the JSON pointer to `old` and `new`.
+pub(super) fn diff_values(
+    path: &mut String,
+    old: &Value,
+    new: &Value,
+    changes: &mut Vec<ValueChange>,
+) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let length = push_segment(path, key);
+                if let Some(new_value) = new.get(key) {
+                    diff_values(path, old_value, new_value, changes);
+                } else {
+                    changes.push(ValueChange::Removed {
+                        path: path.clone(),
+                        value: old_value.clone(),
+                    });
+                }
+                path.truncate(length);
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    let length = push_segment(path, key);
+                    changes.push(ValueChange::Added {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    });
+                    path.truncate(length);
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let length = push_segment(path, &index.to_string());
+                match (old.get(index), new.get(index)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_values(path, old_value, new_value, changes);
+                    }
+                    (Some(old_value), None) => changes.push(ValueChange::Removed {
+                        path: path.clone(),
+                        value: old_value.clone(),
+                    }),
+                    (None, Some(new_value)) => changes.push(ValueChange::Added {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    }),
+                    (None, None) => {}
+                }
+                path.truncate(length);
+            }
+        }
+        _ => changes.push(ValueChange::Changed {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+/// Appends the escaped `segment` to the JSON pointer `path` and returns its previous length.
+fn push_segment(path: &mut String, segment: &str) -> usize {
+    let length = path.len();
+    path.push('/');
+    for character in segment.chars() {
+        match character {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            _ => path.push(character),
+        }
+    }
+    length
+}
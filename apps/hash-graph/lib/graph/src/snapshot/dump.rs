@@ -0,0 +1,548 @@
+use std::{borrow::Cow, collections::HashSet, num::NonZeroUsize, sync::Arc, thread};
+
+use error_stack::{ensure, Result, ResultExt};
+use futures::{future, TryStreamExt};
+use graph_types::{
+    knowledge::entity::Entity,
+    ontology::{
+        CustomOntologyMetadata, DataTypeWithMetadata, EntityTypeWithMetadata, OntologyTypeVersion,
+        PropertyTypeWithMetadata,
+    },
+};
+use temporal_versioning::{
+    ClosedTemporalBound, DecisionTime, Interval, LeftClosedTemporalInterval, LimitedTemporalBound,
+    OpenTemporalBound, TemporalBound, Timestamp, TransactionTime,
+};
+use type_system::{url::VersionedUrl, DataType, EntityType, PropertyType};
+use uuid::Uuid;
+
+use crate::{
+    knowledge::EntityQueryPath,
+    ontology::{DataTypeQueryPath, EntityTypeQueryPath, PropertyTypeQueryPath},
+    snapshot::{
+        entity::EntitySnapshotRecord, BlockProtocolModuleVersions, CustomGlobalMetadata,
+        OntologyTypeSnapshotRecord, SnapshotDumpError, SnapshotMetadata, SNAPSHOT_VERSION,
+    },
+    store::{
+        crud::Read,
+        query::{Filter, FilterExpression, Parameter, ParameterList},
+        AsClient, PostgresStore, Record,
+    },
+    subgraph::{
+        edges::SharedEdgeKind,
+        temporal_axes::{
+            PinnedTemporalAxisUnresolved, QueryTemporalAxes, QueryTemporalAxesUnresolved,
+            VariableTemporalAxisUnresolved,
+        },
+    },
+};
+
+/// The maximum number of connections used by default to read records concurrently.
+///
+/// Together with the connection exporting the transaction snapshot, this fits into the default
+/// size of a connection pool.
+pub const DEFAULT_MAX_PARALLELISM: usize = 8;
+
+/// Returns the ontology types, which the selected ontology types depend on, including the selected
+/// ontology types themselves.
+///
+/// The ontology types are selected by their base URL and version.
+const ONTOLOGY_DEPENDENCIES_QUERY: &str = r#"
+    WITH RECURSIVE dependencies AS (
+        SELECT ontology_id
+        FROM ontology_ids
+        JOIN unnest($1::text[], $2::bigint[]) AS selected(base_url, version)
+          USING (base_url, version)
+      UNION
+        SELECT edges.target
+        FROM dependencies
+        JOIN (
+            SELECT source_entity_type_ontology_id AS source,
+                   target_entity_type_ontology_id AS target
+            FROM entity_type_inherits_from
+          UNION ALL
+            SELECT source_entity_type_ontology_id, target_entity_type_ontology_id
+            FROM entity_type_constrains_links_on
+          UNION ALL
+            SELECT source_entity_type_ontology_id, target_entity_type_ontology_id
+            FROM entity_type_constrains_link_destinations_on
+          UNION ALL
+            SELECT source_entity_type_ontology_id, target_property_type_ontology_id
+            FROM entity_type_constrains_properties_on
+          UNION ALL
+            SELECT source_property_type_ontology_id, target_property_type_ontology_id
+            FROM property_type_constrains_properties_on
+          UNION ALL
+            SELECT source_property_type_ontology_id, target_data_type_ontology_id
+            FROM property_type_constrains_values_on
+        ) AS edges ON edges.source = dependencies.ontology_id
+    )
+    SELECT ontology_id FROM dependencies;
+"#;
+
+/// Selects the records contained in a snapshot dump.
+///
+/// By default, all records are selected.
+pub struct SnapshotDumpFilter<'f> {
+    pub data_types: Filter<'f, DataTypeWithMetadata>,
+    pub property_types: Filter<'f, PropertyTypeWithMetadata>,
+    pub entity_types: Filter<'f, EntityTypeWithMetadata>,
+    pub entities: Filter<'f, Entity>,
+    /// Also dump the records, which the selected records depend on.
+    ///
+    /// This includes the ontology types the selected ontology types and entities transitively
+    /// refer to, and the entities the selected links transitively point to, so the snapshot can
+    /// be restored into an empty store.
+    pub include_dependencies: bool,
+}
+
+impl Default for SnapshotDumpFilter<'_> {
+    fn default() -> Self {
+        Self {
+            data_types: Filter::All(vec![]),
+            property_types: Filter::All(vec![]),
+            entity_types: Filter::All(vec![]),
+            entities: Filter::All(vec![]),
+            include_dependencies: false,
+        }
+    }
+}
+
+impl<'f> SnapshotDumpFilter<'f> {
+    /// Only selects the entities and the owned ontology types, which are owned by one of the
+    /// `owners`.
+    #[must_use]
+    pub fn owned_by(self, owners: &'f [Uuid]) -> Self {
+        Self {
+            data_types: restrict(
+                self.data_types,
+                Filter::In(
+                    FilterExpression::Path(DataTypeQueryPath::OwnedById),
+                    ParameterList::Uuid(owners),
+                ),
+            ),
+            property_types: restrict(
+                self.property_types,
+                Filter::In(
+                    FilterExpression::Path(PropertyTypeQueryPath::OwnedById),
+                    ParameterList::Uuid(owners),
+                ),
+            ),
+            entity_types: restrict(
+                self.entity_types,
+                Filter::In(
+                    FilterExpression::Path(EntityTypeQueryPath::OwnedById),
+                    ParameterList::Uuid(owners),
+                ),
+            ),
+            entities: restrict(
+                self.entities,
+                Filter::In(
+                    FilterExpression::Path(EntityQueryPath::OwnedById),
+                    ParameterList::Uuid(owners),
+                ),
+            ),
+            include_dependencies: self.include_dependencies,
+        }
+    }
+
+    /// Only selects the `entity_types` and the entities of one of these types.
+    ///
+    /// No data types or property types are selected, use [`include_dependencies`] to dump the
+    /// ontology types the entity types depend on.
+    ///
+    /// [`include_dependencies`]: Self::include_dependencies
+    #[must_use]
+    pub fn of_entity_types(self, entity_types: &'f [VersionedUrl]) -> Self {
+        Self {
+            data_types: restrict(self.data_types, Filter::Any(vec![])),
+            property_types: restrict(self.property_types, Filter::Any(vec![])),
+            entity_types: restrict(
+                self.entity_types,
+                Filter::Any(entity_types.iter().map(Filter::for_versioned_url).collect()),
+            ),
+            entities: restrict(
+                self.entities,
+                Filter::Any(
+                    entity_types
+                        .iter()
+                        .map(|entity_type| {
+                            Filter::All(vec![
+                                Filter::Equal(
+                                    Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                                        edge_kind: SharedEdgeKind::IsOfType,
+                                        path: EntityTypeQueryPath::BaseUrl,
+                                        inheritance_depth: Some(0),
+                                    })),
+                                    Some(FilterExpression::Parameter(Parameter::Text(
+                                        Cow::Borrowed(entity_type.base_url.as_str()),
+                                    ))),
+                                ),
+                                Filter::Equal(
+                                    Some(FilterExpression::Path(EntityQueryPath::EntityTypeEdge {
+                                        edge_kind: SharedEdgeKind::IsOfType,
+                                        path: EntityTypeQueryPath::Version,
+                                        inheritance_depth: Some(0),
+                                    })),
+                                    Some(FilterExpression::Parameter(
+                                        Parameter::OntologyTypeVersion(OntologyTypeVersion::new(
+                                            entity_type.version,
+                                        )),
+                                    )),
+                                ),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+            include_dependencies: self.include_dependencies,
+        }
+    }
+
+    /// Returns if all entities are selected.
+    pub(super) fn selects_all_entities(&self) -> bool {
+        matches!(&self.entities, Filter::All(filters) if filters.is_empty())
+    }
+
+    /// Returns if all records are selected.
+    pub(super) fn selects_all(&self) -> bool {
+        matches!(&self.data_types, Filter::All(filters) if filters.is_empty())
+            && matches!(&self.property_types, Filter::All(filters) if filters.is_empty())
+            && matches!(&self.entity_types, Filter::All(filters) if filters.is_empty())
+            && self.selects_all_entities()
+    }
+
+    /// Resolves the records, which the selected records depend on.
+    ///
+    /// Returns `None` if [`include_dependencies`] is not set.
+    ///
+    /// # Errors
+    ///
+    /// - If reading the selected records or their dependencies from the store fails
+    ///
+    /// [`include_dependencies`]: Self::include_dependencies
+    pub(super) async fn dependencies<C: AsClient>(
+        &self,
+        store: &PostgresStore<C>,
+        temporal_axes: Option<&QueryTemporalAxes>,
+    ) -> Result<Option<SnapshotDependencies>, SnapshotDumpError> {
+        if !self.include_dependencies {
+            return Ok(None);
+        }
+
+        let mut ontology_types = HashSet::new();
+        read_ontology_types::<C, DataType>(
+            store,
+            &self.data_types,
+            temporal_axes,
+            &mut ontology_types,
+        )
+        .await?;
+        read_ontology_types::<C, PropertyType>(
+            store,
+            &self.property_types,
+            temporal_axes,
+            &mut ontology_types,
+        )
+        .await?;
+        read_ontology_types::<C, EntityType>(
+            store,
+            &self.entity_types,
+            temporal_axes,
+            &mut ontology_types,
+        )
+        .await?;
+
+        let mut entity_uuids = HashSet::new();
+        let mut linked_entities = read_entities(
+            store,
+            &self.entities,
+            temporal_axes,
+            &mut entity_uuids,
+            &mut ontology_types,
+        )
+        .await?;
+        loop {
+            linked_entities.retain(|entity_uuid| !entity_uuids.contains(entity_uuid));
+            if linked_entities.is_empty() {
+                break;
+            }
+            linked_entities = read_entities(
+                store,
+                &Filter::In(
+                    FilterExpression::Path(EntityQueryPath::Uuid),
+                    ParameterList::Uuid(&linked_entities),
+                ),
+                temporal_axes,
+                &mut entity_uuids,
+                &mut ontology_types,
+            )
+            .await?;
+        }
+
+        let (base_urls, versions): (Vec<_>, Vec<_>) = ontology_types.into_iter().unzip();
+        let ontology_ids = store
+            .as_client()
+            .query(ONTOLOGY_DEPENDENCIES_QUERY, &[&base_urls, &versions])
+            .await
+            .change_context(SnapshotDumpError::Query)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect();
+
+        Ok(Some(SnapshotDependencies {
+            ontology_ids,
+            entity_uuids: entity_uuids.into_iter().collect(),
+        }))
+    }
+}
+
+/// The records selected by a [`SnapshotDumpFilter`] including the records they depend on.
+pub(super) struct SnapshotDependencies {
+    pub ontology_ids: Arc<[Uuid]>,
+    pub entity_uuids: Arc<[Uuid]>,
+}
+
+/// Adds the `restriction` to the conditions of `filter`.
+fn restrict<'f, R: Record>(filter: Filter<'f, R>, restriction: Filter<'f, R>) -> Filter<'f, R> {
+    if let Filter::All(mut filters) = filter {
+        filters.push(restriction);
+        Filter::All(filters)
+    } else {
+        Filter::All(vec![filter, restriction])
+    }
+}
+
+/// Reads the base URL and version of the ontology types matching `filter` into `ontology_types`.
+async fn read_ontology_types<C, T>(
+    store: &PostgresStore<C>,
+    filter: &Filter<'_, <PostgresStore<C> as Read<OntologyTypeSnapshotRecord<T>>>::Record>,
+    temporal_axes: Option<&QueryTemporalAxes>,
+    ontology_types: &mut HashSet<(String, i64)>,
+) -> Result<(), SnapshotDumpError>
+where
+    C: AsClient,
+    PostgresStore<C>: Read<OntologyTypeSnapshotRecord<T>>,
+{
+    Read::<OntologyTypeSnapshotRecord<T>>::read(store, filter, temporal_axes)
+        .await
+        .change_context(SnapshotDumpError::Query)?
+        .map_err(|report| report.change_context(SnapshotDumpError::Read))
+        .try_for_each(|ontology_type| {
+            ontology_types.insert((
+                ontology_type
+                    .metadata
+                    .record_id
+                    .base_url
+                    .as_str()
+                    .to_owned(),
+                i64::from(ontology_type.metadata.record_id.version.inner()),
+            ));
+            future::ready(Ok(()))
+        })
+        .await
+}
+
+/// Reads the UUIDs and entity types of the entities matching `filter`.
+///
+/// Returns the UUIDs of the entities linked by the read entities.
+async fn read_entities<C: AsClient>(
+    store: &PostgresStore<C>,
+    filter: &Filter<'_, Entity>,
+    temporal_axes: Option<&QueryTemporalAxes>,
+    entity_uuids: &mut HashSet<Uuid>,
+    ontology_types: &mut HashSet<(String, i64)>,
+) -> Result<Vec<Uuid>, SnapshotDumpError> {
+    let mut linked_entities = Vec::new();
+    Read::<Entity>::read(store, filter, temporal_axes)
+        .await
+        .change_context(SnapshotDumpError::Query)?
+        .map_err(|report| report.change_context(SnapshotDumpError::Read))
+        .try_for_each(|entity| {
+            entity_uuids.insert(entity.metadata.record_id().entity_id.entity_uuid.as_uuid());
+            let entity_type_id = entity.metadata.entity_type_id();
+            ontology_types.insert((
+                entity_type_id.base_url.as_str().to_owned(),
+                i64::from(entity_type_id.version),
+            ));
+            if let Some(link_data) = entity.link_data {
+                linked_entities.push(link_data.left_entity_id.entity_uuid.as_uuid());
+                linked_entities.push(link_data.right_entity_id.entity_uuid.as_uuid());
+            }
+            future::ready(Ok(()))
+        })
+        .await?;
+
+    Ok(linked_entities)
+}
+
+/// Specifies which records are contained in a snapshot dump.
+///
+/// By default, the full history of all records is dumped.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SnapshotDumpSettings {
+    /// Only dump the records, which were present in the store at this transaction time.
+    pub transaction_time: Option<Timestamp<TransactionTime>>,
+    /// Only dump the records, which were valid at this decision time.
+    pub decision_time: Option<Timestamp<DecisionTime>>,
+    /// Only dump the records, which were created or closed at or after this transaction time.
+    ///
+    /// This creates an incremental snapshot, which can be restored on top of a snapshot containing
+    /// all changes up to this transaction time. Usually, this is the
+    /// [`transaction_time`] of the previous snapshot. This cannot be combined with a pinned
+    /// transaction time or decision time.
+    ///
+    /// [`transaction_time`]: CustomGlobalMetadata::transaction_time
+    pub since: Option<Timestamp<TransactionTime>>,
+    /// The number of connections used to read records concurrently.
+    ///
+    /// Defaults to the available parallelism of the machine, but at most
+    /// [`DEFAULT_MAX_PARALLELISM`] connections are used by default.
+    pub parallelism: Option<NonZeroUsize>,
+}
+
+impl SnapshotDumpSettings {
+    /// Creates the metadata for a snapshot dumped with these settings.
+    ///
+    /// # Errors
+    ///
+    /// - If `since` is combined with a pinned timestamp
+    /// - If the transaction time horizon could not be read from the store
+    pub(super) async fn metadata<C: AsClient>(
+        self,
+        store: &PostgresStore<C>,
+    ) -> Result<SnapshotMetadata, SnapshotDumpError> {
+        ensure!(
+            self.since.is_none()
+                || (self.transaction_time.is_none() && self.decision_time.is_none()),
+            SnapshotDumpError::InvalidSettings
+        );
+
+        let transaction_time = if let Some(transaction_time) = self.transaction_time {
+            transaction_time
+        } else {
+            store
+                .transaction_time_horizon()
+                .await
+                .change_context(SnapshotDumpError::Query)?
+        };
+
+        Ok(SnapshotMetadata {
+            block_protocol_module_versions: BlockProtocolModuleVersions {
+                graph: SNAPSHOT_VERSION,
+            },
+            custom: CustomGlobalMetadata {
+                transaction_time: Some(transaction_time),
+                since: self.since,
+                has_manifest: true,
+            },
+        })
+    }
+
+    /// Returns the number of connections used to read records concurrently.
+    pub(super) fn parallelism(&self) -> usize {
+        self.parallelism.map_or_else(
+            || {
+                thread::available_parallelism()
+                    .map_or(1, NonZeroUsize::get)
+                    .min(DEFAULT_MAX_PARALLELISM)
+            },
+            NonZeroUsize::get,
+        )
+    }
+
+    /// Returns the temporal axes to read the records specified by the settings.
+    ///
+    /// Returns `None` if no axis is pinned, so the full history is read.
+    pub(super) fn temporal_axes(&self) -> Option<QueryTemporalAxes> {
+        let temporal_axes = match (self.transaction_time, self.decision_time) {
+            (None, None) => return None,
+            (transaction_time @ Some(_), decision_time) => {
+                QueryTemporalAxesUnresolved::DecisionTime {
+                    pinned: PinnedTemporalAxisUnresolved::new(transaction_time),
+                    variable: decision_time.map_or_else(
+                        || {
+                            VariableTemporalAxisUnresolved::new(
+                                Some(TemporalBound::Unbounded),
+                                None,
+                            )
+                        },
+                        |decision_time| {
+                            VariableTemporalAxisUnresolved::new(
+                                Some(TemporalBound::Inclusive(decision_time)),
+                                Some(LimitedTemporalBound::Inclusive(decision_time)),
+                            )
+                        },
+                    ),
+                }
+            }
+            (None, decision_time @ Some(_)) => QueryTemporalAxesUnresolved::TransactionTime {
+                pinned: PinnedTemporalAxisUnresolved::new(decision_time),
+                variable: VariableTemporalAxisUnresolved::new(Some(TemporalBound::Unbounded), None),
+            },
+        };
+
+        Some(temporal_axes.resolve())
+    }
+
+    /// Restricts the `filter` to the records contained in an incremental snapshot.
+    ///
+    /// The records are selected by the interval at the `transaction_time` path, so the condition
+    /// is evaluated by the store and only the changed records are read.
+    pub(super) fn restrict_to_changes<'f, R: Record>(
+        &self,
+        filter: Filter<'f, R>,
+        transaction_time: R::QueryPath<'f>,
+    ) -> Filter<'f, R> {
+        match self.since {
+            Some(since) => restrict(filter, Filter::ChangedSince(transaction_time, since)),
+            None => filter,
+        }
+    }
+
+    /// Adjusts the temporal metadata of an entity to the state at the pinned timestamps.
+    ///
+    /// At the pinned timestamps, it was not known yet, when the edition will be superseded, so the
+    /// intervals on the pinned axes are unbounded.
+    pub(super) fn pin_entity(&self, entity: &mut EntitySnapshotRecord) {
+        if let Some(temporal_versioning) = &mut entity.metadata.temporal_versioning {
+            if self.transaction_time.is_some() {
+                temporal_versioning.transaction_time =
+                    unbounded_end(temporal_versioning.transaction_time);
+            }
+            if self.decision_time.is_some() {
+                temporal_versioning.decision_time =
+                    unbounded_end(temporal_versioning.decision_time);
+            }
+        }
+    }
+
+    /// Adjusts the metadata of an ontology type to the state at the pinned transaction time.
+    ///
+    /// At the pinned transaction time, the ontology type was not archived yet.
+    pub(super) fn pin_ontology_type(&self, metadata: &mut CustomOntologyMetadata) {
+        if self.transaction_time.is_some() {
+            let (CustomOntologyMetadata::Owned {
+                provenance,
+                temporal_versioning,
+                ..
+            }
+            | CustomOntologyMetadata::External {
+                provenance,
+                temporal_versioning,
+                ..
+            }) = metadata;
+            temporal_versioning.transaction_time =
+                unbounded_end(temporal_versioning.transaction_time);
+            provenance.record_archived_by_id = None;
+        }
+    }
+}
+
+fn unbounded_end<A>(interval: LeftClosedTemporalInterval<A>) -> LeftClosedTemporalInterval<A> {
+    let (ClosedTemporalBound::Inclusive(start), _) = interval.into_bounds();
+    Interval::new_unchecked(
+        ClosedTemporalBound::Inclusive(start),
+        OpenTemporalBound::Unbounded,
+    )
+}
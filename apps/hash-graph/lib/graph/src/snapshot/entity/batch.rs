@@ -5,7 +5,7 @@ use tokio_postgres::GenericClient;
 use crate::{
     snapshot::{
         entity::{EntityEditionRow, EntityIdRow, EntityLinkEdgeRow, EntityTemporalMetadataRow},
-        WriteBatch,
+        SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
     },
     store::{AsClient, InsertionError, PostgresStore},
 };
@@ -136,6 +136,186 @@ impl<C: AsClient> WriteBatch<C> for EntityRowBatch {
         Ok(())
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM entity_ids_tmp
+                        WHERE (owned_by_id, entity_uuid) IN (SELECT owned_by_id, entity_uuid FROM entity_ids);
+
+                    DELETE FROM entity_editions_tmp
+                        WHERE entity_edition_id IN (SELECT entity_edition_id FROM entity_editions);
+
+                    DELETE FROM entity_temporal_metadata
+                        USING entity_temporal_metadata_tmp
+                        WHERE entity_temporal_metadata.owned_by_id = entity_temporal_metadata_tmp.owned_by_id
+                          AND entity_temporal_metadata.entity_uuid = entity_temporal_metadata_tmp.entity_uuid
+                          AND entity_temporal_metadata.entity_edition_id = entity_temporal_metadata_tmp.entity_edition_id
+                          AND entity_temporal_metadata.decision_time = entity_temporal_metadata_tmp.decision_time
+                          AND lower(entity_temporal_metadata.transaction_time) = lower(entity_temporal_metadata_tmp.transaction_time);
+
+                    DELETE FROM entity_link_edges_tmp
+                        WHERE (owned_by_id, entity_uuid) IN (SELECT owned_by_id, entity_uuid FROM entity_has_left_entity);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        on_conflict: SnapshotConflictBehavior,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+
+        // An edition conflicts if the store contains it with different properties or if it
+        // overlaps with an edition of the same entity in the store.
+        client
+            .simple_query(
+                r"
+                    DELETE FROM entity_ids_tmp
+                        WHERE (owned_by_id, entity_uuid) IN (SELECT owned_by_id, entity_uuid FROM entity_ids);
+
+                    DELETE FROM entity_link_edges_tmp
+                        WHERE (owned_by_id, entity_uuid) IN (SELECT owned_by_id, entity_uuid FROM entity_has_left_entity);
+
+                    DELETE FROM entity_temporal_metadata_tmp
+                        USING entity_temporal_metadata
+                        WHERE entity_temporal_metadata.entity_edition_id = entity_temporal_metadata_tmp.entity_edition_id
+                          AND entity_temporal_metadata.decision_time = entity_temporal_metadata_tmp.decision_time
+                          AND entity_temporal_metadata.transaction_time = entity_temporal_metadata_tmp.transaction_time;
+
+                    CREATE TEMPORARY TABLE entity_merge_tmp ON COMMIT DROP AS
+                        SELECT
+                            entity_editions_tmp.entity_edition_id,
+                            entity_editions.entity_edition_id IS NOT NULL AS present,
+                            entity_editions.properties IS DISTINCT FROM entity_editions_tmp.properties
+                                AND entity_editions.entity_edition_id IS NOT NULL
+                            OR EXISTS (
+                                SELECT 1
+                                FROM entity_temporal_metadata_tmp
+                                INNER JOIN entity_temporal_metadata ON
+                                    entity_temporal_metadata.owned_by_id = entity_temporal_metadata_tmp.owned_by_id
+                                    AND entity_temporal_metadata.entity_uuid = entity_temporal_metadata_tmp.entity_uuid
+                                    AND entity_temporal_metadata.decision_time && entity_temporal_metadata_tmp.decision_time
+                                    AND entity_temporal_metadata.transaction_time && entity_temporal_metadata_tmp.transaction_time
+                                WHERE entity_temporal_metadata_tmp.entity_edition_id = entity_editions_tmp.entity_edition_id
+                            ) AS conflicting
+                        FROM entity_editions_tmp
+                        LEFT OUTER JOIN entity_editions
+                            ON entity_editions.entity_edition_id = entity_editions_tmp.entity_edition_id;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        let row = client
+            .query_one(
+                r"
+                    SELECT
+                        count(*) FILTER (WHERE present AND NOT conflicting),
+                        count(*) FILTER (WHERE conflicting)
+                    FROM entity_merge_tmp;
+                ",
+                &[],
+            )
+            .await
+            .change_context(InsertionError)?;
+        let skipped: i64 = row.get(0);
+        let conflicting: i64 = row.get(1);
+        report.entity_editions.skipped +=
+            usize::try_from(skipped).change_context(InsertionError)?;
+        report.entity_editions.conflicting +=
+            usize::try_from(conflicting).change_context(InsertionError)?;
+
+        match on_conflict {
+            SnapshotConflictBehavior::Overwrite => {
+                // The temporal rows in the store overlapping with the snapshot are replaced. An
+                // edition in the store, which has no temporal row left and is not contained in the
+                // snapshot, is superseded by the snapshot and is removed with its entity type.
+                client
+                    .simple_query(
+                        r"
+                            WITH updated_types AS (
+                                UPDATE entity_is_of_type
+                                    SET entity_type_ontology_id = ontology_ids.ontology_id
+                                    FROM entity_editions_tmp
+                                    INNER JOIN entity_merge_tmp
+                                        ON entity_merge_tmp.entity_edition_id = entity_editions_tmp.entity_edition_id
+                                    INNER JOIN ontology_ids
+                                        ON ontology_ids.base_url = entity_editions_tmp.entity_type_base_url
+                                       AND ontology_ids.version = entity_editions_tmp.entity_type_version
+                                    WHERE entity_is_of_type.entity_edition_id = entity_editions_tmp.entity_edition_id
+                                      AND entity_merge_tmp.present
+                                      AND entity_merge_tmp.conflicting
+                            )
+                            UPDATE entity_editions
+                                SET
+                                    properties = entity_editions_tmp.properties,
+                                    left_to_right_order = entity_editions_tmp.left_to_right_order,
+                                    right_to_left_order = entity_editions_tmp.right_to_left_order,
+                                    archived = entity_editions_tmp.archived
+                                FROM entity_editions_tmp
+                                INNER JOIN entity_merge_tmp
+                                    ON entity_merge_tmp.entity_edition_id = entity_editions_tmp.entity_edition_id
+                                WHERE entity_editions.entity_edition_id = entity_editions_tmp.entity_edition_id
+                                  AND entity_merge_tmp.present
+                                  AND entity_merge_tmp.conflicting;
+
+                            WITH overwritten_rows AS (
+                                DELETE FROM entity_temporal_metadata
+                                    USING entity_temporal_metadata_tmp
+                                    WHERE entity_temporal_metadata.owned_by_id = entity_temporal_metadata_tmp.owned_by_id
+                                      AND entity_temporal_metadata.entity_uuid = entity_temporal_metadata_tmp.entity_uuid
+                                      AND entity_temporal_metadata.decision_time && entity_temporal_metadata_tmp.decision_time
+                                      AND entity_temporal_metadata.transaction_time && entity_temporal_metadata_tmp.transaction_time
+                                    RETURNING entity_temporal_metadata.ctid, entity_temporal_metadata.entity_edition_id
+                            ), superseded_editions AS (
+                                SELECT DISTINCT entity_edition_id
+                                FROM overwritten_rows
+                                WHERE entity_edition_id NOT IN (SELECT entity_edition_id FROM entity_temporal_metadata_tmp)
+                                  AND NOT EXISTS (
+                                      SELECT 1
+                                      FROM entity_temporal_metadata
+                                      WHERE entity_temporal_metadata.entity_edition_id = overwritten_rows.entity_edition_id
+                                        AND entity_temporal_metadata.ctid NOT IN (SELECT ctid FROM overwritten_rows)
+                                  )
+                            ), superseded_types AS (
+                                DELETE FROM entity_is_of_type
+                                    WHERE entity_edition_id IN (SELECT entity_edition_id FROM superseded_editions)
+                            )
+                            DELETE FROM entity_editions
+                                WHERE entity_edition_id IN (SELECT entity_edition_id FROM superseded_editions);
+
+                            DELETE FROM entity_editions_tmp
+                                WHERE entity_edition_id IN (SELECT entity_edition_id FROM entity_merge_tmp WHERE present);
+                        ",
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+            SnapshotConflictBehavior::Fail | SnapshotConflictBehavior::Skip => {
+                client
+                    .simple_query(
+                        r"
+                            DELETE FROM entity_temporal_metadata_tmp
+                                WHERE entity_edition_id IN (SELECT entity_edition_id FROM entity_merge_tmp WHERE conflicting);
+
+                            DELETE FROM entity_editions_tmp
+                                WHERE entity_edition_id IN (SELECT entity_edition_id FROM entity_merge_tmp WHERE present OR conflicting);
+                        ",
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+        }
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         postgres_client
             .as_client()
@@ -159,11 +339,11 @@ impl<C: AsClient> WriteBatch<C> for EntityRowBatch {
                     INSERT INTO entity_is_of_type
                         SELECT
                             entity_edition_id,
-                            ontology_ids_tmp.ontology_id AS entity_type_ontology_id
+                            ontology_ids.ontology_id AS entity_type_ontology_id
                         FROM entity_editions_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = entity_editions_tmp.entity_type_base_url
-                            AND ontology_ids_tmp.version = entity_editions_tmp.entity_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = entity_editions_tmp.entity_type_base_url
+                            AND ontology_ids.version = entity_editions_tmp.entity_type_version;
 
                     INSERT INTO entity_has_left_entity
                         SELECT
@@ -2,6 +2,8 @@ use std::{error::Error, fmt};
 
 #[derive(Debug)]
 pub enum SnapshotDumpError {
+    InvalidSettings,
+    Connection,
     Read,
     Query,
     Write,
@@ -10,6 +12,8 @@ pub enum SnapshotDumpError {
 impl fmt::Display for SnapshotDumpError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::InvalidSettings => write!(f, "the snapshot dump settings are invalid"),
+            Self::Connection => write!(f, "could not acquire a connection to the store"),
             Self::Read => write!(f, "could not read a snapshot record"),
             Self::Query => write!(f, "could not query snapshot records from the store"),
             Self::Write => write!(f, "could not write a snapshot record into the sink"),
@@ -23,6 +27,13 @@ impl Error for SnapshotDumpError {}
 pub enum SnapshotRestoreError {
     Unsupported,
     MissingMetadata,
+    Truncated,
+    Corrupted,
+    Incremental,
+    NotIncremental,
+    Conflict,
+    InterruptedRestore,
+    Locked,
     Read,
     Buffer,
     Write,
@@ -33,6 +44,22 @@ impl fmt::Display for SnapshotRestoreError {
         match self {
             Self::Unsupported => write!(f, "The snapshot contains unsupported entries"),
             Self::MissingMetadata => write!(f, "The snapshot does not contain metadata"),
+            Self::Truncated => write!(f, "The snapshot is truncated"),
+            Self::Corrupted => write!(
+                f,
+                "The checksum of the snapshot does not match its manifest"
+            ),
+            Self::Incremental => write!(
+                f,
+                "The snapshot is incremental and can only be restored on top of its base snapshot"
+            ),
+            Self::NotIncremental => write!(f, "The snapshot is not incremental"),
+            Self::Conflict => write!(f, "The snapshot conflicts with records in the store"),
+            Self::InterruptedRestore => write!(
+                f,
+                "An interrupted snapshot restore has to be resumed or its checkpoint discarded"
+            ),
+            Self::Locked => write!(f, "Another snapshot restore is in progress"),
             Self::Read => write!(f, "could not read a snapshot entry"),
             Self::Buffer => write!(f, "could not buffer a snapshot entry"),
             Self::Write => write!(f, "could not write a snapshot entry into the store"),
@@ -41,3 +68,43 @@ impl fmt::Display for SnapshotRestoreError {
 }
 
 impl Error for SnapshotRestoreError {}
+
+#[derive(Debug)]
+pub enum SnapshotUpgradeError {
+    MissingMetadata,
+    UnsupportedVersion,
+    Upgrade,
+    Deserialize,
+}
+
+impl fmt::Display for SnapshotUpgradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMetadata => write!(f, "The snapshot does not start with its metadata"),
+            Self::UnsupportedVersion => write!(f, "The snapshot version is not supported"),
+            Self::Upgrade => write!(f, "could not upgrade a snapshot entry"),
+            Self::Deserialize => write!(f, "could not deserialize a snapshot entry"),
+        }
+    }
+}
+
+impl Error for SnapshotUpgradeError {}
+
+#[derive(Debug)]
+pub enum SnapshotDiffError {
+    Read,
+    Spill,
+    Compare,
+}
+
+impl fmt::Display for SnapshotDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read => write!(f, "could not read a snapshot entry"),
+            Self::Spill => write!(f, "could not sort the snapshot on disk"),
+            Self::Compare => write!(f, "could not compare a snapshot record"),
+        }
+    }
+}
+
+impl Error for SnapshotDiffError {}
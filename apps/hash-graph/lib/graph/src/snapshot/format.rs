@@ -0,0 +1,292 @@
+use std::{io, pin::Pin};
+
+use async_compression::tokio::{bufread::ZstdDecoder, write::ZstdEncoder};
+use bytes::{Buf, Bytes, BytesMut};
+use error_stack::{Report, ResultExt};
+use futures::{stream::BoxStream, Sink, StreamExt};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite};
+use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
+
+use crate::snapshot::{
+    codec::{CborDecoder, CborEncoder, JsonLinesDecoder, JsonLinesEncoder, CBOR_MAGIC},
+    upgrade::SnapshotUpgrader,
+    SnapshotEntry,
+};
+
+/// The magic number at the start of every zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// A boxed sink to write snapshot entries into.
+pub type SnapshotSink = Pin<Box<dyn Sink<SnapshotEntry, Error = Report<io::Error>> + Send>>;
+
+/// The format a snapshot is encoded in.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum SnapshotFormat {
+    /// Newline-delimited JSON with one snapshot entry per line.
+    #[default]
+    JsonLines,
+    /// Newline-delimited JSON compressed with zstd.
+    JsonLinesZstd,
+    /// Length-delimited CBOR, starting with the CBOR self-describe tag.
+    Cbor,
+}
+
+impl SnapshotFormat {
+    /// Detects the format of a snapshot from its first bytes.
+    ///
+    /// Compressed and binary snapshots start with a magic header. Every other input is treated
+    /// as JSON Lines.
+    #[must_use]
+    pub fn from_header(header: &[u8]) -> Self {
+        if header.starts_with(&ZSTD_MAGIC) {
+            Self::JsonLinesZstd
+        } else if header.starts_with(&CBOR_MAGIC) {
+            Self::Cbor
+        } else {
+            Self::JsonLines
+        }
+    }
+
+    /// Detects the format of the snapshot provided by `reader` without consuming any input.
+    ///
+    /// # Errors
+    ///
+    /// - If reading from `reader` fails
+    pub async fn detect(reader: &mut (impl AsyncBufRead + Unpin + Send)) -> io::Result<Self> {
+        Ok(Self::from_header(reader.fill_buf().await?))
+    }
+
+    /// Creates a sink, which writes snapshot entries in this format into `writer`.
+    ///
+    /// The sink has to be closed to write the end of compressed snapshots.
+    pub fn encode(self, writer: impl AsyncWrite + Send + 'static) -> SnapshotSink {
+        match self {
+            Self::JsonLines => Box::pin(FramedWrite::new(writer, JsonLinesEncoder::default())),
+            Self::JsonLinesZstd => Box::pin(FramedWrite::new(
+                ZstdEncoder::new(writer),
+                JsonLinesEncoder::default(),
+            )),
+            Self::Cbor => Box::pin(FramedWrite::new(writer, CborEncoder::default())),
+        }
+    }
+
+    /// Creates a stream, which reads snapshot entries in this format from `reader`.
+    ///
+    /// Snapshots of an older version are upgraded to [`SNAPSHOT_VERSION`] while they are read. The
+    /// entries of a snapshot, which is already of the current version, are deserialized directly.
+    ///
+    /// [`SNAPSHOT_VERSION`]: crate::snapshot::SNAPSHOT_VERSION
+    pub fn decode(
+        self,
+        reader: impl AsyncBufRead + Send + 'static,
+    ) -> BoxStream<'static, Result<SnapshotEntry, Report<io::Error>>> {
+        let mut upgrader = SnapshotUpgrader::default();
+        self.decode_encoded(reader)
+            .map(move |entry| {
+                upgrader.upgrade(&entry?).map_err(|report| {
+                    report.change_context(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "could not upgrade snapshot entry",
+                    ))
+                })
+            })
+            .boxed()
+    }
+
+    /// Creates a stream, which reads the snapshot entries in this format without deserializing
+    /// them.
+    fn decode_encoded(
+        self,
+        reader: impl AsyncBufRead + Send + 'static,
+    ) -> BoxStream<'static, Result<EncodedEntry, Report<io::Error>>> {
+        match self {
+            Self::JsonLines => FramedRead::new(
+                reader,
+                EncodedEntryDecoder::JsonLines(JsonLinesDecoder::new()),
+            )
+            .boxed(),
+            Self::JsonLinesZstd => FramedRead::new(
+                ZstdDecoder::new(reader),
+                EncodedEntryDecoder::JsonLines(JsonLinesDecoder::new()),
+            )
+            .boxed(),
+            Self::Cbor => {
+                FramedRead::new(reader, EncodedEntryDecoder::Cbor(CborDecoder::default())).boxed()
+            }
+        }
+    }
+}
+
+/// A snapshot entry, which was read from the input but not deserialized yet.
+///
+/// This allows to choose the type to deserialize into after reading the entry, e.g. to only
+/// deserialize into an intermediate value, if the entry has to be upgraded.
+#[derive(Debug)]
+pub(crate) enum EncodedEntry {
+    Json { line: String, line_number: usize },
+    Cbor { item: Bytes, item_number: usize },
+}
+
+impl EncodedEntry {
+    /// Deserializes the entry into `T`.
+    ///
+    /// # Errors
+    ///
+    /// - If the entry is not a valid `T`
+    pub(crate) fn deserialize<T: DeserializeOwned>(&self) -> Result<T, Report<io::Error>> {
+        match self {
+            Self::Json { line, line_number } => serde_json::from_str(line)
+                .map_err(io::Error::from)
+                .attach_printable_lazy(|| format!("line in input: {line_number}")),
+            Self::Cbor { item, item_number } => ciborium::de::from_reader(item.clone().reader())
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                .attach_printable_lazy(|| format!("item in input: {item_number}")),
+        }
+    }
+}
+
+/// Reads [`EncodedEntry`]s in the format of the snapshot.
+enum EncodedEntryDecoder {
+    JsonLines(JsonLinesDecoder<SnapshotEntry>),
+    Cbor(CborDecoder<SnapshotEntry>),
+}
+
+impl Decoder for EncodedEntryDecoder {
+    // `Decoder::Error` requires `From<io::Error>` so we need to use `Report<io::Error>` here.
+    type Error = Report<io::Error>;
+    type Item = EncodedEntry;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<EncodedEntry>, Self::Error> {
+        match self {
+            Self::JsonLines(decoder) => {
+                Ok(decoder.decode_line(buf)?.map(|line| EncodedEntry::Json {
+                    line,
+                    line_number: decoder.current_line(),
+                }))
+            }
+            Self::Cbor(decoder) => Ok(decoder.decode_frame(buf)?.map(|item| EncodedEntry::Cbor {
+                item: item.freeze(),
+                item_number: decoder.current_item(),
+            })),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<EncodedEntry>, Self::Error> {
+        match self {
+            Self::JsonLines(decoder) => {
+                Ok(decoder
+                    .decode_line_eof(buf)?
+                    .map(|line| EncodedEntry::Json {
+                        line,
+                        line_number: decoder.current_line(),
+                    }))
+            }
+            Self::Cbor(_) => match self.decode(buf)? {
+                Some(entry) => Ok(Some(entry)),
+                None if buf.is_empty() => Ok(None),
+                None => Err(Report::new(io::Error::new(
+                    io::ErrorKind::Other,
+                    "bytes remaining on stream",
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::{SinkExt, TryStreamExt};
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+    use crate::snapshot::{
+        BlockProtocolModuleVersions, CustomGlobalMetadata, SnapshotManifest, SnapshotMetadata,
+        SnapshotRecordCounts, SNAPSHOT_VERSION,
+    };
+
+    fn entries() -> Vec<SnapshotEntry> {
+        vec![
+            SnapshotEntry::Snapshot(SnapshotMetadata {
+                block_protocol_module_versions: BlockProtocolModuleVersions {
+                    graph: SNAPSHOT_VERSION,
+                },
+                custom: CustomGlobalMetadata {
+                    has_manifest: true,
+                    ..CustomGlobalMetadata::default()
+                },
+            }),
+            SnapshotEntry::Manifest(SnapshotManifest {
+                records: SnapshotRecordCounts::default(),
+                checksum: "00".to_owned(),
+                database: "graph".to_owned(),
+                dumped_at: serde_json::from_str("\"2023-01-01T00:00:00Z\"")
+                    .expect("could not parse timestamp"),
+                custom: HashMap::new(),
+            }),
+        ]
+    }
+
+    async fn encode(format: SnapshotFormat, entries: Vec<SnapshotEntry>) -> Vec<u8> {
+        let (writer, mut reader) = tokio::io::duplex(1 << 16);
+        let mut sink = format.encode(writer);
+        for entry in entries {
+            sink.feed(entry).await.expect("could not encode entry");
+        }
+        sink.close().await.expect("could not close sink");
+        drop(sink);
+
+        let mut encoded = Vec::new();
+        reader
+            .read_to_end(&mut encoded)
+            .await
+            .expect("could not read encoded snapshot");
+        encoded
+    }
+
+    #[tokio::test]
+    async fn formats_are_detected_and_decoded() {
+        for format in [
+            SnapshotFormat::JsonLines,
+            SnapshotFormat::JsonLinesZstd,
+            SnapshotFormat::Cbor,
+        ] {
+            let mut reader = io::Cursor::new(encode(format, entries()).await);
+            assert_eq!(
+                SnapshotFormat::detect(&mut reader)
+                    .await
+                    .expect("could not detect format"),
+                format
+            );
+
+            let decoded: Vec<_> = format
+                .decode(reader)
+                .try_collect()
+                .await
+                .expect("could not decode snapshot");
+            assert_eq!(decoded, entries(), "{format:?} snapshot was not decoded");
+        }
+    }
+
+    #[test]
+    fn unknown_header_is_json_lines() {
+        assert_eq!(
+            SnapshotFormat::from_header(b"{\"type\":\"snapshot\"}"),
+            SnapshotFormat::JsonLines
+        );
+        assert_eq!(SnapshotFormat::from_header(&[]), SnapshotFormat::JsonLines);
+    }
+
+    #[tokio::test]
+    async fn cbor_without_header_is_rejected() {
+        let encoded = encode(SnapshotFormat::Cbor, entries()).await;
+        let decoded: Result<Vec<_>, _> = SnapshotFormat::Cbor
+            .decode(io::Cursor::new(encoded[CBOR_MAGIC.len()..].to_vec()))
+            .try_collect()
+            .await;
+        decoded.expect_err("the CBOR self-describe tag is missing");
+    }
+}
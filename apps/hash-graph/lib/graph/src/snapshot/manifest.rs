@@ -0,0 +1,165 @@
+use std::{collections::HashMap, fmt::Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use temporal_versioning::{Timestamp, TransactionTime};
+
+use crate::snapshot::SnapshotEntry;
+
+/// Describes the content of a snapshot.
+///
+/// The manifest is the last entry of a snapshot, so a snapshot, which was truncated, does not
+/// contain it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    /// The number of records of each kind contained in the snapshot.
+    pub records: SnapshotRecordCounts,
+    /// The SHA-256 based checksum of the entries in the snapshot.
+    ///
+    /// Each entry is hashed from its canonical JSON representation and the hashes are summed up,
+    /// so the checksum neither depends on the format nor on the order of the entries.
+    pub checksum: String,
+    /// The name of the database the snapshot was dumped from.
+    pub database: String,
+    /// The point in time the snapshot was dumped at.
+    pub dumped_at: Timestamp<TransactionTime>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom: HashMap<String, Value>,
+}
+
+/// The number of records of each kind contained in a snapshot.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRecordCounts {
+    pub data_types: usize,
+    pub property_types: usize,
+    pub entity_types: usize,
+    pub entities: usize,
+    pub ownership_transfers: usize,
+}
+
+impl SnapshotRecordCounts {
+    /// Counts the record contained in the `entry`.
+    pub(crate) fn add(&mut self, entry: &SnapshotEntry) {
+        match entry {
+            SnapshotEntry::Snapshot(_) | SnapshotEntry::Manifest(_) => {}
+            SnapshotEntry::DataType(_) => self.data_types += 1,
+            SnapshotEntry::PropertyType(_) => self.property_types += 1,
+            SnapshotEntry::EntityType(_) => self.entity_types += 1,
+            SnapshotEntry::Entity(_) => self.entities += 1,
+            SnapshotEntry::OwnershipTransfer(_) => self.ownership_transfers += 1,
+        }
+    }
+
+    /// Returns the number of records of any kind.
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.data_types
+            + self.property_types
+            + self.entity_types
+            + self.entities
+            + self.ownership_transfers
+    }
+}
+
+/// Accumulates the record counts and the checksum of the entries in a snapshot.
+///
+/// Each entry is hashed independently from its canonical JSON representation, i.e. with sorted
+/// object keys and without whitespace, so the checksum does not depend on the format the snapshot
+/// is encoded in. As the entries of a snapshot are not ordered, the checksum is the sum of these
+/// hashes modulo 2^256. The manifest itself is not part of the checksum.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct SnapshotContent {
+    records: SnapshotRecordCounts,
+    checksum: [u8; 32],
+}
+
+impl SnapshotContent {
+    pub(crate) fn add(&mut self, entry: &SnapshotEntry) {
+        self.add_value(entry, &serde_json::to_value(entry).unwrap_or(Value::Null));
+    }
+
+    /// Adds the `entry`, but hashes the JSON `value` it was decoded from.
+    ///
+    /// This is used for upgraded entries, so the content matches the snapshot before the upgrade.
+    pub(crate) fn add_value(&mut self, entry: &SnapshotEntry, value: &Value) {
+        if let SnapshotEntry::Manifest(_) = entry {
+            return;
+        }
+        self.records.add(entry);
+
+        let mut hasher = Sha256::new();
+        write_canonical(value, &mut hasher);
+
+        let mut carry = 0_u16;
+        for (sum, byte) in self.checksum.iter_mut().zip(hasher.finalize()).rev() {
+            let [low, high] = (u16::from(*sum) + u16::from(byte) + carry).to_le_bytes();
+            *sum = low;
+            carry = u16::from(high);
+        }
+    }
+
+    pub(crate) const fn records(&self) -> SnapshotRecordCounts {
+        self.records
+    }
+
+    /// Returns the checksum as lowercase hexadecimal string.
+    pub(crate) fn checksum(&self) -> String {
+        self.checksum
+            .iter()
+            .fold(String::with_capacity(64), |mut checksum, byte| {
+                // Writing into a `String` cannot fail
+                let _ = write!(checksum, "{byte:02x}");
+                checksum
+            })
+    }
+
+    /// Creates the manifest describing the content.
+    pub(crate) fn manifest(
+        &self,
+        database: String,
+        dumped_at: Timestamp<TransactionTime>,
+    ) -> SnapshotManifest {
+        SnapshotManifest {
+            records: self.records,
+            checksum: self.checksum(),
+            database,
+            dumped_at,
+            custom: HashMap::new(),
+        }
+    }
+}
+
+/// Feeds the canonical JSON representation of the `value` into the `hasher`.
+fn write_canonical(value: &Value, hasher: &mut Sha256) {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+
+            hasher.update(b"{");
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    hasher.update(b",");
+                }
+                hasher.update(Value::from(key.as_str()).to_string());
+                hasher.update(b":");
+                write_canonical(value, hasher);
+            }
+            hasher.update(b"}");
+        }
+        Value::Array(array) => {
+            hasher.update(b"[");
+            for (index, value) in array.iter().enumerate() {
+                if index > 0 {
+                    hasher.update(b",");
+                }
+                write_canonical(value, hasher);
+            }
+            hasher.update(b"]");
+        }
+        scalar => hasher.update(scalar.to_string()),
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use temporal_versioning::{Timestamp, TransactionTime};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -16,12 +17,30 @@ pub struct BlockProtocolModuleVersions {
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CustomGlobalMetadata;
+pub struct CustomGlobalMetadata {
+    /// The transaction time up to which all changes are contained in the snapshot.
+    ///
+    /// This is the high-water mark an incremental snapshot on top of this snapshot starts from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_time: Option<Timestamp<TransactionTime>>,
+    /// If the snapshot is incremental, the transaction time since which changes are contained.
+    ///
+    /// An incremental snapshot can only be restored on top of a snapshot, which contains all
+    /// changes up to this transaction time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<Timestamp<TransactionTime>>,
+    /// Whether the snapshot ends with a [`SnapshotManifest`].
+    ///
+    /// If set, a snapshot without a manifest is considered to be truncated.
+    ///
+    /// [`SnapshotManifest`]: crate::snapshot::SnapshotManifest
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub has_manifest: bool,
+}
 
 impl CustomGlobalMetadata {
     #[must_use]
-    #[expect(clippy::unused_self)]
     const fn is_empty(&self) -> bool {
-        true
+        self.transaction_time.is_none() && self.since.is_none() && !self.has_manifest
     }
 }
@@ -3,7 +3,9 @@ use error_stack::{Result, ResultExt};
 use tokio_postgres::GenericClient;
 
 use crate::{
-    snapshot::{ontology::table::DataTypeRow, WriteBatch},
+    snapshot::{
+        ontology::table::DataTypeRow, SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
+    },
     store::{AsClient, InsertionError, PostgresStore},
 };
 
@@ -53,6 +55,56 @@ impl<C: AsClient> WriteBatch<C> for DataTypeRowBatch {
         Ok(())
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM data_types_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM data_types);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        on_conflict: SnapshotConflictBehavior,
+        _report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+        if on_conflict == SnapshotConflictBehavior::Overwrite {
+            client
+                .simple_query(
+                    r"
+                        UPDATE data_types
+                            SET schema = data_types_tmp.schema
+                            FROM data_types_tmp
+                            INNER JOIN ontology_merge_tmp
+                                ON ontology_merge_tmp.snapshot_ontology_id = data_types_tmp.ontology_id
+                            WHERE data_types.ontology_id = ontology_merge_tmp.store_ontology_id
+                              AND ontology_merge_tmp.conflicting;
+                    ",
+                )
+                .await
+                .change_context(InsertionError)?;
+        }
+
+        client
+            .simple_query(
+                r"
+                    DELETE FROM data_types_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         postgres_client
             .as_client()
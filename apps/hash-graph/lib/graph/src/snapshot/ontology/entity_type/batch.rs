@@ -8,7 +8,7 @@ use crate::{
             EntityTypeConstrainsLinkDestinationsOnRow, EntityTypeConstrainsLinksOnRow,
             EntityTypeConstrainsPropertiesOnRow, EntityTypeInheritsFromRow, EntityTypeRow,
         },
-        WriteBatch,
+        SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
     },
     store::{AsClient, InsertionError, PostgresStore},
 };
@@ -156,6 +156,140 @@ impl<C: AsClient> WriteBatch<C> for EntityTypeRowBatch {
         Ok(())
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM entity_types_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM entity_types);
+
+                    DELETE FROM entity_type_constrains_properties_on_tmp
+                        WHERE source_entity_type_ontology_id IN (SELECT ontology_id FROM entity_types);
+
+                    DELETE FROM entity_type_inherits_from_tmp
+                        WHERE source_entity_type_ontology_id IN (SELECT ontology_id FROM entity_types);
+
+                    DELETE FROM entity_type_constrains_links_on_tmp
+                        WHERE source_entity_type_ontology_id IN (SELECT ontology_id FROM entity_types);
+
+                    DELETE FROM entity_type_constrains_link_destinations_on_tmp
+                        WHERE source_entity_type_ontology_id IN (SELECT ontology_id FROM entity_types);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        on_conflict: SnapshotConflictBehavior,
+        _report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+        match on_conflict {
+            SnapshotConflictBehavior::Overwrite => {
+                // The references of the overwritten entity types are replaced by the
+                // references in the snapshot, which are moved to the ontology ids in the store.
+                client
+                    .simple_query(
+                        r"
+                            UPDATE entity_types
+                                SET schema = entity_types_tmp.schema
+                                FROM entity_types_tmp
+                                INNER JOIN ontology_merge_tmp
+                                    ON ontology_merge_tmp.snapshot_ontology_id = entity_types_tmp.ontology_id
+                                WHERE entity_types.ontology_id = ontology_merge_tmp.store_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+
+                            DELETE FROM entity_type_constrains_properties_on
+                                WHERE source_entity_type_ontology_id IN (SELECT store_ontology_id FROM ontology_merge_tmp WHERE conflicting);
+
+                            DELETE FROM entity_type_inherits_from
+                                WHERE source_entity_type_ontology_id IN (SELECT store_ontology_id FROM ontology_merge_tmp WHERE conflicting);
+
+                            DELETE FROM entity_type_constrains_links_on
+                                WHERE source_entity_type_ontology_id IN (SELECT store_ontology_id FROM ontology_merge_tmp WHERE conflicting);
+
+                            DELETE FROM entity_type_constrains_link_destinations_on
+                                WHERE source_entity_type_ontology_id IN (SELECT store_ontology_id FROM ontology_merge_tmp WHERE conflicting);
+
+                            DELETE FROM entity_type_constrains_properties_on_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp WHERE NOT conflicting);
+
+                            DELETE FROM entity_type_inherits_from_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp WHERE NOT conflicting);
+
+                            DELETE FROM entity_type_constrains_links_on_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp WHERE NOT conflicting);
+
+                            DELETE FROM entity_type_constrains_link_destinations_on_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp WHERE NOT conflicting);
+
+                            UPDATE entity_type_constrains_properties_on_tmp
+                                SET source_entity_type_ontology_id = ontology_merge_tmp.store_ontology_id
+                                FROM ontology_merge_tmp
+                                WHERE entity_type_constrains_properties_on_tmp.source_entity_type_ontology_id = ontology_merge_tmp.snapshot_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+
+                            UPDATE entity_type_inherits_from_tmp
+                                SET source_entity_type_ontology_id = ontology_merge_tmp.store_ontology_id
+                                FROM ontology_merge_tmp
+                                WHERE entity_type_inherits_from_tmp.source_entity_type_ontology_id = ontology_merge_tmp.snapshot_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+
+                            UPDATE entity_type_constrains_links_on_tmp
+                                SET source_entity_type_ontology_id = ontology_merge_tmp.store_ontology_id
+                                FROM ontology_merge_tmp
+                                WHERE entity_type_constrains_links_on_tmp.source_entity_type_ontology_id = ontology_merge_tmp.snapshot_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+
+                            UPDATE entity_type_constrains_link_destinations_on_tmp
+                                SET source_entity_type_ontology_id = ontology_merge_tmp.store_ontology_id
+                                FROM ontology_merge_tmp
+                                WHERE entity_type_constrains_link_destinations_on_tmp.source_entity_type_ontology_id = ontology_merge_tmp.snapshot_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+                        ",
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+            SnapshotConflictBehavior::Fail | SnapshotConflictBehavior::Skip => {
+                client
+                    .simple_query(
+                        r"
+                            DELETE FROM entity_type_constrains_properties_on_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                            DELETE FROM entity_type_inherits_from_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                            DELETE FROM entity_type_constrains_links_on_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                            DELETE FROM entity_type_constrains_link_destinations_on_tmp
+                                WHERE source_entity_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+                        ",
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+        }
+
+        client
+            .simple_query(
+                r"
+                    DELETE FROM entity_types_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         postgres_client
             .as_client()
@@ -167,38 +301,38 @@ impl<C: AsClient> WriteBatch<C> for EntityTypeRowBatch {
                     INSERT INTO entity_type_inherits_from
                         SELECT
                             source_entity_type_ontology_id,
-                            ontology_ids_tmp.ontology_id AS target_entity_type_ontology_id
+                            ontology_ids.ontology_id AS target_entity_type_ontology_id
                         FROM entity_type_inherits_from_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = entity_type_inherits_from_tmp.target_entity_type_base_url
-                            AND ontology_ids_tmp.version = entity_type_inherits_from_tmp.target_entity_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = entity_type_inherits_from_tmp.target_entity_type_base_url
+                            AND ontology_ids.version = entity_type_inherits_from_tmp.target_entity_type_version;
 
                     INSERT INTO entity_type_constrains_properties_on
                         SELECT
                             source_entity_type_ontology_id,
-                            ontology_ids_tmp.ontology_id AS target_entity_type_ontology_id
+                            ontology_ids.ontology_id AS target_entity_type_ontology_id
                         FROM entity_type_constrains_properties_on_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = entity_type_constrains_properties_on_tmp.target_property_type_base_url
-                            AND ontology_ids_tmp.version = entity_type_constrains_properties_on_tmp.target_property_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = entity_type_constrains_properties_on_tmp.target_property_type_base_url
+                            AND ontology_ids.version = entity_type_constrains_properties_on_tmp.target_property_type_version;
 
                     INSERT INTO entity_type_constrains_links_on
                         SELECT
                             source_entity_type_ontology_id,
-                            ontology_ids_tmp.ontology_id AS target_entity_type_ontology_id
+                            ontology_ids.ontology_id AS target_entity_type_ontology_id
                         FROM entity_type_constrains_links_on_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = entity_type_constrains_links_on_tmp.target_entity_type_base_url
-                            AND ontology_ids_tmp.version = entity_type_constrains_links_on_tmp.target_entity_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = entity_type_constrains_links_on_tmp.target_entity_type_base_url
+                            AND ontology_ids.version = entity_type_constrains_links_on_tmp.target_entity_type_version;
 
                     INSERT INTO entity_type_constrains_link_destinations_on
                         SELECT
                             source_entity_type_ontology_id,
-                            ontology_ids_tmp.ontology_id AS target_entity_type_ontology_id
+                            ontology_ids.ontology_id AS target_entity_type_ontology_id
                         FROM entity_type_constrains_link_destinations_on_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = entity_type_constrains_link_destinations_on_tmp.target_entity_type_base_url
-                            AND ontology_ids_tmp.version = entity_type_constrains_link_destinations_on_tmp.target_entity_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = entity_type_constrains_link_destinations_on_tmp.target_entity_type_base_url
+                            AND ontology_ids.version = entity_type_constrains_link_destinations_on_tmp.target_entity_type_version;
                 ",
             )
             .await
@@ -82,6 +82,7 @@ impl Sink<OntologyTypeSnapshotRecord<EntityType>> for EntityTypeSender {
             .start_send_unpin((ontology_id, OntologyElementMetadata {
                 record_id: entity_type.metadata.record_id,
                 custom: entity_type.metadata.custom.common,
+                deprecation: entity_type.metadata.deprecation,
             }))
             .attach_printable("could not send metadata")?;
 
@@ -5,10 +5,10 @@ use tokio_postgres::GenericClient;
 use crate::{
     snapshot::{
         ontology::{
-            table::OntologyTemporalMetadataRow, OntologyExternalMetadataRow, OntologyIdRow,
-            OntologyOwnedMetadataRow,
+            table::{OntologyDeprecationRow, OntologyTemporalMetadataRow},
+            OntologyExternalMetadataRow, OntologyIdRow, OntologyOwnedMetadataRow,
         },
-        WriteBatch,
+        SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
     },
     store::{AsClient, InsertionError, PostgresStore},
 };
@@ -18,6 +18,7 @@ pub enum OntologyTypeMetadataRowBatch {
     TemporalMetadata(Vec<OntologyTemporalMetadataRow>),
     OwnedMetadata(Vec<OntologyOwnedMetadataRow>),
     ExternalMetadata(Vec<OntologyExternalMetadataRow>),
+    Deprecations(Vec<OntologyDeprecationRow>),
 }
 
 #[async_trait]
@@ -46,6 +47,10 @@ impl<C: AsClient> WriteBatch<C> for OntologyTypeMetadataRowBatch {
                     CREATE TEMPORARY TABLE ontology_external_metadata_tmp
                         (LIKE ontology_external_metadata INCLUDING ALL)
                         ON COMMIT DROP;
+
+                    CREATE TEMPORARY TABLE ontology_deprecations_tmp
+                        (LIKE ontology_deprecations INCLUDING ALL)
+                        ON COMMIT DROP;
                 ",
             )
             .await
@@ -121,21 +126,144 @@ impl<C: AsClient> WriteBatch<C> for OntologyTypeMetadataRowBatch {
                     tracing::info!("Read {} ontology external metadata", rows.len());
                 }
             }
+            Self::Deprecations(ontology_deprecations) => {
+                let rows = client
+                    .query(
+                        r"
+                            INSERT INTO ontology_deprecations_tmp
+                            SELECT DISTINCT * FROM UNNEST($1::ontology_deprecations[])
+                            RETURNING 1;
+                        ",
+                        &[ontology_deprecations],
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+                if !rows.is_empty() {
+                    tracing::info!("Read {} ontology deprecations", rows.len());
+                }
+            }
         }
         Ok(())
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM ontology_ids_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM ontology_ids);
+
+                    DELETE FROM ontology_temporal_metadata
+                        USING ontology_temporal_metadata_tmp
+                        WHERE ontology_temporal_metadata.ontology_id = ontology_temporal_metadata_tmp.ontology_id
+                          AND lower(ontology_temporal_metadata.transaction_time) = lower(ontology_temporal_metadata_tmp.transaction_time);
+
+                    DELETE FROM ontology_owned_metadata_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM ontology_owned_metadata);
+
+                    DELETE FROM ontology_external_metadata_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM ontology_external_metadata);
+
+                    DELETE FROM ontology_deprecations_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM ontology_deprecations);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        _on_conflict: SnapshotConflictBehavior,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+
+        // Ontology types are identified by their versioned URL across stores, so the snapshot
+        // records are mapped to the records in the store. The schema tables of the different
+        // ontology types are used to detect conflicts, the types themselves are merged when
+        // preparing the respective schema batches.
+        client
+            .simple_query(
+                r"
+                    CREATE TEMPORARY TABLE ontology_merge_tmp ON COMMIT DROP AS
+                        SELECT
+                            ontology_ids_tmp.ontology_id AS snapshot_ontology_id,
+                            ontology_ids.ontology_id AS store_ontology_id,
+                            COALESCE(data_types_tmp.schema, property_types_tmp.schema, entity_types_tmp.schema)
+                                IS DISTINCT FROM COALESCE(data_types.schema, property_types.schema, entity_types.schema)
+                                AS conflicting
+                        FROM ontology_ids_tmp
+                        INNER JOIN ontology_ids USING (base_url, version)
+                        LEFT OUTER JOIN data_types_tmp ON data_types_tmp.ontology_id = ontology_ids_tmp.ontology_id
+                        LEFT OUTER JOIN property_types_tmp ON property_types_tmp.ontology_id = ontology_ids_tmp.ontology_id
+                        LEFT OUTER JOIN entity_types_tmp ON entity_types_tmp.ontology_id = ontology_ids_tmp.ontology_id
+                        LEFT OUTER JOIN data_types ON data_types.ontology_id = ontology_ids.ontology_id
+                        LEFT OUTER JOIN property_types ON property_types.ontology_id = ontology_ids.ontology_id
+                        LEFT OUTER JOIN entity_types ON entity_types.ontology_id = ontology_ids.ontology_id;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        let row = client
+            .query_one(
+                r"
+                    SELECT
+                        count(*) FILTER (WHERE NOT conflicting),
+                        count(*) FILTER (WHERE conflicting)
+                    FROM ontology_merge_tmp;
+                ",
+                &[],
+            )
+            .await
+            .change_context(InsertionError)?;
+        let skipped: i64 = row.get(0);
+        let conflicting: i64 = row.get(1);
+        report.ontology_types.skipped += usize::try_from(skipped).change_context(InsertionError)?;
+        report.ontology_types.conflicting +=
+            usize::try_from(conflicting).change_context(InsertionError)?;
+
+        // The metadata of the ontology types in the store is kept in any case.
+        client
+            .simple_query(
+                r"
+                    DELETE FROM ontology_ids_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                    DELETE FROM ontology_temporal_metadata_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                    DELETE FROM ontology_owned_metadata_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                    DELETE FROM ontology_external_metadata_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                    DELETE FROM ontology_deprecations_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         postgres_client
             .as_client()
             .client()
             .simple_query(
                 r"
-                    INSERT INTO base_urls                  SELECT DISTINCT base_url FROM ontology_ids_tmp;
+                    INSERT INTO base_urls                  SELECT DISTINCT base_url FROM ontology_ids_tmp ON CONFLICT DO NOTHING;
                     INSERT INTO ontology_ids               SELECT * FROM ontology_ids_tmp;
                     INSERT INTO ontology_temporal_metadata SELECT * FROM ontology_temporal_metadata_tmp;
                     INSERT INTO ontology_owned_metadata    SELECT * FROM ontology_owned_metadata_tmp;
                     INSERT INTO ontology_external_metadata SELECT * FROM ontology_external_metadata_tmp;
+                    INSERT INTO ontology_deprecations      SELECT * FROM ontology_deprecations_tmp;
                 ",
             )
             .await
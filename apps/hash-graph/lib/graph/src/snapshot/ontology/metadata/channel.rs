@@ -9,14 +9,15 @@ use futures::{
     stream::{select_all, BoxStream, SelectAll},
     Sink, SinkExt, Stream, StreamExt,
 };
-use graph_types::ontology::{CustomOntologyMetadata, OntologyElementMetadata};
+use graph_types::ontology::{CustomOntologyMetadata, OntologyElementMetadata, OntologyTypeVersion};
 use uuid::Uuid;
 
 use crate::snapshot::{
     account::AccountSender,
     ontology::{
-        table::OntologyTemporalMetadataRow, OntologyExternalMetadataRow, OntologyIdRow,
-        OntologyOwnedMetadataRow, OntologyTypeMetadataRowBatch,
+        table::{OntologyDeprecationRow, OntologyTemporalMetadataRow},
+        OntologyExternalMetadataRow, OntologyIdRow, OntologyOwnedMetadataRow,
+        OntologyTypeMetadataRowBatch,
     },
     SnapshotRestoreError,
 };
@@ -28,6 +29,7 @@ pub struct OntologyTypeMetadataSender {
     temporal_metadata: Sender<OntologyTemporalMetadataRow>,
     owned_metadata: Sender<OntologyOwnedMetadataRow>,
     external_metadata: Sender<OntologyExternalMetadataRow>,
+    deprecation: Sender<OntologyDeprecationRow>,
 }
 
 impl Sink<(Uuid, OntologyElementMetadata)> for OntologyTypeMetadataSender {
@@ -48,6 +50,9 @@ impl Sink<(Uuid, OntologyElementMetadata)> for OntologyTypeMetadataSender {
         ready!(self.external_metadata.poll_ready_unpin(cx))
             .change_context(SnapshotRestoreError::Read)
             .attach_printable("could not poll external metadata sender")?;
+        ready!(self.deprecation.poll_ready_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not poll deprecation sender")?;
 
         Poll::Ready(Ok(()))
     }
@@ -110,6 +115,28 @@ impl Sink<(Uuid, OntologyElementMetadata)> for OntologyTypeMetadataSender {
             .change_context(SnapshotRestoreError::Read)
             .attach_printable("could not send temporal metadata")?;
 
+        if let Some(deprecation) = metadata.deprecation {
+            let (successor_base_url, successor_version) = deprecation
+                .successor
+                .map(|successor| {
+                    (
+                        successor.base_url.as_str().to_owned(),
+                        OntologyTypeVersion::new(successor.version),
+                    )
+                })
+                .unzip();
+            self.deprecation
+                .start_send(OntologyDeprecationRow {
+                    ontology_id,
+                    deprecated_by_id: deprecation.deprecated_by_id,
+                    deprecated_at: deprecation.deprecated_at,
+                    successor_base_url,
+                    successor_version,
+                })
+                .change_context(SnapshotRestoreError::Read)
+                .attach_printable("could not send deprecation")?;
+        }
+
         Ok(())
     }
 
@@ -128,6 +155,9 @@ impl Sink<(Uuid, OntologyElementMetadata)> for OntologyTypeMetadataSender {
         ready!(self.external_metadata.poll_flush_unpin(cx))
             .change_context(SnapshotRestoreError::Read)
             .attach_printable("could not flush external metadata sender")?;
+        ready!(self.deprecation.poll_flush_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not flush deprecation sender")?;
 
         Poll::Ready(Ok(()))
     }
@@ -147,6 +177,9 @@ impl Sink<(Uuid, OntologyElementMetadata)> for OntologyTypeMetadataSender {
         ready!(self.external_metadata.poll_close_unpin(cx))
             .change_context(SnapshotRestoreError::Read)
             .attach_printable("could not close external metadata sender")?;
+        ready!(self.deprecation.poll_close_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not close deprecation sender")?;
 
         Poll::Ready(Ok(()))
     }
@@ -172,6 +205,7 @@ pub fn ontology_metadata_channel(
     let (temporal_metadata_tx, temporal_metadata_rx) = mpsc::channel(chunk_size);
     let (owned_metadata_tx, owned_metadata_rx) = mpsc::channel(chunk_size);
     let (external_metadata_tx, external_metadata_rx) = mpsc::channel(chunk_size);
+    let (deprecation_tx, deprecation_rx) = mpsc::channel(chunk_size);
 
     (
         OntologyTypeMetadataSender {
@@ -180,6 +214,7 @@ pub fn ontology_metadata_channel(
             temporal_metadata: temporal_metadata_tx,
             owned_metadata: owned_metadata_tx,
             external_metadata: external_metadata_tx,
+            deprecation: deprecation_tx,
         },
         OntologyTypeMetadataReceiver {
             stream: select_all([
@@ -199,6 +234,10 @@ pub fn ontology_metadata_channel(
                     .ready_chunks(chunk_size)
                     .map(OntologyTypeMetadataRowBatch::ExternalMetadata)
                     .boxed(),
+                deprecation_rx
+                    .ready_chunks(chunk_size)
+                    .map(OntologyTypeMetadataRowBatch::Deprecations)
+                    .boxed(),
             ]),
         },
     )
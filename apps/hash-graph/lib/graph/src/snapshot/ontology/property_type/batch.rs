@@ -8,7 +8,7 @@ use crate::{
             PropertyTypeConstrainsPropertiesOnRow, PropertyTypeConstrainsValuesOnRow,
             PropertyTypeRow,
         },
-        WriteBatch,
+        SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
     },
     store::{AsClient, InsertionError, PostgresStore},
 };
@@ -107,6 +107,104 @@ impl<C: AsClient> WriteBatch<C> for PropertyTypeRowBatch {
         Ok(())
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM property_types_tmp
+                        WHERE ontology_id IN (SELECT ontology_id FROM property_types);
+
+                    DELETE FROM property_type_constrains_values_on_tmp
+                        WHERE source_property_type_ontology_id IN (SELECT ontology_id FROM property_types);
+
+                    DELETE FROM property_type_constrains_properties_on_tmp
+                        WHERE source_property_type_ontology_id IN (SELECT ontology_id FROM property_types);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        on_conflict: SnapshotConflictBehavior,
+        _report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+        match on_conflict {
+            SnapshotConflictBehavior::Overwrite => {
+                // The references of the overwritten property types are replaced by the
+                // references in the snapshot, which are moved to the ontology ids in the store.
+                client
+                    .simple_query(
+                        r"
+                            UPDATE property_types
+                                SET schema = property_types_tmp.schema
+                                FROM property_types_tmp
+                                INNER JOIN ontology_merge_tmp
+                                    ON ontology_merge_tmp.snapshot_ontology_id = property_types_tmp.ontology_id
+                                WHERE property_types.ontology_id = ontology_merge_tmp.store_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+
+                            DELETE FROM property_type_constrains_values_on
+                                WHERE source_property_type_ontology_id IN (SELECT store_ontology_id FROM ontology_merge_tmp WHERE conflicting);
+
+                            DELETE FROM property_type_constrains_properties_on
+                                WHERE source_property_type_ontology_id IN (SELECT store_ontology_id FROM ontology_merge_tmp WHERE conflicting);
+
+                            DELETE FROM property_type_constrains_values_on_tmp
+                                WHERE source_property_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp WHERE NOT conflicting);
+
+                            DELETE FROM property_type_constrains_properties_on_tmp
+                                WHERE source_property_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp WHERE NOT conflicting);
+
+                            UPDATE property_type_constrains_values_on_tmp
+                                SET source_property_type_ontology_id = ontology_merge_tmp.store_ontology_id
+                                FROM ontology_merge_tmp
+                                WHERE property_type_constrains_values_on_tmp.source_property_type_ontology_id = ontology_merge_tmp.snapshot_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+
+                            UPDATE property_type_constrains_properties_on_tmp
+                                SET source_property_type_ontology_id = ontology_merge_tmp.store_ontology_id
+                                FROM ontology_merge_tmp
+                                WHERE property_type_constrains_properties_on_tmp.source_property_type_ontology_id = ontology_merge_tmp.snapshot_ontology_id
+                                  AND ontology_merge_tmp.conflicting;
+                        ",
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+            SnapshotConflictBehavior::Fail | SnapshotConflictBehavior::Skip => {
+                client
+                    .simple_query(
+                        r"
+                            DELETE FROM property_type_constrains_values_on_tmp
+                                WHERE source_property_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+
+                            DELETE FROM property_type_constrains_properties_on_tmp
+                                WHERE source_property_type_ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+                        ",
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+            }
+        }
+
+        client
+            .simple_query(
+                r"
+                    DELETE FROM property_types_tmp
+                        WHERE ontology_id IN (SELECT snapshot_ontology_id FROM ontology_merge_tmp);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         postgres_client
             .as_client()
@@ -118,20 +216,20 @@ impl<C: AsClient> WriteBatch<C> for PropertyTypeRowBatch {
                     INSERT INTO property_type_constrains_values_on
                         SELECT
                             source_property_type_ontology_id,
-                            ontology_ids_tmp.ontology_id AS target_data_type_ontology_id
+                            ontology_ids.ontology_id AS target_data_type_ontology_id
                         FROM property_type_constrains_values_on_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = property_type_constrains_values_on_tmp.target_data_type_base_url
-                            AND ontology_ids_tmp.version = property_type_constrains_values_on_tmp.target_data_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = property_type_constrains_values_on_tmp.target_data_type_base_url
+                            AND ontology_ids.version = property_type_constrains_values_on_tmp.target_data_type_version;
 
                     INSERT INTO property_type_constrains_properties_on
                         SELECT
                             source_property_type_ontology_id,
-                            ontology_ids_tmp.ontology_id AS target_property_type_ontology_id
+                            ontology_ids.ontology_id AS target_property_type_ontology_id
                         FROM property_type_constrains_properties_on_tmp
-                        INNER JOIN ontology_ids_tmp ON
-                            ontology_ids_tmp.base_url = property_type_constrains_properties_on_tmp.target_property_type_base_url
-                            AND ontology_ids_tmp.version = property_type_constrains_properties_on_tmp.target_property_type_version;
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = property_type_constrains_properties_on_tmp.target_property_type_base_url
+                            AND ontology_ids.version = property_type_constrains_properties_on_tmp.target_property_type_version;
                 ",
             )
             .await
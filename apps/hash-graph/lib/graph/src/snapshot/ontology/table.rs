@@ -1,6 +1,6 @@
 use graph_types::{
     ontology::OntologyTypeVersion,
-    provenance::{OwnedById, RecordArchivedById, RecordCreatedById},
+    provenance::{DeprecatedById, OwnedById, RecordArchivedById, RecordCreatedById},
 };
 use postgres_types::{Json, ToSql};
 use temporal_versioning::{LeftClosedTemporalInterval, TransactionTime};
@@ -39,6 +39,16 @@ pub struct OntologyTemporalMetadataRow {
     pub record_archived_by_id: Option<RecordArchivedById>,
 }
 
+#[derive(Debug, ToSql)]
+#[postgres(name = "ontology_deprecations")]
+pub struct OntologyDeprecationRow {
+    pub ontology_id: Uuid,
+    pub deprecated_by_id: DeprecatedById,
+    pub deprecated_at: OffsetDateTime,
+    pub successor_base_url: Option<String>,
+    pub successor_version: Option<OntologyTypeVersion>,
+}
+
 #[derive(Debug, ToSql)]
 #[postgres(name = "data_types")]
 pub struct DataTypeRow {
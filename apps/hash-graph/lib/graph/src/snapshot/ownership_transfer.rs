@@ -0,0 +1,9 @@
+mod batch;
+mod channel;
+mod table;
+
+pub use self::{
+    batch::OwnershipTransferRowBatch,
+    channel::{channel, OwnershipTransferReceiver, OwnershipTransferSender},
+    table::{EntityOwnershipTransferRow, OntologyOwnershipTransferRow, OwnershipTransferRow},
+};
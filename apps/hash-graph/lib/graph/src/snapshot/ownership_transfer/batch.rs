@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use error_stack::{Result, ResultExt};
+use tokio_postgres::GenericClient;
+
+use crate::{
+    snapshot::{
+        ownership_transfer::{
+            EntityOwnershipTransferRow, OntologyOwnershipTransferRow, OwnershipTransferRow,
+        },
+        SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
+    },
+    store::{AsClient, InsertionError, PostgresStore},
+};
+
+pub enum OwnershipTransferRowBatch {
+    Transfers(Vec<OwnershipTransferRow>),
+    OntologyTypes(Vec<OntologyOwnershipTransferRow>),
+    Entities(Vec<EntityOwnershipTransferRow>),
+}
+
+#[async_trait]
+impl<C: AsClient> WriteBatch<C> for OwnershipTransferRowBatch {
+    async fn begin(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    CREATE TEMPORARY TABLE ownership_transfers_tmp
+                        (LIKE ownership_transfers INCLUDING ALL)
+                        ON COMMIT DROP;
+
+                    CREATE TEMPORARY TABLE ontology_ownership_transfers_tmp (
+                        transfer_id UUID NOT NULL,
+                        base_url TEXT NOT NULL,
+                        version INT8 NOT NULL,
+                        PRIMARY KEY (transfer_id, base_url, version)
+                    ) ON COMMIT DROP;
+
+                    CREATE TEMPORARY TABLE entity_ownership_transfers_tmp
+                        (LIKE entity_ownership_transfers INCLUDING ALL)
+                        ON COMMIT DROP;
+                ",
+            )
+            .await
+            .change_context(InsertionError)
+            .attach_printable("could not create temporary tables")?;
+        Ok(())
+    }
+
+    async fn write(&self, postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+        match self {
+            Self::Transfers(transfers) => {
+                let rows = client
+                    .query(
+                        r"
+                            INSERT INTO ownership_transfers_tmp
+                            SELECT DISTINCT * FROM UNNEST($1::ownership_transfers[])
+                            ON CONFLICT DO NOTHING
+                            RETURNING 1;
+                        ",
+                        &[transfers],
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+                if !rows.is_empty() {
+                    tracing::info!("Read {} ownership transfers", rows.len());
+                }
+            }
+            Self::OntologyTypes(ontology_types) => {
+                let rows = client
+                    .query(
+                        r"
+                            INSERT INTO ontology_ownership_transfers_tmp
+                            SELECT DISTINCT * FROM UNNEST($1::ontology_ownership_transfers_tmp[])
+                            ON CONFLICT DO NOTHING
+                            RETURNING 1;
+                        ",
+                        &[ontology_types],
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+                if !rows.is_empty() {
+                    tracing::info!("Read {} transferred ontology types", rows.len());
+                }
+            }
+            Self::Entities(entities) => {
+                let rows = client
+                    .query(
+                        r"
+                            INSERT INTO entity_ownership_transfers_tmp
+                            SELECT DISTINCT * FROM UNNEST($1::entity_ownership_transfers[])
+                            ON CONFLICT DO NOTHING
+                            RETURNING 1;
+                        ",
+                        &[entities],
+                    )
+                    .await
+                    .change_context(InsertionError)?;
+                if !rows.is_empty() {
+                    tracing::info!("Read {} transferred entities", rows.len());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    DELETE FROM ontology_ownership_transfers_tmp
+                        WHERE transfer_id IN (SELECT transfer_id FROM ownership_transfers);
+
+                    DELETE FROM entity_ownership_transfers_tmp
+                        WHERE transfer_id IN (SELECT transfer_id FROM ownership_transfers);
+
+                    DELETE FROM ownership_transfers_tmp
+                        WHERE transfer_id IN (SELECT transfer_id FROM ownership_transfers);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        _on_conflict: SnapshotConflictBehavior,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let client = postgres_client.as_client().client();
+
+        // A transfer is never changed after it was recorded, so a transfer already present in
+        // the store is skipped together with the ontology types and entities it moved.
+        client
+            .simple_query(
+                r"
+                    DELETE FROM ontology_ownership_transfers_tmp
+                        WHERE transfer_id IN (SELECT transfer_id FROM ownership_transfers);
+
+                    DELETE FROM entity_ownership_transfers_tmp
+                        WHERE transfer_id IN (SELECT transfer_id FROM ownership_transfers);
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        let skipped = client
+            .query(
+                r"
+                    DELETE FROM ownership_transfers_tmp
+                        WHERE transfer_id IN (SELECT transfer_id FROM ownership_transfers)
+                        RETURNING 1;
+                ",
+                &[],
+            )
+            .await
+            .change_context(InsertionError)?;
+        report.ownership_transfers.skipped += skipped.len();
+        Ok(())
+    }
+
+    async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        postgres_client
+            .as_client()
+            .client()
+            .simple_query(
+                r"
+                    INSERT INTO ownership_transfers SELECT * FROM ownership_transfers_tmp;
+
+                    INSERT INTO ontology_ownership_transfers
+                        SELECT
+                            ontology_ownership_transfers_tmp.transfer_id,
+                            ontology_ids.ontology_id
+                        FROM ontology_ownership_transfers_tmp
+                        INNER JOIN ontology_ids ON
+                            ontology_ids.base_url = ontology_ownership_transfers_tmp.base_url
+                            AND ontology_ids.version = ontology_ownership_transfers_tmp.version;
+
+                    INSERT INTO entity_ownership_transfers
+                        SELECT * FROM entity_ownership_transfers_tmp;
+                ",
+            )
+            .await
+            .change_context(InsertionError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use error_stack::{Report, ResultExt};
+use futures::{
+    channel::mpsc::{self, Sender},
+    stream::{select_all, BoxStream, SelectAll},
+    Sink, SinkExt, Stream, StreamExt,
+};
+use graph_types::ontology::OntologyTypeVersion;
+
+use crate::{
+    snapshot::{
+        account::AccountSender,
+        ownership_transfer::{
+            EntityOwnershipTransferRow, OntologyOwnershipTransferRow, OwnershipTransferRow,
+            OwnershipTransferRowBatch,
+        },
+        SnapshotRestoreError,
+    },
+    store::OwnershipTransferRecord,
+};
+
+/// A sink to insert [`OwnershipTransferRecord`]s.
+///
+/// An `OwnershipTransferSender` with the corresponding [`OwnershipTransferReceiver`] are created
+/// using the [`channel`] function.
+#[derive(Debug, Clone)]
+pub struct OwnershipTransferSender {
+    // A transfer refers to three accounts, so a separate account sender is used for each of them
+    // as every sender only has capacity for one account after being polled.
+    from_account: AccountSender,
+    to_account: AccountSender,
+    transferred_by_account: AccountSender,
+    transfer: Sender<OwnershipTransferRow>,
+    ontology_types: Sender<Vec<OntologyOwnershipTransferRow>>,
+    entities: Sender<Vec<EntityOwnershipTransferRow>>,
+}
+
+// This is a direct wrapper around several `Sink<mpsc::Sender>` and `AccountSender`s with
+// error-handling added to make it easier to use. It's taking an `OwnershipTransferRecord` and
+// sending the individual rows to the corresponding sinks.
+impl Sink<OwnershipTransferRecord> for OwnershipTransferSender {
+    type Error = Report<SnapshotRestoreError>;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.from_account.poll_ready_unpin(cx))
+            .attach_printable("could not poll account sender")?;
+        ready!(self.to_account.poll_ready_unpin(cx))
+            .attach_printable("could not poll account sender")?;
+        ready!(self.transferred_by_account.poll_ready_unpin(cx))
+            .attach_printable("could not poll account sender")?;
+        ready!(self.transfer.poll_ready_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not poll ownership transfer sender")?;
+        ready!(self.ontology_types.poll_ready_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not poll transferred ontology types sender")?;
+        ready!(self.entities.poll_ready_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not poll transferred entities sender")?;
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        transfer: OwnershipTransferRecord,
+    ) -> Result<(), Self::Error> {
+        self.from_account
+            .start_send_unpin(transfer.from_owned_by_id.as_account_id())
+            .attach_printable("could not send account")?;
+        self.to_account
+            .start_send_unpin(transfer.to_owned_by_id.as_account_id())
+            .attach_printable("could not send account")?;
+        self.transferred_by_account
+            .start_send_unpin(transfer.transferred_by_id.as_account_id())
+            .attach_printable("could not send account")?;
+
+        self.transfer
+            .start_send_unpin(OwnershipTransferRow {
+                transfer_id: transfer.transfer_id,
+                from_owned_by_id: transfer.from_owned_by_id,
+                to_owned_by_id: transfer.to_owned_by_id,
+                transferred_by_id: transfer.transferred_by_id,
+                transferred_at: transfer.transferred_at,
+            })
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not send ownership transfer")?;
+
+        let ontology_types: Vec<_> = transfer
+            .ontology_type_ids
+            .into_iter()
+            .map(|url| OntologyOwnershipTransferRow {
+                transfer_id: transfer.transfer_id,
+                base_url: url.base_url.as_str().to_owned(),
+                version: OntologyTypeVersion::new(url.version),
+            })
+            .collect();
+        if !ontology_types.is_empty() {
+            self.ontology_types
+                .start_send_unpin(ontology_types)
+                .change_context(SnapshotRestoreError::Read)
+                .attach_printable("could not send transferred ontology types")?;
+        }
+
+        let entities: Vec<_> = transfer
+            .entity_uuids
+            .into_iter()
+            .map(|entity_uuid| EntityOwnershipTransferRow {
+                transfer_id: transfer.transfer_id,
+                entity_uuid,
+            })
+            .collect();
+        if !entities.is_empty() {
+            self.entities
+                .start_send_unpin(entities)
+                .change_context(SnapshotRestoreError::Read)
+                .attach_printable("could not send transferred entities")?;
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.from_account.poll_flush_unpin(cx))
+            .attach_printable("could not flush account sender")?;
+        ready!(self.to_account.poll_flush_unpin(cx))
+            .attach_printable("could not flush account sender")?;
+        ready!(self.transferred_by_account.poll_flush_unpin(cx))
+            .attach_printable("could not flush account sender")?;
+        ready!(self.transfer.poll_flush_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not flush ownership transfer sender")?;
+        ready!(self.ontology_types.poll_flush_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not flush transferred ontology types sender")?;
+        ready!(self.entities.poll_flush_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not flush transferred entities sender")?;
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        ready!(self.from_account.poll_close_unpin(cx))
+            .attach_printable("could not close account sender")?;
+        ready!(self.to_account.poll_close_unpin(cx))
+            .attach_printable("could not close account sender")?;
+        ready!(self.transferred_by_account.poll_close_unpin(cx))
+            .attach_printable("could not close account sender")?;
+        ready!(self.transfer.poll_close_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not close ownership transfer sender")?;
+        ready!(self.ontology_types.poll_close_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not close transferred ontology types sender")?;
+        ready!(self.entities.poll_close_unpin(cx))
+            .change_context(SnapshotRestoreError::Read)
+            .attach_printable("could not close transferred entities sender")?;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A stream to emit [`OwnershipTransferRowBatch`]es.
+///
+/// An [`OwnershipTransferSender`] with the corresponding `OwnershipTransferReceiver` are created
+/// using the [`channel`] function.
+pub struct OwnershipTransferReceiver {
+    stream: SelectAll<BoxStream<'static, OwnershipTransferRowBatch>>,
+}
+
+// This is a direct wrapper around the underlying stream, batches the row in chunks, and unifies
+// the `Item` into a single enumeration.
+impl Stream for OwnershipTransferReceiver {
+    type Item = OwnershipTransferRowBatch;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
+
+/// Creates a new [`OwnershipTransferSender`] and [`OwnershipTransferReceiver`] pair.
+///
+/// The `chunk_size` parameter determines the number of rows that are sent in a single
+/// [`OwnershipTransferRowBatch`].
+pub fn channel(
+    chunk_size: usize,
+    account_sender: AccountSender,
+) -> (OwnershipTransferSender, OwnershipTransferReceiver) {
+    let (transfer_tx, transfer_rx) = mpsc::channel(chunk_size);
+    let (ontology_types_tx, ontology_types_rx) = mpsc::channel(chunk_size);
+    let (entities_tx, entities_rx) = mpsc::channel(chunk_size);
+
+    (
+        OwnershipTransferSender {
+            from_account: account_sender.clone(),
+            to_account: account_sender.clone(),
+            transferred_by_account: account_sender,
+            transfer: transfer_tx,
+            ontology_types: ontology_types_tx,
+            entities: entities_tx,
+        },
+        OwnershipTransferReceiver {
+            stream: select_all([
+                transfer_rx
+                    .ready_chunks(chunk_size)
+                    .map(OwnershipTransferRowBatch::Transfers)
+                    .boxed(),
+                ontology_types_rx
+                    .ready_chunks(chunk_size)
+                    .map(|values| {
+                        OwnershipTransferRowBatch::OntologyTypes(
+                            values.into_iter().flatten().collect(),
+                        )
+                    })
+                    .boxed(),
+                entities_rx
+                    .ready_chunks(chunk_size)
+                    .map(|values| {
+                        OwnershipTransferRowBatch::Entities(values.into_iter().flatten().collect())
+                    })
+                    .boxed(),
+            ]),
+        },
+    )
+}
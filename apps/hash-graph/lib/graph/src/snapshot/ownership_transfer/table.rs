@@ -0,0 +1,33 @@
+use graph_types::{
+    knowledge::entity::EntityUuid,
+    ontology::OntologyTypeVersion,
+    provenance::{OwnedById, TransferredById},
+};
+use postgres_types::ToSql;
+use temporal_versioning::{Timestamp, TransactionTime};
+use uuid::Uuid;
+
+#[derive(Debug, ToSql)]
+#[postgres(name = "ownership_transfers")]
+pub struct OwnershipTransferRow {
+    pub transfer_id: Uuid,
+    pub from_owned_by_id: OwnedById,
+    pub to_owned_by_id: OwnedById,
+    pub transferred_by_id: TransferredById,
+    pub transferred_at: Timestamp<TransactionTime>,
+}
+
+#[derive(Debug, ToSql)]
+#[postgres(name = "ontology_ownership_transfers_tmp")]
+pub struct OntologyOwnershipTransferRow {
+    pub transfer_id: Uuid,
+    pub base_url: String,
+    pub version: OntologyTypeVersion,
+}
+
+#[derive(Debug, ToSql)]
+#[postgres(name = "entity_ownership_transfers")]
+pub struct EntityOwnershipTransferRow {
+    pub transfer_id: Uuid,
+    pub entity_uuid: EntityUuid,
+}
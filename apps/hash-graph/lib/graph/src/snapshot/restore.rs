@@ -1,7 +1,19 @@
 mod batch;
 mod channel;
+mod checkpoint;
+mod mode;
+mod progress;
+mod report;
+mod settings;
 
 pub use self::{
     batch::SnapshotRecordBatch,
     channel::{channel, SnapshotRecordReceiver, SnapshotRecordSender},
+    checkpoint::{
+        drop_staging, lock_staging, prepare_staging, unlock_staging, update_checkpoint, use_staging,
+    },
+    mode::{SnapshotConflictBehavior, SnapshotRestoreMode},
+    progress::SnapshotRestoreProgress,
+    report::{SnapshotRestoreCounts, SnapshotRestoreReport},
+    settings::SnapshotRestoreSettings,
 };
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
-use error_stack::Result;
+use error_stack::{Result, ResultExt};
+use tokio_postgres::GenericClient;
 
 use crate::{
     snapshot::{
@@ -9,7 +10,8 @@ use crate::{
             DataTypeRowBatch, EntityTypeRowBatch, OntologyTypeMetadataRowBatch,
             PropertyTypeRowBatch,
         },
-        WriteBatch,
+        ownership_transfer::OwnershipTransferRowBatch,
+        SnapshotConflictBehavior, SnapshotRestoreReport, WriteBatch,
     },
     store::{AsClient, InsertionError, PostgresStore},
 };
@@ -21,6 +23,44 @@ pub enum SnapshotRecordBatch {
     PropertyTypes(PropertyTypeRowBatch),
     EntityTypes(EntityTypeRowBatch),
     Entities(EntityRowBatch),
+    OwnershipTransfers(OwnershipTransferRowBatch),
+}
+
+impl SnapshotRecordBatch {
+    /// Adds the number of records, which are going to be inserted into the store, to the `report`.
+    pub async fn count_inserted<C: AsClient>(
+        postgres_client: &PostgresStore<C>,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        let row = postgres_client
+            .as_client()
+            .client()
+            .query_one(
+                r"
+                    SELECT
+                        (SELECT count(*) FROM accounts_tmp),
+                        (SELECT count(*) FROM ontology_ids_tmp),
+                        (SELECT count(*) FROM entity_editions_tmp),
+                        (SELECT count(*) FROM ownership_transfers_tmp);
+                ",
+                &[],
+            )
+            .await
+            .change_context(InsertionError)?;
+
+        let accounts: i64 = row.get(0);
+        let ontology_types: i64 = row.get(1);
+        let entity_editions: i64 = row.get(2);
+        let ownership_transfers: i64 = row.get(3);
+        report.accounts.inserted += usize::try_from(accounts).change_context(InsertionError)?;
+        report.ontology_types.inserted +=
+            usize::try_from(ontology_types).change_context(InsertionError)?;
+        report.entity_editions.inserted +=
+            usize::try_from(entity_editions).change_context(InsertionError)?;
+        report.ownership_transfers.inserted +=
+            usize::try_from(ownership_transfers).change_context(InsertionError)?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -32,6 +72,7 @@ impl<C: AsClient> WriteBatch<C> for SnapshotRecordBatch {
         PropertyTypeRowBatch::begin(postgres_client).await?;
         EntityTypeRowBatch::begin(postgres_client).await?;
         EntityRowBatch::begin(postgres_client).await?;
+        OwnershipTransferRowBatch::begin(postgres_client).await?;
         Ok(())
     }
 
@@ -43,9 +84,38 @@ impl<C: AsClient> WriteBatch<C> for SnapshotRecordBatch {
             Self::PropertyTypes(property) => property.write(postgres_client).await,
             Self::EntityTypes(entity_type) => entity_type.write(postgres_client).await,
             Self::Entities(entity) => entity.write(postgres_client).await,
+            Self::OwnershipTransfers(transfer) => transfer.write(postgres_client).await,
         }
     }
 
+    async fn prepare_incremental(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
+        AccountRowBatch::prepare_incremental(postgres_client).await?;
+        OntologyTypeMetadataRowBatch::prepare_incremental(postgres_client).await?;
+        DataTypeRowBatch::prepare_incremental(postgres_client).await?;
+        PropertyTypeRowBatch::prepare_incremental(postgres_client).await?;
+        EntityTypeRowBatch::prepare_incremental(postgres_client).await?;
+        EntityRowBatch::prepare_incremental(postgres_client).await?;
+        OwnershipTransferRowBatch::prepare_incremental(postgres_client).await?;
+        Ok(())
+    }
+
+    async fn prepare_merge(
+        postgres_client: &PostgresStore<C>,
+        on_conflict: SnapshotConflictBehavior,
+        report: &mut SnapshotRestoreReport,
+    ) -> Result<(), InsertionError> {
+        AccountRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        // The ontology metadata has to be prepared first as it maps the ontology types of the
+        // snapshot to the ontology types in the store.
+        OntologyTypeMetadataRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        DataTypeRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        PropertyTypeRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        EntityTypeRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        EntityRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        OwnershipTransferRowBatch::prepare_merge(postgres_client, on_conflict, report).await?;
+        Ok(())
+    }
+
     async fn commit(postgres_client: &PostgresStore<C>) -> Result<(), InsertionError> {
         AccountRowBatch::commit(postgres_client).await?;
         OntologyTypeMetadataRowBatch::commit(postgres_client).await?;
@@ -53,6 +123,7 @@ impl<C: AsClient> WriteBatch<C> for SnapshotRecordBatch {
         PropertyTypeRowBatch::commit(postgres_client).await?;
         EntityTypeRowBatch::commit(postgres_client).await?;
         EntityRowBatch::commit(postgres_client).await?;
+        OwnershipTransferRowBatch::commit(postgres_client).await?;
         Ok(())
     }
 }
@@ -15,6 +15,7 @@ use crate::snapshot::{
     account,
     entity::{self, EntitySender},
     ontology::{self, DataTypeSender, EntityTypeSender, PropertyTypeSender},
+    ownership_transfer::{self, OwnershipTransferSender},
     restore::batch::SnapshotRecordBatch,
     SnapshotEntry, SnapshotMetadata, SnapshotRestoreError,
 };
@@ -26,6 +27,7 @@ pub struct SnapshotRecordSender {
     property_type: PropertyTypeSender,
     entity_type: EntityTypeSender,
     entity: EntitySender,
+    ownership_transfer: OwnershipTransferSender,
 }
 
 impl Sink<SnapshotEntry> for SnapshotRecordSender {
@@ -46,6 +48,8 @@ impl Sink<SnapshotEntry> for SnapshotRecordSender {
             .attach_printable("could not poll entity type sender")?;
         ready!(self.entity.poll_ready_unpin(cx))
             .attach_printable("could not poll entity sender")?;
+        ready!(self.ownership_transfer.poll_ready_unpin(cx))
+            .attach_printable("could not poll ownership transfer sender")?;
 
         Poll::Ready(Ok(()))
     }
@@ -73,6 +77,12 @@ impl Sink<SnapshotEntry> for SnapshotRecordSender {
                 .entity
                 .start_send_unpin(entity)
                 .attach_printable("could not send entity"),
+            SnapshotEntry::OwnershipTransfer(transfer) => self
+                .ownership_transfer
+                .start_send_unpin(transfer)
+                .attach_printable("could not send ownership transfer"),
+            // The manifest is verified while reading the snapshot
+            SnapshotEntry::Manifest(_) => Ok(()),
         }
     }
 
@@ -91,6 +101,8 @@ impl Sink<SnapshotEntry> for SnapshotRecordSender {
             .attach_printable("could not flush entity type sender")?;
         ready!(self.entity.poll_flush_unpin(cx))
             .attach_printable("could not flush entity sender")?;
+        ready!(self.ownership_transfer.poll_flush_unpin(cx))
+            .attach_printable("could not flush ownership transfer sender")?;
 
         Poll::Ready(Ok(()))
     }
@@ -110,6 +122,8 @@ impl Sink<SnapshotEntry> for SnapshotRecordSender {
             .attach_printable("could not close entity type sender")?;
         ready!(self.entity.poll_close_unpin(cx))
             .attach_printable("could not close entity sender")?;
+        ready!(self.ownership_transfer.poll_close_unpin(cx))
+            .attach_printable("could not close ownership transfer sender")?;
 
         Poll::Ready(Ok(()))
     }
@@ -144,7 +158,9 @@ pub fn channel(
         ontology::property_type_channel(chunk_size, ontology_metadata_tx.clone());
     let (entity_type_tx, entity_type_rx) =
         ontology::entity_type_channel(chunk_size, ontology_metadata_tx);
-    let (entity_tx, entity_rx) = entity::channel(chunk_size, account_tx);
+    let (entity_tx, entity_rx) = entity::channel(chunk_size, account_tx.clone());
+    let (ownership_transfer_tx, ownership_transfer_rx) =
+        ownership_transfer::channel(chunk_size, account_tx);
 
     (
         SnapshotRecordSender {
@@ -153,6 +169,7 @@ pub fn channel(
             property_type: property_type_tx,
             entity_type: entity_type_tx,
             entity: entity_tx,
+            ownership_transfer: ownership_transfer_tx,
         },
         SnapshotRecordReceiver {
             stream: select_all(vec![
@@ -166,6 +183,9 @@ pub fn channel(
                     .boxed(),
                 entity_type_rx.map(SnapshotRecordBatch::EntityTypes).boxed(),
                 entity_rx.map(SnapshotRecordBatch::Entities).boxed(),
+                ownership_transfer_rx
+                    .map(SnapshotRecordBatch::OwnershipTransfers)
+                    .boxed(),
             ]),
         },
         metadata_rx,
@@ -0,0 +1,219 @@
+use error_stack::{Report, Result, ResultExt};
+use tokio_postgres::GenericClient;
+
+use crate::{
+    snapshot::{restore::SnapshotRecordBatch, SnapshotRestoreError, WriteBatch},
+    store::{AsClient, PostgresStore},
+};
+
+/// Creates the staging tables for a resumable restore if they do not exist yet.
+///
+/// The staging tables are created from the temporary tables of the `begin` stage, but are stored
+/// in the `snapshot_restore` schema, so they survive the connection. As the temporary tables are
+/// dropped on commit, both are created inside of the same transaction. The number of entries
+/// processed up to the last checkpoint is stored alongside.
+///
+/// Returns the number of entries processed by an interrupted restore, which is `0` if a new
+/// restore is started. The staging tables of an interrupted restore are only discarded if
+/// `discard_checkpoint` is set.
+///
+/// # Errors
+///
+/// - [`InterruptedRestore`], if the staging tables of an interrupted restore exist, but neither
+///   `resume` nor `discard_checkpoint` is set
+///
+/// [`InterruptedRestore`]: SnapshotRestoreError::InterruptedRestore
+pub async fn prepare_staging<C: AsClient>(
+    client: &mut PostgresStore<C>,
+    resume: bool,
+    discard_checkpoint: bool,
+) -> Result<usize, SnapshotRestoreError> {
+    let mut exists = client
+        .as_client()
+        .client()
+        .query_one(
+            "SELECT to_regclass('snapshot_restore.restore_checkpoint') IS NOT NULL;",
+            &[],
+        )
+        .await
+        .change_context(SnapshotRestoreError::Write)?
+        .get(0);
+    if exists && !resume {
+        if !discard_checkpoint {
+            return Err(Report::new(SnapshotRestoreError::InterruptedRestore)
+                .attach_printable("the staging tables in the `snapshot_restore` schema exist"));
+        }
+
+        tracing::info!("discarding the checkpoint of the interrupted snapshot restore");
+        client
+            .as_client()
+            .client()
+            .simple_query("DROP SCHEMA snapshot_restore CASCADE;")
+            .await
+            .change_context(SnapshotRestoreError::Write)?;
+        exists = false;
+    }
+
+    if exists {
+        let entries: i64 = client
+            .as_client()
+            .client()
+            .query_one(
+                "SELECT entries FROM snapshot_restore.restore_checkpoint;",
+                &[],
+            )
+            .await
+            .change_context(SnapshotRestoreError::Write)?
+            .get(0);
+        return usize::try_from(entries).change_context(SnapshotRestoreError::Write);
+    }
+    if resume {
+        tracing::info!("no interrupted snapshot restore found, restoring from the start");
+    }
+
+    let transaction = client
+        .transaction()
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+
+    SnapshotRecordBatch::begin(&transaction)
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+
+    transaction
+        .as_client()
+        .client()
+        .simple_query(
+            r"
+                CREATE SCHEMA snapshot_restore;
+
+                DO $$
+                DECLARE
+                    staging_table TEXT;
+                BEGIN
+                    FOR staging_table IN
+                        SELECT relname FROM pg_class
+                        WHERE relnamespace = pg_my_temp_schema()
+                          AND relkind = 'r'
+                          AND relname LIKE '%\_tmp'
+                    LOOP
+                        EXECUTE format(
+                            'CREATE UNLOGGED TABLE snapshot_restore.%I (LIKE pg_temp.%I INCLUDING ALL)',
+                            staging_table,
+                            staging_table
+                        );
+                    END LOOP;
+                END
+                $$;
+
+                CREATE TABLE snapshot_restore.restore_checkpoint (entries BIGINT NOT NULL);
+                INSERT INTO snapshot_restore.restore_checkpoint VALUES (0);
+            ",
+        )
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+
+    transaction
+        .commit()
+        .await
+        .change_context(SnapshotRestoreError::Write)
+        .attach_printable("unable to create the staging tables")?;
+
+    Ok(0)
+}
+
+/// Takes the session-level advisory lock guarding the staging tables.
+///
+/// The lock has to be released by [`unlock_staging`] on the same connection.
+///
+/// # Errors
+///
+/// - [`Locked`], if another restore holds the lock
+///
+/// [`Locked`]: SnapshotRestoreError::Locked
+pub async fn lock_staging<C: AsClient>(
+    client: &PostgresStore<C>,
+) -> Result<(), SnapshotRestoreError> {
+    let locked: bool = client
+        .as_client()
+        .client()
+        .query_one(
+            "SELECT pg_try_advisory_lock(hashtext('snapshot_restore'));",
+            &[],
+        )
+        .await
+        .change_context(SnapshotRestoreError::Write)?
+        .get(0);
+    if locked {
+        Ok(())
+    } else {
+        Err(Report::new(SnapshotRestoreError::Locked))
+    }
+}
+
+/// Releases the lock taken by [`lock_staging`].
+pub async fn unlock_staging<C: AsClient>(
+    client: &PostgresStore<C>,
+) -> Result<(), SnapshotRestoreError> {
+    client
+        .as_client()
+        .client()
+        .query_one(
+            "SELECT pg_advisory_unlock(hashtext('snapshot_restore'));",
+            &[],
+        )
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+    Ok(())
+}
+
+/// Resolves the staging tables instead of the temporary tables for the current transaction.
+pub async fn use_staging<C: AsClient>(
+    client: &PostgresStore<C>,
+) -> Result<(), SnapshotRestoreError> {
+    client
+        .as_client()
+        .client()
+        .simple_query(
+            r"
+                SELECT set_config(
+                    'search_path',
+                    'snapshot_restore, ' || current_setting('search_path') || ', pg_temp',
+                    true
+                );
+            ",
+        )
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+    Ok(())
+}
+
+/// Records that the first `entries` entries of the snapshot were written into the staging tables.
+pub async fn update_checkpoint<C: AsClient>(
+    client: &PostgresStore<C>,
+    entries: usize,
+) -> Result<(), SnapshotRestoreError> {
+    client
+        .as_client()
+        .client()
+        .execute(
+            "UPDATE snapshot_restore.restore_checkpoint SET entries = $1;",
+            &[&i64::try_from(entries).change_context(SnapshotRestoreError::Write)?],
+        )
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+    Ok(())
+}
+
+/// Drops the staging tables after their records were moved into the store.
+pub async fn drop_staging<C: AsClient>(
+    client: &PostgresStore<C>,
+) -> Result<(), SnapshotRestoreError> {
+    client
+        .as_client()
+        .client()
+        .simple_query("DROP SCHEMA snapshot_restore CASCADE;")
+        .await
+        .change_context(SnapshotRestoreError::Write)?;
+    Ok(())
+}
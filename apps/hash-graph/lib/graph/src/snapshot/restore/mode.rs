@@ -0,0 +1,42 @@
+use crate::store::ConflictBehavior;
+
+/// Specifies how a snapshot is restored into the store.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SnapshotRestoreMode {
+    /// Restores a full snapshot into an empty store.
+    #[default]
+    Empty,
+    /// Restores an incremental snapshot on top of the snapshot it was dumped since.
+    Incremental,
+    /// Merges a full snapshot into a store, which already contains records.
+    ///
+    /// Records already present in the store are skipped. Records, which are present in the store
+    /// but differ from the snapshot, are handled as specified by the conflict behavior.
+    Merge(SnapshotConflictBehavior),
+}
+
+/// Specifies how records of a snapshot are handled, which conflict with records in the store.
+///
+/// An ontology type conflicts if the store contains a different schema for the same versioned
+/// URL. An entity edition conflicts if the store contains the edition with different properties
+/// or if it overlaps with an edition of the same entity in the store.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum SnapshotConflictBehavior {
+    /// Aborts the restore if any conflict is detected.
+    #[default]
+    Fail,
+    /// Keeps the records in the store and skips the conflicting records of the snapshot.
+    Skip,
+    /// Replaces the records in the store with the conflicting records of the snapshot.
+    Overwrite,
+}
+
+impl From<ConflictBehavior> for SnapshotConflictBehavior {
+    fn from(conflict_behavior: ConflictBehavior) -> Self {
+        match conflict_behavior {
+            ConflictBehavior::Fail => Self::Fail,
+            ConflictBehavior::Skip => Self::Skip,
+        }
+    }
+}
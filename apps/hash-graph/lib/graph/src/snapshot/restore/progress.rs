@@ -0,0 +1,78 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::snapshot::{SnapshotEntry, SnapshotRecordCounts};
+
+/// Tracks how many records of a snapshot were read while restoring it.
+#[derive(Debug, Clone)]
+pub struct SnapshotRestoreProgress {
+    records: SnapshotRecordCounts,
+    started_at: Instant,
+    reported_at: Instant,
+}
+
+impl Default for SnapshotRestoreProgress {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            records: SnapshotRecordCounts::default(),
+            started_at: now,
+            reported_at: now,
+        }
+    }
+}
+
+impl SnapshotRestoreProgress {
+    /// Counts the record contained in the `entry`.
+    pub fn add(&mut self, entry: &SnapshotEntry) {
+        self.records.add(entry);
+    }
+
+    /// Returns the number of records read so far.
+    #[must_use]
+    pub const fn records(&self) -> SnapshotRecordCounts {
+        self.records
+    }
+
+    /// Returns the average number of records read per second.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "the rate is only used for reporting"
+    )]
+    pub fn records_per_second(&self) -> f64 {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.records.total() as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns `true` if at least `interval` elapsed since the last time this returned `true`.
+    pub fn is_due(&mut self, interval: Duration) -> bool {
+        if self.reported_at.elapsed() < interval {
+            return false;
+        }
+        self.reported_at = Instant::now();
+        true
+    }
+}
+
+impl fmt::Display for SnapshotRestoreProgress {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{} data types, {} property types, {} entity types, {} entities, {} ownership \
+             transfers ({:.0} records/s)",
+            self.records.data_types,
+            self.records.property_types,
+            self.records.entity_types,
+            self.records.entities,
+            self.records.ownership_transfers,
+            self.records_per_second()
+        )
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// The number of records of one kind handled while restoring a snapshot.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SnapshotRestoreCounts {
+    /// The number of records inserted into the store.
+    pub inserted: usize,
+    /// The number of records skipped, because they are already present in the store.
+    pub skipped: usize,
+    /// The number of records conflicting with records in the store.
+    ///
+    /// Depending on the [`SnapshotConflictBehavior`], these records were either skipped or have
+    /// overwritten the records in the store.
+    ///
+    /// [`SnapshotConflictBehavior`]: crate::snapshot::SnapshotConflictBehavior
+    pub conflicting: usize,
+}
+
+impl fmt::Display for SnapshotRestoreCounts {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{} inserted, {} skipped, {} conflicting",
+            self.inserted, self.skipped, self.conflicting
+        )
+    }
+}
+
+/// Summarizes which records were written when restoring a snapshot.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SnapshotRestoreReport {
+    pub accounts: SnapshotRestoreCounts,
+    pub ontology_types: SnapshotRestoreCounts,
+    pub entity_editions: SnapshotRestoreCounts,
+    pub ownership_transfers: SnapshotRestoreCounts,
+}
+
+impl SnapshotRestoreReport {
+    /// Returns the number of records of any kind conflicting with records in the store.
+    #[must_use]
+    pub const fn conflicting(&self) -> usize {
+        self.accounts.conflicting
+            + self.ontology_types.conflicting
+            + self.entity_editions.conflicting
+            + self.ownership_transfers.conflicting
+    }
+}
+
+impl fmt::Display for SnapshotRestoreReport {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(fmt, "accounts: {}", self.accounts)?;
+        writeln!(fmt, "ontology types: {}", self.ontology_types)?;
+        writeln!(fmt, "entity editions: {}", self.entity_editions)?;
+        write!(fmt, "ownership transfers: {}", self.ownership_transfers)
+    }
+}
@@ -0,0 +1,39 @@
+use std::num::NonZeroUsize;
+
+use crate::snapshot::SnapshotRestoreMode;
+
+/// Specifies how a snapshot is restored into the store.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SnapshotRestoreSettings {
+    /// The number of records buffered for each record kind before they are written.
+    pub chunk_size: usize,
+    pub mode: SnapshotRestoreMode,
+    /// If specified, the restore commits a checkpoint after every `checkpoint_interval` entries.
+    ///
+    /// The records are written into staging tables, which outlive the connection, so an
+    /// interrupted restore can be resumed from the last checkpoint. The records are only moved
+    /// into the store after the whole snapshot was read.
+    pub checkpoint_interval: Option<NonZeroUsize>,
+    /// Continues an interrupted restore from its last checkpoint.
+    ///
+    /// The same snapshot has to be passed again, the entries up to the checkpoint are skipped. If
+    /// no interrupted restore exists, the snapshot is restored from the start.
+    pub resume: bool,
+    /// Discards the checkpoint of an interrupted restore and restores from the start.
+    ///
+    /// If neither this nor `resume` is set, the restore fails if an interrupted restore exists.
+    /// Has no effect if `resume` is set.
+    pub discard_checkpoint: bool,
+}
+
+impl Default for SnapshotRestoreSettings {
+    fn default() -> Self {
+        Self {
+            chunk_size: 10_000,
+            mode: SnapshotRestoreMode::default(),
+            checkpoint_interval: None,
+            resume: false,
+            discard_checkpoint: false,
+        }
+    }
+}
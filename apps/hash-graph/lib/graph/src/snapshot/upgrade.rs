@@ -0,0 +1,459 @@
+use error_stack::{Report, Result, ResultExt};
+use semver::Version;
+use serde_json::Value;
+
+use crate::snapshot::{
+    format::EncodedEntry, manifest::SnapshotContent, SnapshotEntry, SnapshotUpgradeError,
+};
+
+/// The version of the snapshot format written by [`SnapshotStore::dump_snapshot`].
+///
+/// The version has to be bumped whenever the shape of a [`SnapshotEntry`] changes. To keep older
+/// snapshots readable, an upgrade from the previous version has to be added at the same time.
+///
+/// [`SnapshotStore::dump_snapshot`]: crate::snapshot::SnapshotStore::dump_snapshot
+pub const SNAPSHOT_VERSION: Version = Version::new(0, 4, 0);
+
+/// Converts the entries of a snapshot from one snapshot version to the next one.
+#[derive(Debug)]
+struct SnapshotUpgrade {
+    from: Version,
+    to: Version,
+    /// Converts an entry in the shape of `from` into the shape of `to`.
+    ///
+    /// The entry is passed as it was decoded, so it can't be assumed to be valid. The version
+    /// in the snapshot metadata does not have to be updated by the upgrade.
+    upgrade_entry: fn(&mut Value) -> Result<(), SnapshotUpgradeError>,
+}
+
+/// The upgrades from older snapshot versions, ordered by the version they upgrade from.
+///
+/// The upgrades are chained, so a snapshot is upgraded to [`SNAPSHOT_VERSION`] by applying every
+/// upgrade starting at its version.
+const SNAPSHOT_UPGRADES: &[SnapshotUpgrade] = &[SnapshotUpgrade {
+    from: Version::new(0, 3, 0),
+    to: SNAPSHOT_VERSION,
+    upgrade_entry: add_ownership_transfer_count,
+}];
+
+/// Adds the number of ownership transfers to the record counts of the manifest.
+///
+/// Snapshots before version 0.4.0 don't contain ownership transfers.
+fn add_ownership_transfer_count(entry: &mut Value) -> Result<(), SnapshotUpgradeError> {
+    if entry["type"] == "manifest" {
+        entry
+            .get_mut("records")
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| Report::new(SnapshotUpgradeError::Upgrade))?
+            .insert("ownershipTransfers".to_owned(), Value::from(0));
+    }
+    Ok(())
+}
+
+/// Returns the upgrades out of `available` to apply to a snapshot of `version`.
+///
+/// Returns `None` if the snapshot can't be upgraded to [`SNAPSHOT_VERSION`].
+fn upgrades_from(
+    available: &'static [SnapshotUpgrade],
+    version: &Version,
+) -> Option<&'static [SnapshotUpgrade]> {
+    if *version == SNAPSHOT_VERSION {
+        return Some(&[]);
+    }
+
+    let start = available
+        .iter()
+        .position(|upgrade| upgrade.from == *version)?;
+    let upgrades = &available[start..];
+    let mut current = version;
+    for upgrade in upgrades {
+        if upgrade.from != *current {
+            return None;
+        }
+        current = &upgrade.to;
+    }
+
+    (*current == SNAPSHOT_VERSION).then_some(upgrades)
+}
+
+/// Converts the decoded entries of a snapshot into [`SnapshotEntry`]s of the current
+/// [`SNAPSHOT_VERSION`].
+///
+/// The version is read from the snapshot metadata, which is expected to be the first entry of the
+/// snapshot. The entries of older snapshots are decoded into intermediate JSON values and upgraded
+/// before they are deserialized, so changing the shape of an entry does not make older snapshots
+/// unreadable. The entries of snapshots of the current version are deserialized directly.
+///
+/// As upgrading changes the entries of a snapshot, the record counts and the checksum of the
+/// manifest are recalculated for upgraded snapshots. If the manifest didn't match the original
+/// snapshot, it is kept as is, so the mismatch can still be detected.
+#[derive(Debug)]
+pub(crate) struct SnapshotUpgrader {
+    /// The upgrades, which can be applied to a snapshot.
+    available: &'static [SnapshotUpgrade],
+    /// The version of the snapshot, once its metadata has been read.
+    version: Option<Version>,
+    upgrades: &'static [SnapshotUpgrade],
+    original: SnapshotContent,
+    upgraded: SnapshotContent,
+}
+
+impl Default for SnapshotUpgrader {
+    fn default() -> Self {
+        Self::with_upgrades(SNAPSHOT_UPGRADES)
+    }
+}
+
+impl SnapshotUpgrader {
+    fn with_upgrades(available: &'static [SnapshotUpgrade]) -> Self {
+        Self {
+            available,
+            version: None,
+            upgrades: &[],
+            original: SnapshotContent::default(),
+            upgraded: SnapshotContent::default(),
+        }
+    }
+
+    /// Converts the next decoded entry of the snapshot into a [`SnapshotEntry`].
+    ///
+    /// # Errors
+    ///
+    /// - [`MissingMetadata`], if the first entry is not the snapshot metadata
+    /// - [`UnsupportedVersion`], if the snapshot can't be upgraded to [`SNAPSHOT_VERSION`]
+    /// - [`Upgrade`], if upgrading the entry fails
+    /// - [`Deserialize`], if the upgraded entry is not a valid [`SnapshotEntry`]
+    ///
+    /// [`MissingMetadata`]: SnapshotUpgradeError::MissingMetadata
+    /// [`UnsupportedVersion`]: SnapshotUpgradeError::UnsupportedVersion
+    /// [`Upgrade`]: SnapshotUpgradeError::Upgrade
+    /// [`Deserialize`]: SnapshotUpgradeError::Deserialize
+    pub(crate) fn upgrade(
+        &mut self,
+        entry: &EncodedEntry,
+    ) -> Result<SnapshotEntry, SnapshotUpgradeError> {
+        let mut entry = if self.version.is_none() {
+            let metadata = entry
+                .deserialize::<Value>()
+                .change_context(SnapshotUpgradeError::Deserialize)?;
+            self.read_version(&metadata)?;
+            if self.upgrades.is_empty() {
+                return serde_json::from_value(metadata)
+                    .change_context(SnapshotUpgradeError::Deserialize);
+            }
+            metadata
+        } else if self.upgrades.is_empty() {
+            return entry
+                .deserialize()
+                .change_context(SnapshotUpgradeError::Deserialize);
+        } else {
+            entry
+                .deserialize::<Value>()
+                .change_context(SnapshotUpgradeError::Deserialize)?
+        };
+
+        let original = entry.clone();
+        for upgrade in self.upgrades {
+            (upgrade.upgrade_entry)(&mut entry).attach_printable_lazy(|| {
+                format!(
+                    "could not upgrade snapshot entry from version {} to {}",
+                    upgrade.from, upgrade.to
+                )
+            })?;
+        }
+        let mut entry =
+            serde_json::from_value(entry).change_context(SnapshotUpgradeError::Deserialize)?;
+
+        match &mut entry {
+            SnapshotEntry::Snapshot(metadata) => {
+                metadata.block_protocol_module_versions.graph = SNAPSHOT_VERSION;
+            }
+            SnapshotEntry::Manifest(manifest) => {
+                if manifest.records == self.original.records()
+                    && manifest.checksum == self.original.checksum()
+                {
+                    manifest.records = self.upgraded.records();
+                    manifest.checksum = self.upgraded.checksum();
+                }
+                return Ok(entry);
+            }
+            _ => {}
+        }
+
+        self.original.add_value(&entry, &original);
+        self.upgraded.add(&entry);
+        Ok(entry)
+    }
+
+    fn read_version(&mut self, entry: &Value) -> Result<(), SnapshotUpgradeError> {
+        if entry.get("type").and_then(Value::as_str) != Some("snapshot") {
+            return Err(Report::new(SnapshotUpgradeError::MissingMetadata));
+        }
+
+        let version = entry
+            .pointer("/blockProtocolModuleVersions/graph")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Report::new(SnapshotUpgradeError::MissingMetadata))
+            .attach_printable("the snapshot metadata does not contain the graph version")?
+            .parse::<Version>()
+            .map_err(|error| {
+                Report::new(SnapshotUpgradeError::UnsupportedVersion)
+                    .attach_printable(error.to_string())
+            })?;
+
+        self.upgrades = upgrades_from(self.available, &version)
+            .ok_or_else(|| Report::new(SnapshotUpgradeError::UnsupportedVersion))
+            .attach_printable_lazy(|| {
+                format!("snapshots of version {version} can't be upgraded to {SNAPSHOT_VERSION}")
+            })?;
+        if !self.upgrades.is_empty() {
+            tracing::info!(%version, "upgrading snapshot to version {SNAPSHOT_VERSION}");
+        }
+        self.version = Some(version);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::snapshot::{
+        BlockProtocolModuleVersions, CustomGlobalMetadata, SnapshotManifest, SnapshotMetadata,
+        SnapshotRecordCounts,
+    };
+
+    /// Renames the `manifest` flag of the snapshot metadata to `hasManifest`.
+    fn rename_manifest_flag(entry: &mut Value) -> Result<(), SnapshotUpgradeError> {
+        if entry["type"] == "snapshot" {
+            let custom = entry["custom"]
+                .as_object_mut()
+                .ok_or_else(|| Report::new(SnapshotUpgradeError::Upgrade))?;
+            if let Some(has_manifest) = custom.remove("manifest") {
+                custom.insert("hasManifest".to_owned(), has_manifest);
+            }
+        }
+        Ok(())
+    }
+
+    /// Renames the `recordCounts` of the manifest to `records`.
+    fn rename_record_counts(entry: &mut Value) -> Result<(), SnapshotUpgradeError> {
+        if entry["type"] == "manifest" {
+            let manifest = entry
+                .as_object_mut()
+                .ok_or_else(|| Report::new(SnapshotUpgradeError::Upgrade))?;
+            let records = manifest
+                .remove("recordCounts")
+                .ok_or_else(|| Report::new(SnapshotUpgradeError::Upgrade))?;
+            manifest.insert("records".to_owned(), records);
+        }
+        Ok(())
+    }
+
+    const TEST_UPGRADES: &[SnapshotUpgrade] = &[
+        SnapshotUpgrade {
+            from: Version::new(0, 1, 0),
+            to: Version::new(0, 2, 0),
+            upgrade_entry: rename_manifest_flag,
+        },
+        SnapshotUpgrade {
+            from: Version::new(0, 2, 0),
+            to: SNAPSHOT_VERSION,
+            upgrade_entry: rename_record_counts,
+        },
+    ];
+
+    fn json_entry(value: &Value) -> EncodedEntry {
+        EncodedEntry::Json {
+            line: value.to_string(),
+            line_number: 1,
+        }
+    }
+
+    fn cbor_entry(value: &Value) -> EncodedEntry {
+        let mut item = Vec::new();
+        ciborium::ser::into_writer(value, &mut item).expect("should be able to encode as CBOR");
+        EncodedEntry::Cbor {
+            item: item.into(),
+            item_number: 1,
+        }
+    }
+
+    fn metadata(version: &Version, custom: &Value) -> Value {
+        json!({
+            "type": "snapshot",
+            "blockProtocolModuleVersions": { "graph": version.to_string() },
+            "custom": custom,
+        })
+    }
+
+    fn current_metadata() -> SnapshotEntry {
+        SnapshotEntry::Snapshot(SnapshotMetadata {
+            block_protocol_module_versions: BlockProtocolModuleVersions {
+                graph: SNAPSHOT_VERSION,
+            },
+            custom: CustomGlobalMetadata {
+                has_manifest: true,
+                ..CustomGlobalMetadata::default()
+            },
+        })
+    }
+
+    /// Creates a manifest, which stores the record counts in the field `records`.
+    fn manifest(records: &str, checksum: &str) -> Value {
+        let mut manifest = json!({
+            "type": "manifest",
+            "checksum": checksum,
+            "database": "graph",
+            "dumpedAt": "2023-01-01T00:00:00Z",
+        });
+        manifest[records] = json!(SnapshotRecordCounts::default());
+        manifest
+    }
+
+    fn expect_manifest(entry: SnapshotEntry) -> SnapshotManifest {
+        match entry {
+            SnapshotEntry::Manifest(manifest) => manifest,
+            entry => panic!("expected a manifest, got {entry:?}"),
+        }
+    }
+
+    #[test]
+    fn current_version_is_deserialized_directly() {
+        let metadata = metadata(&SNAPSHOT_VERSION, &json!({ "hasManifest": true }));
+        let manifest = manifest("records", "00");
+
+        for encode in [json_entry, cbor_entry] {
+            let mut upgrader = SnapshotUpgrader::with_upgrades(TEST_UPGRADES);
+            assert_eq!(
+                upgrader
+                    .upgrade(&encode(&metadata))
+                    .expect("should be able to read the metadata"),
+                current_metadata()
+            );
+            assert_eq!(
+                expect_manifest(
+                    upgrader
+                        .upgrade(&encode(&manifest))
+                        .expect("should be able to read the manifest")
+                )
+                .checksum,
+                "00",
+                "the manifest of a current snapshot should not be changed"
+            );
+        }
+    }
+
+    #[test]
+    fn older_version_is_upgraded() {
+        let metadata = metadata(&Version::new(0, 1, 0), &json!({ "manifest": true }));
+
+        let mut original = SnapshotContent::default();
+        original.add_value(&current_metadata(), &metadata);
+        let mut upgraded = SnapshotContent::default();
+        upgraded.add(&current_metadata());
+
+        let mut upgrader = SnapshotUpgrader::with_upgrades(TEST_UPGRADES);
+        assert_eq!(
+            upgrader
+                .upgrade(&json_entry(&metadata))
+                .expect("should be able to upgrade the metadata"),
+            current_metadata()
+        );
+        let manifest = expect_manifest(
+            upgrader
+                .upgrade(&json_entry(&manifest("recordCounts", &original.checksum())))
+                .expect("should be able to upgrade the manifest"),
+        );
+        assert_eq!(manifest.records, upgraded.records());
+        assert_eq!(
+            manifest.checksum,
+            upgraded.checksum(),
+            "the checksum should be recalculated for the upgraded snapshot"
+        );
+        assert_ne!(manifest.checksum, original.checksum());
+    }
+
+    #[test]
+    fn ownership_transfer_count_is_added() {
+        let metadata = metadata(&Version::new(0, 3, 0), &json!({ "hasManifest": true }));
+        let mut manifest = manifest("records", "00");
+        manifest["records"]
+            .as_object_mut()
+            .expect("the record counts should be an object")
+            .remove("ownershipTransfers");
+
+        let mut upgrader = SnapshotUpgrader::default();
+        assert_eq!(
+            upgrader
+                .upgrade(&json_entry(&metadata))
+                .expect("should be able to upgrade the metadata"),
+            current_metadata()
+        );
+        let manifest = expect_manifest(
+            upgrader
+                .upgrade(&json_entry(&manifest))
+                .expect("should be able to upgrade the manifest"),
+        );
+        assert_eq!(manifest.records, SnapshotRecordCounts::default());
+    }
+
+    #[test]
+    fn mismatching_manifest_is_kept() {
+        let mut upgrader = SnapshotUpgrader::with_upgrades(TEST_UPGRADES);
+        upgrader
+            .upgrade(&json_entry(&metadata(
+                &Version::new(0, 2, 0),
+                &json!({ "hasManifest": true }),
+            )))
+            .expect("should be able to upgrade the metadata");
+        let manifest = expect_manifest(
+            upgrader
+                .upgrade(&json_entry(&manifest("recordCounts", "00")))
+                .expect("should be able to upgrade the manifest"),
+        );
+        assert_eq!(manifest.checksum, "00");
+    }
+
+    #[test]
+    fn failing_upgrade_is_reported() {
+        let mut upgrader = SnapshotUpgrader::with_upgrades(TEST_UPGRADES);
+        upgrader
+            .upgrade(&json_entry(&metadata(
+                &Version::new(0, 2, 0),
+                &json!({ "hasManifest": true }),
+            )))
+            .expect("should be able to upgrade the metadata");
+        let report = upgrader
+            .upgrade(&json_entry(&manifest("records", "00")))
+            .expect_err("the manifest should not be upgradable");
+        assert!(matches!(
+            report.current_context(),
+            SnapshotUpgradeError::Upgrade
+        ));
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let report = SnapshotUpgrader::with_upgrades(TEST_UPGRADES)
+            .upgrade(&json_entry(&metadata(&Version::new(0, 0, 1), &json!({}))))
+            .expect_err("the version should not be supported");
+        assert!(matches!(
+            report.current_context(),
+            SnapshotUpgradeError::UnsupportedVersion
+        ));
+    }
+
+    #[test]
+    fn missing_metadata_is_rejected() {
+        let report = SnapshotUpgrader::with_upgrades(TEST_UPGRADES)
+            .upgrade(&json_entry(&manifest("records", "00")))
+            .expect_err("the snapshot should start with its metadata");
+        assert!(matches!(
+            report.current_context(),
+            SnapshotUpgradeError::MissingMetadata
+        ));
+    }
+}
@@ -0,0 +1,808 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use graph_types::{
+    account::AccountId,
+    knowledge::entity::{EntityEditionId, EntityId, EntityRecordId, EntityTemporalMetadata},
+    ontology::{CustomOntologyMetadata, OntologyTypeDeprecation},
+    provenance::ProvenanceMetadata,
+};
+use serde::Serialize;
+use serde_json::Value;
+use temporal_versioning::{ClosedTemporalBound, LeftClosedTemporalInterval, OpenTemporalBound};
+use type_system::{url::VersionedUrl, DataType, EntityType, PropertyType};
+use uuid::Uuid;
+
+use crate::{
+    ontology::validation::OntologySchemas,
+    snapshot::{
+        entity::EntitySnapshotRecord, manifest::SnapshotContent, OntologyTypeSnapshotRecord,
+        SnapshotEntry, SnapshotManifest, SnapshotMetadata, SnapshotRecordCounts, SNAPSHOT_VERSION,
+    },
+    store::OwnershipTransferRecord,
+};
+
+/// Identifies the record in a snapshot, in which a [`SnapshotProblem`] was found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "id")]
+pub enum SnapshotRecordId {
+    Metadata,
+    Manifest,
+    DataType(VersionedUrl),
+    PropertyType(VersionedUrl),
+    EntityType(VersionedUrl),
+    Entity(EntityRecordId),
+    OwnershipTransfer(Uuid),
+}
+
+impl fmt::Display for SnapshotRecordId {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Metadata => fmt.write_str("snapshot metadata"),
+            Self::Manifest => fmt.write_str("snapshot manifest"),
+            Self::DataType(url) => write!(fmt, "data type {url}"),
+            Self::PropertyType(url) => write!(fmt, "property type {url}"),
+            Self::EntityType(url) => write!(fmt, "entity type {url}"),
+            Self::Entity(record_id) => write!(
+                fmt,
+                "entity {} (edition {})",
+                record_id.entity_id,
+                record_id.edition_id.as_uuid()
+            ),
+            Self::OwnershipTransfer(transfer_id) => write!(fmt, "ownership transfer {transfer_id}"),
+        }
+    }
+}
+
+impl From<&SnapshotEntry> for SnapshotRecordId {
+    fn from(entry: &SnapshotEntry) -> Self {
+        match entry {
+            SnapshotEntry::Snapshot(_) => Self::Metadata,
+            SnapshotEntry::Manifest(_) => Self::Manifest,
+            SnapshotEntry::DataType(data_type) => {
+                Self::DataType(VersionedUrl::from(data_type.metadata.record_id.clone()))
+            }
+            SnapshotEntry::PropertyType(property_type) => {
+                Self::PropertyType(VersionedUrl::from(property_type.metadata.record_id.clone()))
+            }
+            SnapshotEntry::EntityType(entity_type) => {
+                Self::EntityType(VersionedUrl::from(entity_type.metadata.record_id.clone()))
+            }
+            SnapshotEntry::Entity(entity) => Self::Entity(entity.metadata.record_id),
+            SnapshotEntry::OwnershipTransfer(transfer) => {
+                Self::OwnershipTransfer(transfer.transfer_id)
+            }
+        }
+    }
+}
+
+/// The kind of a problem found in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotProblemKind {
+    /// The snapshot does not contain metadata.
+    MissingMetadata,
+    /// The snapshot contains more than one metadata entry.
+    DuplicateMetadata,
+    /// The snapshot was created by an unsupported version of the graph.
+    UnsupportedVersion(semver::Version),
+    /// The snapshot announces a manifest but does not end with it.
+    MissingManifest,
+    /// The number of records in the snapshot does not match the manifest.
+    RecordCountMismatch {
+        expected: SnapshotRecordCounts,
+        actual: SnapshotRecordCounts,
+    },
+    /// The checksum of the snapshot does not match the manifest.
+    ChecksumMismatch,
+    /// The record is contained more than once in the snapshot.
+    DuplicateRecord,
+    /// The schema of the ontology type is not valid.
+    InvalidSchema(String),
+    /// A referenced data type is not contained in the snapshot.
+    MissingDataType(VersionedUrl),
+    /// A referenced property type is not contained in the snapshot.
+    MissingPropertyType(VersionedUrl),
+    /// A referenced entity type is not contained in the snapshot.
+    MissingEntityType(VersionedUrl),
+    /// A linked entity is not contained in the snapshot.
+    MissingEntity(EntityId),
+    /// A referenced account is not created by any record in the snapshot.
+    UndeclaredAccount(AccountId),
+    /// The properties of the entity do not validate against its entity type.
+    InvalidProperties(String),
+    /// A temporal interval does not contain any point in time.
+    EmptyInterval,
+    /// The temporal row of the edition overlaps with another temporal row of the same entity.
+    OverlappingEditions(EntityEditionId),
+}
+
+impl fmt::Display for SnapshotProblemKind {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMetadata => fmt.write_str("the snapshot does not contain metadata"),
+            Self::DuplicateMetadata => {
+                fmt.write_str("the snapshot contains more than one metadata entry")
+            }
+            Self::UnsupportedVersion(version) => {
+                write!(fmt, "graph version {version} is not supported")
+            }
+            Self::MissingManifest => {
+                fmt.write_str("the snapshot is truncated, the manifest is missing")
+            }
+            Self::RecordCountMismatch { expected, actual } => write!(
+                fmt,
+                "the snapshot contains {actual:?} records, but {expected:?} were expected"
+            ),
+            Self::ChecksumMismatch => {
+                fmt.write_str("the checksum of the snapshot does not match the manifest")
+            }
+            Self::DuplicateRecord => fmt.write_str("the record is contained more than once"),
+            Self::InvalidSchema(error) => write!(fmt, "the schema is invalid: {error}"),
+            Self::MissingDataType(url) => write!(fmt, "the data type {url} is missing"),
+            Self::MissingPropertyType(url) => write!(fmt, "the property type {url} is missing"),
+            Self::MissingEntityType(url) => write!(fmt, "the entity type {url} is missing"),
+            Self::MissingEntity(entity_id) => write!(fmt, "the entity {entity_id} is missing"),
+            Self::UndeclaredAccount(account_id) => {
+                write!(fmt, "the account {account_id} is not declared")
+            }
+            Self::InvalidProperties(error) => write!(fmt, "the properties are invalid: {error}"),
+            Self::EmptyInterval => fmt.write_str("a temporal interval is empty"),
+            Self::OverlappingEditions(edition_id) => write!(
+                fmt,
+                "the edition overlaps with edition {}",
+                edition_id.as_uuid()
+            ),
+        }
+    }
+}
+
+/// A problem found in a snapshot, which would prevent restoring it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotProblem {
+    pub record: SnapshotRecordId,
+    pub kind: SnapshotProblemKind,
+}
+
+impl fmt::Display for SnapshotProblem {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}: {}", self.record, self.kind)
+    }
+}
+
+/// A reference from a record to an ontology type.
+enum OntologyTypeReference {
+    DataType(VersionedUrl),
+    PropertyType(VersionedUrl),
+    EntityType(VersionedUrl),
+}
+
+/// Verifies a snapshot without restoring it into a store.
+///
+/// The entries of the snapshot are passed to [`verify`] in any order. As records may reference
+/// records, which appear later in the snapshot, most checks are done when calling [`finish`],
+/// which returns all problems found in the snapshot.
+///
+/// The verifier checks that
+///   - the snapshot contains supported metadata,
+///   - the snapshot is complete and matches its manifest, if it has one,
+///   - no record is contained more than once,
+///   - every referenced ontology type is contained in the snapshot,
+///   - every entity linked by a link entity is contained in the snapshot,
+///   - every referenced account is declared, i.e. owns an entity, created an ontology type, or took
+///     part in an ownership transfer,
+///   - the properties of every entity validate against its entity type,
+///   - every temporal interval is non-empty and the temporal rows of an entity do not overlap.
+///
+/// [`verify`]: Self::verify
+/// [`finish`]: Self::finish
+#[derive(Default)]
+pub struct SnapshotVerifier {
+    found_metadata: bool,
+    expects_manifest: bool,
+    manifest: Option<SnapshotManifest>,
+    content: SnapshotContent,
+    problems: Vec<SnapshotProblem>,
+    schemas: OntologySchemas,
+    ontology_references: Vec<(SnapshotRecordId, OntologyTypeReference)>,
+    editions: HashMap<EntityId, Vec<(EntityEditionId, Option<EntityTemporalMetadata>)>>,
+    entity_references: Vec<(SnapshotRecordId, EntityId)>,
+    declared_accounts: HashSet<AccountId>,
+    account_references: Vec<(SnapshotRecordId, AccountId)>,
+    entities: Vec<EntitySnapshotRecord>,
+    ownership_transfers: HashSet<Uuid>,
+}
+
+impl SnapshotVerifier {
+    fn report(&mut self, record: SnapshotRecordId, kind: SnapshotProblemKind) {
+        self.problems.push(SnapshotProblem { record, kind });
+    }
+
+    /// Verifies a single entry of the snapshot.
+    pub fn verify(&mut self, entry: SnapshotEntry) {
+        self.content.add(&entry);
+        match entry {
+            SnapshotEntry::Snapshot(metadata) => self.verify_metadata(&metadata),
+            SnapshotEntry::DataType(data_type) => self.verify_data_type(data_type),
+            SnapshotEntry::PropertyType(property_type) => self.verify_property_type(property_type),
+            SnapshotEntry::EntityType(entity_type) => self.verify_entity_type(entity_type),
+            SnapshotEntry::Entity(entity) => self.verify_entity(entity),
+            SnapshotEntry::OwnershipTransfer(transfer) => self.verify_ownership_transfer(&transfer),
+            SnapshotEntry::Manifest(manifest) => {
+                if self.manifest.replace(manifest).is_some() {
+                    self.report(
+                        SnapshotRecordId::Manifest,
+                        SnapshotProblemKind::DuplicateRecord,
+                    );
+                }
+            }
+        }
+    }
+
+    fn verify_metadata(&mut self, metadata: &SnapshotMetadata) {
+        if self.found_metadata {
+            self.report(
+                SnapshotRecordId::Metadata,
+                SnapshotProblemKind::DuplicateMetadata,
+            );
+        }
+        self.found_metadata = true;
+        self.expects_manifest |= metadata.custom.has_manifest;
+
+        let version = &metadata.block_protocol_module_versions.graph;
+        if *version != SNAPSHOT_VERSION {
+            self.report(
+                SnapshotRecordId::Metadata,
+                SnapshotProblemKind::UnsupportedVersion(version.clone()),
+            );
+        }
+    }
+
+    fn verify_ontology_metadata(
+        &mut self,
+        record: &SnapshotRecordId,
+        metadata: &CustomOntologyMetadata,
+        deprecation: Option<&OntologyTypeDeprecation>,
+        successor_reference: fn(VersionedUrl) -> OntologyTypeReference,
+    ) {
+        let (CustomOntologyMetadata::Owned {
+            provenance,
+            temporal_versioning,
+            ..
+        }
+        | CustomOntologyMetadata::External {
+            provenance,
+            temporal_versioning,
+            ..
+        }) = metadata;
+
+        self.declared_accounts
+            .insert(provenance.record_created_by_id.as_account_id());
+        if let Some(record_archived_by_id) = provenance.record_archived_by_id {
+            self.account_references
+                .push((record.clone(), record_archived_by_id.as_account_id()));
+        }
+        if let CustomOntologyMetadata::Owned { owned_by_id, .. } = metadata {
+            self.account_references
+                .push((record.clone(), owned_by_id.as_account_id()));
+        }
+        if let Some(deprecation) = deprecation {
+            self.account_references
+                .push((record.clone(), deprecation.deprecated_by_id.as_account_id()));
+            if let Some(successor) = &deprecation.successor {
+                self.ontology_references
+                    .push((record.clone(), successor_reference(successor.clone())));
+            }
+        }
+
+        if is_empty(&temporal_versioning.transaction_time) {
+            self.report(record.clone(), SnapshotProblemKind::EmptyInterval);
+        }
+    }
+
+    fn verify_data_type(&mut self, data_type: OntologyTypeSnapshotRecord<DataType>) {
+        let url = VersionedUrl::from(data_type.metadata.record_id);
+        let record = SnapshotRecordId::DataType(url.clone());
+
+        self.verify_ontology_metadata(
+            &record,
+            &data_type.metadata.custom,
+            data_type.metadata.deprecation.as_ref(),
+            OntologyTypeReference::DataType,
+        );
+
+        let schema = serde_json::to_value(&data_type.schema).unwrap_or(Value::Null);
+        if let Err(error) = DataType::try_from(data_type.schema) {
+            self.report(
+                record.clone(),
+                SnapshotProblemKind::InvalidSchema(error.to_string()),
+            );
+        }
+
+        if self.schemas.data_types.insert(url, schema).is_some() {
+            self.report(record, SnapshotProblemKind::DuplicateRecord);
+        }
+    }
+
+    fn verify_property_type(&mut self, property_type: OntologyTypeSnapshotRecord<PropertyType>) {
+        let url = VersionedUrl::from(property_type.metadata.record_id);
+        let record = SnapshotRecordId::PropertyType(url.clone());
+
+        self.verify_ontology_metadata(
+            &record,
+            &property_type.metadata.custom,
+            property_type.metadata.deprecation.as_ref(),
+            OntologyTypeReference::PropertyType,
+        );
+
+        let schema = serde_json::to_value(&property_type.schema).unwrap_or(Value::Null);
+        match PropertyType::try_from(property_type.schema) {
+            Ok(property_type) => {
+                for data_type in property_type.data_type_references() {
+                    self.ontology_references.push((
+                        record.clone(),
+                        OntologyTypeReference::DataType(data_type.url().clone()),
+                    ));
+                }
+                for property_type in property_type.property_type_references() {
+                    self.ontology_references.push((
+                        record.clone(),
+                        OntologyTypeReference::PropertyType(property_type.url().clone()),
+                    ));
+                }
+            }
+            Err(error) => self.report(
+                record.clone(),
+                SnapshotProblemKind::InvalidSchema(error.to_string()),
+            ),
+        }
+
+        if self.schemas.property_types.insert(url, schema).is_some() {
+            self.report(record, SnapshotProblemKind::DuplicateRecord);
+        }
+    }
+
+    fn verify_entity_type(&mut self, entity_type: OntologyTypeSnapshotRecord<EntityType>) {
+        let url = VersionedUrl::from(entity_type.metadata.record_id);
+        let record = SnapshotRecordId::EntityType(url.clone());
+
+        self.verify_ontology_metadata(
+            &record,
+            &entity_type.metadata.custom.common,
+            entity_type.metadata.deprecation.as_ref(),
+            OntologyTypeReference::EntityType,
+        );
+
+        let schema = serde_json::to_value(&entity_type.schema).unwrap_or(Value::Null);
+        match EntityType::try_from(entity_type.schema) {
+            Ok(entity_type) => {
+                for parent in entity_type.inherits_from().all_of() {
+                    self.ontology_references.push((
+                        record.clone(),
+                        OntologyTypeReference::EntityType(parent.url().clone()),
+                    ));
+                }
+                for property_type in entity_type.property_type_references() {
+                    self.ontology_references.push((
+                        record.clone(),
+                        OntologyTypeReference::PropertyType(property_type.url().clone()),
+                    ));
+                }
+                for (link, destinations) in entity_type.link_mappings() {
+                    self.ontology_references.push((
+                        record.clone(),
+                        OntologyTypeReference::EntityType(link.url().clone()),
+                    ));
+                    for destination in destinations.unwrap_or_default() {
+                        self.ontology_references.push((
+                            record.clone(),
+                            OntologyTypeReference::EntityType(destination.url().clone()),
+                        ));
+                    }
+                }
+            }
+            Err(error) => self.report(
+                record.clone(),
+                SnapshotProblemKind::InvalidSchema(error.to_string()),
+            ),
+        }
+
+        if self.schemas.entity_types.insert(url, schema).is_some() {
+            self.report(record, SnapshotProblemKind::DuplicateRecord);
+        }
+    }
+
+    fn verify_entity(&mut self, entity: EntitySnapshotRecord) {
+        let record_id = entity.metadata.record_id;
+        let record = SnapshotRecordId::Entity(record_id);
+
+        self.declared_accounts
+            .insert(record_id.entity_id.owned_by_id.as_account_id());
+        if let Some(ProvenanceMetadata {
+            record_created_by_id,
+            record_archived_by_id,
+        }) = entity.metadata.custom.provenance
+        {
+            self.account_references
+                .push((record.clone(), record_created_by_id.as_account_id()));
+            if let Some(record_archived_by_id) = record_archived_by_id {
+                self.account_references
+                    .push((record.clone(), record_archived_by_id.as_account_id()));
+            }
+        }
+
+        self.ontology_references.push((
+            record.clone(),
+            OntologyTypeReference::EntityType(entity.metadata.entity_type_id.clone()),
+        ));
+
+        if let Some(link_data) = &entity.link_data {
+            self.entity_references
+                .push((record.clone(), link_data.left_entity_id));
+            self.entity_references
+                .push((record.clone(), link_data.right_entity_id));
+        }
+
+        if let Some(temporal_versioning) = &entity.metadata.temporal_versioning {
+            if is_empty(&temporal_versioning.decision_time)
+                || is_empty(&temporal_versioning.transaction_time)
+            {
+                self.report(record.clone(), SnapshotProblemKind::EmptyInterval);
+            }
+        }
+
+        // An edition may be valid for multiple combinations of decision time and transaction
+        // time, so it is only a duplicate if the temporal metadata is the same as well.
+        let editions = self.editions.entry(record_id.entity_id).or_default();
+        if editions.iter().any(|(edition_id, temporal_versioning)| {
+            *edition_id == record_id.edition_id
+                && *temporal_versioning == entity.metadata.temporal_versioning
+        }) {
+            self.report(record, SnapshotProblemKind::DuplicateRecord);
+        } else {
+            editions.push((
+                record_id.edition_id,
+                entity.metadata.temporal_versioning.clone(),
+            ));
+            self.entities.push(entity);
+        }
+    }
+
+    fn verify_ownership_transfer(&mut self, transfer: &OwnershipTransferRecord) {
+        // The accounts of a transfer are restored from the transfer, as the previous owner may
+        // not own any record anymore.
+        self.declared_accounts.extend([
+            transfer.from_owned_by_id.as_account_id(),
+            transfer.to_owned_by_id.as_account_id(),
+            transfer.transferred_by_id.as_account_id(),
+        ]);
+
+        if !self.ownership_transfers.insert(transfer.transfer_id) {
+            self.report(
+                SnapshotRecordId::OwnershipTransfer(transfer.transfer_id),
+                SnapshotProblemKind::DuplicateRecord,
+            );
+        }
+    }
+
+    /// Finishes the verification and returns all problems found in the snapshot.
+    #[must_use]
+    pub fn finish(mut self) -> Vec<SnapshotProblem> {
+        if !self.found_metadata {
+            self.report(
+                SnapshotRecordId::Metadata,
+                SnapshotProblemKind::MissingMetadata,
+            );
+        }
+
+        if let Some(manifest) = self.manifest.take() {
+            if manifest.records != self.content.records() {
+                self.report(
+                    SnapshotRecordId::Manifest,
+                    SnapshotProblemKind::RecordCountMismatch {
+                        expected: manifest.records,
+                        actual: self.content.records(),
+                    },
+                );
+            } else if manifest.checksum != self.content.checksum() {
+                self.report(
+                    SnapshotRecordId::Manifest,
+                    SnapshotProblemKind::ChecksumMismatch,
+                );
+            }
+        } else if self.expects_manifest {
+            self.report(
+                SnapshotRecordId::Manifest,
+                SnapshotProblemKind::MissingManifest,
+            );
+        }
+
+        for (record, reference) in std::mem::take(&mut self.ontology_references) {
+            let kind = match reference {
+                OntologyTypeReference::DataType(url)
+                    if !self.schemas.data_types.contains_key(&url) =>
+                {
+                    SnapshotProblemKind::MissingDataType(url)
+                }
+                OntologyTypeReference::PropertyType(url)
+                    if !self.schemas.property_types.contains_key(&url) =>
+                {
+                    SnapshotProblemKind::MissingPropertyType(url)
+                }
+                OntologyTypeReference::EntityType(url)
+                    if !self.schemas.entity_types.contains_key(&url) =>
+                {
+                    SnapshotProblemKind::MissingEntityType(url)
+                }
+                _ => continue,
+            };
+            self.report(record, kind);
+        }
+
+        for (record, entity_id) in std::mem::take(&mut self.entity_references) {
+            if !self.editions.contains_key(&entity_id) {
+                self.report(record, SnapshotProblemKind::MissingEntity(entity_id));
+            }
+        }
+
+        for (record, account_id) in std::mem::take(&mut self.account_references) {
+            if !self.declared_accounts.contains(&account_id) {
+                self.report(record, SnapshotProblemKind::UndeclaredAccount(account_id));
+            }
+        }
+
+        for entity in std::mem::take(&mut self.entities) {
+            let record = SnapshotRecordId::Entity(entity.metadata.record_id);
+
+            let errors = self.schemas.validate_entity(
+                &entity.metadata.entity_type_id,
+                &serde_json::to_value(&entity.properties).unwrap_or(Value::Null),
+            );
+            for error in errors {
+                self.report(
+                    record.clone(),
+                    SnapshotProblemKind::InvalidProperties(error),
+                );
+            }
+
+            // Every temporal row of the entity is compared against all other rows, including the
+            // other rows of the same edition.
+            if let Some(temporal_versioning) = &entity.metadata.temporal_versioning {
+                let overlapping = self.editions[&entity.metadata.record_id.entity_id]
+                    .iter()
+                    .filter_map(|(edition_id, other)| {
+                        let other = other.as_ref()?;
+                        let is_same_row = *edition_id == entity.metadata.record_id.edition_id
+                            && other == temporal_versioning;
+                        (!is_same_row
+                            && temporal_versioning
+                                .decision_time
+                                .overlaps(&other.decision_time)
+                            && temporal_versioning
+                                .transaction_time
+                                .overlaps(&other.transaction_time))
+                        .then_some(*edition_id)
+                    })
+                    .collect::<Vec<_>>();
+                for edition_id in overlapping {
+                    self.report(
+                        record.clone(),
+                        SnapshotProblemKind::OverlappingEditions(edition_id),
+                    );
+                }
+            }
+        }
+
+        self.problems
+    }
+}
+
+/// Returns if the interval does not contain any point in time.
+fn is_empty<A>(interval: &LeftClosedTemporalInterval<A>) -> bool {
+    let ClosedTemporalBound::Inclusive(start) = interval.start();
+    matches!(interval.end(), OpenTemporalBound::Exclusive(end) if end <= start)
+}
+
+#[cfg(test)]
+mod tests {
+    use graph_types::{
+        account::AccountId,
+        knowledge::entity::{EntityProperties, EntityUuid},
+        provenance::OwnedById,
+    };
+    use temporal_versioning::{DecisionTime, TransactionTime};
+    use type_system::url::BaseUrl;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::snapshot::entity::{CustomEntityMetadata, EntityMetadata};
+
+    fn interval<A>(start: &str, end: Option<&str>) -> LeftClosedTemporalInterval<A> {
+        LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(start.parse().expect("should be a valid timestamp")),
+            end.map_or(OpenTemporalBound::Unbounded, |end| {
+                OpenTemporalBound::Exclusive(end.parse().expect("should be a valid timestamp"))
+            }),
+        )
+    }
+
+    fn entity(
+        record_id: EntityRecordId,
+        decision_time: LeftClosedTemporalInterval<DecisionTime>,
+        transaction_time: LeftClosedTemporalInterval<TransactionTime>,
+    ) -> EntitySnapshotRecord {
+        EntitySnapshotRecord {
+            properties: EntityProperties::empty(),
+            metadata: EntityMetadata {
+                record_id,
+                entity_type_id: VersionedUrl {
+                    base_url: BaseUrl::new(
+                        "https://example.com/types/entity-type/thing/".to_owned(),
+                    )
+                    .expect("should be a valid base URL"),
+                    version: 1,
+                },
+                temporal_versioning: Some(EntityTemporalMetadata {
+                    decision_time,
+                    transaction_time,
+                }),
+                custom: CustomEntityMetadata::default(),
+            },
+            link_data: None,
+        }
+    }
+
+    fn record_id(entity_id: EntityId) -> EntityRecordId {
+        EntityRecordId {
+            entity_id,
+            edition_id: EntityEditionId::new(Uuid::new_v4()),
+        }
+    }
+
+    /// Verifies the entities and returns the problems found in their temporal data.
+    fn temporal_problems(entities: Vec<EntitySnapshotRecord>) -> Vec<SnapshotProblem> {
+        let mut verifier = SnapshotVerifier::default();
+        for entity in entities {
+            verifier.verify(SnapshotEntry::Entity(entity));
+        }
+        verifier
+            .finish()
+            .into_iter()
+            .filter(|problem| {
+                matches!(
+                    problem.kind,
+                    SnapshotProblemKind::DuplicateRecord
+                        | SnapshotProblemKind::EmptyInterval
+                        | SnapshotProblemKind::OverlappingEditions(_)
+                )
+            })
+            .collect()
+    }
+
+    fn entity_id() -> EntityId {
+        EntityId {
+            owned_by_id: OwnedById::new(AccountId::new(Uuid::new_v4())),
+            entity_uuid: EntityUuid::new(Uuid::new_v4()),
+        }
+    }
+
+    #[test]
+    fn updated_entity_has_multiple_rows_per_edition() {
+        let entity_id = entity_id();
+        let created = record_id(entity_id);
+        let updated = record_id(entity_id);
+
+        // This is how an edition is stored after it was updated with a later decision time: the
+        // created edition is split into two temporal rows.
+        let problems = temporal_problems(vec![
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-01T00:00:00Z", Some("2000-01-03T00:00:00Z")),
+            ),
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", Some("2000-01-02T00:00:00Z")),
+                interval("2000-01-03T00:00:00Z", None),
+            ),
+            entity(
+                updated,
+                interval("2000-01-02T00:00:00Z", None),
+                interval("2000-01-03T00:00:00Z", None),
+            ),
+        ]);
+
+        assert!(problems.is_empty(), "unexpected problems: {problems:?}");
+    }
+
+    #[test]
+    fn repeated_row_is_duplicate() {
+        let created = record_id(entity_id());
+
+        let problems = temporal_problems(vec![
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-01T00:00:00Z", None),
+            ),
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-01T00:00:00Z", None),
+            ),
+        ]);
+
+        assert_eq!(problems, [SnapshotProblem {
+            record: SnapshotRecordId::Entity(created),
+            kind: SnapshotProblemKind::DuplicateRecord,
+        }]);
+    }
+
+    #[test]
+    fn overlapping_row_of_multi_row_edition() {
+        let entity_id = entity_id();
+        let created = record_id(entity_id);
+        let updated = record_id(entity_id);
+
+        // The second row of the created edition is still valid at the decision time of the
+        // updated edition.
+        let problems = temporal_problems(vec![
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-01T00:00:00Z", Some("2000-01-03T00:00:00Z")),
+            ),
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-03T00:00:00Z", None),
+            ),
+            entity(
+                updated,
+                interval("2000-01-02T00:00:00Z", None),
+                interval("2000-01-03T00:00:00Z", None),
+            ),
+        ]);
+
+        assert_eq!(problems, [
+            SnapshotProblem {
+                record: SnapshotRecordId::Entity(created),
+                kind: SnapshotProblemKind::OverlappingEditions(updated.edition_id),
+            },
+            SnapshotProblem {
+                record: SnapshotRecordId::Entity(updated),
+                kind: SnapshotProblemKind::OverlappingEditions(created.edition_id),
+            },
+        ]);
+    }
+
+    #[test]
+    fn overlapping_rows_of_same_edition() {
+        let created = record_id(entity_id());
+
+        let problems = temporal_problems(vec![
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-01T00:00:00Z", Some("2000-01-03T00:00:00Z")),
+            ),
+            entity(
+                created,
+                interval("2000-01-01T00:00:00Z", None),
+                interval("2000-01-02T00:00:00Z", None),
+            ),
+        ]);
+
+        assert_eq!(problems, [
+            SnapshotProblem {
+                record: SnapshotRecordId::Entity(created),
+                kind: SnapshotProblemKind::OverlappingEditions(created.edition_id),
+            },
+            SnapshotProblem {
+                record: SnapshotRecordId::Entity(created),
+                kind: SnapshotProblemKind::OverlappingEditions(created.edition_id),
+            },
+        ]);
+    }
+}
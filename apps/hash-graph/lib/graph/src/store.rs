@@ -3,6 +3,7 @@ pub mod error;
 pub mod query;
 
 mod account;
+mod change_feed;
 mod config;
 mod knowledge;
 mod migration;
@@ -16,7 +17,11 @@ mod postgres;
 use async_trait::async_trait;
 
 pub use self::{
-    account::AccountStore,
+    account::{AccountStore, OwnershipTransfer, OwnershipTransferRecord},
+    change_feed::{
+        ChangeEvent, ChangeFeedPage, ChangeFeedQuery, ChangeFeedResumeToken, ChangeFeedStore,
+        ChangeKind, ChangedRecord,
+    },
     config::{DatabaseConnectionInfo, DatabaseType},
     error::{
         BaseUrlAlreadyExists, InsertionError, OntologyVersionDoesNotExist, QueryError, StoreError,
@@ -39,11 +44,16 @@ pub use self::{
 /// raised depending on the implementation, e.g. connection issues.
 #[async_trait]
 pub trait Store:
-    AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore + ChangeFeedStore
 {
 }
 impl<S> Store for S where
-    S: AccountStore + DataTypeStore + PropertyTypeStore + EntityTypeStore + EntityStore
+    S: AccountStore
+        + DataTypeStore
+        + PropertyTypeStore
+        + EntityTypeStore
+        + EntityStore
+        + ChangeFeedStore
 {
 }
 
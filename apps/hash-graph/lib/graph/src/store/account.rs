@@ -1,8 +1,51 @@
 use async_trait::async_trait;
 use error_stack::Result;
-use graph_types::account::AccountId;
+use graph_types::{
+    account::AccountId,
+    knowledge::entity::EntityUuid,
+    provenance::{OwnedById, TransferredById},
+};
+use serde::{Deserialize, Serialize};
+use temporal_versioning::{Timestamp, TransactionTime};
+use type_system::url::VersionedUrl;
+use utoipa::ToSchema;
+use uuid::Uuid;
 
-use crate::store::InsertionError;
+use crate::store::{InsertionError, QueryError, UpdateError};
+
+/// The result of transferring the ownership of all ontology types and entities of an account to
+/// another account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipTransfer {
+    pub from_owned_by_id: OwnedById,
+    pub to_owned_by_id: OwnedById,
+    pub transferred_by_id: TransferredById,
+    pub transferred_at: Timestamp<TransactionTime>,
+    /// The number of versions of ontology types, which were transferred.
+    pub ontology_types: u64,
+    /// The number of entities, which were transferred.
+    pub entities: u64,
+}
+
+/// An ownership transfer recorded by [`AccountStore::transfer_ownership`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipTransferRecord {
+    pub transfer_id: Uuid,
+    pub from_owned_by_id: OwnedById,
+    pub to_owned_by_id: OwnedById,
+    pub transferred_by_id: TransferredById,
+    pub transferred_at: Timestamp<TransactionTime>,
+    /// The versions of ontology types, which were transferred.
+    #[schema(value_type = Vec<SHARED_VersionedUrl>)]
+    pub ontology_type_ids: Vec<VersionedUrl>,
+    /// The entities, which were transferred.
+    ///
+    /// Before the transfer, the entity ID of these entities consisted of the `fromOwnedById` and
+    /// their entity UUID.
+    pub entity_uuids: Vec<EntityUuid>,
+}
 
 /// Describes the API of a store implementation for accounts.
 #[async_trait]
@@ -13,4 +56,47 @@ pub trait AccountStore {
     ///
     /// - if insertion failed, e.g. because the [`AccountId`] already exists.
     async fn insert_account_id(&mut self, account_id: AccountId) -> Result<(), InsertionError>;
+
+    /// Transfers the ownership of all ontology types and entities owned by `from_owned_by_id` to
+    /// `to_owned_by_id`.
+    ///
+    /// Ontology types keep their URLs. Entities keep their [`EntityUuid`], but as the owner is part
+    /// of the [`EntityId`], their current editions are moved to a new [`EntityId`]. The history of
+    /// the entities up to the transfer is kept at the previous [`EntityId`]. Links from or to the
+    /// moved entities are updated accordingly. The transfer is recorded, so the previous owner can
+    /// be looked up using [`get_ownership_transfers`].
+    ///
+    /// The endpoints of a link are stored per link entity and not per edition. Links of other
+    /// owners pointing to a moved entity are therefore re-pointed to its new [`EntityId`] for all
+    /// of their editions, including closed ones, without creating a new edition. Their history
+    /// can be related to the previous [`EntityId`] through the recorded transfer.
+    ///
+    /// # Errors
+    ///
+    /// - if `from_owned_by_id` and `to_owned_by_id` are the same account.
+    /// - if `to_owned_by_id` or `actor_id` does not exist.
+    /// - [`EntityIdAlreadyExists`] if an entity of `to_owned_by_id` has the same [`EntityUuid`] as
+    ///   an entity to transfer, unless the entity was transferred away from `to_owned_by_id`
+    ///   before.
+    ///
+    /// [`EntityId`]: graph_types::knowledge::entity::EntityId
+    /// [`EntityIdAlreadyExists`]: crate::store::error::EntityIdAlreadyExists
+    /// [`get_ownership_transfers`]: Self::get_ownership_transfers
+    async fn transfer_ownership(
+        &mut self,
+        from_owned_by_id: OwnedById,
+        to_owned_by_id: OwnedById,
+        actor_id: TransferredById,
+    ) -> Result<OwnershipTransfer, UpdateError>;
+
+    /// Returns the ownership transfers from or to `owned_by_id`, ordered by the time they were
+    /// transferred at.
+    ///
+    /// # Errors
+    ///
+    /// - if reading the ownership transfers failed.
+    async fn get_ownership_transfers(
+        &self,
+        owned_by_id: OwnedById,
+    ) -> Result<Vec<OwnershipTransferRecord>, QueryError>;
 }
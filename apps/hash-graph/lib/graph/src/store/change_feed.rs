@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use error_stack::Result;
+use graph_types::{knowledge::entity::EntityRecordId, ontology::OntologyTypeRecordId};
+use serde::{Deserialize, Serialize};
+use temporal_versioning::{Timestamp, TransactionTime};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::store::QueryError;
+
+/// The kind of change a [`ChangeEvent`] describes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// The first edition of an entity or the first version of an ontology type was written.
+    Created,
+    /// A new edition of an entity or a new version of an ontology type was written, or an
+    /// ontology type was unarchived.
+    Updated,
+    /// An entity or an ontology type was archived.
+    Archived,
+    /// An edition of an entity stopped being valid in transaction time for some decision time,
+    /// e.g. because it was superseded by a newer edition.
+    Closed,
+}
+
+/// The record a [`ChangeEvent`] refers to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "recordId")]
+pub enum ChangedRecord {
+    DataType(OntologyTypeRecordId),
+    PropertyType(OntologyTypeRecordId),
+    EntityType(OntologyTypeRecordId),
+    Entity(EntityRecordId),
+}
+
+/// A change of a record in the store.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    pub kind: ChangeKind,
+    pub record: ChangedRecord,
+    pub transaction_time: Timestamp<TransactionTime>,
+}
+
+/// Position in the change feed directly after a [`ChangeEvent`].
+///
+/// The token is opaque and should only be passed back in a subsequent [`ChangeFeedQuery`] to
+/// continue reading the change feed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChangeFeedResumeToken {
+    pub(crate) transaction_time: Timestamp<TransactionTime>,
+    pub(crate) sequence_id: Uuid,
+}
+
+/// Selects the changes to read from the change feed.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChangeFeedQuery {
+    /// Only changes at or after this transaction time are returned.
+    pub since: Timestamp<TransactionTime>,
+    /// Only changes before this transaction time are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    pub until: Option<Timestamp<TransactionTime>>,
+    /// Only changes after the position of the token are returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    pub resume_token: Option<ChangeFeedResumeToken>,
+    /// The maximum number of changes to return.
+    pub limit: usize,
+}
+
+/// A page of the change feed.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeFeedPage {
+    pub events: Vec<ChangeEvent>,
+    /// The token to pass in the next query to continue after the returned events.
+    ///
+    /// If no events were returned, this is the token of the query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(nullable = false)]
+    pub resume_token: Option<ChangeFeedResumeToken>,
+    /// Whether more changes are available, which were not returned because of the limit.
+    pub has_more: bool,
+}
+
+/// Describes the API of a store implementation for reading changes in the store.
+#[async_trait]
+pub trait ChangeFeedStore {
+    /// Returns the changes to entities and ontology types in the transaction time range specified
+    /// by the [`ChangeFeedQuery`], ordered by transaction time.
+    ///
+    /// A change is emitted when an edition or an ontology type version started or stopped being
+    /// the latest record in transaction time. Changes are only returned up to the point in
+    /// transaction time, at which no transaction is in progress anymore. Polling with the returned
+    /// [`ChangeFeedResumeToken`] therefore does not miss writes of concurrent transactions.
+    ///
+    /// # Errors
+    ///
+    /// - if the store can't see all transactions in progress, e.g. because the Postgres role lacks
+    ///   `pg_read_all_stats`
+    /// - if reading the changes from the store failed
+    async fn get_changes(&self, query: &ChangeFeedQuery) -> Result<ChangeFeedPage, QueryError>;
+}
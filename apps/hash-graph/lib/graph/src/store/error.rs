@@ -85,6 +85,18 @@ impl fmt::Display for EntityDoesNotExist {
 
 impl Context for EntityDoesNotExist {}
 
+#[derive(Debug)]
+#[must_use]
+pub struct EntityIdAlreadyExists;
+
+impl fmt::Display for EntityIdAlreadyExists {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("tried to move an entity to an entity ID which already exists")
+    }
+}
+
+impl Context for EntityIdAlreadyExists {}
+
 #[derive(Debug)]
 #[must_use]
 pub struct RaceConditionOnUpdate;
@@ -14,11 +14,14 @@ use graph_types::{
     },
     ontology::{
         DataTypeWithMetadata, EntityTypeMetadata, EntityTypeWithMetadata, OntologyElementMetadata,
-        OntologyTemporalMetadata, OntologyType, OntologyTypeReference, OntologyTypeVersion,
-        PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
-        PartialOntologyElementMetadata, PropertyTypeWithMetadata,
+        OntologyTemporalMetadata, OntologyType, OntologyTypeDeprecation, OntologyTypeReference,
+        OntologyTypeVersion, PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata,
+        PartialEntityTypeMetadata, PartialOntologyElementMetadata, PropertyTypeWithMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
+        TransferredById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use tarpc::context;
 use temporal_versioning::{DecisionTime, Timestamp};
@@ -31,12 +34,14 @@ use type_system::{
 };
 
 use crate::{
-    ontology::domain_validator::DomainValidator,
+    ontology::{domain_validator::DomainValidator, OntologyTypeUsage, ResolvedEntityType},
     store::{
         crud::Read,
         query::{Filter, OntologyQueryPath},
-        AccountStore, ConflictBehavior, DataTypeStore, EntityStore, EntityTypeStore,
-        InsertionError, PropertyTypeStore, QueryError, Record, StoreError, StorePool, UpdateError,
+        AccountStore, ChangeFeedPage, ChangeFeedQuery, ChangeFeedStore, ConflictBehavior,
+        DataTypeStore, EntityStore, EntityTypeStore, InsertionError, OwnershipTransfer,
+        OwnershipTransferRecord, PropertyTypeStore, QueryError, Record, StoreError, StorePool,
+        UpdateError,
     },
     subgraph::{
         edges::GraphResolveDepths,
@@ -539,12 +544,41 @@ where
 #[async_trait]
 impl<S, A> AccountStore for FetchingStore<S, A>
 where
-    S: AccountStore + Send,
+    S: AccountStore + Send + Sync,
     A: Send + Sync,
 {
     async fn insert_account_id(&mut self, account_id: AccountId) -> Result<(), InsertionError> {
         self.store.insert_account_id(account_id).await
     }
+
+    async fn transfer_ownership(
+        &mut self,
+        from_owned_by_id: OwnedById,
+        to_owned_by_id: OwnedById,
+        actor_id: TransferredById,
+    ) -> Result<OwnershipTransfer, UpdateError> {
+        self.store
+            .transfer_ownership(from_owned_by_id, to_owned_by_id, actor_id)
+            .await
+    }
+
+    async fn get_ownership_transfers(
+        &self,
+        owned_by_id: OwnedById,
+    ) -> Result<Vec<OwnershipTransferRecord>, QueryError> {
+        self.store.get_ownership_transfers(owned_by_id).await
+    }
+}
+
+#[async_trait]
+impl<S, A> ChangeFeedStore for FetchingStore<S, A>
+where
+    S: ChangeFeedStore + Send + Sync,
+    A: Send + Sync,
+{
+    async fn get_changes(&self, query: &ChangeFeedQuery) -> Result<ChangeFeedPage, QueryError> {
+        self.store.get_changes(query).await
+    }
 }
 
 #[async_trait]
@@ -576,6 +610,13 @@ where
         self.store.get_data_type(query).await
     }
 
+    async fn get_data_type_usage(
+        &self,
+        data_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        self.store.get_data_type_usage(data_type_id).await
+    }
+
     async fn update_data_type(
         &mut self,
         data_type: DataType,
@@ -603,6 +644,17 @@ where
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
         self.store.unarchive_data_type(id, actor_id).await
     }
+
+    async fn deprecate_data_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        actor_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.store
+            .deprecate_data_type(id, successor, actor_id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -641,6 +693,13 @@ where
         self.store.get_property_type(query).await
     }
 
+    async fn get_property_type_usage(
+        &self,
+        property_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        self.store.get_property_type_usage(property_type_id).await
+    }
+
     async fn update_property_type(
         &mut self,
         property_type: PropertyType,
@@ -670,6 +729,17 @@ where
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
         self.store.unarchive_property_type(id, actor_id).await
     }
+
+    async fn deprecate_property_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        actor_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.store
+            .deprecate_property_type(id, successor, actor_id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -706,6 +776,13 @@ where
         self.store.get_entity_type(query).await
     }
 
+    async fn get_resolved_entity_type(
+        &self,
+        entity_type_id: &VersionedUrl,
+    ) -> Result<ResolvedEntityType, QueryError> {
+        self.store.get_resolved_entity_type(entity_type_id).await
+    }
+
     async fn update_entity_type(
         &mut self,
         entity_type: EntityType,
@@ -736,6 +813,17 @@ where
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
         self.store.unarchive_entity_type(id, actor_id).await
     }
+
+    async fn deprecate_entity_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        actor_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.store
+            .deprecate_entity_type(id, successor, actor_id)
+            .await
+    }
 }
 
 #[async_trait]
@@ -19,7 +19,7 @@ use crate::{
 ///
 /// [Entities]: Entity
 #[async_trait]
-pub trait EntityStore: crud::Read<Entity> {
+pub trait EntityStore: crud::Read<Entity, Record = Entity> {
     /// Creates a new [`Entity`].
     ///
     /// # Errors:
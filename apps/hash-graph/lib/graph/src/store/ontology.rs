@@ -5,10 +5,10 @@ use error_stack::Result;
 use graph_types::{
     ontology::{
         DataTypeWithMetadata, EntityTypeMetadata, EntityTypeWithMetadata, OntologyElementMetadata,
-        OntologyTemporalMetadata, PartialEntityTypeMetadata, PartialOntologyElementMetadata,
-        PropertyTypeWithMetadata,
+        OntologyTemporalMetadata, OntologyTypeDeprecation, PartialEntityTypeMetadata,
+        PartialOntologyElementMetadata, PropertyTypeWithMetadata,
     },
-    provenance::{RecordArchivedById, RecordCreatedById},
+    provenance::{DeprecatedById, RecordArchivedById, RecordCreatedById},
 };
 use type_system::{
     url::{BaseUrl, VersionedUrl},
@@ -16,13 +16,14 @@ use type_system::{
 };
 
 use crate::{
+    ontology::{OntologyTypeUsage, ResolvedEntityType},
     store::{crud, ConflictBehavior, InsertionError, QueryError, UpdateError},
     subgraph::{query::StructuralQuery, Subgraph},
 };
 
 /// Describes the API of a store implementation for [`DataType`]s.
 #[async_trait]
-pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
+pub trait DataTypeStore: crud::Read<DataTypeWithMetadata, Record = DataTypeWithMetadata> {
     /// Creates a new [`DataType`].
     ///
     /// # Errors:
@@ -68,6 +69,20 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
         query: &StructuralQuery<DataTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError>;
 
+    /// Get the property types, entity types, and entities using the [`DataType`] identified by
+    /// `data_type_id`.
+    ///
+    /// Entities are counted if they currently have a value for a property type directly
+    /// referencing the [`DataType`].
+    ///
+    /// # Errors
+    ///
+    /// - if the requested [`DataType`] doesn't exist.
+    async fn get_data_type_usage(
+        &self,
+        data_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError>;
+
     /// Update the definition of an existing [`DataType`].
     ///
     /// # Errors
@@ -100,11 +115,30 @@ pub trait DataTypeStore: crud::Read<DataTypeWithMetadata> {
         id: &VersionedUrl,
         actor_id: RecordCreatedById,
     ) -> Result<OntologyTemporalMetadata, UpdateError>;
+
+    /// Deprecates the definition of an existing [`DataType`].
+    ///
+    /// A deprecated [`DataType`] can still be read and used. If `successor` is specified, it's
+    /// recommended to use it instead. Deprecating a [`DataType`] again replaces the previous
+    /// deprecation.
+    ///
+    /// # Errors
+    ///
+    /// - if the [`DataType`] doesn't exist.
+    /// - if the `successor` doesn't exist.
+    async fn deprecate_data_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        actor_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError>;
 }
 
 /// Describes the API of a store implementation for [`PropertyType`]s.
 #[async_trait]
-pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
+pub trait PropertyTypeStore:
+    crud::Read<PropertyTypeWithMetadata, Record = PropertyTypeWithMetadata>
+{
     /// Creates a new [`PropertyType`].
     ///
     /// # Errors:
@@ -152,6 +186,20 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
         query: &StructuralQuery<PropertyTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError>;
 
+    /// Get the property types, entity types, and entities using the [`PropertyType`] identified
+    /// by `property_type_id`.
+    ///
+    /// Entities are counted if they currently have a value for the [`PropertyType`], either
+    /// directly or nested in a property object.
+    ///
+    /// # Errors
+    ///
+    /// - if the requested [`PropertyType`] doesn't exist.
+    async fn get_property_type_usage(
+        &self,
+        property_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError>;
+
     /// Update the definition of an existing [`PropertyType`].
     ///
     /// # Errors
@@ -184,11 +232,30 @@ pub trait PropertyTypeStore: crud::Read<PropertyTypeWithMetadata> {
         id: &VersionedUrl,
         actor_id: RecordCreatedById,
     ) -> Result<OntologyTemporalMetadata, UpdateError>;
+
+    /// Deprecates the definition of an existing [`PropertyType`].
+    ///
+    /// A deprecated [`PropertyType`] can still be read and used. If `successor` is specified, it's
+    /// recommended to use it instead. Deprecating a [`PropertyType`] again replaces the previous
+    /// deprecation.
+    ///
+    /// # Errors
+    ///
+    /// - if the [`PropertyType`] doesn't exist.
+    /// - if the `successor` doesn't exist.
+    async fn deprecate_property_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        actor_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError>;
 }
 
 /// Describes the API of a store implementation for [`EntityType`]s.
 #[async_trait]
-pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
+pub trait EntityTypeStore:
+    crud::Read<EntityTypeWithMetadata, Record = EntityTypeWithMetadata>
+{
     /// Creates a new [`EntityType`].
     ///
     /// # Errors:
@@ -234,6 +301,17 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
         query: &StructuralQuery<EntityTypeWithMetadata>,
     ) -> Result<Subgraph, QueryError>;
 
+    /// Get the [`EntityType`] identified by `entity_type_id` with its inheritance merged and all
+    /// property types and data types inlined.
+    ///
+    /// # Errors
+    ///
+    /// - if the requested [`EntityType`] or any ontology type it depends on doesn't exist.
+    async fn get_resolved_entity_type(
+        &self,
+        entity_type_id: &VersionedUrl,
+    ) -> Result<ResolvedEntityType, QueryError>;
+
     /// Update the definition of an existing [`EntityType`].
     ///
     /// # Errors
@@ -267,4 +345,21 @@ pub trait EntityTypeStore: crud::Read<EntityTypeWithMetadata> {
         id: &VersionedUrl,
         actor_id: RecordCreatedById,
     ) -> Result<OntologyTemporalMetadata, UpdateError>;
+
+    /// Deprecates the definition of an existing [`EntityType`].
+    ///
+    /// A deprecated [`EntityType`] can still be read and used. If `successor` is specified, it's
+    /// recommended to use it instead. Deprecating an [`EntityType`] again replaces the previous
+    /// deprecation.
+    ///
+    /// # Errors
+    ///
+    /// - if the [`EntityType`] doesn't exist.
+    /// - if the `successor` doesn't exist.
+    async fn deprecate_entity_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        actor_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError>;
 }
@@ -1,3 +1,4 @@
+mod change_feed;
 mod knowledge;
 mod ontology;
 
@@ -9,21 +10,26 @@ mod traversal_context;
 use async_trait::async_trait;
 use error_stack::{Report, Result, ResultExt};
 #[cfg(hash_graph_test_environment)]
-use graph_types::knowledge::entity::{
-    EntityEditionId, EntityId, EntityProperties, EntityTemporalMetadata,
-};
+use graph_types::knowledge::entity::{EntityEditionId, EntityProperties, EntityTemporalMetadata};
 use graph_types::{
     account::AccountId,
-    knowledge::link::LinkOrder,
+    knowledge::{
+        entity::{EntityId, EntityUuid},
+        link::LinkOrder,
+    },
     ontology::{
         CustomOntologyMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
-        OntologyTypeRecordId, OntologyTypeVersion, PartialCustomOntologyMetadata,
+        OntologyTypeDeprecation, OntologyTypeRecordId, OntologyTypeVersion,
+        PartialCustomOntologyMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
+        TransferredById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 #[cfg(hash_graph_test_environment)]
-use temporal_versioning::{DecisionTime, Timestamp};
-use temporal_versioning::{LeftClosedTemporalInterval, TransactionTime};
+use temporal_versioning::DecisionTime;
+use temporal_versioning::{LeftClosedTemporalInterval, Timestamp, TransactionTime};
 use time::OffsetDateTime;
 #[cfg(hash_graph_test_environment)]
 use tokio_postgres::{binary_copy::BinaryCopyInWriter, types::Type};
@@ -33,6 +39,7 @@ use type_system::{
     url::{BaseUrl, VersionedUrl},
     DataTypeReference, EntityType, EntityTypeReference, PropertyType, PropertyTypeReference,
 };
+use uuid::Uuid;
 
 pub use self::{
     pool::{AsClient, PostgresStorePool},
@@ -41,10 +48,13 @@ pub use self::{
 #[cfg(hash_graph_test_environment)]
 use crate::store::error::DeletionError;
 use crate::store::{
-    error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
+    error::{
+        EntityIdAlreadyExists, OntologyTypeIsNotOwned, OntologyVersionDoesNotExist,
+        VersionedUrlAlreadyExists,
+    },
     postgres::ontology::{OntologyDatabaseType, OntologyId},
-    AccountStore, BaseUrlAlreadyExists, ConflictBehavior, InsertionError, QueryError, StoreError,
-    UpdateError,
+    AccountStore, BaseUrlAlreadyExists, ConflictBehavior, InsertionError, OwnershipTransfer,
+    OwnershipTransferRecord, QueryError, StoreError, UpdateError,
 };
 
 /// A Postgres-backed store
@@ -317,6 +327,95 @@ where
         })
     }
 
+    async fn deprecate_ontology_type(
+        &self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        deprecated_by_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        if let Some(successor) = successor {
+            if successor == id {
+                return Err(Report::new(UpdateError)
+                    .attach_printable("an ontology type cannot be its own successor")
+                    .attach_printable(id.clone()));
+            }
+
+            let exists: bool = self
+                .as_client()
+                .query_one(
+                    r#"
+                        SELECT EXISTS (
+                            SELECT 1
+                            FROM ontology_ids
+                            WHERE base_url = $1 AND version = $2
+                        );
+                    "#,
+                    &[
+                        &successor.base_url.as_str(),
+                        &OntologyTypeVersion::new(successor.version),
+                    ],
+                )
+                .await
+                .change_context(UpdateError)?
+                .get(0);
+            if !exists {
+                return Err(Report::new(OntologyVersionDoesNotExist)
+                    .attach_printable(successor.clone())
+                    .change_context(UpdateError));
+            }
+        }
+
+        let query: &str = r#"
+          INSERT INTO ontology_deprecations (
+            ontology_id,
+            deprecated_by_id,
+            deprecated_at,
+            successor_base_url,
+            successor_version
+          ) VALUES (
+            (SELECT ontology_id FROM ontology_ids WHERE base_url = $1 AND version = $2),
+            $3,
+            now(),
+            $4,
+            $5
+          )
+          ON CONFLICT (ontology_id) DO UPDATE SET
+            deprecated_by_id = EXCLUDED.deprecated_by_id,
+            deprecated_at = EXCLUDED.deprecated_at,
+            successor_base_url = EXCLUDED.successor_base_url,
+            successor_version = EXCLUDED.successor_version
+          RETURNING deprecated_at;
+        "#;
+
+        let deprecated_at = self
+            .as_client()
+            .query_one(query, &[
+                &id.base_url.as_str(),
+                &OntologyTypeVersion::new(id.version),
+                &deprecated_by_id,
+                &successor.map(|successor| successor.base_url.as_str()),
+                &successor.map(|successor| OntologyTypeVersion::new(successor.version)),
+            ])
+            .await
+            .map_err(Report::new)
+            .map_err(|report| match report.current_context().code() {
+                Some(&SqlState::NOT_NULL_VIOLATION) => report
+                    .change_context(OntologyVersionDoesNotExist)
+                    .attach_printable(id.clone())
+                    .change_context(UpdateError),
+                _ => report
+                    .change_context(UpdateError)
+                    .attach_printable(id.clone()),
+            })?
+            .get(0);
+
+        Ok(OntologyTypeDeprecation {
+            deprecated_by_id,
+            deprecated_at,
+            successor: successor.cloned(),
+        })
+    }
+
     async fn create_ontology_owned_metadata(
         &self,
         ontology_id: OntologyId,
@@ -794,16 +893,20 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                 owned_by_id,
                 temporal_versioning: OntologyTemporalMetadata { transaction_time },
             },
+            deprecation: None,
         }))
     }
 
     /// Updates the latest version of [`VersionedUrl::base_url`] and creates a new [`OntologyId`]
     /// for it.
     ///
+    /// The previous version is the highest version below the new version, so versions may be
+    /// skipped, e.g. when importing a later version of an ontology type.
+    ///
     /// # Errors
     ///
     /// - [`VersionedUrlAlreadyExists`] if [`VersionedUrl`] does already exist in the database
-    /// - [`OntologyVersionDoesNotExist`] if the previous version does not exist
+    /// - [`OntologyVersionDoesNotExist`] if no previous version exists
     /// - [`OntologyTypeIsNotOwned`] if ontology type is an external ontology type
     #[tracing::instrument(level = "debug", skip(self))]
     async fn update_owned_ontology_id(
@@ -826,11 +929,16 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                   FROM ontology_owned_metadata
                   NATURAL JOIN ontology_ids
                   WHERE base_url = $1
-                    AND version = $2
+                    AND version = (
+                      SELECT MAX(version)
+                      FROM ontology_ids
+                      WHERE base_url = $1
+                        AND version < $2
+                    )
                   LIMIT 1 -- There might be multiple versions of the same ontology, but we only
                           -- care about the `owned_by_id` which does not change when (un-)archiving.
                 ;"#,
-                &[&url.base_url.as_str(), &i64::from(url.version - 1)],
+                &[&url.base_url.as_str(), &i64::from(url.version)],
             )
             .await
             .change_context(UpdateError)?
@@ -844,9 +952,9 @@ impl PostgresStore<tokio_postgres::Transaction<'_>> {
                     SELECT 1
                     FROM ontology_ids
                     WHERE base_url = $1
-                      AND version = $2
+                      AND version < $2
                   );"#,
-                    &[&url.base_url.as_str(), &i64::from(url.version - 1)],
+                    &[&url.base_url.as_str(), &i64::from(url.version)],
                 )
                 .await
                 .change_context(UpdateError)
@@ -1211,15 +1319,363 @@ impl<C: AsClient> AccountStore for PostgresStore<C> {
 
         Ok(())
     }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn transfer_ownership(
+        &mut self,
+        from_owned_by_id: OwnedById,
+        to_owned_by_id: OwnedById,
+        actor_id: TransferredById,
+    ) -> Result<OwnershipTransfer, UpdateError> {
+        if from_owned_by_id == to_owned_by_id {
+            return Err(Report::new(UpdateError)
+                .attach_printable("cannot transfer the ownership to the current owner")
+                .attach_printable(from_owned_by_id));
+        }
+
+        let transaction = self.transaction().await.change_context(UpdateError)?;
+
+        let row = transaction
+            .as_client()
+            .query_one(
+                r#"
+                    INSERT INTO ownership_transfers (
+                        transfer_id,
+                        from_owned_by_id,
+                        to_owned_by_id,
+                        transferred_by_id,
+                        transferred_at
+                    ) VALUES (gen_random_uuid(), $1, $2, $3, now())
+                    RETURNING transfer_id, transferred_at;
+                "#,
+                &[&from_owned_by_id, &to_owned_by_id, &actor_id],
+            )
+            .await
+            .change_context(UpdateError)
+            .attach_printable(from_owned_by_id)
+            .attach_printable(to_owned_by_id)?;
+        let transfer_id: Uuid = row.get(0);
+        let transferred_at = Timestamp::from_anonymous(row.get(1));
+
+        // Ontology types are identified by their URL, so only the owner has to be changed. The
+        // previous owner is recorded with the transfer.
+        let ontology_types = transaction
+            .as_client()
+            .execute(
+                r#"
+                    WITH transferred AS (
+                        UPDATE ontology_owned_metadata
+                        SET owned_by_id = $2
+                        WHERE owned_by_id = $1
+                        RETURNING ontology_id
+                    )
+                    INSERT INTO ontology_ownership_transfers (transfer_id, ontology_id)
+                    SELECT $3, ontology_id FROM transferred;
+                "#,
+                &[&from_owned_by_id, &to_owned_by_id, &transfer_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        // Only entities, which currently exist, are transferred. Entities, which were transferred
+        // away from the receiving account before, keep their entity ID at the receiving account.
+        let entities = transaction
+            .as_client()
+            .execute(
+                r#"
+                    INSERT INTO entity_ownership_transfers (transfer_id, entity_uuid)
+                    SELECT DISTINCT $2::UUID, entity_uuid
+                    FROM entity_temporal_metadata
+                    WHERE owned_by_id = $1
+                      AND upper(transaction_time) IS NULL;
+                "#,
+                &[&from_owned_by_id, &transfer_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        let conflicting_entity_uuids: Vec<EntityUuid> = transaction
+            .as_client()
+            .query(
+                r#"
+                    SELECT entity_uuid
+                    FROM entity_ownership_transfers AS transferred
+                    WHERE transfer_id = $2
+                      AND EXISTS (
+                          SELECT 1
+                          FROM entity_ids
+                          WHERE owned_by_id = $1
+                            AND entity_uuid = transferred.entity_uuid
+                      )
+                      AND NOT EXISTS (
+                          SELECT 1
+                          FROM entity_ownership_transfers AS previous
+                          INNER JOIN ownership_transfers USING (transfer_id)
+                          WHERE ownership_transfers.from_owned_by_id = $1
+                            AND previous.entity_uuid = transferred.entity_uuid
+                      );
+                "#,
+                &[&to_owned_by_id, &transfer_id],
+            )
+            .await
+            .change_context(UpdateError)?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect();
+        if !conflicting_entity_uuids.is_empty() {
+            let mut report = Report::new(EntityIdAlreadyExists);
+            for entity_uuid in conflicting_entity_uuids {
+                report = report.attach_printable(EntityId {
+                    owned_by_id: to_owned_by_id,
+                    entity_uuid,
+                });
+            }
+            return Err(report.change_context(UpdateError));
+        }
+
+        // The owner is part of the entity ID, so the current rows of the entities are moved to the
+        // new entity IDs starting at the transfer, while the history up to the transfer is kept at
+        // the previous entity IDs. Changing the owner does not create a new version of the entity.
+        transaction
+            .as_client()
+            .execute(
+                r#"
+                    INSERT INTO entity_ids (owned_by_id, entity_uuid)
+                    SELECT $1, entity_uuid FROM entity_ownership_transfers WHERE transfer_id = $2
+                    ON CONFLICT DO NOTHING;
+                "#,
+                &[&to_owned_by_id, &transfer_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+        transaction
+            .as_client()
+            .execute(
+                r#"
+                    WITH current_rows AS (
+                        SELECT ctid, lower(transaction_time) AS transaction_time_start
+                        FROM entity_temporal_metadata
+                        WHERE owned_by_id = $1
+                          AND upper(transaction_time) IS NULL
+                    ), transferred_rows AS (
+                        UPDATE entity_temporal_metadata
+                        SET owned_by_id = $2,
+                            transaction_time = tstzrange(now(), NULL, '[)')
+                        FROM current_rows
+                        WHERE entity_temporal_metadata.ctid = current_rows.ctid
+                        RETURNING
+                            entity_temporal_metadata.entity_uuid,
+                            entity_temporal_metadata.entity_edition_id,
+                            entity_temporal_metadata.decision_time,
+                            current_rows.transaction_time_start
+                    )
+                    INSERT INTO entity_temporal_metadata (
+                        owned_by_id,
+                        entity_uuid,
+                        entity_edition_id,
+                        decision_time,
+                        transaction_time
+                    )
+                    SELECT
+                        $1,
+                        entity_uuid,
+                        entity_edition_id,
+                        decision_time,
+                        tstzrange(transaction_time_start, now(), '[)')
+                    FROM transferred_rows
+                    WHERE transaction_time_start < now();
+                "#,
+                &[&from_owned_by_id, &to_owned_by_id],
+            )
+            .await
+            .change_context(UpdateError)?;
+
+        // The links of the moved link entities are copied to their new entity IDs, replacing the
+        // links of entities transferred back to their previous owner. Other links are updated to
+        // point to the new entity IDs, unless they are part of the history of the previous owner.
+        // As the endpoints are not versioned, this applies to all editions of links of other
+        // owners, see `AccountStore::transfer_ownership`.
+        for (table, endpoint) in [
+            ("entity_has_left_entity", "left"),
+            ("entity_has_right_entity", "right"),
+        ] {
+            transaction
+                .as_client()
+                .execute(
+                    &format!(
+                        r#"
+                            DELETE FROM {table}
+                            WHERE owned_by_id = $1
+                              AND entity_uuid IN (
+                                  SELECT entity_uuid FROM entity_ownership_transfers WHERE transfer_id = $2
+                              );
+                        "#
+                    ),
+                    &[&to_owned_by_id, &transfer_id],
+                )
+                .await
+                .change_context(UpdateError)?;
+            transaction
+                .as_client()
+                .execute(
+                    &format!(
+                        r#"
+                            INSERT INTO {table} (
+                                owned_by_id,
+                                entity_uuid,
+                                {endpoint}_owned_by_id,
+                                {endpoint}_entity_uuid
+                            )
+                            SELECT
+                                $2,
+                                entity_uuid,
+                                CASE
+                                    WHEN {endpoint}_owned_by_id = $1 AND {endpoint}_entity_uuid IN (
+                                        SELECT entity_uuid FROM entity_ownership_transfers WHERE transfer_id = $3
+                                    ) THEN $2
+                                    ELSE {endpoint}_owned_by_id
+                                END,
+                                {endpoint}_entity_uuid
+                            FROM {table}
+                            WHERE owned_by_id = $1
+                              AND entity_uuid IN (
+                                  SELECT entity_uuid FROM entity_ownership_transfers WHERE transfer_id = $3
+                              );
+                        "#
+                    ),
+                    &[&from_owned_by_id, &to_owned_by_id, &transfer_id],
+                )
+                .await
+                .change_context(UpdateError)?;
+            transaction
+                .as_client()
+                .execute(
+                    &format!(
+                        r#"
+                            UPDATE {table}
+                            SET {endpoint}_owned_by_id = $2
+                            WHERE owned_by_id <> $1
+                              AND {endpoint}_owned_by_id = $1
+                              AND {endpoint}_entity_uuid IN (
+                                  SELECT entity_uuid FROM entity_ownership_transfers WHERE transfer_id = $3
+                              );
+                        "#
+                    ),
+                    &[&from_owned_by_id, &to_owned_by_id, &transfer_id],
+                )
+                .await
+                .change_context(UpdateError)?;
+        }
+
+        transaction.commit().await.change_context(UpdateError)?;
+
+        Ok(OwnershipTransfer {
+            from_owned_by_id,
+            to_owned_by_id,
+            transferred_by_id: actor_id,
+            transferred_at,
+            ontology_types,
+            entities,
+        })
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_ownership_transfers(
+        &self,
+        owned_by_id: OwnedById,
+    ) -> Result<Vec<OwnershipTransferRecord>, QueryError> {
+        self.read_ownership_transfers(Some(owned_by_id), None, None)
+            .await
+    }
 }
 
 impl<C: AsClient> PostgresStore<C> {
+    /// Reads the ownership transfers from or to `owned_by_id`, which were transferred in the
+    /// specified interval, ordered by the time they were transferred at.
+    ///
+    /// If no account is specified, the transfers of all accounts are read.
+    pub(crate) async fn read_ownership_transfers(
+        &self,
+        owned_by_id: Option<OwnedById>,
+        transferred_since: Option<Timestamp<TransactionTime>>,
+        transferred_until: Option<Timestamp<TransactionTime>>,
+    ) -> Result<Vec<OwnershipTransferRecord>, QueryError> {
+        self.as_client()
+            .query(
+                r#"
+                    SELECT
+                        transfer_id,
+                        from_owned_by_id,
+                        to_owned_by_id,
+                        transferred_by_id,
+                        transferred_at,
+                        ARRAY(
+                            SELECT base_url
+                            FROM ontology_ownership_transfers
+                            INNER JOIN ontology_ids USING (ontology_id)
+                            WHERE ontology_ownership_transfers.transfer_id = ownership_transfers.transfer_id
+                            ORDER BY base_url, version
+                        ),
+                        ARRAY(
+                            SELECT version
+                            FROM ontology_ownership_transfers
+                            INNER JOIN ontology_ids USING (ontology_id)
+                            WHERE ontology_ownership_transfers.transfer_id = ownership_transfers.transfer_id
+                            ORDER BY base_url, version
+                        ),
+                        ARRAY(
+                            SELECT entity_uuid
+                            FROM entity_ownership_transfers
+                            WHERE entity_ownership_transfers.transfer_id = ownership_transfers.transfer_id
+                            ORDER BY entity_uuid
+                        )
+                    FROM ownership_transfers
+                    WHERE ($1::UUID IS NULL OR $1 IN (from_owned_by_id, to_owned_by_id))
+                      AND ($2::TIMESTAMPTZ IS NULL OR transferred_at >= $2)
+                      AND ($3::TIMESTAMPTZ IS NULL OR transferred_at <= $3)
+                    ORDER BY transferred_at, transfer_id;
+                "#,
+                &[&owned_by_id, &transferred_since, &transferred_until],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| {
+                let base_urls: Vec<String> = row.get(5);
+                let versions: Vec<OntologyTypeVersion> = row.get(6);
+                Ok(OwnershipTransferRecord {
+                    transfer_id: row.get(0),
+                    from_owned_by_id: row.get(1),
+                    to_owned_by_id: row.get(2),
+                    transferred_by_id: row.get(3),
+                    transferred_at: Timestamp::from_anonymous(row.get(4)),
+                    ontology_type_ids: base_urls
+                        .into_iter()
+                        .zip(versions)
+                        .map(|(base_url, version)| {
+                            Ok(VersionedUrl {
+                                base_url: BaseUrl::new(base_url).change_context(QueryError)?,
+                                version: version.inner(),
+                            })
+                        })
+                        .collect::<Result<_, QueryError>>()?,
+                    entity_uuids: row.get(7),
+                })
+            })
+            .collect()
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     #[cfg(hash_graph_test_environment)]
     pub async fn delete_accounts(&mut self) -> Result<(), DeletionError> {
         self.as_client()
             .client()
-            .simple_query("DELETE FROM accounts;")
+            .simple_query(
+                "DELETE FROM entity_ownership_transfers;
+                 DELETE FROM ontology_ownership_transfers;
+                 DELETE FROM ownership_transfers;
+                 DELETE FROM accounts;",
+            )
             .await
             .change_context(DeletionError)?;
 
@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    knowledge::entity::{EntityId, EntityRecordId},
+    ontology::OntologyTypeRecordId,
+};
+use temporal_versioning::{Timestamp, TransactionTime};
+use tokio_postgres::Row;
+use type_system::url::BaseUrl;
+
+use crate::store::{
+    AsClient, ChangeEvent, ChangeFeedPage, ChangeFeedQuery, ChangeFeedResumeToken, ChangeFeedStore,
+    ChangeKind, ChangedRecord, PostgresStore, QueryError,
+};
+
+/// Returns all change events in the order of their transaction time.
+///
+/// Every row contains the kind of the changed record, the kind of the change, the transaction time
+/// and a sequence id to order changes at the same transaction time. Depending on the kind of the
+/// record, either the ontology columns (`base_url`, `version`) or the entity columns
+/// (`owned_by_id`, `entity_uuid`) are set. For entities, the sequence id is the edition id.
+///
+/// An entity edition is written at the earliest start of its temporal rows and closed at the end
+/// of any of its temporal rows. Every kind of change is read with a range scan on the start or the
+/// end of the transaction time, so only the requested page is read.
+///
+/// Changes are only returned before the horizon passed as `$6`, see
+/// [`PostgresStore::transaction_time_horizon`].
+const CHANGE_FEED_QUERY: &str = r#"
+    WITH
+      bounds AS (
+        SELECT
+          GREATEST($1::TIMESTAMPTZ, $3::TIMESTAMPTZ) AS since,
+          LEAST($2::TIMESTAMPTZ, $6::TIMESTAMPTZ) AS until
+      ),
+      ontology_types AS NOT MATERIALIZED (
+        SELECT ontology_id, 'dataType' AS record_kind FROM data_types
+        UNION ALL
+        SELECT ontology_id, 'propertyType' AS record_kind FROM property_types
+        UNION ALL
+        SELECT ontology_id, 'entityType' AS record_kind FROM entity_types
+      ),
+      changes AS (
+        (
+          SELECT DISTINCT
+            'entity' AS record_kind,
+            CASE
+              WHEN NOT EXISTS (
+                SELECT 1
+                FROM entity_temporal_metadata AS previous
+                WHERE previous.owned_by_id = written.owned_by_id
+                  AND previous.entity_uuid = written.entity_uuid
+                  AND lower(previous.transaction_time) < lower(written.transaction_time)
+              ) THEN 'created'
+              WHEN entity_editions.archived THEN 'archived'
+              ELSE 'updated'
+            END AS change_kind,
+            lower(written.transaction_time) AS transaction_time,
+            written.entity_edition_id AS sequence_id,
+            NULL::TEXT AS base_url,
+            NULL::BIGINT AS version,
+            written.owned_by_id,
+            written.entity_uuid
+          FROM entity_temporal_metadata AS written
+          JOIN entity_editions USING (entity_edition_id)
+          WHERE lower(written.transaction_time) >= (SELECT since FROM bounds)
+            AND lower(written.transaction_time) < (SELECT until FROM bounds)
+            AND ($3::TIMESTAMPTZ IS NULL
+              OR (lower(written.transaction_time), written.entity_edition_id) > ($3, $4))
+            AND NOT EXISTS (
+              SELECT 1
+              FROM entity_temporal_metadata AS earlier
+              WHERE earlier.entity_edition_id = written.entity_edition_id
+                AND lower(earlier.transaction_time) < lower(written.transaction_time)
+            )
+          ORDER BY transaction_time, sequence_id
+          LIMIT $5
+        )
+
+        UNION ALL
+
+        (
+          SELECT DISTINCT
+            'entity' AS record_kind,
+            'closed' AS change_kind,
+            upper(closed.transaction_time) AS transaction_time,
+            closed.entity_edition_id AS sequence_id,
+            NULL::TEXT AS base_url,
+            NULL::BIGINT AS version,
+            closed.owned_by_id,
+            closed.entity_uuid
+          FROM entity_temporal_metadata AS closed
+          WHERE upper(closed.transaction_time) >= (SELECT since FROM bounds)
+            AND upper(closed.transaction_time) < (SELECT until FROM bounds)
+            AND ($3::TIMESTAMPTZ IS NULL
+              OR (upper(closed.transaction_time), closed.entity_edition_id) > ($3, $4))
+          ORDER BY transaction_time, sequence_id
+          LIMIT $5
+        )
+
+        UNION ALL
+
+        (
+          SELECT
+            ontology_types.record_kind,
+            CASE
+              WHEN ontology_ids.version = 1 AND NOT EXISTS (
+                SELECT 1
+                FROM ontology_temporal_metadata AS previous
+                WHERE previous.ontology_id = ontology_temporal_metadata.ontology_id
+                  AND lower(previous.transaction_time)
+                    < lower(ontology_temporal_metadata.transaction_time)
+              ) THEN 'created'
+              ELSE 'updated'
+            END AS change_kind,
+            lower(ontology_temporal_metadata.transaction_time) AS transaction_time,
+            ontology_temporal_metadata.ontology_id AS sequence_id,
+            ontology_ids.base_url,
+            ontology_ids.version,
+            NULL::UUID AS owned_by_id,
+            NULL::UUID AS entity_uuid
+          FROM ontology_temporal_metadata
+          JOIN ontology_ids USING (ontology_id)
+          JOIN ontology_types USING (ontology_id)
+          WHERE lower(ontology_temporal_metadata.transaction_time) >= (SELECT since FROM bounds)
+            AND lower(ontology_temporal_metadata.transaction_time) < (SELECT until FROM bounds)
+            AND ($3::TIMESTAMPTZ IS NULL
+              OR (
+                lower(ontology_temporal_metadata.transaction_time),
+                ontology_temporal_metadata.ontology_id
+              ) > ($3, $4))
+          ORDER BY
+            lower(ontology_temporal_metadata.transaction_time),
+            ontology_temporal_metadata.ontology_id
+          LIMIT $5
+        )
+
+        UNION ALL
+
+        (
+          SELECT
+            ontology_types.record_kind,
+            'archived' AS change_kind,
+            upper(ontology_temporal_metadata.transaction_time) AS transaction_time,
+            ontology_temporal_metadata.ontology_id AS sequence_id,
+            ontology_ids.base_url,
+            ontology_ids.version,
+            NULL::UUID AS owned_by_id,
+            NULL::UUID AS entity_uuid
+          FROM ontology_temporal_metadata
+          JOIN ontology_ids USING (ontology_id)
+          JOIN ontology_types USING (ontology_id)
+          WHERE upper(ontology_temporal_metadata.transaction_time) >= (SELECT since FROM bounds)
+            AND upper(ontology_temporal_metadata.transaction_time) < (SELECT until FROM bounds)
+            AND ($3::TIMESTAMPTZ IS NULL
+              OR (
+                upper(ontology_temporal_metadata.transaction_time),
+                ontology_temporal_metadata.ontology_id
+              ) > ($3, $4))
+          ORDER BY
+            upper(ontology_temporal_metadata.transaction_time),
+            ontology_temporal_metadata.ontology_id
+          LIMIT $5
+        )
+      )
+    SELECT
+      record_kind,
+      change_kind,
+      transaction_time,
+      sequence_id,
+      base_url,
+      version,
+      owned_by_id,
+      entity_uuid
+    FROM changes
+    ORDER BY transaction_time, sequence_id
+    LIMIT $5;
+"#;
+
+/// Returns the transaction time up to which all changes are visible.
+///
+/// This is the start of the oldest transaction still in progress. Transactions use the time they
+/// started as transaction time, so a running transaction could still write records before the
+/// current time.
+///
+/// Postgres only reports the start of transactions of other roles to members of
+/// `pg_read_all_stats`. The first column states if the current role is allowed to see them.
+const TRANSACTION_TIME_HORIZON_QUERY: &str = r#"
+    SELECT
+      pg_has_role('pg_read_all_stats', 'USAGE'),
+      LEAST(
+        clock_timestamp(),
+        (
+          SELECT min(xact_start)
+          FROM pg_stat_activity
+          WHERE pid <> pg_backend_pid()
+            AND backend_type = 'client backend'
+            AND xact_start IS NOT NULL
+        )
+      );
+"#;
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Returns the transaction time up to which all changes are visible.
+    ///
+    /// All records written before the horizon are committed, so reading changes up to the horizon
+    /// never skips a record, which is committed later.
+    ///
+    /// # Errors
+    ///
+    /// - If the role of the store is not a member of `pg_read_all_stats`, as the transactions of
+    ///   other roles would be missing
+    /// - If the horizon could not be read from the store
+    pub(crate) async fn transaction_time_horizon(
+        &self,
+    ) -> Result<Timestamp<TransactionTime>, QueryError> {
+        let row = self
+            .as_client()
+            .query_one(TRANSACTION_TIME_HORIZON_QUERY, &[])
+            .await
+            .change_context(QueryError)?;
+
+        let can_read_all_stats: bool = row.get(0);
+        if !can_read_all_stats {
+            return Err(Report::new(QueryError).attach_printable(
+                "the role of the store has to be a member of `pg_read_all_stats` to see the \
+                 transactions of other roles",
+            ));
+        }
+
+        Ok(Timestamp::from_anonymous(row.get(1)))
+    }
+}
+
+fn change_event_from_row(row: &Row) -> Result<(ChangeEvent, ChangeFeedResumeToken), QueryError> {
+    let record_kind: &str = row.get(0);
+    let change_kind: &str = row.get(1);
+    let transaction_time = Timestamp::from_anonymous(row.get(2));
+
+    let kind = match change_kind {
+        "created" => ChangeKind::Created,
+        "updated" => ChangeKind::Updated,
+        "archived" => ChangeKind::Archived,
+        "closed" => ChangeKind::Closed,
+        _ => {
+            return Err(Report::new(QueryError)
+                .attach_printable(format!("unknown change kind: {change_kind}")));
+        }
+    };
+
+    let ontology_record_id = || -> Result<_, QueryError> {
+        Ok(OntologyTypeRecordId {
+            base_url: BaseUrl::new(row.get(4)).change_context(QueryError)?,
+            version: row.get(5),
+        })
+    };
+    let record = match record_kind {
+        "dataType" => ChangedRecord::DataType(ontology_record_id()?),
+        "propertyType" => ChangedRecord::PropertyType(ontology_record_id()?),
+        "entityType" => ChangedRecord::EntityType(ontology_record_id()?),
+        "entity" => ChangedRecord::Entity(EntityRecordId {
+            entity_id: EntityId {
+                owned_by_id: row.get(6),
+                entity_uuid: row.get(7),
+            },
+            edition_id: row.get(3),
+        }),
+        _ => {
+            return Err(Report::new(QueryError)
+                .attach_printable(format!("unknown record kind: {record_kind}")));
+        }
+    };
+
+    Ok((
+        ChangeEvent {
+            kind,
+            record,
+            transaction_time,
+        },
+        ChangeFeedResumeToken {
+            transaction_time,
+            sequence_id: row.get(3),
+        },
+    ))
+}
+
+#[async_trait]
+impl<C: AsClient> ChangeFeedStore for PostgresStore<C> {
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_changes(&self, query: &ChangeFeedQuery) -> Result<ChangeFeedPage, QueryError> {
+        // One more change than requested is read to determine if more changes are available.
+        let limit = i64::try_from(query.limit)
+            .unwrap_or(i64::MAX)
+            .saturating_add(1);
+
+        let horizon = self.transaction_time_horizon().await?;
+        let rows = self
+            .as_client()
+            .query(CHANGE_FEED_QUERY, &[
+                &query.since,
+                &query.until,
+                &query.resume_token.map(|token| token.transaction_time),
+                &query.resume_token.map(|token| token.sequence_id),
+                &limit,
+                &horizon,
+            ])
+            .await
+            .change_context(QueryError)?;
+
+        let has_more = rows.len() > query.limit;
+        let mut events = Vec::with_capacity(rows.len().min(query.limit));
+        let mut resume_token = query.resume_token;
+        for row in rows.iter().take(query.limit) {
+            let (event, token) = change_event_from_row(row)?;
+            events.push(event);
+            resume_token = Some(token);
+        }
+
+        Ok(ChangeFeedPage {
+            events,
+            resume_token,
+            has_more,
+        })
+    }
+}
@@ -1,8 +1,10 @@
 mod data_type;
 mod entity_type;
+mod import;
 mod ontology_id;
 mod property_type;
 mod read;
+mod usage;
 
 #[cfg(hash_graph_test_environment)]
 use error_stack::{Result, ResultExt};
@@ -48,6 +50,33 @@ impl PostgresStore<Transaction<'_>> {
         &self,
         ontology_ids: &[OntologyId],
     ) -> Result<(), DeletionError> {
+        self.as_client()
+            .query(
+                r"
+                    DELETE FROM ontology_deprecations
+                    WHERE ontology_id = ANY($1)
+                       OR (successor_base_url, successor_version) IN (
+                           SELECT base_url, version
+                           FROM ontology_ids
+                           WHERE ontology_id = ANY($1)
+                       )
+                ",
+                &[&ontology_ids],
+            )
+            .await
+            .change_context(DeletionError)?;
+
+        self.as_client()
+            .query(
+                r"
+                    DELETE FROM ontology_ownership_transfers
+                    WHERE ontology_id = ANY($1)
+                ",
+                &[&ontology_ids],
+            )
+            .await
+            .change_context(DeletionError)?;
+
         self.as_client()
             .query(
                 r"
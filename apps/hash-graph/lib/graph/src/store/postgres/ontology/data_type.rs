@@ -6,9 +6,9 @@ use futures::{stream, TryStreamExt};
 use graph_types::{
     ontology::{
         DataTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
-        PartialOntologyElementMetadata,
+        OntologyTypeDeprecation, PartialOntologyElementMetadata,
     },
-    provenance::{RecordArchivedById, RecordCreatedById},
+    provenance::{DeprecatedById, RecordArchivedById, RecordCreatedById},
 };
 use temporal_versioning::RightBoundedTemporalInterval;
 use type_system::{url::VersionedUrl, DataType};
@@ -16,9 +16,13 @@ use type_system::{url::VersionedUrl, DataType};
 #[cfg(hash_graph_test_environment)]
 use crate::store::error::DeletionError;
 use crate::{
+    ontology::OntologyTypeUsage,
     store::{
         crud::Read,
-        postgres::{ontology::OntologyId, TraversalContext},
+        postgres::{
+            ontology::{usage::UsedOntologyType, OntologyId},
+            TraversalContext,
+        },
         AsClient, ConflictBehavior, DataTypeStore, InsertionError, PostgresStore, QueryError,
         Record, UpdateError,
     },
@@ -181,6 +185,15 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_data_type_usage(
+        &self,
+        data_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        self.read_ontology_type_usage(data_type_id, UsedOntologyType::DataType)
+            .await
+    }
+
     #[tracing::instrument(level = "info", skip(self, data_type))]
     async fn update_data_type(
         &mut self,
@@ -213,4 +226,14 @@ impl<C: AsClient> DataTypeStore for PostgresStore<C> {
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
         self.unarchive_ontology_type(id, record_created_by_id).await
     }
+
+    async fn deprecate_data_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        deprecated_by_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.deprecate_ontology_type(id, successor, deprecated_by_id)
+            .await
+    }
 }
@@ -5,10 +5,11 @@ use error_stack::{Report, Result, ResultExt};
 use futures::{stream, TryStreamExt};
 use graph_types::{
     ontology::{
-        EntityTypeMetadata, EntityTypeWithMetadata, OntologyTemporalMetadata, OntologyTypeRecordId,
-        PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
+        EntityTypeMetadata, EntityTypeWithMetadata, OntologyTemporalMetadata,
+        OntologyTypeDeprecation, OntologyTypeRecordId, PartialCustomEntityTypeMetadata,
+        PartialCustomOntologyMetadata, PartialEntityTypeMetadata,
     },
-    provenance::{ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
+    provenance::{DeprecatedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use temporal_versioning::RightBoundedTemporalInterval;
 use type_system::{
@@ -19,6 +20,10 @@ use type_system::{
 #[cfg(hash_graph_test_environment)]
 use crate::store::error::DeletionError;
 use crate::{
+    ontology::{
+        validation::{OntologySchemas, SchemaReference},
+        ResolvedEntityType,
+    },
     store::{
         crud::Read,
         postgres::{
@@ -327,6 +332,25 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_resolved_entity_type(
+        &self,
+        entity_type_id: &VersionedUrl,
+    ) -> Result<ResolvedEntityType, QueryError> {
+        let mut schemas = OntologySchemas::default();
+        schemas
+            .read(self, vec![SchemaReference::EntityType(
+                entity_type_id.clone(),
+            )])
+            .await?;
+
+        ResolvedEntityType::resolve(&schemas, entity_type_id).ok_or_else(|| {
+            Report::new(QueryError).attach_printable(format!(
+                "entity type `{entity_type_id}` could not be resolved"
+            ))
+        })
+    }
+
     #[tracing::instrument(level = "info", skip(self, entity_type))]
     async fn update_entity_type(
         &mut self,
@@ -393,4 +417,14 @@ impl<C: AsClient> EntityTypeStore for PostgresStore<C> {
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
         self.unarchive_ontology_type(id, record_created_by_id).await
     }
+
+    async fn deprecate_entity_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        deprecated_by_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.deprecate_ontology_type(id, successor, deprecated_by_id)
+            .await
+    }
 }
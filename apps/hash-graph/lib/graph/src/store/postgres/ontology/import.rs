@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+
+use error_stack::{Report, Result, ResultExt};
+use graph_types::{
+    ontology::{
+        OntologyTypeRecordId, OntologyTypeVersion, PartialCustomEntityTypeMetadata,
+        PartialCustomOntologyMetadata, PartialEntityTypeMetadata, PartialOntologyElementMetadata,
+    },
+    provenance::{OwnedById, ProvenanceMetadata, RecordCreatedById},
+};
+use serde_json::Value;
+use type_system::{url::VersionedUrl, DataType, EntityType, PropertyType};
+
+use crate::{
+    ontology::{ImportedOntologyType, OntologyImportSummary, OntologyTypeImport},
+    store::{
+        error::VersionedUrlAlreadyExists, postgres::ontology::OntologyDatabaseType, AsClient,
+        ConflictBehavior, DataTypeStore, EntityTypeStore, InsertionError, PostgresStore,
+        PropertyTypeStore,
+    },
+};
+
+/// How an ontology type is imported.
+enum ImportAction {
+    /// The ontology type does not exist, yet.
+    Create,
+    /// A previous version of the ontology type exists.
+    Update,
+    /// The ontology type already exists with the same schema.
+    Skip,
+}
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Imports the ontology types in a single transaction.
+    ///
+    /// Ontology types, which already exist with the same schema, are skipped. If a previous
+    /// version of an ontology type exists or is imported, the ontology type is imported as an
+    /// update of the latest of them, otherwise it's created and owned by `owned_by_id`. Consecutive
+    /// ontology types to create are created in a batch, so they may reference each other.
+    ///
+    /// # Errors
+    ///
+    /// - [`VersionedUrlAlreadyExists`] if an ontology type already exists with a different schema
+    /// - if an ontology type references an ontology type, which is neither imported nor exists
+    /// - if creating or updating any of the ontology types fails
+    #[tracing::instrument(level = "info", skip(self, import), fields(ontology_types = import.len()))]
+    pub async fn import_ontology_types(
+        &mut self,
+        import: OntologyTypeImport,
+        owned_by_id: OwnedById,
+        actor_id: RecordCreatedById,
+    ) -> Result<OntologyImportSummary, InsertionError> {
+        let mut transaction = self.transaction().await.change_context(InsertionError)?;
+
+        transaction.ensure_references_exist(&import).await?;
+
+        let mut summary = OntologyImportSummary::default();
+        let mut pending = Vec::new();
+        for ontology_type in import {
+            // Later versions of an ontology type to create are imported as updates of it
+            if pending.iter().any(|pending: &ImportedOntologyType| {
+                pending.id().base_url == ontology_type.id().base_url
+            }) {
+                summary.created.extend(
+                    transaction
+                        .create_imported_types(pending.drain(..).collect(), owned_by_id, actor_id)
+                        .await?,
+                );
+            }
+
+            match transaction.import_action(&ontology_type).await? {
+                ImportAction::Skip => summary.unchanged.push(ontology_type.id().clone()),
+                ImportAction::Create => pending.push(ontology_type),
+                ImportAction::Update => {
+                    // The update may reference ontology types, which are pending to be created
+                    summary.created.extend(
+                        transaction
+                            .create_imported_types(
+                                pending.drain(..).collect(),
+                                owned_by_id,
+                                actor_id,
+                            )
+                            .await?,
+                    );
+
+                    let id = ontology_type.id().clone();
+                    match ontology_type {
+                        ImportedOntologyType::DataType(data_type) => transaction
+                            .update_data_type(data_type, actor_id)
+                            .await
+                            .map(drop),
+                        ImportedOntologyType::PropertyType(property_type) => transaction
+                            .update_property_type(property_type, actor_id)
+                            .await
+                            .map(drop),
+                        ImportedOntologyType::EntityType(entity_type) => transaction
+                            .update_entity_type(entity_type, actor_id, None)
+                            .await
+                            .map(drop),
+                    }
+                    .change_context(InsertionError)
+                    .attach_printable_lazy(|| id.clone())?;
+                    summary.updated.push(id);
+                }
+            }
+        }
+        summary.created.extend(
+            transaction
+                .create_imported_types(pending, owned_by_id, actor_id)
+                .await?,
+        );
+
+        transaction.commit().await.change_context(InsertionError)?;
+
+        Ok(summary)
+    }
+
+    /// Checks that every ontology type referenced by the `import`, which is not imported itself,
+    /// exists.
+    async fn ensure_references_exist(
+        &self,
+        import: &OntologyTypeImport,
+    ) -> Result<(), InsertionError> {
+        let imported = import
+            .iter()
+            .map(ImportedOntologyType::id)
+            .collect::<HashSet<_>>();
+
+        let mut checked = HashSet::new();
+        let mut missing = Vec::new();
+        for ontology_type in import.iter() {
+            for reference in ontology_type.references() {
+                if imported.contains(&reference) || !checked.insert(reference.clone()) {
+                    continue;
+                }
+
+                let exists = self
+                    .as_client()
+                    .query_opt(
+                        r"
+                            SELECT 1
+                            FROM ontology_ids
+                            WHERE base_url = $1 AND version = $2
+                        ",
+                        &[
+                            &reference.base_url.as_str(),
+                            &OntologyTypeVersion::new(reference.version),
+                        ],
+                    )
+                    .await
+                    .change_context(InsertionError)?
+                    .is_some();
+                if !exists {
+                    missing.push(format!(
+                        "`{reference}` referenced by `{}` does not exist",
+                        ontology_type.id()
+                    ));
+                }
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            let mut report = Report::new(InsertionError);
+            for missing in missing {
+                report = report.attach_printable(missing);
+            }
+            Err(report)
+        }
+    }
+
+    /// Determines how the ontology type is imported by comparing it to the stored versions.
+    async fn import_action(
+        &self,
+        ontology_type: &ImportedOntologyType,
+    ) -> Result<ImportAction, InsertionError> {
+        let id = ontology_type.id();
+        let latest_version: Option<OntologyTypeVersion> = self
+            .as_client()
+            .query_one(
+                r"
+                    SELECT MAX(version)
+                    FROM ontology_ids
+                    WHERE base_url = $1
+                ",
+                &[&id.base_url.as_str()],
+            )
+            .await
+            .change_context(InsertionError)?
+            .get(0);
+
+        match latest_version {
+            None => return Ok(ImportAction::Create),
+            Some(latest_version) if latest_version.inner() < id.version => {
+                return Ok(ImportAction::Update);
+            }
+            Some(_) => {}
+        }
+
+        let table = match ontology_type {
+            ImportedOntologyType::DataType(_) => DataType::table(),
+            ImportedOntologyType::PropertyType(_) => PropertyType::table(),
+            ImportedOntologyType::EntityType(_) => EntityType::table(),
+        };
+        // The table name is not user input, so it's safe to format it into the query.
+        let stored_schema: Option<Value> = self
+            .as_client()
+            .query_opt(
+                &format!(
+                    r"
+                        SELECT schema
+                        FROM {table}
+                        JOIN ontology_ids USING (ontology_id)
+                        WHERE base_url = $1 AND version = $2
+                    "
+                ),
+                &[&id.base_url.as_str(), &OntologyTypeVersion::new(id.version)],
+            )
+            .await
+            .change_context(InsertionError)?
+            .map(|row| row.get(0));
+
+        if stored_schema.is_some_and(|schema| schema == ontology_type.schema()) {
+            Ok(ImportAction::Skip)
+        } else {
+            Err(Report::new(VersionedUrlAlreadyExists)
+                .attach_printable(id.clone())
+                .change_context(InsertionError))
+        }
+    }
+
+    /// Creates the ontology types in one batch per kind.
+    ///
+    /// As data types are created before property types and property types before entity types,
+    /// every ontology type may reference any other ontology type in the batch.
+    async fn create_imported_types(
+        &mut self,
+        ontology_types: Vec<ImportedOntologyType>,
+        owned_by_id: OwnedById,
+        actor_id: RecordCreatedById,
+    ) -> Result<Vec<VersionedUrl>, InsertionError> {
+        let common_metadata = PartialCustomOntologyMetadata::Owned {
+            provenance: ProvenanceMetadata {
+                record_created_by_id: actor_id,
+                record_archived_by_id: None,
+            },
+            owned_by_id,
+        };
+        let ontology_element_metadata = |id: &VersionedUrl| PartialOntologyElementMetadata {
+            record_id: OntologyTypeRecordId::from(id.clone()),
+            custom: common_metadata.clone(),
+        };
+
+        let mut created = Vec::with_capacity(ontology_types.len());
+        let mut data_types = Vec::new();
+        let mut property_types = Vec::new();
+        let mut entity_types = Vec::new();
+        for ontology_type in ontology_types {
+            created.push(ontology_type.id().clone());
+            match ontology_type {
+                ImportedOntologyType::DataType(data_type) => {
+                    let metadata = ontology_element_metadata(data_type.id());
+                    data_types.push((data_type, metadata));
+                }
+                ImportedOntologyType::PropertyType(property_type) => {
+                    let metadata = ontology_element_metadata(property_type.id());
+                    property_types.push((property_type, metadata));
+                }
+                ImportedOntologyType::EntityType(entity_type) => {
+                    let metadata = PartialEntityTypeMetadata {
+                        record_id: OntologyTypeRecordId::from(entity_type.id().clone()),
+                        custom: PartialCustomEntityTypeMetadata {
+                            common: common_metadata.clone(),
+                            label_property: None,
+                        },
+                    };
+                    entity_types.push((entity_type, metadata));
+                }
+            }
+        }
+
+        if !data_types.is_empty() {
+            self.create_data_types(data_types, ConflictBehavior::Fail)
+                .await?;
+        }
+        if !property_types.is_empty() {
+            self.create_property_types(property_types, ConflictBehavior::Fail)
+                .await?;
+        }
+        if !entity_types.is_empty() {
+            self.create_entity_types(entity_types, ConflictBehavior::Fail)
+                .await?;
+        }
+
+        Ok(created)
+    }
+}
@@ -5,10 +5,10 @@ use error_stack::{Report, Result, ResultExt};
 use futures::{stream, TryStreamExt};
 use graph_types::{
     ontology::{
-        OntologyElementMetadata, OntologyTemporalMetadata, PartialOntologyElementMetadata,
-        PropertyTypeWithMetadata,
+        OntologyElementMetadata, OntologyTemporalMetadata, OntologyTypeDeprecation,
+        PartialOntologyElementMetadata, PropertyTypeWithMetadata,
     },
-    provenance::{RecordArchivedById, RecordCreatedById},
+    provenance::{DeprecatedById, RecordArchivedById, RecordCreatedById},
 };
 use temporal_versioning::RightBoundedTemporalInterval;
 use type_system::{url::VersionedUrl, PropertyType};
@@ -16,10 +16,11 @@ use type_system::{url::VersionedUrl, PropertyType};
 #[cfg(hash_graph_test_environment)]
 use crate::store::error::DeletionError;
 use crate::{
+    ontology::OntologyTypeUsage,
     store::{
         crud::Read,
         postgres::{
-            ontology::{read::OntologyTypeTraversalData, OntologyId},
+            ontology::{read::OntologyTypeTraversalData, usage::UsedOntologyType, OntologyId},
             query::ReferenceTable,
             TraversalContext,
         },
@@ -299,6 +300,15 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
         Ok(subgraph)
     }
 
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_property_type_usage(
+        &self,
+        property_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        self.read_ontology_type_usage(property_type_id, UsedOntologyType::PropertyType)
+            .await
+    }
+
     #[tracing::instrument(level = "info", skip(self, property_type))]
     async fn update_property_type(
         &mut self,
@@ -346,4 +356,14 @@ impl<C: AsClient> PropertyTypeStore for PostgresStore<C> {
     ) -> Result<OntologyTemporalMetadata, UpdateError> {
         self.unarchive_ontology_type(id, record_created_by_id).await
     }
+
+    async fn deprecate_property_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+        deprecated_by_id: DeprecatedById,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.deprecate_ontology_type(id, successor, deprecated_by_id)
+            .await
+    }
 }
@@ -8,16 +8,18 @@ use graph_types::{
     ontology::{
         CustomEntityTypeMetadata, CustomOntologyMetadata, DataTypeWithMetadata, EntityTypeMetadata,
         EntityTypeWithMetadata, OntologyElementMetadata, OntologyTemporalMetadata,
-        OntologyTypeRecordId, OntologyTypeVersion, OntologyTypeWithMetadata,
-        PropertyTypeWithMetadata,
+        OntologyTypeDeprecation, OntologyTypeRecordId, OntologyTypeVersion,
+        OntologyTypeWithMetadata, PropertyTypeWithMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordArchivedById, RecordCreatedById},
 };
 use postgres_types::{FromSql, Type};
 use serde::Deserialize;
 use temporal_versioning::RightBoundedTemporalInterval;
 use time::OffsetDateTime;
-use tokio_postgres::GenericClient;
+use tokio_postgres::{GenericClient, Row};
 use type_system::{
     url::{BaseUrl, VersionedUrl},
     DataType, EntityType, PropertyType,
@@ -70,6 +72,40 @@ impl<'a> FromSql<'a> for AdditionalOntologyMetadata {
     }
 }
 
+/// The selection indices of the columns describing the deprecation of an ontology type.
+#[derive(Copy, Clone)]
+struct DeprecationIndices {
+    deprecated_by_id: usize,
+    deprecated_at: usize,
+    successor_base_url: usize,
+    successor_version: usize,
+}
+
+impl DeprecationIndices {
+    fn read(self, row: &Row) -> Result<Option<OntologyTypeDeprecation>, QueryError> {
+        let Some(deprecated_by_id) = row.get::<_, Option<AccountId>>(self.deprecated_by_id) else {
+            return Ok(None);
+        };
+
+        let successor = match (
+            row.get::<_, Option<String>>(self.successor_base_url),
+            row.get::<_, Option<OntologyTypeVersion>>(self.successor_version),
+        ) {
+            (Some(base_url), Some(version)) => Some(VersionedUrl {
+                base_url: BaseUrl::new(base_url).change_context(QueryError)?,
+                version: version.inner(),
+            }),
+            _ => None,
+        };
+
+        Ok(Some(OntologyTypeDeprecation {
+            deprecated_by_id: DeprecatedById::new(deprecated_by_id),
+            deprecated_at: row.get(self.deprecated_at),
+            successor,
+        }))
+    }
+}
+
 #[async_trait]
 impl<C: AsClient> Read<OntologyTypeSnapshotRecord<DataType>> for PostgresStore<C> {
     type Record = DataTypeWithMetadata;
@@ -109,6 +145,12 @@ impl<C: AsClient> Read<OntologyTypeSnapshotRecord<DataType>> for PostgresStore<C
             compiler.add_selection_path(&DataTypeQueryPath::RecordArchivedById);
         let additional_metadata_index =
             compiler.add_selection_path(&DataTypeQueryPath::AdditionalMetadata);
+        let deprecation_indices = DeprecationIndices {
+            deprecated_by_id: compiler.add_selection_path(&DataTypeQueryPath::DeprecatedById),
+            deprecated_at: compiler.add_selection_path(&DataTypeQueryPath::DeprecatedAt),
+            successor_base_url: compiler.add_selection_path(&DataTypeQueryPath::SuccessorBaseUrl),
+            successor_version: compiler.add_selection_path(&DataTypeQueryPath::SuccessorVersion),
+        };
 
         compiler.add_filter(filter);
         let (statement, parameters) = compiler.compile();
@@ -163,6 +205,7 @@ impl<C: AsClient> Read<OntologyTypeSnapshotRecord<DataType>> for PostgresStore<C
                             version: row.get(version_index),
                         },
                         custom: custom_metadata,
+                        deprecation: deprecation_indices.read(&row)?,
                     },
                 })
             });
@@ -210,6 +253,14 @@ impl<C: AsClient> Read<OntologyTypeSnapshotRecord<PropertyType>> for PostgresSto
             compiler.add_selection_path(&PropertyTypeQueryPath::RecordArchivedById);
         let additional_metadata_index =
             compiler.add_selection_path(&PropertyTypeQueryPath::AdditionalMetadata);
+        let deprecation_indices = DeprecationIndices {
+            deprecated_by_id: compiler.add_selection_path(&PropertyTypeQueryPath::DeprecatedById),
+            deprecated_at: compiler.add_selection_path(&PropertyTypeQueryPath::DeprecatedAt),
+            successor_base_url: compiler
+                .add_selection_path(&PropertyTypeQueryPath::SuccessorBaseUrl),
+            successor_version: compiler
+                .add_selection_path(&PropertyTypeQueryPath::SuccessorVersion),
+        };
 
         compiler.add_filter(filter);
         let (statement, parameters) = compiler.compile();
@@ -264,6 +315,7 @@ impl<C: AsClient> Read<OntologyTypeSnapshotRecord<PropertyType>> for PostgresSto
                             version: row.get(version_index),
                         },
                         custom: custom_metadata,
+                        deprecation: deprecation_indices.read(&row)?,
                     },
                 })
             });
@@ -311,6 +363,12 @@ impl<C: AsClient> Read<OntologyTypeSnapshotRecord<EntityType>> for PostgresStore
             compiler.add_selection_path(&EntityTypeQueryPath::RecordArchivedById);
         let additional_metadata_index =
             compiler.add_selection_path(&EntityTypeQueryPath::AdditionalMetadata);
+        let deprecation_indices = DeprecationIndices {
+            deprecated_by_id: compiler.add_selection_path(&EntityTypeQueryPath::DeprecatedById),
+            deprecated_at: compiler.add_selection_path(&EntityTypeQueryPath::DeprecatedAt),
+            successor_base_url: compiler.add_selection_path(&EntityTypeQueryPath::SuccessorBaseUrl),
+            successor_version: compiler.add_selection_path(&EntityTypeQueryPath::SuccessorVersion),
+        };
         let label_property_index = compiler.add_selection_path(&EntityTypeQueryPath::LabelProperty);
 
         compiler.add_filter(filter);
@@ -375,6 +433,7 @@ impl<C: AsClient> Read<OntologyTypeSnapshotRecord<EntityType>> for PostgresStore
                             common: custom_metadata,
                             label_property,
                         },
+                        deprecation: deprecation_indices.read(&row)?,
                     },
                 })
             });
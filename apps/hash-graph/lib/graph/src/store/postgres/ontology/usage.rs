@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use error_stack::{Report, Result, ResultExt};
+use graph_types::ontology::{OntologyTypeRecordId, OntologyTypeVersion};
+use tokio_postgres::Row;
+use type_system::url::{BaseUrl, VersionedUrl};
+
+use crate::{
+    ontology::{EntityTypeUsage, OntologyTypeUsage, OwnerUsage, PropertyTypeUsage},
+    store::{
+        error::OntologyVersionDoesNotExist, postgres::ontology::OntologyId, AsClient,
+        PostgresStore, QueryError,
+    },
+};
+
+/// The kind of ontology type a usage report is requested for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum UsedOntologyType {
+    DataType,
+    PropertyType,
+}
+
+impl UsedOntologyType {
+    const fn table(self) -> &'static str {
+        match self {
+            Self::DataType => "data_types",
+            Self::PropertyType => "property_types",
+        }
+    }
+
+    /// Returns a recursive common table expression `referencing_property_types`, which contains
+    /// the property types referencing the ontology type `$1` with the number of property types
+    /// traversed to reach it.
+    ///
+    /// For property types, the property type itself is contained with a depth of `0`. Cyclic
+    /// references are only followed once.
+    fn referencing_property_types(self) -> String {
+        let base_case = match self {
+            Self::DataType => {
+                r"
+                    SELECT
+                        source_property_type_ontology_id,
+                        1,
+                        ARRAY[source_property_type_ontology_id]
+                    FROM property_type_constrains_values_on
+                    WHERE target_data_type_ontology_id = $1
+                "
+            }
+            Self::PropertyType => "SELECT $1::UUID, 0, ARRAY[$1::UUID]",
+        };
+
+        format!(
+            r"
+                referencing_property_types (ontology_id, depth, path) AS (
+                    {base_case}
+                    UNION ALL
+                    SELECT
+                        source_property_type_ontology_id,
+                        referencing_property_types.depth + 1,
+                        referencing_property_types.path || source_property_type_ontology_id
+                    FROM property_type_constrains_properties_on
+                    JOIN referencing_property_types
+                      ON target_property_type_ontology_id = referencing_property_types.ontology_id
+                    WHERE NOT source_property_type_ontology_id = ANY(referencing_property_types.path)
+                )
+            "
+        )
+    }
+
+    /// The depth of the property types directly referencing the ontology type.
+    const fn direct_depth(self) -> u32 {
+        match self {
+            Self::DataType => 1,
+            Self::PropertyType => 0,
+        }
+    }
+}
+
+fn versioned_url(row: &Row, base_url_index: usize) -> Result<VersionedUrl, QueryError> {
+    Ok(VersionedUrl::from(OntologyTypeRecordId {
+        base_url: BaseUrl::new(row.get(base_url_index)).change_context(QueryError)?,
+        version: row.get(base_url_index + 1),
+    }))
+}
+
+fn count(row: &Row, index: usize) -> u64 {
+    row.get::<_, i64>(index).unsigned_abs()
+}
+
+impl<C: AsClient> PostgresStore<C> {
+    /// Reads the usage of the ontology type `id` of the given `kind`.
+    ///
+    /// Only entities, which are neither archived nor superseded at the time of the request, are
+    /// counted.
+    ///
+    /// # Errors
+    ///
+    /// - [`OntologyVersionDoesNotExist`] if the ontology type does not exist
+    pub(super) async fn read_ontology_type_usage(
+        &self,
+        id: &VersionedUrl,
+        kind: UsedOntologyType,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        // The table name is not user input, so it's safe to format it into the query.
+        let ontology_id: OntologyId = self
+            .as_client()
+            .query_opt(
+                &format!(
+                    r"
+                        SELECT ontology_id
+                        FROM ontology_ids
+                        JOIN {} USING (ontology_id)
+                        WHERE base_url = $1 AND version = $2
+                    ",
+                    kind.table()
+                ),
+                &[&id.base_url.as_str(), &OntologyTypeVersion::new(id.version)],
+            )
+            .await
+            .change_context(QueryError)?
+            .ok_or_else(|| {
+                Report::new(OntologyVersionDoesNotExist)
+                    .attach_printable(id.clone())
+                    .change_context(QueryError)
+            })?
+            .get(0);
+
+        let referencing_property_types = kind.referencing_property_types();
+
+        let property_types = self
+            .as_client()
+            .query(
+                &format!(
+                    r"
+                        WITH RECURSIVE {referencing_property_types}
+                        SELECT base_url, version, owned_by_id, MIN(depth)
+                        FROM referencing_property_types
+                        JOIN ontology_ids USING (ontology_id)
+                        LEFT JOIN ontology_owned_metadata USING (ontology_id)
+                        WHERE depth > 0
+                        GROUP BY ontology_id, base_url, version, owned_by_id
+                        ORDER BY base_url, version
+                    "
+                ),
+                &[&ontology_id],
+            )
+            .await
+            .change_context(QueryError)?
+            .into_iter()
+            .map(|row| {
+                Ok(PropertyTypeUsage {
+                    property_type_id: versioned_url(&row, 0)?,
+                    owned_by_id: row.get(2),
+                    depth: row.get::<_, i32>(3).unsigned_abs(),
+                })
+            })
+            .collect::<Result<Vec<_>, QueryError>>()?;
+
+        let mut entity_type_indices = HashMap::new();
+        let mut entity_types = Vec::new();
+        for row in self
+            .as_client()
+            .query(
+                &format!(
+                    r"
+                        WITH RECURSIVE {referencing_property_types}
+                        SELECT
+                            ontology_ids.ontology_id,
+                            ontology_ids.base_url,
+                            ontology_ids.version,
+                            ontology_owned_metadata.owned_by_id,
+                            MIN(referencing_property_types.depth) = {},
+                            MIN(closed_entity_type_constrains_properties_on.inheritance_depth) > 0
+                        FROM closed_entity_type_constrains_properties_on
+                        JOIN referencing_property_types
+                          ON target_property_type_ontology_id = referencing_property_types.ontology_id
+                        JOIN ontology_ids
+                          ON ontology_ids.ontology_id = source_entity_type_ontology_id
+                        LEFT JOIN ontology_owned_metadata
+                          ON ontology_owned_metadata.ontology_id = ontology_ids.ontology_id
+                        GROUP BY
+                            ontology_ids.ontology_id,
+                            ontology_ids.base_url,
+                            ontology_ids.version,
+                            ontology_owned_metadata.owned_by_id
+                        ORDER BY ontology_ids.base_url, ontology_ids.version
+                    ",
+                    kind.direct_depth()
+                ),
+                &[&ontology_id],
+            )
+            .await
+            .change_context(QueryError)?
+        {
+            entity_type_indices.insert(row.get::<_, OntologyId>(0), entity_types.len());
+            entity_types.push(EntityTypeUsage {
+                entity_type_id: versioned_url(&row, 1)?,
+                owned_by_id: row.get(3),
+                direct: row.get(4),
+                inherited: row.get(5),
+                entities: 0,
+                owners: Vec::new(),
+            });
+        }
+
+        // Values are keyed by the base URL of the property type. For data types, only the values
+        // of property types directly referencing the data type are considered.
+        let property_base_urls = match kind {
+            UsedOntologyType::DataType => property_types
+                .iter()
+                .filter(|property_type| property_type.depth == kind.direct_depth())
+                .map(|property_type| property_type.property_type_id.base_url.as_str())
+                .collect::<Vec<_>>(),
+            UsedOntologyType::PropertyType => vec![id.base_url.as_str()],
+        };
+
+        let mut owners = Vec::new();
+        if !entity_types.is_empty() && !property_base_urls.is_empty() {
+            for row in self
+                .as_client()
+                .query(
+                    &format!(
+                        r"
+                            WITH RECURSIVE {referencing_property_types},
+                            entity_types AS (
+                                SELECT DISTINCT source_entity_type_ontology_id AS ontology_id
+                                FROM closed_entity_type_constrains_properties_on
+                                JOIN referencing_property_types
+                                  ON target_property_type_ontology_id
+                                   = referencing_property_types.ontology_id
+                            )
+                            SELECT
+                                entity_is_of_type.entity_type_ontology_id,
+                                entity_temporal_metadata.owned_by_id,
+                                COUNT(DISTINCT (
+                                    entity_temporal_metadata.owned_by_id,
+                                    entity_temporal_metadata.entity_uuid
+                                ))
+                            FROM entity_temporal_metadata
+                            JOIN entity_editions
+                              ON entity_editions.entity_edition_id
+                               = entity_temporal_metadata.entity_edition_id
+                            JOIN entity_is_of_type
+                              ON entity_is_of_type.entity_edition_id
+                               = entity_temporal_metadata.entity_edition_id
+                            JOIN entity_types
+                              ON entity_types.ontology_id
+                               = entity_is_of_type.entity_type_ontology_id
+                            WHERE entity_temporal_metadata.decision_time @> now()
+                              AND entity_temporal_metadata.transaction_time @> now()
+                              AND NOT entity_editions.archived
+                              AND EXISTS (
+                                  SELECT 1
+                                  FROM jsonb_path_query(entity_editions.properties, 'strict $.**')
+                                    AS property_value
+                                  WHERE jsonb_typeof(property_value) = 'object'
+                                    AND property_value ?| $2
+                              )
+                            GROUP BY GROUPING SETS (
+                                (
+                                    entity_is_of_type.entity_type_ontology_id,
+                                    entity_temporal_metadata.owned_by_id
+                                ),
+                                (entity_temporal_metadata.owned_by_id)
+                            )
+                            ORDER BY entity_temporal_metadata.owned_by_id
+                        "
+                    ),
+                    &[&ontology_id, &property_base_urls],
+                )
+                .await
+                .change_context(QueryError)?
+            {
+                let owner_usage = OwnerUsage {
+                    owned_by_id: row.get(1),
+                    entities: count(&row, 2),
+                };
+                // Rows without an entity type are the totals per owner
+                match row.get::<_, Option<OntologyId>>(0) {
+                    Some(entity_type_ontology_id) => {
+                        let entity_type = entity_type_indices
+                            .get(&entity_type_ontology_id)
+                            .and_then(|index| entity_types.get_mut(*index))
+                            .ok_or_else(|| {
+                                Report::new(QueryError).attach_printable(format!(
+                                    "entities counted for unknown entity type \
+                                     `{entity_type_ontology_id}`"
+                                ))
+                            })?;
+                        entity_type.entities += owner_usage.entities;
+                        entity_type.owners.push(owner_usage);
+                    }
+                    None => owners.push(owner_usage),
+                }
+            }
+        }
+
+        Ok(OntologyTypeUsage {
+            ontology_type_id: id.clone(),
+            property_types,
+            entity_types,
+            owners,
+        })
+    }
+}
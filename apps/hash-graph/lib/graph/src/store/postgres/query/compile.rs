@@ -359,6 +359,26 @@ impl<'p, R: PostgresRecord> SelectCompiler<'p, R> {
 
                 Condition::ContainsSegment(left_filter, right_filter)
             }
+            Filter::ChangedSince(path, since) => {
+                let interval = self.compile_path_column(path);
+                self.artifacts.parameters.push(since);
+                let since_index = self.artifacts.parameters.len();
+
+                Condition::Any(vec![
+                    Condition::GreaterOrEqual(
+                        Expression::Function(Function::Lower(Box::new(Expression::Column(
+                            interval,
+                        )))),
+                        Expression::Parameter(since_index),
+                    ),
+                    Condition::GreaterOrEqual(
+                        Expression::Function(Function::Upper(Box::new(Expression::Column(
+                            interval,
+                        )))),
+                        Expression::Parameter(since_index),
+                    ),
+                ])
+            }
         }
     }
 
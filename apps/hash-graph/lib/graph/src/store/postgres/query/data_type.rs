@@ -6,8 +6,8 @@ use crate::{
     ontology::DataTypeQueryPath,
     store::postgres::query::{
         table::{
-            Column, DataTypes, JsonField, OntologyAdditionalMetadata, OntologyIds,
-            OntologyOwnedMetadata, OntologyTemporalMetadata, ReferenceTable, Relation,
+            Column, DataTypes, JsonField, OntologyAdditionalMetadata, OntologyDeprecations,
+            OntologyIds, OntologyOwnedMetadata, OntologyTemporalMetadata, ReferenceTable, Relation,
         },
         PostgresQueryPath, PostgresRecord, Table,
     },
@@ -32,6 +32,10 @@ impl PostgresQueryPath for DataTypeQueryPath<'_> {
             Self::BaseUrl | Self::Version => vec![Relation::OntologyIds],
             Self::OwnedById => vec![Relation::OntologyOwnedMetadata],
             Self::AdditionalMetadata => vec![Relation::OntologyAdditionalMetadata],
+            Self::DeprecatedById
+            | Self::DeprecatedAt
+            | Self::SuccessorBaseUrl
+            | Self::SuccessorVersion => vec![Relation::OntologyDeprecations],
             Self::TransactionTime | Self::RecordCreatedById | Self::RecordArchivedById => vec![],
             Self::PropertyTypeEdge {
                 edge_kind: OntologyEdgeKind::ConstrainsValuesOn,
@@ -60,6 +64,16 @@ impl PostgresQueryPath for DataTypeQueryPath<'_> {
             Self::RecordArchivedById => {
                 Column::OntologyTemporalMetadata(OntologyTemporalMetadata::RecordArchivedById)
             }
+            Self::DeprecatedById => {
+                Column::OntologyDeprecations(OntologyDeprecations::DeprecatedById)
+            }
+            Self::DeprecatedAt => Column::OntologyDeprecations(OntologyDeprecations::DeprecatedAt),
+            Self::SuccessorBaseUrl => {
+                Column::OntologyDeprecations(OntologyDeprecations::SuccessorBaseUrl)
+            }
+            Self::SuccessorVersion => {
+                Column::OntologyDeprecations(OntologyDeprecations::SuccessorVersion)
+            }
             Self::OntologyId => Column::DataTypes(DataTypes::OntologyId),
             Self::Schema(path) => path
                 .as_ref()
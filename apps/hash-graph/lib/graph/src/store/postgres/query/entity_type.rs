@@ -6,8 +6,8 @@ use crate::{
     ontology::EntityTypeQueryPath,
     store::postgres::query::{
         table::{
-            Column, EntityTypes, JsonField, OntologyAdditionalMetadata, OntologyIds,
-            OntologyOwnedMetadata, OntologyTemporalMetadata, ReferenceTable, Relation,
+            Column, EntityTypes, JsonField, OntologyAdditionalMetadata, OntologyDeprecations,
+            OntologyIds, OntologyOwnedMetadata, OntologyTemporalMetadata, ReferenceTable, Relation,
         },
         PostgresQueryPath, PostgresRecord, Table,
     },
@@ -35,6 +35,10 @@ impl PostgresQueryPath for EntityTypeQueryPath<'_> {
             Self::BaseUrl | Self::Version => vec![Relation::OntologyIds],
             Self::OwnedById => vec![Relation::OntologyOwnedMetadata],
             Self::AdditionalMetadata => vec![Relation::OntologyAdditionalMetadata],
+            Self::DeprecatedById
+            | Self::DeprecatedAt
+            | Self::SuccessorBaseUrl
+            | Self::SuccessorVersion => vec![Relation::OntologyDeprecations],
             Self::TransactionTime | Self::RecordCreatedById | Self::RecordArchivedById => vec![],
             Self::PropertyTypeEdge {
                 edge_kind: OntologyEdgeKind::ConstrainsPropertiesOn,
@@ -117,6 +121,16 @@ impl PostgresQueryPath for EntityTypeQueryPath<'_> {
             Self::RecordArchivedById => {
                 Column::OntologyTemporalMetadata(OntologyTemporalMetadata::RecordArchivedById)
             }
+            Self::DeprecatedById => {
+                Column::OntologyDeprecations(OntologyDeprecations::DeprecatedById)
+            }
+            Self::DeprecatedAt => Column::OntologyDeprecations(OntologyDeprecations::DeprecatedAt),
+            Self::SuccessorBaseUrl => {
+                Column::OntologyDeprecations(OntologyDeprecations::SuccessorBaseUrl)
+            }
+            Self::SuccessorVersion => {
+                Column::OntologyDeprecations(OntologyDeprecations::SuccessorVersion)
+            }
             Self::OntologyId => Column::EntityTypes(EntityTypes::OntologyId),
             Self::Schema(path) => path
                 .as_ref()
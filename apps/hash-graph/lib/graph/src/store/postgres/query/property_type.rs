@@ -6,8 +6,9 @@ use crate::{
     ontology::PropertyTypeQueryPath,
     store::postgres::query::{
         table::{
-            Column, JsonField, OntologyAdditionalMetadata, OntologyIds, OntologyOwnedMetadata,
-            OntologyTemporalMetadata, PropertyTypes, ReferenceTable, Relation,
+            Column, JsonField, OntologyAdditionalMetadata, OntologyDeprecations, OntologyIds,
+            OntologyOwnedMetadata, OntologyTemporalMetadata, PropertyTypes, ReferenceTable,
+            Relation,
         },
         PostgresQueryPath, PostgresRecord, Table,
     },
@@ -31,6 +32,10 @@ impl PostgresQueryPath for PropertyTypeQueryPath<'_> {
             Self::BaseUrl | Self::Version => vec![Relation::OntologyIds],
             Self::OwnedById => vec![Relation::OntologyOwnedMetadata],
             Self::AdditionalMetadata => vec![Relation::OntologyAdditionalMetadata],
+            Self::DeprecatedById
+            | Self::DeprecatedAt
+            | Self::SuccessorBaseUrl
+            | Self::SuccessorVersion => vec![Relation::OntologyDeprecations],
             Self::TransactionTime | Self::RecordCreatedById | Self::RecordArchivedById => vec![],
             Self::DataTypeEdge {
                 edge_kind: OntologyEdgeKind::ConstrainsValuesOn,
@@ -81,6 +86,16 @@ impl PostgresQueryPath for PropertyTypeQueryPath<'_> {
             Self::RecordArchivedById => {
                 Column::OntologyTemporalMetadata(OntologyTemporalMetadata::RecordArchivedById)
             }
+            Self::DeprecatedById => {
+                Column::OntologyDeprecations(OntologyDeprecations::DeprecatedById)
+            }
+            Self::DeprecatedAt => Column::OntologyDeprecations(OntologyDeprecations::DeprecatedAt),
+            Self::SuccessorBaseUrl => {
+                Column::OntologyDeprecations(OntologyDeprecations::SuccessorBaseUrl)
+            }
+            Self::SuccessorVersion => {
+                Column::OntologyDeprecations(OntologyDeprecations::SuccessorVersion)
+            }
             Self::OntologyId => Column::PropertyTypes(PropertyTypes::OntologyId),
             Self::Schema(path) => {
                 path.as_ref()
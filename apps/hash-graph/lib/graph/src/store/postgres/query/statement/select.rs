@@ -82,6 +82,7 @@ mod tests {
         ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
     };
     use postgres_types::ToSql;
+    use temporal_versioning::Timestamp;
     use uuid::Uuid;
 
     use crate::{
@@ -208,6 +209,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entity_changed_since() {
+        let since = Timestamp::now();
+        let mut compiler = SelectCompiler::<Entity>::with_asterisk(None);
+        let filter = Filter::All(vec![
+            Filter::Equal(
+                Some(FilterExpression::Path(EntityQueryPath::Uuid)),
+                Some(FilterExpression::Parameter(Parameter::Uuid(Uuid::nil()))),
+            ),
+            Filter::ChangedSince(EntityQueryPath::TransactionTime, since),
+        ]);
+        compiler.add_filter(&filter);
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM "entity_temporal_metadata" AS "entity_temporal_metadata_0_0_0"
+            WHERE ("entity_temporal_metadata_0_0_0"."entity_uuid" = $1)
+              AND (((lower("entity_temporal_metadata_0_0_0"."transaction_time") >= $2)
+                OR (upper("entity_temporal_metadata_0_0_0"."transaction_time") >= $2)))
+            "#,
+            &[&Uuid::nil(), &since],
+        );
+    }
+
+    #[test]
+    fn data_type_changed_since() {
+        let since = Timestamp::now();
+        let mut compiler = SelectCompiler::<DataTypeWithMetadata>::with_asterisk(None);
+        let filter = Filter::ChangedSince(DataTypeQueryPath::TransactionTime, since);
+        compiler.add_filter(&filter);
+        test_compilation(
+            &compiler,
+            r#"
+            SELECT *
+            FROM "ontology_temporal_metadata" AS "ontology_temporal_metadata_0_0_0"
+            WHERE ((lower("ontology_temporal_metadata_0_0_0"."transaction_time") >= $1)
+                OR (upper("ontology_temporal_metadata_0_0_0"."transaction_time") >= $1))
+            "#,
+            &[&since],
+        );
+    }
+
     #[test]
     fn specific_version() {
         let temporal_axes = QueryTemporalAxesUnresolved::default().resolve();
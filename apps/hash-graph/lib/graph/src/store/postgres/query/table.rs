@@ -23,6 +23,7 @@ pub enum Table {
     OntologyOwnedMetadata,
     OntologyExternalMetadata,
     OntologyAdditionalMetadata,
+    OntologyDeprecations,
     DataTypes,
     PropertyTypes,
     EntityTypes,
@@ -282,6 +283,7 @@ impl Table {
             Self::OntologyOwnedMetadata => "ontology_owned_metadata",
             Self::OntologyExternalMetadata => "ontology_external_metadata",
             Self::OntologyAdditionalMetadata => "ontology_additional_metadata",
+            Self::OntologyDeprecations => "ontology_deprecations",
             Self::DataTypes => "data_types",
             Self::PropertyTypes => "property_types",
             Self::EntityTypes => "entity_types",
@@ -354,6 +356,15 @@ pub enum OntologyAdditionalMetadata {
     AdditionalMetadata,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OntologyDeprecations {
+    OntologyId,
+    DeprecatedById,
+    DeprecatedAt,
+    SuccessorBaseUrl,
+    SuccessorVersion,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum OntologyTemporalMetadata {
     OntologyId,
@@ -460,6 +471,29 @@ impl OntologyAdditionalMetadata {
     }
 }
 
+impl OntologyDeprecations {
+    fn transpile_column(self, table: &impl Transpile, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let column = match self {
+            Self::OntologyId => "ontology_id",
+            Self::DeprecatedById => "deprecated_by_id",
+            Self::DeprecatedAt => "deprecated_at",
+            Self::SuccessorBaseUrl => "successor_base_url",
+            Self::SuccessorVersion => "successor_version",
+        };
+        table.transpile(fmt)?;
+        write!(fmt, r#"."{column}""#)
+    }
+
+    pub const fn parameter_type(self) -> ParameterType {
+        match self {
+            Self::OntologyId | Self::DeprecatedById => ParameterType::Uuid,
+            Self::DeprecatedAt => ParameterType::Timestamp,
+            Self::SuccessorBaseUrl => ParameterType::Text,
+            Self::SuccessorVersion => ParameterType::OntologyTypeVersion,
+        }
+    }
+}
+
 impl OntologyTemporalMetadata {
     fn transpile_column(self, table: &impl Transpile, fmt: &mut fmt::Formatter) -> fmt::Result {
         let column = match self {
@@ -973,6 +1007,7 @@ pub enum Column<'p> {
     OntologyOwnedMetadata(OntologyOwnedMetadata),
     OntologyExternalMetadata(OntologyExternalMetadata),
     OntologyAdditionalMetadata(OntologyAdditionalMetadata),
+    OntologyDeprecations(OntologyDeprecations),
     DataTypes(DataTypes<'p>),
     PropertyTypes(PropertyTypes<'p>),
     EntityTypes(EntityTypes<'p>),
@@ -997,6 +1032,7 @@ impl<'p> Column<'p> {
             Self::OntologyOwnedMetadata(_) => Table::OntologyOwnedMetadata,
             Self::OntologyExternalMetadata(_) => Table::OntologyExternalMetadata,
             Self::OntologyAdditionalMetadata(_) => Table::OntologyAdditionalMetadata,
+            Self::OntologyDeprecations(_) => Table::OntologyDeprecations,
             Self::DataTypes(_) => Table::DataTypes,
             Self::PropertyTypes(_) => Table::PropertyTypes,
             Self::EntityTypes(_) => Table::EntityTypes,
@@ -1052,7 +1088,8 @@ impl<'p> Column<'p> {
             Self::EntityHasLeftEntity(_)
             | Self::EntityHasRightEntity(_)
             | Self::OntologyOwnedMetadata(_)
-            | Self::OntologyExternalMetadata(_) => true,
+            | Self::OntologyExternalMetadata(_)
+            | Self::OntologyDeprecations(_) => true,
             _ => false,
         }
     }
@@ -1073,6 +1110,7 @@ impl<'p> Column<'p> {
             Self::OntologyAdditionalMetadata(column) => {
                 (Column::OntologyAdditionalMetadata(column), None)
             }
+            Self::OntologyDeprecations(column) => (Column::OntologyDeprecations(column), None),
             Self::DataTypes(column) => {
                 let (column, parameter) = column.into_owned(current_parameter_index);
                 (Column::DataTypes(column), parameter)
@@ -1136,6 +1174,7 @@ impl Column<'static> {
             Self::OntologyOwnedMetadata(column) => column.transpile_column(table, fmt),
             Self::OntologyExternalMetadata(column) => column.transpile_column(table, fmt),
             Self::OntologyAdditionalMetadata(column) => column.transpile_column(table, fmt),
+            Self::OntologyDeprecations(column) => column.transpile_column(table, fmt),
             Self::DataTypes(column) => column.transpile_column(table, fmt),
             Self::PropertyTypes(column) => column.transpile_column(table, fmt),
             Self::EntityTypes(column) => column.transpile_column(table, fmt),
@@ -1164,6 +1203,7 @@ impl Column<'static> {
             Self::OntologyOwnedMetadata(column) => column.parameter_type(),
             Self::OntologyExternalMetadata(column) => column.parameter_type(),
             Self::OntologyAdditionalMetadata(column) => column.parameter_type(),
+            Self::OntologyDeprecations(column) => column.parameter_type(),
             Self::DataTypes(column) => column.parameter_type(),
             Self::PropertyTypes(column) => column.parameter_type(),
             Self::EntityTypes(column) => column.parameter_type(),
@@ -1268,6 +1308,7 @@ pub enum Relation {
     OntologyOwnedMetadata,
     OntologyExternalMetadata,
     OntologyAdditionalMetadata,
+    OntologyDeprecations,
     DataTypeIds,
     PropertyTypeIds,
     EntityTypeIds,
@@ -1360,6 +1401,12 @@ impl Relation {
                     ),
                 })
             }
+            Self::OntologyDeprecations => {
+                ForeignKeyJoin::from_reference(ForeignKeyReference::Single {
+                    on: Column::OntologyTemporalMetadata(OntologyTemporalMetadata::OntologyId),
+                    join: Column::OntologyDeprecations(OntologyDeprecations::OntologyId),
+                })
+            }
             Self::DataTypeIds => ForeignKeyJoin::from_reference(ForeignKeyReference::Single {
                 on: Column::OntologyTemporalMetadata(OntologyTemporalMetadata::OntologyId),
                 join: Column::DataTypes(DataTypes::OntologyId),
@@ -8,6 +8,7 @@ use graph_types::{
 };
 use serde::Deserialize;
 use serde_json::{Number, Value};
+use temporal_versioning::{Timestamp, TransactionTime};
 use type_system::url::{BaseUrl, VersionedUrl};
 use uuid::Uuid;
 
@@ -24,6 +25,7 @@ use crate::{
 #[derive(Derivative, Deserialize)]
 #[derivative(
     Debug(bound = "R::QueryPath<'p>: fmt::Debug"),
+    Clone(bound = "R::QueryPath<'p>: Clone"),
     PartialEq(bound = "R::QueryPath<'p>: PartialEq")
 )]
 #[serde(
@@ -47,6 +49,9 @@ pub enum Filter<'p, R: Record + ?Sized> {
     StartsWith(FilterExpression<'p, R>, FilterExpression<'p, R>),
     EndsWith(FilterExpression<'p, R>, FilterExpression<'p, R>),
     ContainsSegment(FilterExpression<'p, R>, FilterExpression<'p, R>),
+    /// Matches records, whose transaction time interval starts or ends at or after the timestamp.
+    #[serde(skip)]
+    ChangedSince(R::QueryPath<'p>, Timestamp<TransactionTime>),
 }
 
 impl<'p, R> Filter<'p, R>
@@ -142,6 +147,7 @@ where
                     parameter.convert_to_parameter_type(ParameterType::Text)?;
                 }
             }
+            Self::ChangedSince(..) => {}
         }
 
         Ok(())
@@ -152,6 +158,7 @@ where
 #[derive(Derivative, Deserialize)]
 #[derivative(
     Debug(bound = "R::QueryPath<'p>: fmt::Debug"),
+    Clone(bound = "R::QueryPath<'p>: Clone"),
     PartialEq(bound = "R::QueryPath<'p>: PartialEq")
 )]
 #[serde(
@@ -163,7 +170,7 @@ pub enum FilterExpression<'p, R: Record + ?Sized> {
     Parameter(Parameter<'p>),
 }
 
-#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 #[serde(untagged)]
 pub enum Parameter<'p> {
     Boolean(bool),
@@ -7,22 +7,31 @@ pub mod vertices;
 use std::{
     collections::{
         hash_map::{RandomState, RawEntryMut},
-        HashSet,
+        HashMap, HashSet,
     },
     hash::Hash,
 };
 
+use graph_types::{
+    knowledge::entity::{Entity, EntityId},
+    ontology::EntityTypeWithMetadata,
+};
+use temporal_versioning::{LeftClosedTemporalInterval, TemporalTagged, TimeAxis};
+
 use self::{
     edges::{Edges, GraphResolveDepths},
     identifier::GraphElementVertexId,
     temporal_axes::{QueryTemporalAxes, QueryTemporalAxesUnresolved, SubgraphTemporalAxes},
-    vertices::Vertices,
+    vertices::{OntologyVertex, TemporalVertex, Vertices},
 };
 use crate::{
     store::Record,
     subgraph::{
-        edges::{EdgeDirection, EdgeKind},
-        identifier::{EdgeEndpoint, VertexId},
+        edges::{EdgeDirection, EdgeKind, KnowledgeGraphEdgeKind},
+        identifier::{
+            EdgeEndpoint, EntityIdWithInterval, EntityTypeVertexId, EntityVertexId, VertexId,
+        },
+        temporal_axes::VariableAxis,
     },
 };
 
@@ -65,6 +74,191 @@ impl Subgraph {
         vertex_id.subgraph_entry(&self.vertices)
     }
 
+    /// Returns an iterator over the root vertices of the subgraph.
+    pub fn roots(&self) -> impl Iterator<Item = &GraphElementVertexId> {
+        self.roots.iter()
+    }
+
+    /// Returns an iterator over all vertices of the kind specified by `V`, which are valid at the
+    /// temporal axes of the subgraph.
+    pub fn iter_vertices<V>(&self) -> impl Iterator<Item = (&V, &V::Record)>
+    where
+        V: VertexId<Record: TemporalVertex>,
+    {
+        V::subgraph_vertices(&self.vertices)
+            .iter()
+            .filter(|(_, record)| record.is_valid_at(&self.temporal_axes.resolved))
+    }
+
+    /// Returns an iterator over the right endpoints of the edges of the given kind and direction
+    /// starting at `vertex_id`.
+    ///
+    /// As an edge kind may connect different kinds of vertices, the kind of the right endpoint `R`
+    /// may have to be specified explicitly.
+    pub fn vertex_edges<'s, R, L, K>(
+        &'s self,
+        vertex_id: &L,
+        edge_kind: K,
+        direction: EdgeDirection,
+    ) -> impl Iterator<Item = <&'s K::EdgeSet as IntoIterator>::Item>
+    where
+        R: EdgeEndpoint,
+        L: VertexId<BaseId: Eq + Hash, RevisionId: Ord>,
+        K: EdgeKind<L, R> + Eq + Hash,
+        &'s K::EdgeSet: IntoIterator,
+    {
+        edge_kind
+            .subgraph_entry(&self.edges)
+            .get(vertex_id, edge_kind, direction)
+            .into_iter()
+            .flatten()
+    }
+
+    /// Returns an iterator over the right endpoints of the outgoing edges of the given kind
+    /// starting at `vertex_id`.
+    ///
+    /// See [`vertex_edges`] for more information.
+    ///
+    /// [`vertex_edges`]: Self::vertex_edges
+    pub fn outgoing_edges<'s, R, L, K>(
+        &'s self,
+        vertex_id: &L,
+        edge_kind: K,
+    ) -> impl Iterator<Item = <&'s K::EdgeSet as IntoIterator>::Item>
+    where
+        R: EdgeEndpoint,
+        L: VertexId<BaseId: Eq + Hash, RevisionId: Ord>,
+        K: EdgeKind<L, R> + Eq + Hash,
+        &'s K::EdgeSet: IntoIterator,
+    {
+        self.vertex_edges::<R, L, K>(vertex_id, edge_kind, EdgeDirection::Outgoing)
+    }
+
+    /// Returns an iterator over the right endpoints of the incoming edges of the given kind
+    /// starting at `vertex_id`.
+    ///
+    /// See [`vertex_edges`] for more information.
+    ///
+    /// [`vertex_edges`]: Self::vertex_edges
+    pub fn incoming_edges<'s, R, L, K>(
+        &'s self,
+        vertex_id: &L,
+        edge_kind: K,
+    ) -> impl Iterator<Item = <&'s K::EdgeSet as IntoIterator>::Item>
+    where
+        R: EdgeEndpoint,
+        L: VertexId<BaseId: Eq + Hash, RevisionId: Ord>,
+        K: EdgeKind<L, R> + Eq + Hash,
+        &'s K::EdgeSet: IntoIterator,
+    {
+        self.vertex_edges::<R, L, K>(vertex_id, edge_kind, EdgeDirection::Incoming)
+    }
+
+    /// Returns an iterator over the records of the ontology vertices, which are reachable from
+    /// `vertex_id` by following a single edge of the given kind and direction.
+    ///
+    /// Endpoints, which are not contained in the subgraph or are not valid at the temporal axes of
+    /// the subgraph, are skipped. To follow edges between entities, use [`linked_entities`]
+    /// instead.
+    ///
+    /// [`linked_entities`]: Self::linked_entities
+    pub fn neighbors<'s, R, L, K>(
+        &'s self,
+        vertex_id: &L,
+        edge_kind: K,
+        direction: EdgeDirection,
+    ) -> impl Iterator<Item = &'s R::Record>
+    where
+        R: VertexId<Record: OntologyVertex> + EdgeEndpoint + Eq + Hash + 's,
+        L: VertexId<BaseId: Eq + Hash, RevisionId: Ord>,
+        K: EdgeKind<L, R, EdgeSet = HashSet<R>> + Eq + Hash,
+    {
+        self.vertex_edges::<R, L, K>(vertex_id, edge_kind, direction)
+            .filter_map(move |right_endpoint| right_endpoint.subgraph_entry(&self.vertices))
+            .filter(move |record| record.is_valid_at(&self.temporal_axes.resolved))
+    }
+
+    /// Returns an iterator over the entities, which are reachable from `vertex_id` by following a
+    /// single edge of the given kind and direction.
+    ///
+    /// An edge between two entities is only valid for an interval on the variable temporal axis.
+    /// For every edge, all editions of the target entity in the subgraph, which overlap with the
+    /// interval of the edge, are returned.
+    pub fn linked_entities<'s>(
+        &'s self,
+        vertex_id: &EntityVertexId,
+        edge_kind: KnowledgeGraphEdgeKind,
+        direction: EdgeDirection,
+    ) -> impl Iterator<Item = &'s Entity> {
+        let entity_index = self.entity_index();
+        self.vertex_edges::<EntityIdWithInterval, _, _>(vertex_id, edge_kind, direction)
+            .flat_map(|right_endpoint| {
+                entity_index
+                    .get(&right_endpoint.entity_id)
+                    .into_iter()
+                    .flatten()
+                    .filter(move |entity| {
+                        self.entity_interval(entity)
+                            .overlaps(&right_endpoint.interval)
+                    })
+            })
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the [`EntityTypeWithMetadata`] of the provided `entity` if it's contained in the
+    /// subgraph.
+    pub fn entity_type_of(&self, entity: &Entity) -> Option<&EntityTypeWithMetadata> {
+        self.get_vertex::<EntityTypeWithMetadata>(&EntityTypeVertexId::from(
+            entity.metadata.entity_type_id().clone(),
+        ))
+    }
+
+    /// Returns the interval of the entity edition on the variable temporal axis of the subgraph.
+    pub fn entity_interval(&self, entity: &Entity) -> LeftClosedTemporalInterval<VariableAxis> {
+        let temporal_metadata = entity.metadata.temporal_versioning();
+        match self.temporal_axes.resolved.variable_time_axis() {
+            TimeAxis::DecisionTime => temporal_metadata.decision_time.cast(),
+            TimeAxis::TransactionTime => temporal_metadata.transaction_time.cast(),
+        }
+    }
+
+    /// Returns an iterator over the editions of the entity in the subgraph, whose interval on the
+    /// variable temporal axis overlaps with `interval`.
+    ///
+    /// The editions are returned in ascending order of their interval.
+    pub fn entity_editions(
+        &self,
+        entity_id: EntityId,
+        interval: LeftClosedTemporalInterval<VariableAxis>,
+    ) -> impl Iterator<Item = &Entity> {
+        let mut editions = self
+            .vertices
+            .entities
+            .iter()
+            .filter(|(vertex_id, entity)| {
+                vertex_id.base_id == entity_id && self.entity_interval(entity).overlaps(&interval)
+            })
+            .collect::<Vec<_>>();
+        editions.sort_unstable_by_key(|(vertex_id, _)| vertex_id.revision_id);
+        editions.into_iter().map(|(_, entity)| entity)
+    }
+
+    /// Returns the editions of every entity in the subgraph in ascending order of their revision.
+    ///
+    /// The index is not cached, as `vertices` may be modified directly, which would leave a cached
+    /// index stale.
+    fn entity_index(&self) -> HashMap<EntityId, Vec<&Entity>> {
+        let mut index = HashMap::<_, Vec<_>>::new();
+        let mut vertices = self.vertices.entities.iter().collect::<Vec<_>>();
+        vertices.sort_unstable_by_key(|(vertex_id, _)| vertex_id.revision_id);
+        for (vertex_id, entity) in vertices {
+            index.entry(vertex_id.base_id).or_default().push(entity);
+        }
+        index
+    }
+
     pub fn insert_vertex<R: Record>(&mut self, vertex_id: R::VertexId, record: R)
     where
         R::VertexId: Eq + Hash,
@@ -93,3 +287,401 @@ impl Subgraph {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use graph_types::{
+        account::AccountId,
+        knowledge::entity::{
+            EntityEditionId, EntityMetadata, EntityProperties, EntityRecordId,
+            EntityTemporalMetadata, EntityUuid,
+        },
+        ontology::{
+            CustomEntityTypeMetadata, CustomOntologyMetadata, EntityTypeMetadata,
+            OntologyTemporalMetadata, OntologyTypeRecordId, OntologyTypeVersion,
+        },
+        provenance::{OwnedById, ProvenanceMetadata, RecordCreatedById},
+    };
+    use temporal_versioning::{
+        ClosedTemporalBound, DecisionTime, LimitedTemporalBound, OpenTemporalBound, TemporalBound,
+        TransactionTime,
+    };
+    use type_system::{
+        repr,
+        url::{BaseUrl, VersionedUrl},
+        EntityType,
+    };
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::subgraph::{
+        edges::OntologyEdgeKind,
+        identifier::PropertyTypeVertexId,
+        temporal_axes::{PinnedTemporalAxisUnresolved, VariableTemporalAxisUnresolved},
+    };
+
+    fn interval<A>(start: &str, end: Option<&str>) -> LeftClosedTemporalInterval<A> {
+        LeftClosedTemporalInterval::new(
+            ClosedTemporalBound::Inclusive(start.parse().expect("should be a valid timestamp")),
+            end.map_or(OpenTemporalBound::Unbounded, |end| {
+                OpenTemporalBound::Exclusive(end.parse().expect("should be a valid timestamp"))
+            }),
+        )
+    }
+
+    fn provenance() -> ProvenanceMetadata {
+        ProvenanceMetadata {
+            record_created_by_id: RecordCreatedById::new(AccountId::new(Uuid::nil())),
+            record_archived_by_id: None,
+        }
+    }
+
+    fn entity_type(
+        json: &str,
+        transaction_time: LeftClosedTemporalInterval<TransactionTime>,
+    ) -> EntityTypeWithMetadata {
+        let schema = EntityType::try_from(
+            serde_json::from_str::<repr::EntityType>(json).expect("should be valid JSON"),
+        )
+        .expect("should be a valid entity type");
+        EntityTypeWithMetadata {
+            metadata: EntityTypeMetadata {
+                record_id: OntologyTypeRecordId::from(schema.id().clone()),
+                custom: CustomEntityTypeMetadata {
+                    label_property: None,
+                    common: CustomOntologyMetadata::Owned {
+                        provenance: provenance(),
+                        temporal_versioning: OntologyTemporalMetadata { transaction_time },
+                        owned_by_id: OwnedById::new(AccountId::new(Uuid::nil())),
+                    },
+                },
+                deprecation: None,
+            },
+            schema,
+        }
+    }
+
+    fn entity(
+        entity_id: EntityId,
+        decision_time: LeftClosedTemporalInterval<DecisionTime>,
+    ) -> Entity {
+        Entity {
+            properties: EntityProperties::empty(),
+            link_data: None,
+            metadata: EntityMetadata::new(
+                EntityRecordId {
+                    entity_id,
+                    edition_id: EntityEditionId::new(Uuid::new_v4()),
+                },
+                EntityTemporalMetadata {
+                    decision_time,
+                    transaction_time: interval("2000-01-01T00:00:00Z", None),
+                },
+                VersionedUrl::from_str("https://example.com/person/v/1")
+                    .expect("should be a valid URL"),
+                provenance(),
+                false,
+            ),
+        }
+    }
+
+    fn insert_entity(subgraph: &mut Subgraph, entity: Entity) {
+        let vertex_id = entity.vertex_id(subgraph.temporal_axes.resolved.variable_time_axis());
+        subgraph.insert_vertex(vertex_id, entity);
+    }
+
+    fn entity_type_vertex_id(url: &str) -> EntityTypeVertexId {
+        EntityTypeVertexId {
+            base_id: BaseUrl::new(url.to_owned()).expect("should be valid URL"),
+            revision_id: OntologyTypeVersion::new(1),
+        }
+    }
+
+    #[test]
+    fn outgoing_ontology_edges() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default();
+        let mut subgraph = Subgraph::new(
+            GraphResolveDepths::default(),
+            temporal_axes.clone(),
+            temporal_axes.resolve(),
+        );
+
+        let person = entity_type_vertex_id("https://example.com/person/");
+        let agent = entity_type_vertex_id("https://example.com/agent/");
+        let name = PropertyTypeVertexId {
+            base_id: BaseUrl::new("https://example.com/name/".to_owned())
+                .expect("should be valid URL"),
+            revision_id: OntologyTypeVersion::new(1),
+        };
+
+        subgraph.insert_edge(
+            &person,
+            OntologyEdgeKind::InheritsFrom,
+            EdgeDirection::Outgoing,
+            agent.clone(),
+        );
+        subgraph.insert_edge(
+            &person,
+            OntologyEdgeKind::ConstrainsPropertiesOn,
+            EdgeDirection::Outgoing,
+            name.clone(),
+        );
+
+        assert_eq!(
+            subgraph
+                .outgoing_edges::<EntityTypeVertexId, _, _>(&person, OntologyEdgeKind::InheritsFrom)
+                .collect::<Vec<_>>(),
+            [&agent]
+        );
+        assert_eq!(
+            subgraph
+                .outgoing_edges::<PropertyTypeVertexId, _, _>(
+                    &person,
+                    OntologyEdgeKind::ConstrainsPropertiesOn
+                )
+                .collect::<Vec<_>>(),
+            [&name]
+        );
+        assert_eq!(
+            subgraph
+                .incoming_edges::<EntityTypeVertexId, _, _>(&person, OntologyEdgeKind::InheritsFrom)
+                .count(),
+            0
+        );
+        assert_eq!(
+            subgraph
+                .outgoing_edges::<EntityTypeVertexId, _, _>(&agent, OntologyEdgeKind::InheritsFrom)
+                .count(),
+            0
+        );
+
+        // Neither of the vertices were inserted into the subgraph
+        assert_eq!(
+            subgraph
+                .neighbors::<EntityTypeVertexId, _, _>(
+                    &person,
+                    OntologyEdgeKind::InheritsFrom,
+                    EdgeDirection::Outgoing
+                )
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn neighbors_respect_transaction_time() {
+        let temporal_axes = QueryTemporalAxesUnresolved::DecisionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(Some(
+                "2010-01-01T00:00:00Z"
+                    .parse()
+                    .expect("should be a valid timestamp"),
+            )),
+            variable: VariableTemporalAxisUnresolved::new(None, None),
+        };
+        let mut subgraph = Subgraph::new(
+            GraphResolveDepths::default(),
+            temporal_axes.clone(),
+            temporal_axes.resolve(),
+        );
+
+        let person = entity_type(
+            graph_test_data::entity_type::PERSON_V1,
+            interval("2000-01-01T00:00:00Z", None),
+        );
+        // Was already superseded at the pinned transaction time
+        let organization = entity_type(
+            graph_test_data::entity_type::ORGANIZATION_V1,
+            interval("2000-01-01T00:00:00Z", Some("2005-01-01T00:00:00Z")),
+        );
+        // Was not yet created at the pinned transaction time
+        let book = entity_type(
+            graph_test_data::entity_type::BOOK_V1,
+            interval("2020-01-01T00:00:00Z", None),
+        );
+        let building = entity_type(
+            graph_test_data::entity_type::BUILDING_V1,
+            interval("2005-01-01T00:00:00Z", None),
+        );
+
+        let person_id = person.vertex_id(TimeAxis::TransactionTime);
+        for entity_type in [organization, book, building] {
+            let vertex_id = entity_type.vertex_id(TimeAxis::TransactionTime);
+            subgraph.insert_edge(
+                &person_id,
+                OntologyEdgeKind::InheritsFrom,
+                EdgeDirection::Outgoing,
+                vertex_id.clone(),
+            );
+            subgraph.insert_vertex(vertex_id, entity_type);
+        }
+        subgraph.insert_vertex(person_id.clone(), person);
+
+        let neighbors = subgraph
+            .neighbors::<EntityTypeVertexId, _, _>(
+                &person_id,
+                OntologyEdgeKind::InheritsFrom,
+                EdgeDirection::Outgoing,
+            )
+            .map(|entity_type| entity_type.schema.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(neighbors, [
+            "https://blockprotocol.org/@alice/types/entity-type/building/v/1"
+        ]);
+    }
+
+    #[test]
+    fn entity_editions_are_looked_up_by_entity_id() {
+        let temporal_axes = QueryTemporalAxesUnresolved::default();
+        let mut subgraph = Subgraph::new(
+            GraphResolveDepths::default(),
+            temporal_axes.clone(),
+            temporal_axes.resolve(),
+        );
+
+        let alice = EntityId {
+            owned_by_id: OwnedById::new(AccountId::new(Uuid::nil())),
+            entity_uuid: EntityUuid::new(Uuid::new_v4()),
+        };
+        let bob = EntityId {
+            owned_by_id: OwnedById::new(AccountId::new(Uuid::nil())),
+            entity_uuid: EntityUuid::new(Uuid::new_v4()),
+        };
+
+        insert_entity(
+            &mut subgraph,
+            entity(
+                alice,
+                interval("2010-01-01T00:00:00Z", Some("2020-01-01T00:00:00Z")),
+            ),
+        );
+        insert_entity(
+            &mut subgraph,
+            entity(
+                alice,
+                interval("2000-01-01T00:00:00Z", Some("2010-01-01T00:00:00Z")),
+            ),
+        );
+        insert_entity(
+            &mut subgraph,
+            entity(bob, interval("2000-01-01T00:00:00Z", None)),
+        );
+
+        let decision_times = |entity_id, interval| {
+            subgraph
+                .entity_editions(entity_id, interval)
+                .map(|entity| entity.metadata.temporal_versioning().decision_time)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            decision_times(alice, interval("1990-01-01T00:00:00Z", None)),
+            [
+                interval("2000-01-01T00:00:00Z", Some("2010-01-01T00:00:00Z")),
+                interval("2010-01-01T00:00:00Z", Some("2020-01-01T00:00:00Z")),
+            ]
+        );
+        assert_eq!(
+            decision_times(alice, interval("2015-01-01T00:00:00Z", None)),
+            [interval(
+                "2010-01-01T00:00:00Z",
+                Some("2020-01-01T00:00:00Z")
+            )]
+        );
+        assert_eq!(
+            decision_times(bob, interval("1990-01-01T00:00:00Z", None)),
+            [interval("2000-01-01T00:00:00Z", None)]
+        );
+
+        // Inserting a vertex after a lookup has to be picked up by later lookups
+        insert_entity(
+            &mut subgraph,
+            entity(alice, interval("2020-01-01T00:00:00Z", None)),
+        );
+        assert_eq!(
+            subgraph
+                .entity_editions(alice, interval("2015-01-01T00:00:00Z", None))
+                .count(),
+            2
+        );
+
+        // The same applies to vertices, which are modified directly
+        subgraph
+            .vertices
+            .entities
+            .retain(|vertex_id, _| vertex_id.base_id != alice);
+        assert_eq!(
+            subgraph
+                .entity_editions(alice, interval("1990-01-01T00:00:00Z", None))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn iter_vertices_respect_temporal_axes() {
+        let temporal_axes = QueryTemporalAxesUnresolved::DecisionTime {
+            pinned: PinnedTemporalAxisUnresolved::new(None),
+            variable: VariableTemporalAxisUnresolved::new(
+                Some(TemporalBound::Inclusive(
+                    "2015-01-01T00:00:00Z"
+                        .parse()
+                        .expect("should be a valid timestamp"),
+                )),
+                Some(LimitedTemporalBound::Inclusive(
+                    "2016-01-01T00:00:00Z"
+                        .parse()
+                        .expect("should be a valid timestamp"),
+                )),
+            ),
+        };
+        let mut subgraph = Subgraph::new(
+            GraphResolveDepths::default(),
+            temporal_axes.clone(),
+            temporal_axes.resolve(),
+        );
+
+        let alice = EntityId {
+            owned_by_id: OwnedById::new(AccountId::new(Uuid::nil())),
+            entity_uuid: EntityUuid::new(Uuid::new_v4()),
+        };
+        for decision_time in [
+            interval("2000-01-01T00:00:00Z", Some("2010-01-01T00:00:00Z")),
+            interval("2010-01-01T00:00:00Z", Some("2020-01-01T00:00:00Z")),
+            interval("2020-01-01T00:00:00Z", None),
+        ] {
+            insert_entity(&mut subgraph, entity(alice, decision_time));
+        }
+
+        let person = entity_type(
+            graph_test_data::entity_type::PERSON_V1,
+            interval("2000-01-01T00:00:00Z", None),
+        );
+        subgraph.insert_vertex(person.vertex_id(TimeAxis::TransactionTime), person);
+        // Was not yet created at the pinned transaction time
+        let book = entity_type(
+            graph_test_data::entity_type::BOOK_V1,
+            interval("2100-01-01T00:00:00Z", None),
+        );
+        subgraph.insert_vertex(book.vertex_id(TimeAxis::TransactionTime), book);
+
+        assert_eq!(
+            subgraph
+                .iter_vertices::<EntityVertexId>()
+                .map(|(_, entity)| entity.metadata.temporal_versioning().decision_time)
+                .collect::<Vec<_>>(),
+            [interval(
+                "2010-01-01T00:00:00Z",
+                Some("2020-01-01T00:00:00Z")
+            )]
+        );
+        assert_eq!(
+            subgraph
+                .iter_vertices::<EntityTypeVertexId>()
+                .map(|(_, entity_type)| entity_type.schema.id().to_string())
+                .collect::<Vec<_>>(),
+            ["https://blockprotocol.org/@alice/types/entity-type/person/v/1"]
+        );
+    }
+}
@@ -12,7 +12,7 @@ pub use self::{
     edge::{EdgeDirection, OutwardEdge},
     kind::{
         EdgeKind, EdgeResolveDepths, GraphResolveDepths, KnowledgeGraphEdgeKind, OntologyEdgeKind,
-        OutgoingEdgeResolveDepth, SharedEdgeKind,
+        OntologyEdgeResolveDepth, OntologyResolveDepth, OutgoingEdgeResolveDepth, SharedEdgeKind,
     },
 };
 use crate::subgraph::{
@@ -71,6 +71,56 @@ where
             .insert(right_endpoint);
     }
 
+    /// Returns the endpoints of the edges of the given kind and direction starting at
+    /// `vertex_id`.
+    ///
+    /// Returns [`None`] if there are no such edges.
+    pub fn get(&self, vertex_id: &V, edge_kind: K, direction: EdgeDirection) -> Option<&E>
+    where
+        V::BaseId: Hash + Eq,
+        V::RevisionId: Ord,
+        K: Hash + Eq,
+    {
+        self.edges
+            .get(vertex_id.base_id())?
+            .get(&vertex_id.revision_id())?
+            .get(&EdgeData {
+                kind: edge_kind,
+                direction,
+            })
+    }
+
+    /// Returns an iterator over all edges in the adjacency list.
+    ///
+    /// Alongside the edge, the base id and the revision id of the left endpoint are returned.
+    pub fn iter<'a>(
+        &'a self,
+    ) -> impl Iterator<
+        Item = (
+            &'a V::BaseId,
+            &'a V::RevisionId,
+            OutwardEdge<K, <&'a E as IntoIterator>::Item>,
+        ),
+    >
+    where
+        K: Copy,
+        &'a E: IntoIterator,
+    {
+        self.edges.iter().flat_map(|(base_id, revisions)| {
+            revisions.iter().flat_map(move |(revision_id, edges)| {
+                edges.iter().flat_map(move |(edge, targets)| {
+                    targets.into_iter().map(move |right_endpoint| {
+                        (base_id, revision_id, OutwardEdge {
+                            kind: edge.kind,
+                            direction: edge.direction,
+                            right_endpoint,
+                        })
+                    })
+                })
+            })
+        })
+    }
+
     pub fn into_flattened<O>(
         self,
     ) -> impl Iterator<Item = (V::BaseId, BTreeMap<V::RevisionId, Vec<O>>)>
@@ -1,10 +1,10 @@
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{HashMap, HashSet},
     hash::{BuildHasher, Hash},
 };
 
 use graph_types::knowledge::entity::EntityId;
-use temporal_versioning::LeftClosedTemporalInterval;
+use temporal_versioning::{ClosedTemporalBound, IntervalSet, OpenTemporalBound, Timestamp};
 
 use crate::subgraph::{
     identifier::{EdgeEndpoint, EntityIdWithInterval},
@@ -27,7 +27,14 @@ impl<S: BuildHasher, E: EdgeEndpoint + Eq + Hash> EdgeEndpointSet for HashSet<E,
 
 #[derive(Debug, Default)]
 pub struct EntityIdWithIntervalSet {
-    inner: HashMap<EntityId, BTreeSet<LeftClosedTemporalInterval<VariableAxis>>>,
+    inner: HashMap<
+        EntityId,
+        IntervalSet<
+            Timestamp<VariableAxis>,
+            ClosedTemporalBound<VariableAxis>,
+            OpenTemporalBound<VariableAxis>,
+        >,
+    >,
 }
 
 impl IntoIterator for EntityIdWithIntervalSet {
@@ -37,47 +44,26 @@ impl IntoIterator for EntityIdWithIntervalSet {
 
     fn into_iter(self) -> Self::IntoIter {
         self.inner.into_iter().flat_map(|(entity_id, intervals)| {
-            // This merges overlapping intervals
-            //  Examples   |       1       |       2       |       3
-            //  ===========|===============|===============|===============
-            //  Interval A | [--]          | (--]          | [--]
-            //  Interval B |      [-]      |      [-)      |      [-)
-            //  Interval C |  [--]         |  [--]         |  [---]
-            //  Interval D |           [-] |           [-] |        [----]
-            //  -----------|---------------|---------------|---------------
-            //  Union      | [------]  [-] | (------)  [-] | [-----------]
-
-            // TODO: This is a very primitive implementation. Instead of iterating over all elements
-            //       and merging them, we should use a data structure that allows for efficient
-            //       merging of intervals. This is probably a tree structure that allows for
-            //       efficient merging of overlapping intervals.
-            //   See https://en.wikipedia.org/wiki/Interval_tree
-            //    or https://en.wikipedia.org/wiki/Segment_tree
-            //       For simplicity, we stick with this fairly robust implementation for now, but
-            //       as a short-term optimization, we could utilize `#![feature(generators)]` to
-            //       avoid the allocation of the intermediate vector.
             intervals
                 .into_iter()
-                .fold(
-                    Vec::<LeftClosedTemporalInterval<VariableAxis>>::new(),
-                    |mut acc, interval| {
-                        // The intervals are sorted, it's only necessary to check the union of this
-                        // with the last interval, if it overlaps two of the previous ones (which
-                        // would make it necessary to check the union with more than just the last)
-                        // then those would have been merged into one in the previous iteration
-                        // (again because they are sorted).
-                        if let Some(last) = acc.pop() {
-                            // `union` either returns one or two intervals, depending on whether
-                            // they overlap or not. If two intervals are returned, the ordering is
-                            // stable, so we can just push them in order.
-                            acc.extend(last.union(interval));
-                        } else {
-                            acc.push(interval);
-                        }
-                        acc
-                    },
-                )
-                .into_iter()
+                .map(move |interval| EntityIdWithInterval {
+                    entity_id,
+                    interval,
+                })
+        })
+    }
+}
+
+impl<'a> IntoIterator for &'a EntityIdWithIntervalSet {
+    type Item = EntityIdWithInterval;
+
+    type IntoIter = impl Iterator<Item = Self::Item> + 'a;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter().flat_map(|(&entity_id, intervals)| {
+            intervals
+                .iter()
+                .copied()
                 .map(move |interval| EntityIdWithInterval {
                     entity_id,
                     interval,
@@ -1,7 +1,10 @@
-use std::{collections::HashSet, convert::identity};
+use std::{collections::HashSet, convert::identity, fmt};
 
-use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::{
+    openapi::{ObjectBuilder, OneOfBuilder, RefOr, Schema, SchemaType},
+    ToSchema,
+};
 
 use crate::subgraph::{
     edges::{
@@ -17,6 +20,8 @@ use crate::subgraph::{
 pub trait EdgeKind<L: VertexId, R: EdgeEndpoint>: Sized {
     type EdgeSet: EdgeEndpointSet<EdgeEndpoint = R>;
 
+    fn subgraph_entry<'a>(&self, edges: &'a Edges) -> &'a AdjacencyList<L, Self, Self::EdgeSet>;
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -54,6 +59,13 @@ pub enum OntologyEdgeKind {
 impl EdgeKind<EntityTypeVertexId, EntityTypeVertexId> for OntologyEdgeKind {
     type EdgeSet = HashSet<EntityTypeVertexId>;
 
+    fn subgraph_entry<'a>(
+        &self,
+        edges: &'a Edges,
+    ) -> &'a AdjacencyList<EntityTypeVertexId, Self, Self::EdgeSet> {
+        &edges.entity_type_to_entity_type
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -65,6 +77,13 @@ impl EdgeKind<EntityTypeVertexId, EntityTypeVertexId> for OntologyEdgeKind {
 impl EdgeKind<EntityTypeVertexId, PropertyTypeVertexId> for OntologyEdgeKind {
     type EdgeSet = HashSet<PropertyTypeVertexId>;
 
+    fn subgraph_entry<'a>(
+        &self,
+        edges: &'a Edges,
+    ) -> &'a AdjacencyList<EntityTypeVertexId, Self, Self::EdgeSet> {
+        &edges.entity_type_to_property_type
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -76,6 +95,13 @@ impl EdgeKind<EntityTypeVertexId, PropertyTypeVertexId> for OntologyEdgeKind {
 impl EdgeKind<PropertyTypeVertexId, PropertyTypeVertexId> for OntologyEdgeKind {
     type EdgeSet = HashSet<PropertyTypeVertexId>;
 
+    fn subgraph_entry<'a>(
+        &self,
+        edges: &'a Edges,
+    ) -> &'a AdjacencyList<PropertyTypeVertexId, Self, Self::EdgeSet> {
+        &edges.property_type_to_property_type
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -87,6 +113,13 @@ impl EdgeKind<PropertyTypeVertexId, PropertyTypeVertexId> for OntologyEdgeKind {
 impl EdgeKind<PropertyTypeVertexId, DataTypeVertexId> for OntologyEdgeKind {
     type EdgeSet = HashSet<DataTypeVertexId>;
 
+    fn subgraph_entry<'a>(
+        &self,
+        edges: &'a Edges,
+    ) -> &'a AdjacencyList<PropertyTypeVertexId, Self, Self::EdgeSet> {
+        &edges.property_type_to_data_type
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -114,6 +147,13 @@ pub enum KnowledgeGraphEdgeKind {
 impl EdgeKind<EntityVertexId, EntityIdWithInterval> for KnowledgeGraphEdgeKind {
     type EdgeSet = EntityIdWithIntervalSet;
 
+    fn subgraph_entry<'a>(
+        &self,
+        edges: &'a Edges,
+    ) -> &'a AdjacencyList<EntityVertexId, Self, Self::EdgeSet> {
+        &edges.entity_to_entity
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -135,6 +175,13 @@ pub enum SharedEdgeKind {
 impl EdgeKind<EntityVertexId, EntityTypeVertexId> for SharedEdgeKind {
     type EdgeSet = HashSet<EntityTypeVertexId>;
 
+    fn subgraph_entry<'a>(
+        &self,
+        edges: &'a Edges,
+    ) -> &'a AdjacencyList<EntityVertexId, Self, Self::EdgeSet> {
+        &edges.entity_to_entity_type
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         edges: &'a mut Edges,
@@ -176,15 +223,156 @@ impl OutgoingEdgeResolveDepth {
     }
 }
 
+/// The depth up to which outgoing edges of an [`OntologyEdgeKind`] are resolved.
+///
+/// Serialized as a non-negative integer or as `"fixpoint"`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OntologyResolveDepth {
+    /// Edges are followed at most the given number of times.
+    Limited(u8),
+    /// Edges are followed until no new vertices are discovered, so the subgraph contains the
+    /// transitive closure of the edge without having to guess a sufficient depth.
+    Fixpoint,
+}
+
+impl Default for OntologyResolveDepth {
+    fn default() -> Self {
+        Self::Limited(0)
+    }
+}
+
+impl OntologyResolveDepth {
+    /// Returns `true` if a traversal with this depth reaches at least the vertices a traversal
+    /// with `other` reaches.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Fixpoint, _) => true,
+            (Self::Limited(_), Self::Fixpoint) => false,
+            (Self::Limited(depth), Self::Limited(other)) => depth >= other,
+        }
+    }
+
+    /// Returns the depth after following one edge, or `None` if no edge may be followed.
+    ///
+    /// A fixpoint depth is not decremented. The traversal terminates as soon as no new vertices
+    /// are discovered because already traversed vertices are skipped.
+    #[must_use]
+    pub fn decrement(self) -> Option<Self> {
+        match self {
+            Self::Limited(depth) => depth.checked_sub(1).map(Self::Limited),
+            Self::Fixpoint => Some(Self::Fixpoint),
+        }
+    }
+}
+
+const FIXPOINT: &str = "fixpoint";
+
+impl Serialize for OntologyResolveDepth {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Limited(depth) => serializer.serialize_u8(*depth),
+            Self::Fixpoint => serializer.serialize_str(FIXPOINT),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OntologyResolveDepth {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DepthVisitor;
+
+        impl<'de> de::Visitor<'de> for DepthVisitor {
+            type Value = OntologyResolveDepth;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "a non-negative integer or `\"{FIXPOINT}\"`")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                u8::try_from(value)
+                    .map(OntologyResolveDepth::Limited)
+                    .map_err(|_error| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                u8::try_from(value)
+                    .map(OntologyResolveDepth::Limited)
+                    .map_err(|_error| E::invalid_value(de::Unexpected::Signed(value), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                if value == FIXPOINT {
+                    Ok(OntologyResolveDepth::Fixpoint)
+                } else {
+                    Err(E::invalid_value(de::Unexpected::Str(value), &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_any(DepthVisitor)
+    }
+}
+
+impl ToSchema<'_> for OntologyResolveDepth {
+    fn schema() -> (&'static str, RefOr<Schema>) {
+        (
+            "OntologyResolveDepth",
+            OneOfBuilder::new()
+                .item(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::Integer)
+                        .minimum(Some(0.0))
+                        .maximum(Some(f64::from(u8::MAX))),
+                )
+                .item(
+                    ObjectBuilder::new()
+                        .schema_type(SchemaType::String)
+                        .enum_values(Some([FIXPOINT])),
+                )
+                .into(),
+        )
+    }
+}
+
+/// The depth of outgoing edges of an [`OntologyEdgeKind`] to resolve.
+///
+/// In addition to a numeric depth, the depth may be specified as `"fixpoint"`, see
+/// [`OntologyResolveDepth::Fixpoint`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OntologyEdgeResolveDepth {
+    pub outgoing: OntologyResolveDepth,
+    #[serde(default, skip)]
+    #[doc(hidden)]
+    /// This is not used yet, but will be used in the future to support incoming edges.
+    pub incoming: u8,
+}
+
+impl OntologyEdgeResolveDepth {
+    /// Returns a depth which resolves outgoing edges until no new vertices are discovered.
+    #[must_use]
+    pub const fn fixpoint() -> Self {
+        Self {
+            outgoing: OntologyResolveDepth::Fixpoint,
+            incoming: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.outgoing.contains(other.outgoing) && self.incoming >= other.incoming
+    }
+}
+
 /// TODO: DOC - <https://app.asana.com/0/0/1203438518991188/f>
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct GraphResolveDepths {
-    pub inherits_from: OutgoingEdgeResolveDepth,
-    pub constrains_values_on: OutgoingEdgeResolveDepth,
-    pub constrains_properties_on: OutgoingEdgeResolveDepth,
-    pub constrains_links_on: OutgoingEdgeResolveDepth,
-    pub constrains_link_destinations_on: OutgoingEdgeResolveDepth,
+    pub inherits_from: OntologyEdgeResolveDepth,
+    pub constrains_values_on: OntologyEdgeResolveDepth,
+    pub constrains_properties_on: OntologyEdgeResolveDepth,
+    pub constrains_links_on: OntologyEdgeResolveDepth,
+    pub constrains_link_destinations_on: OntologyEdgeResolveDepth,
     pub is_of_type: OutgoingEdgeResolveDepth,
     pub has_left_entity: EdgeResolveDepths,
     pub has_right_entity: EdgeResolveDepths,
@@ -217,57 +405,64 @@ impl GraphResolveDepths {
 }
 
 pub trait GraphResolveDepthIndex {
-    fn depth_mut(self, direction: EdgeDirection, dephts: &mut GraphResolveDepths) -> &mut u8;
+    /// Follows one edge of this kind in the given `direction` by decrementing the corresponding
+    /// depth.
+    ///
+    /// Returns `false` if no edge of this kind may be followed.
+    fn decrement_depth(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> bool;
+}
+
+fn decrement(depth: &mut u8) -> bool {
+    let Some(decremented) = depth.checked_sub(1) else {
+        return false;
+    };
+    *depth = decremented;
+    true
 }
 
 impl GraphResolveDepthIndex for OntologyEdgeKind {
-    fn depth_mut(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> &mut u8 {
-        match self {
-            Self::InheritsFrom => match direction {
-                EdgeDirection::Incoming => &mut depths.inherits_from.incoming,
-                EdgeDirection::Outgoing => &mut depths.inherits_from.outgoing,
-            },
-            Self::ConstrainsValuesOn => match direction {
-                EdgeDirection::Incoming => &mut depths.constrains_values_on.incoming,
-                EdgeDirection::Outgoing => &mut depths.constrains_values_on.outgoing,
-            },
-            Self::ConstrainsPropertiesOn => match direction {
-                EdgeDirection::Incoming => &mut depths.constrains_properties_on.incoming,
-                EdgeDirection::Outgoing => &mut depths.constrains_properties_on.outgoing,
-            },
-            Self::ConstrainsLinksOn => match direction {
-                EdgeDirection::Incoming => &mut depths.constrains_links_on.incoming,
-                EdgeDirection::Outgoing => &mut depths.constrains_links_on.outgoing,
-            },
-            Self::ConstrainsLinkDestinationsOn => match direction {
-                EdgeDirection::Incoming => &mut depths.constrains_link_destinations_on.incoming,
-                EdgeDirection::Outgoing => &mut depths.constrains_link_destinations_on.outgoing,
-            },
+    fn decrement_depth(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> bool {
+        let depth = match self {
+            Self::InheritsFrom => &mut depths.inherits_from,
+            Self::ConstrainsValuesOn => &mut depths.constrains_values_on,
+            Self::ConstrainsPropertiesOn => &mut depths.constrains_properties_on,
+            Self::ConstrainsLinksOn => &mut depths.constrains_links_on,
+            Self::ConstrainsLinkDestinationsOn => &mut depths.constrains_link_destinations_on,
+        };
+        match direction {
+            EdgeDirection::Incoming => decrement(&mut depth.incoming),
+            EdgeDirection::Outgoing => {
+                let Some(decremented) = depth.outgoing.decrement() else {
+                    return false;
+                };
+                depth.outgoing = decremented;
+                true
+            }
         }
     }
 }
 
 impl GraphResolveDepthIndex for SharedEdgeKind {
-    fn depth_mut(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> &mut u8 {
+    fn decrement_depth(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> bool {
         match self {
             Self::IsOfType => match direction {
-                EdgeDirection::Incoming => &mut depths.is_of_type.incoming,
-                EdgeDirection::Outgoing => &mut depths.is_of_type.outgoing,
+                EdgeDirection::Incoming => decrement(&mut depths.is_of_type.incoming),
+                EdgeDirection::Outgoing => decrement(&mut depths.is_of_type.outgoing),
             },
         }
     }
 }
 
 impl GraphResolveDepthIndex for KnowledgeGraphEdgeKind {
-    fn depth_mut(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> &mut u8 {
+    fn decrement_depth(self, direction: EdgeDirection, depths: &mut GraphResolveDepths) -> bool {
         match self {
             Self::HasLeftEntity => match direction {
-                EdgeDirection::Incoming => &mut depths.has_left_entity.incoming,
-                EdgeDirection::Outgoing => &mut depths.has_left_entity.outgoing,
+                EdgeDirection::Incoming => decrement(&mut depths.has_left_entity.incoming),
+                EdgeDirection::Outgoing => decrement(&mut depths.has_left_entity.outgoing),
             },
             Self::HasRightEntity => match direction {
-                EdgeDirection::Incoming => &mut depths.has_right_entity.incoming,
-                EdgeDirection::Outgoing => &mut depths.has_right_entity.outgoing,
+                EdgeDirection::Incoming => decrement(&mut depths.has_right_entity.incoming),
+                EdgeDirection::Outgoing => decrement(&mut depths.has_right_entity.outgoing),
             },
         }
     }
@@ -280,8 +475,126 @@ impl GraphResolveDepths {
         kind: impl GraphResolveDepthIndex,
         direction: EdgeDirection,
     ) -> Option<Self> {
-        let depths = kind.depth_mut(direction, &mut self);
-        *depths = depths.checked_sub(1)?;
-        Some(self)
+        kind.decrement_depth(direction, &mut self).then_some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_fixpoint() {
+        let depth: OntologyEdgeResolveDepth =
+            serde_json::from_value(serde_json::json!({ "outgoing": "fixpoint" }))
+                .expect("could not deserialize fixpoint depth");
+        assert_eq!(depth, OntologyEdgeResolveDepth::fixpoint());
+        assert_eq!(
+            serde_json::to_value(depth).expect("could not serialize fixpoint depth"),
+            serde_json::json!({ "outgoing": "fixpoint" })
+        );
+
+        let depth: OntologyEdgeResolveDepth =
+            serde_json::from_value(serde_json::json!({ "outgoing": 2 }))
+                .expect("could not deserialize numeric depth");
+        assert_eq!(depth.outgoing, OntologyResolveDepth::Limited(2));
+
+        serde_json::from_value::<OntologyEdgeResolveDepth>(
+            serde_json::json!({ "outgoing": "infinite" }),
+        )
+        .expect_err("deserialized invalid depth");
+        serde_json::from_value::<OntologyEdgeResolveDepth>(serde_json::json!({ "outgoing": 256 }))
+            .expect_err("deserialized out of range depth");
+    }
+
+    #[test]
+    fn maximum_depth_is_not_fixpoint() {
+        let depth: OntologyEdgeResolveDepth =
+            serde_json::from_value(serde_json::json!({ "outgoing": 255 }))
+                .expect("could not deserialize numeric depth");
+        assert_eq!(depth.outgoing, OntologyResolveDepth::Limited(u8::MAX));
+        assert!(!depth.contains(OntologyEdgeResolveDepth::fixpoint()));
+        assert!(OntologyEdgeResolveDepth::fixpoint().contains(depth));
+
+        let depths = GraphResolveDepths {
+            inherits_from: depth,
+            ..GraphResolveDepths::default()
+        };
+        let decremented = depths
+            .decrement_depth_for_edge(OntologyEdgeKind::InheritsFrom, EdgeDirection::Outgoing)
+            .expect("depth should be traversable");
+        assert_eq!(
+            decremented.inherits_from.outgoing,
+            OntologyResolveDepth::Limited(u8::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn fixpoint_is_rejected_on_knowledge_edges() {
+        let depths = serde_json::json!({
+            "inheritsFrom": { "outgoing": "fixpoint" },
+            "constrainsValuesOn": { "outgoing": 0 },
+            "constrainsPropertiesOn": { "outgoing": 0 },
+            "constrainsLinksOn": { "outgoing": 0 },
+            "constrainsLinkDestinationsOn": { "outgoing": 0 },
+            "isOfType": { "outgoing": 1 },
+            "hasLeftEntity": { "incoming": 0, "outgoing": 0 },
+            "hasRightEntity": { "incoming": 0, "outgoing": 0 },
+        });
+        let deserialized: GraphResolveDepths =
+            serde_json::from_value(depths.clone()).expect("could not deserialize depths");
+        assert_eq!(
+            deserialized.inherits_from,
+            OntologyEdgeResolveDepth::fixpoint()
+        );
+        assert_eq!(deserialized.is_of_type.outgoing, 1);
+
+        for edge in ["isOfType", "hasLeftEntity", "hasRightEntity"] {
+            let mut depths = depths.clone();
+            depths[edge]["outgoing"] = serde_json::json!("fixpoint");
+            serde_json::from_value::<GraphResolveDepths>(depths)
+                .expect_err("deserialized fixpoint depth for a knowledge graph edge");
+        }
+    }
+
+    #[test]
+    fn fixpoint_is_not_decremented() {
+        let depths = GraphResolveDepths {
+            inherits_from: OntologyEdgeResolveDepth::fixpoint(),
+            constrains_properties_on: OntologyEdgeResolveDepth {
+                outgoing: OntologyResolveDepth::Limited(1),
+                incoming: 0,
+            },
+            ..GraphResolveDepths::default()
+        };
+
+        let decremented = depths
+            .decrement_depth_for_edge(OntologyEdgeKind::InheritsFrom, EdgeDirection::Outgoing)
+            .expect("fixpoint depth should be traversable");
+        assert_eq!(decremented, depths);
+        assert!(decremented.contains(depths));
+
+        let decremented = depths
+            .decrement_depth_for_edge(
+                OntologyEdgeKind::ConstrainsPropertiesOn,
+                EdgeDirection::Outgoing,
+            )
+            .expect("depth should be traversable");
+        assert_eq!(
+            decremented.constrains_properties_on.outgoing,
+            OntologyResolveDepth::Limited(0)
+        );
+        assert_eq!(
+            decremented.inherits_from,
+            OntologyEdgeResolveDepth::fixpoint()
+        );
+        assert!(
+            decremented
+                .decrement_depth_for_edge(
+                    OntologyEdgeKind::ConstrainsPropertiesOn,
+                    EdgeDirection::Outgoing,
+                )
+                .is_none()
+        );
     }
 }
@@ -1,4 +1,7 @@
-use std::collections::hash_map::{RandomState, RawEntryMut};
+use std::collections::{
+    hash_map::{RandomState, RawEntryMut},
+    HashMap,
+};
 
 use graph_types::{
     knowledge::entity::{Entity, EntityId},
@@ -26,6 +29,9 @@ pub trait VertexId: Sized {
     /// [`Record`]: Self::Record
     fn subgraph_entry<'a>(&self, vertices: &'a Vertices) -> Option<&'a Self::Record>;
 
+    /// Returns a shared reference to all vertices of this kind in the subgraph.
+    fn subgraph_vertices(vertices: &Vertices) -> &HashMap<Self, Self::Record>;
+
     /// Returns a mutable reference to the [`Record`] vertex in the subgraph.
     ///
     /// [`Record`]: Self::Record
@@ -62,6 +68,10 @@ macro_rules! define_ontology_type_vertex_id {
                 vertices.$vertex_set.get(self)
             }
 
+            fn subgraph_vertices(vertices: &Vertices) -> &HashMap<Self, $ontology_type> {
+                &vertices.$vertex_set
+            }
+
             fn subgraph_entry_mut<'a>(
                 &self,
                 vertices: &'a mut Vertices,
@@ -126,6 +136,10 @@ impl VertexId for EntityVertexId {
         vertices.entities.get(self)
     }
 
+    fn subgraph_vertices(vertices: &Vertices) -> &HashMap<Self, Entity> {
+        &vertices.entities
+    }
+
     fn subgraph_entry_mut<'a>(
         &self,
         vertices: &'a mut Vertices,
@@ -4,9 +4,11 @@ use graph_types::{
     knowledge::entity::Entity,
     ontology::{DataTypeWithMetadata, EntityTypeWithMetadata, PropertyTypeWithMetadata},
 };
+use temporal_versioning::{LeftClosedTemporalInterval, TemporalTagged, TimeAxis, TransactionTime};
 
-use crate::subgraph::identifier::{
-    DataTypeVertexId, EntityTypeVertexId, EntityVertexId, PropertyTypeVertexId,
+use crate::subgraph::{
+    identifier::{DataTypeVertexId, EntityTypeVertexId, EntityVertexId, PropertyTypeVertexId},
+    temporal_axes::{PinnedAxis, QueryTemporalAxes, VariableAxis},
 };
 
 #[derive(Default, Debug)]
@@ -16,3 +18,82 @@ pub struct Vertices {
     pub entity_types: HashMap<EntityTypeVertexId, EntityTypeWithMetadata>,
     pub entities: HashMap<EntityVertexId, Entity>,
 }
+
+/// A vertex of the ontology.
+///
+/// Ontology types are only versioned along the transaction time axis.
+pub trait OntologyVertex {
+    fn transaction_time(&self) -> LeftClosedTemporalInterval<TransactionTime>;
+}
+
+impl OntologyVertex for DataTypeWithMetadata {
+    fn transaction_time(&self) -> LeftClosedTemporalInterval<TransactionTime> {
+        self.metadata.custom.temporal_versioning().transaction_time
+    }
+}
+
+impl OntologyVertex for PropertyTypeWithMetadata {
+    fn transaction_time(&self) -> LeftClosedTemporalInterval<TransactionTime> {
+        self.metadata.custom.temporal_versioning().transaction_time
+    }
+}
+
+impl OntologyVertex for EntityTypeWithMetadata {
+    fn transaction_time(&self) -> LeftClosedTemporalInterval<TransactionTime> {
+        self.metadata
+            .custom
+            .common
+            .temporal_versioning()
+            .transaction_time
+    }
+}
+
+/// A vertex, which can be checked against the temporal axes of a subgraph.
+pub trait TemporalVertex {
+    /// Returns `true` if the vertex is valid at the resolved `temporal_axes`.
+    fn is_valid_at(&self, temporal_axes: &QueryTemporalAxes) -> bool;
+}
+
+/// As ontology types are only versioned along the transaction time axis, the decision time axis is
+/// not taken into account.
+impl<T: OntologyVertex> TemporalVertex for T {
+    fn is_valid_at(&self, temporal_axes: &QueryTemporalAxes) -> bool {
+        let transaction_time = self.transaction_time();
+        match temporal_axes.pinned_time_axis() {
+            TimeAxis::TransactionTime => transaction_time
+                .cast::<PinnedAxis>()
+                .contains_point(&temporal_axes.pinned_timestamp()),
+            TimeAxis::DecisionTime => transaction_time
+                .cast::<VariableAxis>()
+                .overlaps(&temporal_axes.variable_interval()),
+        }
+    }
+}
+
+impl TemporalVertex for Entity {
+    fn is_valid_at(&self, temporal_axes: &QueryTemporalAxes) -> bool {
+        let temporal_metadata = self.metadata.temporal_versioning();
+        match temporal_axes.pinned_time_axis() {
+            TimeAxis::TransactionTime => {
+                temporal_metadata
+                    .transaction_time
+                    .cast::<PinnedAxis>()
+                    .contains_point(&temporal_axes.pinned_timestamp())
+                    && temporal_metadata
+                        .decision_time
+                        .cast::<VariableAxis>()
+                        .overlaps(&temporal_axes.variable_interval())
+            }
+            TimeAxis::DecisionTime => {
+                temporal_metadata
+                    .decision_time
+                    .cast::<PinnedAxis>()
+                    .contains_point(&temporal_axes.pinned_timestamp())
+                    && temporal_metadata
+                        .transaction_time
+                        .cast::<VariableAxis>()
+                        .overlaps(&temporal_axes.variable_interval())
+            }
+        }
+    }
+}
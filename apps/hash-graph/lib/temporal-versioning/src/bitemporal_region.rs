@@ -0,0 +1,305 @@
+use core::{cmp::Ordering, ops::Bound};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "utoipa")]
+use utoipa::{openapi, ToSchema};
+
+use crate::{
+    bounds::{compare_bounds, BoundType},
+    DecisionTime, Interval, IntervalBound, TemporalInterval, TemporalIntervalSet, Timestamp,
+    TransactionTime,
+};
+
+/// An axis-aligned rectangle on the decision time and the transaction time axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BitemporalRectangle {
+    pub decision_time: TemporalInterval<DecisionTime>,
+    pub transaction_time: TemporalInterval<TransactionTime>,
+}
+
+impl BitemporalRectangle {
+    /// Creates a rectangle spanned by the given intervals.
+    pub fn new<DS, DE, TS, TE>(
+        decision_time: Interval<Timestamp<DecisionTime>, DS, DE>,
+        transaction_time: Interval<Timestamp<TransactionTime>, TS, TE>,
+    ) -> Self
+    where
+        DS: IntervalBound<Timestamp<DecisionTime>>,
+        DE: IntervalBound<Timestamp<DecisionTime>>,
+        TS: IntervalBound<Timestamp<TransactionTime>>,
+        TE: IntervalBound<Timestamp<TransactionTime>>,
+    {
+        Self {
+            decision_time: decision_time.convert(),
+            transaction_time: transaction_time.convert(),
+        }
+    }
+}
+
+/// A decision time interval, over which the transaction time intervals of a region don't change.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Slab {
+    decision_time: TemporalInterval<DecisionTime>,
+    transaction_time: TemporalIntervalSet<TransactionTime>,
+}
+
+/// A region on the decision time and the transaction time axis made up of
+/// [`BitemporalRectangle`]s.
+///
+/// The region is stored as sorted, disjoint decision time intervals, each associated with the set
+/// of transaction time intervals covered over the whole decision time interval. Adjacent decision
+/// time intervals with the same transaction time intervals are merged, so two regions covering the
+/// same points are equal, regardless of the rectangles they were built from.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BitemporalRegion {
+    slabs: Vec<Slab>,
+}
+
+impl BitemporalRegion {
+    /// Creates an empty region.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { slabs: Vec::new() }
+    }
+
+    /// Returns `true` if the region does not contain any point.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.slabs.is_empty()
+    }
+
+    /// Returns disjoint rectangles, which cover exactly this region.
+    ///
+    /// The rectangles are ordered by decision time first and by transaction time second.
+    pub fn rectangles(&self) -> impl Iterator<Item = BitemporalRectangle> + '_ {
+        self.slabs.iter().flat_map(|slab| {
+            slab.transaction_time
+                .iter()
+                .map(|&transaction_time| BitemporalRectangle {
+                    decision_time: slab.decision_time,
+                    transaction_time,
+                })
+        })
+    }
+
+    /// Adds the rectangle to the region.
+    pub fn insert(&mut self, rectangle: BitemporalRectangle) {
+        *self = self.union(&Self::from(rectangle));
+    }
+
+    fn slab_at(&self, decision_time: &Timestamp<DecisionTime>) -> Option<&Slab> {
+        let index = self.slabs.partition_point(|slab| {
+            compare_bounds(
+                slab.decision_time.end().as_bound(),
+                Bound::Included(decision_time),
+                BoundType::End,
+                BoundType::End,
+                Ord::cmp,
+            ) == Ordering::Less
+        });
+        self.slabs
+            .get(index)
+            .filter(|slab| slab.decision_time.contains_point(decision_time))
+    }
+
+    /// Returns `true` if the point specified by both timestamps is contained in the region.
+    #[must_use]
+    pub fn contains_point(
+        &self,
+        decision_time: &Timestamp<DecisionTime>,
+        transaction_time: &Timestamp<TransactionTime>,
+    ) -> bool {
+        self.slab_at(decision_time)
+            .is_some_and(|slab| slab.transaction_time.contains_point(transaction_time))
+    }
+
+    /// Returns the transaction time intervals covered by the region when pinning the decision time
+    /// to `decision_time`.
+    #[must_use]
+    pub fn at_decision_time(
+        &self,
+        decision_time: &Timestamp<DecisionTime>,
+    ) -> TemporalIntervalSet<TransactionTime> {
+        self.slab_at(decision_time)
+            .map_or_else(TemporalIntervalSet::new, |slab| {
+                slab.transaction_time.clone()
+            })
+    }
+
+    /// Returns the decision time intervals covered by the region when pinning the transaction time
+    /// to `transaction_time`.
+    #[must_use]
+    pub fn at_transaction_time(
+        &self,
+        transaction_time: &Timestamp<TransactionTime>,
+    ) -> TemporalIntervalSet<DecisionTime> {
+        self.slabs
+            .iter()
+            .filter(|slab| slab.transaction_time.contains_point(transaction_time))
+            .map(|slab| slab.decision_time)
+            .collect()
+    }
+
+    /// Returns a region, which contains all points contained in either region.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.overlay(other, |lhs, rhs| match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => lhs.clone().union(rhs.clone()),
+            (Some(transaction_time), None) | (None, Some(transaction_time)) => {
+                transaction_time.clone()
+            }
+            (None, None) => TemporalIntervalSet::new(),
+        })
+    }
+
+    /// Returns a region, which contains all points contained in both regions.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Self {
+        self.overlay(other, |lhs, rhs| match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => lhs.intersect(rhs),
+            _ => TemporalIntervalSet::new(),
+        })
+    }
+
+    /// Returns a region, which contains all points contained in this region but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.overlay(other, |lhs, rhs| match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => lhs.difference(rhs),
+            (Some(lhs), None) => lhs.clone(),
+            (None, _) => TemporalIntervalSet::new(),
+        })
+    }
+
+    /// Returns the whole decision time axis split into the slabs of this region and the gaps
+    /// between them.
+    fn partition(
+        &self,
+    ) -> Vec<(
+        TemporalInterval<DecisionTime>,
+        Option<&TemporalIntervalSet<TransactionTime>>,
+    )> {
+        let gaps = self
+            .slabs
+            .iter()
+            .map(|slab| slab.decision_time)
+            .collect::<TemporalIntervalSet<DecisionTime>>()
+            .complement();
+
+        let mut partition = self
+            .slabs
+            .iter()
+            .map(|slab| (slab.decision_time, Some(&slab.transaction_time)))
+            .chain(gaps.into_iter().map(|gap| (gap, None)))
+            .collect::<Vec<_>>();
+        partition.sort_unstable_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        partition
+    }
+
+    /// Combines the transaction time intervals of both regions for every decision time interval on
+    /// which neither region changes.
+    ///
+    /// `combine` is passed `None` for a region, which does not cover the decision time interval.
+    fn overlay(
+        &self,
+        other: &Self,
+        combine: impl Fn(
+            Option<&TemporalIntervalSet<TransactionTime>>,
+            Option<&TemporalIntervalSet<TransactionTime>>,
+        ) -> TemporalIntervalSet<TransactionTime>,
+    ) -> Self {
+        let lhs_partition = self.partition();
+        let rhs_partition = other.partition();
+        let mut lhs = lhs_partition.iter().peekable();
+        let mut rhs = rhs_partition.iter().peekable();
+
+        let mut slabs = Vec::<Slab>::new();
+        while let (Some(&&(lhs_interval, lhs_set)), Some(&&(rhs_interval, rhs_set))) =
+            (lhs.peek(), rhs.peek())
+        {
+            if let Some(decision_time) = lhs_interval.intersect(rhs_interval) {
+                let transaction_time = combine(lhs_set, rhs_set);
+                match slabs.last_mut() {
+                    _ if transaction_time.is_empty() => {}
+                    Some(last)
+                        if last.transaction_time == transaction_time
+                            && last.decision_time.is_adjacent_to(&decision_time) =>
+                    {
+                        last.decision_time = last.decision_time.merge(decision_time);
+                    }
+                    _ => slabs.push(Slab {
+                        decision_time,
+                        transaction_time,
+                    }),
+                }
+            }
+
+            // Both partitions cover the whole axis, so the interval, which ends first, cannot
+            // overlap with any other interval of the other partition.
+            if lhs_interval.cmp_end_to_end(&rhs_interval) == Ordering::Less {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
+        }
+
+        Self { slabs }
+    }
+}
+
+impl From<BitemporalRectangle> for BitemporalRegion {
+    fn from(rectangle: BitemporalRectangle) -> Self {
+        Self {
+            slabs: vec![Slab {
+                decision_time: rectangle.decision_time,
+                transaction_time: TemporalIntervalSet::from_iter([rectangle.transaction_time]),
+            }],
+        }
+    }
+}
+
+impl FromIterator<BitemporalRectangle> for BitemporalRegion {
+    fn from_iter<I: IntoIterator<Item = BitemporalRectangle>>(iter: I) -> Self {
+        let mut region = Self::new();
+        region.extend(iter);
+        region
+    }
+}
+
+impl Extend<BitemporalRectangle> for BitemporalRegion {
+    fn extend<I: IntoIterator<Item = BitemporalRectangle>>(&mut self, iter: I) {
+        for rectangle in iter {
+            self.insert(rectangle);
+        }
+    }
+}
+
+impl Serialize for BitemporalRegion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rectangles())
+    }
+}
+
+impl<'de> Deserialize<'de> for BitemporalRegion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<BitemporalRectangle>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl ToSchema<'_> for BitemporalRegion {
+    fn schema() -> (&'static str, openapi::RefOr<openapi::Schema>) {
+        (
+            "BitemporalRegion",
+            openapi::ArrayBuilder::new()
+                .items(openapi::Ref::from_schema_name(
+                    BitemporalRectangle::schema().0,
+                ))
+                .into(),
+        )
+    }
+}
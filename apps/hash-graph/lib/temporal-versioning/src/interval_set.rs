@@ -0,0 +1,390 @@
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Peekable,
+    ops::{Bound, Range},
+    slice,
+};
+use std::vec;
+
+use crate::{
+    bounds::{compare_bounds, BoundType, IntervalBound},
+    Interval,
+};
+
+/// A collection of [`Interval`]s, which is kept normalized.
+///
+/// The intervals in the set are sorted, disjoint, and coalesced, i.e. no two intervals in the set
+/// overlap or are adjacent to each other. Inserting an interval, which overlaps with or is adjacent
+/// to intervals in the set, merges them into a single interval.
+///
+/// Lookups are done by binary search over the sorted intervals.
+pub struct IntervalSet<T, S, E> {
+    intervals: Vec<Interval<T, S, E>>,
+}
+
+impl<T, S, E> IntervalSet<T, S, E> {
+    /// Creates an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the set does not contain any interval.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the number of disjoint intervals in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Returns the intervals in the set in ascending order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Interval<T, S, E>] {
+        &self.intervals
+    }
+
+    /// Returns an iterator over the intervals in the set in ascending order.
+    pub fn iter(&self) -> slice::Iter<'_, Interval<T, S, E>> {
+        self.intervals.iter()
+    }
+
+    /// Removes all intervals from the set.
+    pub fn clear(&mut self) {
+        self.intervals.clear();
+    }
+}
+
+impl<T, S, E> IntervalSet<T, S, E>
+where
+    T: Ord,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+    /// Returns the indices of the intervals in the set, which overlap with `interval`.
+    ///
+    /// If `include_adjacent` is `true`, intervals adjacent to `interval` are included as well.
+    fn touching(
+        &self,
+        interval: &Interval<T, impl IntervalBound<T>, impl IntervalBound<T>>,
+        include_adjacent: bool,
+    ) -> Range<usize> {
+        // As the intervals are sorted and disjoint, both the start and the end of the intervals are
+        // sorted, so the range can be determined by two binary searches.
+        let start = self.intervals.partition_point(|existing| {
+            existing.cmp_end_to_start(interval) == Ordering::Less
+                && !(include_adjacent && existing.is_adjacent_to(interval))
+        });
+        let end = start
+            + self.intervals[start..].partition_point(|existing| {
+                existing.cmp_start_to_end(interval) != Ordering::Greater
+                    || include_adjacent && existing.is_adjacent_to(interval)
+            });
+        start..end
+    }
+
+    /// Pushes an interval to the end of `intervals`, merging it with the last interval if
+    /// possible.
+    ///
+    /// The interval must not start before the last interval in `intervals`.
+    fn push_coalesced(intervals: &mut Vec<Interval<T, S, E>>, interval: Interval<T, S, E>) {
+        if let Some(last) = intervals.pop() {
+            // `union` either returns one or two intervals, depending on whether they overlap or
+            // not. If two intervals are returned, the ordering is stable, so we can just push them
+            // in order.
+            intervals.extend(last.union(interval));
+        } else {
+            intervals.push(interval);
+        }
+    }
+
+    /// Adds the interval to the set.
+    ///
+    /// Intervals in the set, which overlap with or are adjacent to `interval`, are merged with it.
+    pub fn insert(&mut self, interval: Interval<T, S, E>) {
+        let range = self.touching(&interval, true);
+        let index = range.start;
+        let merged = self.intervals.drain(range).fold(interval, Interval::merge);
+        self.intervals.insert(index, merged);
+    }
+
+    /// Removes all points of `interval` from the set.
+    ///
+    /// Intervals in the set, which partially overlap with `interval`, are shrunk or split.
+    pub fn remove(&mut self, interval: Interval<T, S, E>)
+    where
+        T: Clone,
+        S: Clone,
+        E: Clone,
+    {
+        let range = self.touching(&interval, false);
+        let index = range.start;
+        let remaining = self
+            .intervals
+            .drain(range)
+            .flat_map(|existing| existing.difference(interval.clone()))
+            .collect::<Vec<_>>();
+        self.intervals.splice(index..index, remaining);
+    }
+
+    /// Returns `true` if any interval in the set contains `point`.
+    #[must_use]
+    pub fn contains_point(&self, point: &T) -> bool {
+        let index = self.intervals.partition_point(|interval| {
+            compare_bounds(
+                interval.end().as_bound(),
+                Bound::Included(point),
+                BoundType::End,
+                BoundType::End,
+                Ord::cmp,
+            ) == Ordering::Less
+        });
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains_point(point))
+    }
+
+    /// Returns `true` if all points of `interval` are contained in the set.
+    #[must_use]
+    pub fn contains_interval(
+        &self,
+        interval: &Interval<T, impl IntervalBound<T>, impl IntervalBound<T>>,
+    ) -> bool {
+        // As the intervals are coalesced, `interval` can only be contained in a single interval.
+        let range = self.touching(interval, false);
+        range.len() == 1 && self.intervals[range.start].contains_interval(interval)
+    }
+
+    /// Returns `true` if any interval in the set has points in common with `interval`.
+    #[must_use]
+    pub fn overlaps(
+        &self,
+        interval: &Interval<T, impl IntervalBound<T>, impl IntervalBound<T>>,
+    ) -> bool {
+        !self.touching(interval, false).is_empty()
+    }
+
+    /// Returns a set, which contains all points not contained in this set.
+    #[must_use]
+    pub fn complement(&self) -> Self
+    where
+        T: Clone,
+        S: Clone,
+        E: Clone,
+    {
+        let mut complement = Self {
+            intervals: vec![Interval::new_unchecked(
+                S::from_bound(Bound::Unbounded),
+                E::from_bound(Bound::Unbounded),
+            )],
+        };
+        // As the intervals are sorted, each removal only affects the last interval of the
+        // complement.
+        for interval in &self.intervals {
+            complement.remove(interval.clone());
+        }
+        complement
+    }
+
+    /// Returns a set, which contains all points contained in either set.
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let mut intervals = Vec::with_capacity(self.intervals.len() + other.intervals.len());
+        for interval in MergeSorted::new(self.intervals, other.intervals) {
+            Self::push_coalesced(&mut intervals, interval);
+        }
+        Self { intervals }
+    }
+
+    /// Returns a set, which contains all points contained in both sets.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Self
+    where
+        T: Clone,
+        S: Clone,
+        E: Clone,
+    {
+        let mut intervals = Vec::new();
+        let mut lhs = self.intervals.iter().peekable();
+        let mut rhs = other.intervals.iter().peekable();
+
+        while let (Some(&left), Some(&right)) = (lhs.peek(), rhs.peek()) {
+            // As both sets are coalesced, the intersections are coalesced as well.
+            intervals.extend(left.clone().intersect(right.clone()));
+
+            // The interval, which ends first, cannot overlap with any other interval of the other
+            // set.
+            if left.cmp_end_to_end(right) == Ordering::Less {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Returns a set, which contains all points contained in this set but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self
+    where
+        T: Clone,
+        S: Clone,
+        E: Clone,
+    {
+        let mut difference = self.clone();
+        for interval in other {
+            difference.remove(interval.clone());
+        }
+        difference
+    }
+}
+
+/// Merges two sorted vectors of intervals into a single sorted iterator.
+struct MergeSorted<T, S, E> {
+    lhs: Peekable<vec::IntoIter<Interval<T, S, E>>>,
+    rhs: Peekable<vec::IntoIter<Interval<T, S, E>>>,
+}
+
+impl<T, S, E> MergeSorted<T, S, E> {
+    fn new(lhs: Vec<Interval<T, S, E>>, rhs: Vec<Interval<T, S, E>>) -> Self {
+        Self {
+            lhs: lhs.into_iter().peekable(),
+            rhs: rhs.into_iter().peekable(),
+        }
+    }
+}
+
+impl<T, S, E> Iterator for MergeSorted<T, S, E>
+where
+    T: Ord,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+    type Item = Interval<T, S, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.lhs.peek(), self.rhs.peek()) {
+            (Some(lhs), Some(rhs)) if lhs.cmp_start_to_start(rhs) == Ordering::Greater => {
+                self.rhs.next()
+            }
+            (Some(_), _) => self.lhs.next(),
+            (None, _) => self.rhs.next(),
+        }
+    }
+}
+
+impl<T, S, E> Default for IntervalSet<T, S, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S, E> Clone for IntervalSet<T, S, E>
+where
+    T: Clone,
+    S: Clone,
+    E: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            intervals: self.intervals.clone(),
+        }
+    }
+}
+
+impl<T, S, E> PartialEq for IntervalSet<T, S, E>
+where
+    T: PartialEq,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.intervals == other.intervals
+    }
+}
+
+impl<T, S, E> Eq for IntervalSet<T, S, E>
+where
+    T: Eq,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+}
+
+impl<T, S, E> Hash for IntervalSet<T, S, E>
+where
+    S: Hash,
+    E: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.intervals.hash(state);
+    }
+}
+
+impl<T, S, E> fmt::Debug for IntervalSet<T, S, E>
+where
+    T: fmt::Debug,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+impl<T, S, E> FromIterator<Interval<T, S, E>> for IntervalSet<T, S, E>
+where
+    T: Ord,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T, S, E>>>(iter: I) -> Self {
+        let mut sorted = iter.into_iter().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let mut intervals = Vec::with_capacity(sorted.len());
+        for interval in sorted {
+            Self::push_coalesced(&mut intervals, interval);
+        }
+        Self { intervals }
+    }
+}
+
+impl<T, S, E> Extend<Interval<T, S, E>> for IntervalSet<T, S, E>
+where
+    T: Ord,
+    S: IntervalBound<T>,
+    E: IntervalBound<T>,
+{
+    fn extend<I: IntoIterator<Item = Interval<T, S, E>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T, S, E> IntoIterator for IntervalSet<T, S, E> {
+    type IntoIter = vec::IntoIter<Interval<T, S, E>>;
+    type Item = Interval<T, S, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, T, S, E> IntoIterator for &'a IntervalSet<T, S, E> {
+    type IntoIter = slice::Iter<'a, Interval<T, S, E>>;
+    type Item = &'a Interval<T, S, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
@@ -0,0 +1,273 @@
+use core::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    ops::Bound,
+    str::FromStr,
+};
+use std::error::Error;
+
+use crate::{
+    bounds::{compare_bounds, BoundType},
+    ClosedTemporalBound, Interval, IntervalBound, LimitedTemporalBound, OpenTemporalBound,
+    TemporalBound, Timestamp,
+};
+
+/// Notation of an unbounded bound on its own or in the compact interval notation.
+const UNBOUNDED: &str = "..";
+/// Accepted notations of an unbounded start bound of an interval.
+const UNBOUNDED_START: [&str; 2] = [UNBOUNDED, "-∞"];
+/// Accepted notations of an unbounded end bound of an interval.
+const UNBOUNDED_END: [&str; 3] = [UNBOUNDED, "∞", "+∞"];
+
+/// The error returned when parsing a temporal bound or a temporal interval fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIntervalError {
+    /// The input does not follow the notation of a bound or an interval.
+    InvalidSyntax,
+    /// A limit of a bound is not a valid ISO 8601 timestamp.
+    InvalidTimestamp(time::error::Parse),
+    /// The kind of a bound cannot be represented by the bound type, e.g. an unbounded end bound
+    /// for an interval, which requires a limited end bound.
+    UnsupportedBound,
+    /// The start bound of an interval is greater than its end bound.
+    InvalidOrder,
+}
+
+impl fmt::Display for ParseIntervalError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSyntax => fmt.write_str("invalid interval notation"),
+            Self::InvalidTimestamp(_) => fmt.write_str("invalid timestamp"),
+            Self::UnsupportedBound => fmt.write_str("bound is not supported by the bound type"),
+            Self::InvalidOrder => fmt.write_str("start bound is greater than end bound"),
+        }
+    }
+}
+
+impl Error for ParseIntervalError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidTimestamp(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+fn fmt_bound<A>(bound: Bound<&Timestamp<A>>, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match bound {
+        Bound::Included(limit) => {
+            fmt.write_char('[')?;
+            limit.fmt_iso8601(fmt)?;
+            fmt.write_char(']')
+        }
+        Bound::Excluded(limit) => {
+            fmt.write_char('(')?;
+            limit.fmt_iso8601(fmt)?;
+            fmt.write_char(')')
+        }
+        Bound::Unbounded => fmt.write_str(UNBOUNDED),
+    }
+}
+
+fn parse_limit<A>(limit: &str) -> Result<Timestamp<A>, ParseIntervalError> {
+    limit
+        .trim()
+        .parse()
+        .map_err(ParseIntervalError::InvalidTimestamp)
+}
+
+fn parse_bound<A>(bound: &str) -> Result<TemporalBound<A>, ParseIntervalError> {
+    let bound = bound.trim();
+    if bound == UNBOUNDED {
+        Ok(TemporalBound::Unbounded)
+    } else if let Some(limit) = bound.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        Ok(TemporalBound::Inclusive(parse_limit(limit)?))
+    } else if let Some(limit) = bound.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Ok(TemporalBound::Exclusive(parse_limit(limit)?))
+    } else {
+        Err(ParseIntervalError::InvalidSyntax)
+    }
+}
+
+fn parse_interval_bound<A>(
+    bound: &str,
+    inclusive: bool,
+    unbounded: &[&str],
+) -> Result<TemporalBound<A>, ParseIntervalError> {
+    let bound = bound.trim();
+    if unbounded.contains(&bound) {
+        Ok(TemporalBound::Unbounded)
+    } else if inclusive {
+        Ok(TemporalBound::Inclusive(parse_limit(bound)?))
+    } else {
+        Ok(TemporalBound::Exclusive(parse_limit(bound)?))
+    }
+}
+
+fn parse_interval<A>(
+    interval: &str,
+) -> Result<(TemporalBound<A>, TemporalBound<A>), ParseIntervalError> {
+    let interval = interval.trim();
+    let mut chars = interval.chars();
+    if let (Some(open @ ('[' | '(')), Some(close @ (']' | ')'))) = (chars.next(), chars.next_back())
+    {
+        // Timestamps never contain a slash, so it takes precedence over the comma, which may be
+        // used as decimal separator in ISO 8601.
+        let inner = chars.as_str();
+        let (start, end) = inner
+            .split_once('/')
+            .or_else(|| inner.split_once(','))
+            .ok_or(ParseIntervalError::InvalidSyntax)?;
+        Ok((
+            parse_interval_bound(start, open == '[', &UNBOUNDED_START)?,
+            parse_interval_bound(end, close == ']', &UNBOUNDED_END)?,
+        ))
+    } else {
+        let (start, end) = interval
+            .split_once('/')
+            .ok_or(ParseIntervalError::InvalidSyntax)?;
+        Ok((
+            parse_interval_bound(start, true, &UNBOUNDED_START)?,
+            parse_interval_bound(end, false, &UNBOUNDED_END)?,
+        ))
+    }
+}
+
+/// Writes the bound as `[limit]` if inclusive, `(limit)` if exclusive, and `..` if unbounded.
+///
+/// The limit is written as ISO 8601 timestamp.
+impl<A> fmt::Display for TemporalBound<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bound(self.as_bound(), fmt)
+    }
+}
+
+/// Parses a bound in the notation written by the [`Display`] implementation.
+///
+/// [`Display`]: fmt::Display
+impl<A> FromStr for TemporalBound<A> {
+    type Err = ParseIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_bound(s)
+    }
+}
+
+impl<A> fmt::Display for LimitedTemporalBound<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bound(self.as_bound(), fmt)
+    }
+}
+
+impl<A> FromStr for LimitedTemporalBound<A> {
+    type Err = ParseIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(parse_bound(s)?).map_err(|_bound| ParseIntervalError::UnsupportedBound)
+    }
+}
+
+impl<A> fmt::Display for ClosedTemporalBound<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bound(self.as_bound(), fmt)
+    }
+}
+
+impl<A> FromStr for ClosedTemporalBound<A> {
+    type Err = ParseIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(parse_bound(s)?).map_err(|_bound| ParseIntervalError::UnsupportedBound)
+    }
+}
+
+impl<A> fmt::Display for OpenTemporalBound<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_bound(self.as_bound(), fmt)
+    }
+}
+
+impl<A> FromStr for OpenTemporalBound<A> {
+    type Err = ParseIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(parse_bound(s)?).map_err(|_bound| ParseIntervalError::UnsupportedBound)
+    }
+}
+
+/// Writes the interval in bracket notation with ISO 8601 timestamps, e.g.
+/// `[2023-01-01T00:00:00Z, ∞)`.
+///
+/// The alternate form (`{:#}`) writes a compact notation without whitespace and non-ASCII
+/// characters, which is suitable for URLs, e.g. `[2023-01-01T00:00:00Z/..)`.
+impl<A, S, E> fmt::Display for Interval<Timestamp<A>, S, E>
+where
+    S: IntervalBound<Timestamp<A>>,
+    E: IntervalBound<Timestamp<A>>,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (separator, unbounded_start, unbounded_end) = if fmt.alternate() {
+            ("/", UNBOUNDED, UNBOUNDED)
+        } else {
+            (", ", "-∞", "∞")
+        };
+
+        match self.start().as_bound() {
+            Bound::Included(limit) => {
+                fmt.write_char('[')?;
+                limit.fmt_iso8601(fmt)?;
+            }
+            Bound::Excluded(limit) => {
+                fmt.write_char('(')?;
+                limit.fmt_iso8601(fmt)?;
+            }
+            Bound::Unbounded => write!(fmt, "({unbounded_start}")?,
+        }
+        fmt.write_str(separator)?;
+        match self.end().as_bound() {
+            Bound::Included(limit) => {
+                limit.fmt_iso8601(fmt)?;
+                fmt.write_char(']')
+            }
+            Bound::Excluded(limit) => {
+                limit.fmt_iso8601(fmt)?;
+                fmt.write_char(')')
+            }
+            Bound::Unbounded => write!(fmt, "{unbounded_end})"),
+        }
+    }
+}
+
+/// Parses an interval in bracket notation or in ISO 8601 `start/end` notation.
+///
+/// - In bracket notation, `[` and `]` denote an inclusive bound, `(` and `)` an exclusive bound.
+///   The bounds are separated by `,` or `/`, and `..`, `-∞` (start), `∞`, or `+∞` (end) denote an
+///   unbounded bound, e.g. `[2023-01-01T00:00Z, ∞)` or `(../2023-01-01T00:00Z]`.
+/// - In ISO 8601 notation, the start bound is inclusive and the end bound is exclusive, and `..`
+///   denotes an open-ended bound, e.g. `2023-01-01T00:00Z/..`.
+impl<A, S, E> FromStr for Interval<Timestamp<A>, S, E>
+where
+    S: IntervalBound<Timestamp<A>> + TryFrom<TemporalBound<A>>,
+    E: IntervalBound<Timestamp<A>> + TryFrom<TemporalBound<A>>,
+{
+    type Err = ParseIntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = parse_interval(s)?;
+        let start = S::try_from(start).map_err(|_bound| ParseIntervalError::UnsupportedBound)?;
+        let end = E::try_from(end).map_err(|_bound| ParseIntervalError::UnsupportedBound)?;
+
+        if compare_bounds(
+            start.as_bound(),
+            end.as_bound(),
+            BoundType::Start,
+            BoundType::End,
+            Ord::cmp,
+        ) == Ordering::Greater
+        {
+            return Err(ParseIntervalError::InvalidOrder);
+        }
+
+        Ok(Self::new_unchecked(start, end))
+    }
+}
@@ -3,15 +3,21 @@
 pub mod serde;
 
 mod axis;
+mod bitemporal_region;
 mod bounds;
 mod interval;
+mod interval_set;
+mod iso8601;
 mod temporal_bound;
 mod timestamp;
 
 pub use self::{
     axis::{DecisionTime, TemporalTagged, TimeAxis, TransactionTime},
+    bitemporal_region::{BitemporalRectangle, BitemporalRegion},
     bounds::IntervalBound,
     interval::Interval,
+    interval_set::IntervalSet,
+    iso8601::ParseIntervalError,
     temporal_bound::{ClosedTemporalBound, LimitedTemporalBound, OpenTemporalBound, TemporalBound},
     timestamp::Timestamp,
 };
@@ -19,6 +25,9 @@ pub use self::{
 /// A temporal interval, where both bounds are either inclusive, exclusive, or unbounded.
 pub type TemporalInterval<A> = Interval<Timestamp<A>, TemporalBound<A>, TemporalBound<A>>;
 
+/// A set of [`TemporalInterval`]s.
+pub type TemporalIntervalSet<A> = IntervalSet<Timestamp<A>, TemporalBound<A>, TemporalBound<A>>;
+
 /// A temporal interval, where both bounds are either inclusive or exclusive. The lower bound may
 /// also be unbounded.
 pub type RightBoundedTemporalInterval<A> =
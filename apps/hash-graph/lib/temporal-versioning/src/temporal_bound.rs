@@ -95,6 +95,19 @@ impl<A> Clone for LimitedTemporalBound<A> {
     }
 }
 
+impl<A> TryFrom<TemporalBound<A>> for LimitedTemporalBound<A> {
+    type Error = TemporalBound<A>;
+
+    /// Converts the bound if it's limited, otherwise the bound is returned as error.
+    fn try_from(bound: TemporalBound<A>) -> Result<Self, Self::Error> {
+        match bound {
+            TemporalBound::Inclusive(limit) => Ok(Self::Inclusive(limit)),
+            TemporalBound::Exclusive(limit) => Ok(Self::Exclusive(limit)),
+            TemporalBound::Unbounded => Err(bound),
+        }
+    }
+}
+
 impl<A> TemporalTagged for LimitedTemporalBound<A> {
     type Axis = A;
     type Tagged<T> = LimitedTemporalBound<T>;
@@ -154,6 +167,18 @@ impl<A> Clone for ClosedTemporalBound<A> {
     }
 }
 
+impl<A> TryFrom<TemporalBound<A>> for ClosedTemporalBound<A> {
+    type Error = TemporalBound<A>;
+
+    /// Converts the bound if it's inclusive, otherwise the bound is returned as error.
+    fn try_from(bound: TemporalBound<A>) -> Result<Self, Self::Error> {
+        match bound {
+            TemporalBound::Inclusive(limit) => Ok(Self::Inclusive(limit)),
+            TemporalBound::Exclusive(_) | TemporalBound::Unbounded => Err(bound),
+        }
+    }
+}
+
 impl<A> TemporalTagged for ClosedTemporalBound<A> {
     type Axis = A;
     type Tagged<T> = ClosedTemporalBound<T>;
@@ -214,6 +239,19 @@ impl<A> Clone for OpenTemporalBound<A> {
     }
 }
 
+impl<A> TryFrom<TemporalBound<A>> for OpenTemporalBound<A> {
+    type Error = TemporalBound<A>;
+
+    /// Converts the bound if it's exclusive or unbounded, otherwise the bound is returned as error.
+    fn try_from(bound: TemporalBound<A>) -> Result<Self, Self::Error> {
+        match bound {
+            TemporalBound::Exclusive(limit) => Ok(Self::Exclusive(limit)),
+            TemporalBound::Unbounded => Ok(Self::Unbounded),
+            TemporalBound::Inclusive(_) => Err(bound),
+        }
+    }
+}
+
 impl<A> TemporalTagged for OpenTemporalBound<A> {
     type Axis = A;
     type Tagged<T> = OpenTemporalBound<T>;
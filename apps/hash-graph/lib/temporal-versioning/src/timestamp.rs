@@ -9,7 +9,10 @@ use derivative::Derivative;
 #[cfg(feature = "postgres")]
 use postgres_types::{FromSql, ToSql, Type};
 use serde::{Deserialize, Serialize};
-use time::{format_description::well_known::Iso8601, OffsetDateTime};
+use time::{
+    format_description::well_known::{Iso8601, Rfc3339},
+    OffsetDateTime,
+};
 #[cfg(feature = "utoipa")]
 use utoipa::{openapi, ToSchema};
 
@@ -95,6 +98,19 @@ impl<A> Timestamp<A> {
             time: time.time,
         }
     }
+
+    /// Writes the timestamp in the ISO 8601 profile of RFC 3339, e.g. `2023-01-01T00:00:00Z`.
+    ///
+    /// Timestamps, which cannot be represented in RFC 3339, are written in the extended ISO 8601
+    /// format.
+    pub(crate) fn fmt_iso8601(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatted = self
+            .time
+            .format(&Rfc3339)
+            .or_else(|_| self.time.format(&Iso8601::DEFAULT))
+            .map_err(|_error| fmt::Error)?;
+        fmt.write_str(&formatted)
+    }
 }
 
 impl<A> FromStr for Timestamp<A> {
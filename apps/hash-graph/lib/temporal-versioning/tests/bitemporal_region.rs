@@ -0,0 +1,263 @@
+use temporal_versioning::*;
+
+/// All points on both axes, which are checked when comparing the region operations point-wise.
+const POINTS: core::ops::RangeInclusive<u8> = 0..=30;
+
+fn timestamp<A>(second: u8) -> Timestamp<A> {
+    format!("2000-01-01T00:00:{second:02}Z")
+        .parse()
+        .expect("timestamp should be valid")
+}
+
+fn interval<A>(start: u8, end: u8) -> TemporalInterval<A> {
+    Interval::new(
+        TemporalBound::Inclusive(timestamp(start)),
+        TemporalBound::Exclusive(timestamp(end)),
+    )
+}
+
+fn unbounded<A>(start: u8) -> TemporalInterval<A> {
+    Interval::new(
+        TemporalBound::Inclusive(timestamp(start)),
+        TemporalBound::Unbounded,
+    )
+}
+
+const fn rectangle(
+    decision_time: TemporalInterval<DecisionTime>,
+    transaction_time: TemporalInterval<TransactionTime>,
+) -> BitemporalRectangle {
+    BitemporalRectangle {
+        decision_time,
+        transaction_time,
+    }
+}
+
+fn contains(region: &BitemporalRegion, decision_time: u8, transaction_time: u8) -> bool {
+    region.contains_point(&timestamp(decision_time), &timestamp(transaction_time))
+}
+
+struct TestData<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+fn test(
+    test_data: TestData<
+        impl IntoIterator<Item = BitemporalRectangle>,
+        impl IntoIterator<Item = BitemporalRectangle>,
+    >,
+) {
+    let lhs_rectangles = test_data.lhs.into_iter().collect::<Vec<_>>();
+    let rhs_rectangles = test_data.rhs.into_iter().collect::<Vec<_>>();
+    let lhs = lhs_rectangles.iter().copied().collect::<BitemporalRegion>();
+    let rhs = rhs_rectangles.iter().copied().collect::<BitemporalRegion>();
+
+    let union = lhs.union(&rhs);
+    let intersection = lhs.intersect(&rhs);
+    let difference = lhs.difference(&rhs);
+
+    assert_eq!(union, rhs.union(&lhs), "union is not commutative");
+    assert_eq!(
+        intersection,
+        rhs.intersect(&lhs),
+        "intersection is not commutative"
+    );
+    assert_eq!(
+        union.rectangles().collect::<BitemporalRegion>(),
+        union,
+        "region is not reconstructed from its rectangles"
+    );
+    assert_eq!(
+        lhs_rectangles
+            .iter()
+            .chain(&rhs_rectangles)
+            .rev()
+            .copied()
+            .collect::<BitemporalRegion>(),
+        union,
+        "region depends on the insertion order"
+    );
+
+    let in_rectangles = |rectangles: &[BitemporalRectangle], decision_time, transaction_time| {
+        rectangles.iter().any(|rectangle| {
+            rectangle
+                .decision_time
+                .contains_point(&timestamp(decision_time))
+                && rectangle
+                    .transaction_time
+                    .contains_point(&timestamp(transaction_time))
+        })
+    };
+
+    for decision_time in POINTS {
+        for transaction_time in POINTS {
+            let in_lhs = in_rectangles(&lhs_rectangles, decision_time, transaction_time);
+            let in_rhs = in_rectangles(&rhs_rectangles, decision_time, transaction_time);
+            assert_eq!(
+                contains(&lhs, decision_time, transaction_time),
+                in_lhs,
+                "{lhs:?} is wrong at ({decision_time}, {transaction_time})"
+            );
+            assert_eq!(
+                contains(&union, decision_time, transaction_time),
+                in_lhs || in_rhs,
+                "union {union:?} is wrong at ({decision_time}, {transaction_time})"
+            );
+            assert_eq!(
+                contains(&intersection, decision_time, transaction_time),
+                in_lhs && in_rhs,
+                "intersection {intersection:?} is wrong at ({decision_time}, {transaction_time})"
+            );
+            assert_eq!(
+                contains(&difference, decision_time, transaction_time),
+                in_lhs && !in_rhs,
+                "difference {difference:?} is wrong at ({decision_time}, {transaction_time})"
+            );
+            assert_eq!(
+                union
+                    .at_decision_time(&timestamp(decision_time))
+                    .contains_point(&timestamp(transaction_time)),
+                in_lhs || in_rhs,
+                "slice of {union:?} at decision time {decision_time} is wrong"
+            );
+            assert_eq!(
+                union
+                    .at_transaction_time(&timestamp(transaction_time))
+                    .contains_point(&timestamp(decision_time)),
+                in_lhs || in_rhs,
+                "slice of {union:?} at transaction time {transaction_time} is wrong"
+            );
+        }
+    }
+}
+
+#[test]
+fn disjoint() {
+    // Transaction time
+    //    ^
+    //    |      +--+
+    //    |      |B |
+    //    | +--+ +--+
+    //    | |A |
+    //    | +--+
+    //    +-------------> Decision time
+    test(TestData {
+        lhs: [rectangle(interval(0, 5), interval(0, 5))],
+        rhs: [rectangle(interval(10, 15), interval(10, 15))],
+    });
+    test(TestData {
+        lhs: [rectangle(interval(0, 5), interval(0, 5))],
+        rhs: [rectangle(interval(0, 5), interval(10, 15))],
+    });
+}
+
+#[test]
+fn overlapping() {
+    // Transaction time
+    //    ^
+    //    |    +----+
+    //    | +--|-+ B|
+    //    | |A +-|--+
+    //    | +----+
+    //    +-------------> Decision time
+    test(TestData {
+        lhs: [rectangle(interval(0, 10), interval(0, 10))],
+        rhs: [rectangle(interval(5, 15), interval(5, 15))],
+    });
+    test(TestData {
+        lhs: [
+            rectangle(interval(0, 10), interval(0, 10)),
+            rectangle(interval(20, 25), unbounded(5)),
+        ],
+        rhs: [
+            rectangle(interval(5, 22), interval(5, 15)),
+            rectangle(unbounded(24), interval(0, 3)),
+        ],
+    });
+}
+
+#[test]
+fn contained() {
+    test(TestData {
+        lhs: [rectangle(interval(0, 20), interval(0, 20))],
+        rhs: [rectangle(interval(5, 10), interval(5, 10))],
+    });
+    test(TestData {
+        lhs: [rectangle(interval(5, 10), interval(5, 10))],
+        rhs: [rectangle(interval(0, 20), interval(0, 20))],
+    });
+}
+
+#[test]
+fn normalization() {
+    // Both decompositions cover the same L-shaped region:
+    //
+    //  +--+        +--+
+    //  |  |        |  |
+    //  |  +--+     +--+--+
+    //  |  |  |     |     |
+    //  +--+--+     +-----+
+    let vertical = [
+        rectangle(interval(0, 5), interval(0, 10)),
+        rectangle(interval(5, 10), interval(0, 5)),
+    ]
+    .into_iter()
+    .collect::<BitemporalRegion>();
+    let horizontal = [
+        rectangle(interval(0, 10), interval(0, 5)),
+        rectangle(interval(0, 5), interval(5, 10)),
+    ]
+    .into_iter()
+    .collect::<BitemporalRegion>();
+    assert_eq!(vertical, horizontal);
+
+    // Adjacent rectangles with the same transaction time are merged
+    let region = [
+        rectangle(interval(0, 5), interval(0, 10)),
+        rectangle(interval(5, 10), interval(0, 10)),
+    ]
+    .into_iter()
+    .collect::<BitemporalRegion>();
+    assert_eq!(region.rectangles().collect::<Vec<_>>(), [rectangle(
+        interval(0, 10),
+        interval(0, 10)
+    )]);
+
+    assert!(BitemporalRegion::new().is_empty());
+    assert!(
+        BitemporalRegion::from(rectangle(interval(0, 5), interval(0, 5)))
+            .difference(&BitemporalRegion::from(rectangle(
+                interval(0, 10),
+                interval(0, 10)
+            )))
+            .is_empty()
+    );
+}
+
+#[test]
+fn slicing() {
+    let region = [
+        rectangle(interval(0, 10), interval(0, 5)),
+        rectangle(interval(5, 15), interval(10, 20)),
+    ]
+    .into_iter()
+    .collect::<BitemporalRegion>();
+
+    assert_eq!(region.at_decision_time(&timestamp(7)).as_slice(), [
+        interval(0, 5),
+        interval(10, 20)
+    ]);
+    assert_eq!(region.at_decision_time(&timestamp(12)).as_slice(), [
+        interval(10, 20)
+    ]);
+    assert!(region.at_decision_time(&timestamp(15)).is_empty());
+
+    assert_eq!(region.at_transaction_time(&timestamp(3)).as_slice(), [
+        interval(0, 10)
+    ]);
+    assert_eq!(region.at_transaction_time(&timestamp(10)).as_slice(), [
+        interval(5, 15)
+    ]);
+    assert!(region.at_transaction_time(&timestamp(7)).is_empty());
+}
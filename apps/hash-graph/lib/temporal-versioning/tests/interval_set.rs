@@ -0,0 +1,472 @@
+#![feature(lint_reasons)]
+
+use core::ops::Bound;
+
+use temporal_versioning::*;
+
+type TestInterval = Interval<u32, Bound<u32>, Bound<u32>>;
+type TestIntervalSet = IntervalSet<u32, Bound<u32>, Bound<u32>>;
+
+/// All points, which are checked when comparing the set operations point-wise.
+const POINTS: core::ops::RangeInclusive<u32> = 0..=40;
+
+fn set(intervals: impl IntoIterator<Item = TestInterval>) -> TestIntervalSet {
+    intervals.into_iter().collect()
+}
+
+fn assert_normalized(set: &TestIntervalSet) {
+    for window in set.as_slice().windows(2) {
+        let [lhs, rhs] = window else {
+            unreachable!("window size is 2");
+        };
+        assert!(lhs < rhs, "{set:?} is not sorted");
+        assert!(
+            !lhs.overlaps(rhs) && !lhs.is_adjacent_to(rhs),
+            "{set:?} is not coalesced: {lhs:?} and {rhs:?} could be merged"
+        );
+    }
+}
+
+fn assert_equality(actual: &TestIntervalSet, expected: &TestIntervalSet, operator: &'static str) {
+    assert_normalized(actual);
+    assert_eq!(
+        actual, expected,
+        "{operator} output failed, expected {expected:?}, got {actual:?}"
+    );
+}
+
+struct TestData<L, R, I, U, D> {
+    lhs: L,
+    rhs: R,
+    intersection: I,
+    union: U,
+    difference: D,
+}
+
+fn test(
+    test_data: TestData<
+        impl IntoIterator<Item = TestInterval>,
+        impl IntoIterator<Item = TestInterval>,
+        impl IntoIterator<Item = TestInterval>,
+        impl IntoIterator<Item = TestInterval>,
+        impl IntoIterator<Item = TestInterval>,
+    >,
+) {
+    let TestData {
+        lhs,
+        rhs,
+        intersection,
+        union,
+        difference,
+    } = test_data;
+
+    let lhs = set(lhs);
+    let rhs = set(rhs);
+    let intersection = set(intersection);
+    let union = set(union);
+    let difference = set(difference);
+
+    assert_normalized(&lhs);
+    assert_normalized(&rhs);
+
+    assert_equality(&lhs.intersect(&rhs), &intersection, "intersection");
+    assert_equality(&rhs.intersect(&lhs), &intersection, "reversed intersection");
+    assert_equality(&lhs.clone().union(rhs.clone()), &union, "union");
+    assert_equality(&rhs.clone().union(lhs.clone()), &union, "reversed union");
+    assert_equality(&lhs.difference(&rhs), &difference, "difference");
+
+    let mut inserted = lhs.clone();
+    inserted.extend(rhs.clone());
+    assert_equality(&inserted, &union, "union calculated by insertion");
+
+    let mut removed = lhs.clone();
+    for interval in &rhs {
+        removed.remove(*interval);
+    }
+    assert_equality(&removed, &difference, "difference calculated by removal");
+
+    assert_equality(
+        &lhs.intersect(&rhs.complement()),
+        &difference,
+        "difference calculated by complement",
+    );
+
+    for point in POINTS {
+        let in_lhs = lhs.contains_point(&point);
+        let in_rhs = rhs.contains_point(&point);
+        assert_eq!(
+            intersection.contains_point(&point),
+            in_lhs && in_rhs,
+            "intersection of {lhs:?} and {rhs:?} is wrong at {point}"
+        );
+        assert_eq!(
+            union.contains_point(&point),
+            in_lhs || in_rhs,
+            "union of {lhs:?} and {rhs:?} is wrong at {point}"
+        );
+        assert_eq!(
+            difference.contains_point(&point),
+            in_lhs && !in_rhs,
+            "difference of {lhs:?} and {rhs:?} is wrong at {point}"
+        );
+    }
+
+    for interval in &rhs {
+        assert_eq!(
+            lhs.overlaps(interval),
+            !lhs.intersect(&set([*interval])).is_empty(),
+            "`overlaps` of {lhs:?} and {interval:?} is inconsistent with `intersect`"
+        );
+        assert_eq!(
+            lhs.contains_interval(interval),
+            lhs.clone().union(set([*interval])) == lhs,
+            "`contains_interval` of {lhs:?} and {interval:?} is inconsistent with `union`"
+        );
+    }
+}
+
+fn unbounded_unbounded() -> TestInterval {
+    Interval::new(Bound::Unbounded, Bound::Unbounded)
+}
+
+fn included_unbounded(start: u32) -> TestInterval {
+    Interval::new(Bound::Included(start), Bound::Unbounded)
+}
+
+fn excluded_unbounded(start: u32) -> TestInterval {
+    Interval::new(Bound::Excluded(start), Bound::Unbounded)
+}
+
+fn unbounded_included(end: u32) -> TestInterval {
+    Interval::new(Bound::Unbounded, Bound::Included(end))
+}
+
+fn unbounded_excluded(end: u32) -> TestInterval {
+    Interval::new(Bound::Unbounded, Bound::Excluded(end))
+}
+
+fn included_included(start: u32, end: u32) -> TestInterval {
+    Interval::new(Bound::Included(start), Bound::Included(end))
+}
+
+fn included_excluded(start: u32, end: u32) -> TestInterval {
+    Interval::new(Bound::Included(start), Bound::Excluded(end))
+}
+
+fn excluded_included(start: u32, end: u32) -> TestInterval {
+    Interval::new(Bound::Excluded(start), Bound::Included(end))
+}
+
+fn excluded_excluded(start: u32, end: u32) -> TestInterval {
+    Interval::new(Bound::Excluded(start), Bound::Excluded(end))
+}
+
+#[test]
+fn normalization() {
+    // Input:   [--]   [-]  [--)
+    //           [---]    [-]
+    // Set:     [------]  [----)
+    assert_eq!(
+        set([
+            included_excluded(15, 20),
+            included_included(0, 5),
+            included_included(8, 10),
+            included_included(2, 8),
+            included_included(13, 15),
+        ])
+        .as_slice(),
+        [included_included(0, 10), included_excluded(13, 20)]
+    );
+
+    // Input:   [--)
+    //             [--]
+    // Set:     [-----]
+    assert_eq!(
+        set([included_included(5, 10), included_excluded(0, 5)]).as_slice(),
+        [included_included(0, 10)]
+    );
+
+    // Input:   [--)
+    //             (--]
+    // Set:     [--)(--]
+    assert_eq!(
+        set([excluded_included(5, 10), included_excluded(0, 5)]).as_slice(),
+        [included_excluded(0, 5), excluded_included(5, 10)]
+    );
+
+    assert!(set([]).is_empty());
+    assert_eq!(
+        set([unbounded_unbounded(), included_included(0, 5)]).len(),
+        1
+    );
+}
+
+#[test]
+fn insert() {
+    let mut intervals = TestIntervalSet::new();
+
+    intervals.insert(included_excluded(10, 15));
+    intervals.insert(included_excluded(0, 5));
+    intervals.insert(included_excluded(20, 25));
+    assert_eq!(intervals.as_slice(), [
+        included_excluded(0, 5),
+        included_excluded(10, 15),
+        included_excluded(20, 25)
+    ]);
+
+    // Adjacent intervals are merged
+    intervals.insert(included_excluded(5, 7));
+    assert_eq!(intervals.as_slice(), [
+        included_excluded(0, 7),
+        included_excluded(10, 15),
+        included_excluded(20, 25)
+    ]);
+
+    // Bridging multiple intervals merges all of them
+    intervals.insert(included_included(6, 20));
+    assert_eq!(intervals.as_slice(), [included_excluded(0, 25)]);
+
+    intervals.insert(unbounded_excluded(0));
+    assert_eq!(intervals.as_slice(), [unbounded_excluded(25)]);
+
+    intervals.insert(included_unbounded(25));
+    assert_eq!(intervals.as_slice(), [unbounded_unbounded()]);
+}
+
+#[test]
+fn remove() {
+    let mut intervals = set([included_included(0, 10), included_included(20, 30)]);
+
+    // Removing from the middle splits the interval
+    intervals.remove(included_included(3, 5));
+    assert_eq!(intervals.as_slice(), [
+        included_excluded(0, 3),
+        excluded_included(5, 10),
+        included_included(20, 30)
+    ]);
+
+    // Removing across multiple intervals shrinks the outer ones and drops the inner ones
+    intervals.remove(excluded_excluded(2, 25));
+    assert_eq!(intervals.as_slice(), [
+        included_included(0, 2),
+        included_included(25, 30)
+    ]);
+
+    // Removing a disjoint interval is a no-op
+    intervals.remove(included_included(12, 18));
+    assert_eq!(intervals.as_slice(), [
+        included_included(0, 2),
+        included_included(25, 30)
+    ]);
+
+    intervals.remove(unbounded_unbounded());
+    assert!(intervals.is_empty());
+}
+
+#[test]
+fn disjoint() {
+    // Set A:        [--]      [--]
+    // Set B:             [--]
+    // intersection:
+    // union:        [--] [--] [--]
+    // difference:   [--]      [--]
+    test(TestData {
+        lhs: [included_included(0, 5), included_included(20, 25)],
+        rhs: [included_included(10, 15)],
+        intersection: [],
+        union: [
+            included_included(0, 5),
+            included_included(10, 15),
+            included_included(20, 25),
+        ],
+        difference: [included_included(0, 5), included_included(20, 25)],
+    });
+    test(TestData {
+        lhs: [included_included(10, 15)],
+        rhs: [included_included(0, 5), included_included(20, 25)],
+        intersection: [],
+        union: [
+            included_included(0, 5),
+            included_included(10, 15),
+            included_included(20, 25),
+        ],
+        difference: [included_included(10, 15)],
+    });
+
+    // Set A:        [--)  (--]
+    // Set B:           [--]
+    // intersection:
+    // union:        [--------]
+    // difference:   [--)  (--]
+    test(TestData {
+        lhs: [included_excluded(0, 5), excluded_included(10, 15)],
+        rhs: [included_included(5, 10)],
+        intersection: [],
+        union: [included_included(0, 15)],
+        difference: [included_excluded(0, 5), excluded_included(10, 15)],
+    });
+    test(TestData {
+        lhs: [included_included(5, 10)],
+        rhs: [included_excluded(0, 5), excluded_included(10, 15)],
+        intersection: [],
+        union: [included_included(0, 15)],
+        difference: [included_included(5, 10)],
+    });
+}
+
+#[test]
+fn overlapping() {
+    // Set A:        [----]    [----]
+    // Set B:           [-------]
+    // intersection:    [-]    []
+    // union:        [-------------]
+    // difference:   [--)       (---]
+    test(TestData {
+        lhs: [included_included(0, 10), included_included(20, 30)],
+        rhs: [included_included(5, 25)],
+        intersection: [included_included(5, 10), included_included(20, 25)],
+        union: [included_included(0, 30)],
+        difference: [included_excluded(0, 5), excluded_included(25, 30)],
+    });
+    test(TestData {
+        lhs: [included_included(5, 25)],
+        rhs: [included_included(0, 10), included_included(20, 30)],
+        intersection: [included_included(5, 10), included_included(20, 25)],
+        union: [included_included(0, 30)],
+        difference: [excluded_excluded(10, 20)],
+    });
+
+    // Set A:        [---]  [---]  [---]
+    // Set B:          [------]  [---]
+    // intersection:   [-]  [-]  []
+    // union:        [-----------------]
+    // difference:   [-)      (--)  (--]
+    test(TestData {
+        lhs: [
+            included_excluded(0, 10),
+            included_excluded(15, 25),
+            included_excluded(30, 40),
+        ],
+        rhs: [included_excluded(5, 20), included_excluded(23, 33)],
+        intersection: [
+            included_excluded(5, 10),
+            included_excluded(15, 20),
+            included_excluded(23, 25),
+            included_excluded(30, 33),
+        ],
+        union: [included_excluded(0, 40)],
+        difference: [
+            included_excluded(0, 5),
+            included_excluded(20, 23),
+            included_excluded(33, 40),
+        ],
+    });
+    test(TestData {
+        lhs: [included_excluded(5, 20), included_excluded(23, 33)],
+        rhs: [
+            included_excluded(0, 10),
+            included_excluded(15, 25),
+            included_excluded(30, 40),
+        ],
+        intersection: [
+            included_excluded(5, 10),
+            included_excluded(15, 20),
+            included_excluded(23, 25),
+            included_excluded(30, 33),
+        ],
+        union: [included_excluded(0, 40)],
+        difference: [included_excluded(10, 15), included_excluded(25, 30)],
+    });
+}
+
+#[test]
+fn unbounded() {
+    // Set A:        ---]      [---
+    // Set B:           [------]
+    // intersection:    []    []
+    // union:        ----------------
+    // difference:   --)        (---
+    test(TestData {
+        lhs: [unbounded_included(10), included_unbounded(20)],
+        rhs: [included_included(10, 20)],
+        intersection: [included_included(10, 10), included_included(20, 20)],
+        union: [unbounded_unbounded()],
+        difference: [unbounded_excluded(10), excluded_unbounded(20)],
+    });
+    test(TestData {
+        lhs: [included_included(10, 20)],
+        rhs: [unbounded_included(10), included_unbounded(20)],
+        intersection: [included_included(10, 10), included_included(20, 20)],
+        union: [unbounded_unbounded()],
+        difference: [excluded_excluded(10, 20)],
+    });
+
+    // Set A:        ----------------
+    // Set B:        [--]     [--]
+    // intersection: [--]     [--]
+    // union:        ----------------
+    // difference:   --)(-----)(-----
+    test(TestData {
+        lhs: [unbounded_unbounded()],
+        rhs: [included_included(0, 5), included_included(20, 25)],
+        intersection: [included_included(0, 5), included_included(20, 25)],
+        union: [unbounded_unbounded()],
+        difference: [
+            unbounded_excluded(0),
+            excluded_excluded(5, 20),
+            excluded_unbounded(25),
+        ],
+    });
+    test(TestData {
+        lhs: [included_included(0, 5), included_included(20, 25)],
+        rhs: [unbounded_unbounded()],
+        intersection: [included_included(0, 5), included_included(20, 25)],
+        union: [unbounded_unbounded()],
+        difference: [],
+    });
+}
+
+#[test]
+fn equal() {
+    let intervals = [
+        unbounded_excluded(0),
+        included_included(5, 10),
+        excluded_unbounded(20),
+    ];
+    test(TestData {
+        lhs: intervals,
+        rhs: intervals,
+        intersection: intervals,
+        union: intervals,
+        difference: [],
+    });
+    test(TestData {
+        lhs: [],
+        rhs: [],
+        intersection: [],
+        union: [],
+        difference: [],
+    });
+}
+
+#[test]
+fn contains() {
+    let intervals = set([included_excluded(0, 5), excluded_included(5, 10)]);
+
+    assert!(intervals.contains_point(&0));
+    assert!(intervals.contains_point(&4));
+    assert!(!intervals.contains_point(&5));
+    assert!(intervals.contains_point(&6));
+    assert!(intervals.contains_point(&10));
+    assert!(!intervals.contains_point(&11));
+
+    assert!(intervals.contains_interval(&included_included(1, 4)));
+    assert!(intervals.contains_interval(&excluded_included(5, 10)));
+    assert!(!intervals.contains_interval(&included_included(4, 6)));
+    assert!(!intervals.contains_interval(&included_included(8, 12)));
+
+    assert!(intervals.overlaps(&included_included(4, 6)));
+    assert!(intervals.overlaps(&unbounded_included(0)));
+    assert!(!intervals.overlaps(&excluded_unbounded(10)));
+    assert!(!intervals.overlaps(&unbounded_excluded(0)));
+}
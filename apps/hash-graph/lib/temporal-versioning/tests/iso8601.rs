@@ -0,0 +1,241 @@
+use core::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use temporal_versioning::*;
+
+/// Timestamps in ascending order, from which the bounds checked for round-trips are created.
+const TIMESTAMPS: [&str; 5] = [
+    "1970-01-01T00:00:00Z",
+    "2023-01-01T00:00:00Z",
+    "2023-01-01T00:00:00.5Z",
+    "2023-06-15T14:30:45.123456789+02:00",
+    "9999-12-31T23:59:59Z",
+];
+
+fn timestamp<A>(timestamp: &str) -> Timestamp<A> {
+    timestamp.parse().expect("timestamp should be valid")
+}
+
+fn bounds<A>() -> impl Iterator<Item = TemporalBound<A>> {
+    TIMESTAMPS
+        .iter()
+        .flat_map(|limit| {
+            [
+                TemporalBound::Inclusive(timestamp(limit)),
+                TemporalBound::Exclusive(timestamp(limit)),
+            ]
+        })
+        .chain([TemporalBound::Unbounded])
+}
+
+/// Returns `true` if an interval can be created from `start` to `end`.
+fn is_ordered<A>(start: TemporalBound<A>, end: TemporalBound<A>) -> bool {
+    match (start, end) {
+        (TemporalBound::Unbounded, _) | (_, TemporalBound::Unbounded) => true,
+        (TemporalBound::Inclusive(start), TemporalBound::Inclusive(end)) => start <= end,
+        (
+            TemporalBound::Inclusive(start) | TemporalBound::Exclusive(start),
+            TemporalBound::Inclusive(end) | TemporalBound::Exclusive(end),
+        ) => start < end,
+    }
+}
+
+fn assert_round_trip<T>(value: &T)
+where
+    T: Debug + Display + PartialEq + FromStr<Err = ParseIntervalError>,
+{
+    for formatted in [value.to_string(), format!("{value:#}")] {
+        assert_eq!(
+            formatted.parse::<T>().as_ref(),
+            Ok(value),
+            "`{formatted}` does not round-trip"
+        );
+    }
+}
+
+/// Checks, that all intervals with bounds representable by `S` and `E` round-trip through their
+/// textual representation, and that misordered bounds are rejected.
+fn test_interval<S, E>()
+where
+    S: IntervalBound<Timestamp<TransactionTime>> + TryFrom<TemporalBound<TransactionTime>> + Copy,
+    E: IntervalBound<Timestamp<TransactionTime>> + TryFrom<TemporalBound<TransactionTime>> + Copy,
+    Interval<Timestamp<TransactionTime>, S, E>: Debug + PartialEq,
+{
+    let mut checked = 0_usize;
+    for start in bounds() {
+        let Ok(start_bound) = S::try_from(start) else {
+            continue;
+        };
+        for end in bounds() {
+            let Ok(end_bound) = E::try_from(end) else {
+                continue;
+            };
+
+            let interval = Interval::new_unchecked(start_bound, end_bound);
+            if is_ordered(start, end) {
+                assert_round_trip(&interval);
+                checked += 1;
+            } else {
+                assert_eq!(
+                    interval
+                        .to_string()
+                        .parse::<Interval<Timestamp<TransactionTime>, S, E>>(),
+                    Err(ParseIntervalError::InvalidOrder),
+                    "`{interval}` is not ordered"
+                );
+            }
+        }
+    }
+    assert_ne!(checked, 0, "no interval was checked");
+}
+
+#[test]
+fn interval_round_trip() {
+    test_interval::<TemporalBound<_>, TemporalBound<_>>();
+    test_interval::<TemporalBound<_>, LimitedTemporalBound<_>>();
+    test_interval::<ClosedTemporalBound<_>, OpenTemporalBound<_>>();
+    test_interval::<LimitedTemporalBound<_>, OpenTemporalBound<_>>();
+}
+
+#[test]
+fn bound_round_trip() {
+    for bound in bounds::<DecisionTime>() {
+        assert_round_trip(&bound);
+        if let Ok(bound) = LimitedTemporalBound::try_from(bound) {
+            assert_round_trip(&bound);
+        }
+        if let Ok(bound) = ClosedTemporalBound::try_from(bound) {
+            assert_round_trip(&bound);
+        }
+        if let Ok(bound) = OpenTemporalBound::try_from(bound) {
+            assert_round_trip(&bound);
+        }
+    }
+}
+
+#[test]
+fn format() {
+    let interval: TemporalInterval<TransactionTime> = Interval::new(
+        TemporalBound::Inclusive(timestamp("2023-01-01T00:00:00Z")),
+        TemporalBound::Unbounded,
+    );
+    assert_eq!(interval.to_string(), "[2023-01-01T00:00:00Z, ∞)");
+    assert_eq!(format!("{interval:#}"), "[2023-01-01T00:00:00Z/..)");
+
+    let interval: TemporalInterval<TransactionTime> = Interval::new(
+        TemporalBound::Unbounded,
+        TemporalBound::Inclusive(timestamp("2023-01-01T12:30:00.25+01:00")),
+    );
+    assert_eq!(interval.to_string(), "(-∞, 2023-01-01T12:30:00.25+01:00]");
+    assert_eq!(format!("{interval:#}"), "(../2023-01-01T12:30:00.25+01:00]");
+
+    assert_eq!(
+        TemporalBound::<DecisionTime>::Exclusive(timestamp("2023-01-01T00:00:00Z")).to_string(),
+        "(2023-01-01T00:00:00Z)"
+    );
+    assert_eq!(TemporalBound::<DecisionTime>::Unbounded.to_string(), "..");
+}
+
+#[test]
+fn parse() {
+    let start = TemporalBound::Inclusive(timestamp("2023-01-01T00:00:00Z"));
+    let end = TemporalBound::Exclusive(timestamp("2023-02-01T00:00:00Z"));
+
+    for (notation, expected) in [
+        (
+            "[2023-01-01T00:00Z, ∞)",
+            Interval::new(start, TemporalBound::Unbounded),
+        ),
+        (
+            "[2023-01-01T00:00:00Z,+∞)",
+            Interval::new(start, TemporalBound::Unbounded),
+        ),
+        (
+            "2023-01-01T00:00:00Z/2023-02-01T00:00:00Z",
+            Interval::new(start, end),
+        ),
+        (
+            " 2023-01-01T00:00:00Z/.. ",
+            Interval::new(start, TemporalBound::Unbounded),
+        ),
+        (
+            "../2023-02-01T00:00:00Z",
+            Interval::new(TemporalBound::Unbounded, end),
+        ),
+        (
+            "(-∞, 2023-02-01T00:00:00Z)",
+            Interval::new(TemporalBound::Unbounded, end),
+        ),
+        (
+            "(2023-01-01T00:00:00Z, 2023-02-01T00:00:00Z]",
+            Interval::new(
+                TemporalBound::Exclusive(timestamp("2023-01-01T00:00:00Z")),
+                TemporalBound::Inclusive(timestamp("2023-02-01T00:00:00Z")),
+            ),
+        ),
+    ] {
+        assert_eq!(
+            notation.parse::<TemporalInterval<TransactionTime>>(),
+            Ok(expected),
+            "`{notation}` was parsed incorrectly"
+        );
+    }
+}
+
+#[test]
+fn parse_errors() {
+    for notation in [
+        "",
+        "2023-01-01T00:00:00Z",
+        "[2023-01-01T00:00:00Z)",
+        "[2023-01-01T00:00:00Z, ∞",
+        "{2023-01-01T00:00:00Z, ∞}",
+    ] {
+        assert_eq!(
+            notation.parse::<TemporalInterval<TransactionTime>>(),
+            Err(ParseIntervalError::InvalidSyntax),
+            "`{notation}` should have invalid syntax"
+        );
+    }
+
+    for notation in [
+        "[2023-13-01T00:00:00Z, ∞)",
+        "[yesterday, ∞)",
+        "[∞, 2023-01-01T00:00:00Z)",
+    ] {
+        assert!(
+            matches!(
+                notation.parse::<TemporalInterval<TransactionTime>>(),
+                Err(ParseIntervalError::InvalidTimestamp(_))
+            ),
+            "`{notation}` should have an invalid timestamp"
+        );
+    }
+
+    for notation in [
+        "(2023-01-01T00:00:00Z, ∞)",
+        "[2023-01-01T00:00:00Z, 2023-02-01T00:00:00Z]",
+        "(-∞, ∞)",
+    ] {
+        assert_eq!(
+            notation.parse::<LeftClosedTemporalInterval<TransactionTime>>(),
+            Err(ParseIntervalError::UnsupportedBound),
+            "`{notation}` should not be a left-closed interval"
+        );
+    }
+
+    assert_eq!(
+        "[2023-02-01T00:00:00Z, 2023-01-01T00:00:00Z)".parse::<TemporalInterval<TransactionTime>>(),
+        Err(ParseIntervalError::InvalidOrder)
+    );
+    assert_eq!(
+        "..".parse::<ClosedTemporalBound<DecisionTime>>(),
+        Err(ParseIntervalError::UnsupportedBound)
+    );
+    assert_eq!(
+        "2023-01-01T00:00:00Z".parse::<TemporalBound<DecisionTime>>(),
+        Err(ParseIntervalError::InvalidSyntax)
+    );
+}
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS
+  "ontology_deprecations" (
+    "ontology_id" UUID PRIMARY KEY REFERENCES "ontology_ids",
+    "deprecated_by_id" UUID NOT NULL REFERENCES "accounts",
+    "deprecated_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    "successor_base_url" TEXT,
+    "successor_version" BIGINT,
+    FOREIGN KEY ("successor_base_url", "successor_version") REFERENCES "ontology_ids" ("base_url", "version"),
+    CONSTRAINT "ontology_deprecations_successor_check" CHECK (
+      ("successor_base_url" IS NULL) = ("successor_version" IS NULL)
+    )
+  );
@@ -0,0 +1,39 @@
+CREATE TABLE IF NOT EXISTS
+  "ownership_transfers" (
+    "transfer_id" UUID PRIMARY KEY,
+    "from_owned_by_id" UUID NOT NULL REFERENCES "accounts",
+    "to_owned_by_id" UUID NOT NULL REFERENCES "accounts",
+    "transferred_by_id" UUID NOT NULL REFERENCES "accounts",
+    "transferred_at" TIMESTAMP WITH TIME ZONE NOT NULL,
+    CHECK ("from_owned_by_id" <> "to_owned_by_id")
+  );
+
+CREATE TABLE IF NOT EXISTS
+  "ontology_ownership_transfers" (
+    "transfer_id" UUID NOT NULL REFERENCES "ownership_transfers",
+    "ontology_id" UUID NOT NULL REFERENCES "ontology_ids",
+    PRIMARY KEY ("transfer_id", "ontology_id")
+  );
+
+COMMENT
+  ON TABLE ontology_ownership_transfers IS $pga$ The ontology types moved by an ownership transfer. The URLs of the ontology types are not changed. $pga$;
+
+CREATE TABLE IF NOT EXISTS
+  "entity_ownership_transfers" (
+    "transfer_id" UUID NOT NULL REFERENCES "ownership_transfers",
+    "entity_uuid" UUID NOT NULL,
+    PRIMARY KEY ("transfer_id", "entity_uuid")
+  );
+
+COMMENT
+  ON TABLE entity_ownership_transfers IS $pga$ The entities moved by an ownership transfer. The entity ID before the transfer consists of the `from_owned_by_id` of the transfer and the `entity_uuid`. $pga$;
+
+CREATE
+OR REPLACE TRIGGER "update_entity_version_trigger" BEFORE
+UPDATE
+  ON "entity_temporal_metadata" FOR EACH ROW WHEN (OLD.owned_by_id = NEW.owned_by_id)
+EXECUTE
+  PROCEDURE "update_entity_version_trigger" ();
+
+COMMENT
+  ON TRIGGER update_entity_version_trigger ON entity_temporal_metadata IS $pga$ Changing the owner only re-keys the entity when transferring the ownership and does not create a new version of the entity. $pga$;
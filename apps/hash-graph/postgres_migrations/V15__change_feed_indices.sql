@@ -0,0 +1,20 @@
+-- The change feed reads changes up to the start of the oldest transaction in progress, which is
+-- read from `pg_stat_activity`. Postgres hides the start of transactions of other roles unless the
+-- role of the graph is a member of `pg_read_all_stats`, so it has to be granted to the role, e.g.
+-- `GRANT pg_read_all_stats TO graph;`. Reading the change feed or dumping a snapshot fails without
+-- it.
+
+CREATE INDEX
+  "entity_temporal_metadata_transaction_time_start_idx" ON "entity_temporal_metadata" (LOWER("transaction_time"));
+
+CREATE INDEX
+  "entity_temporal_metadata_transaction_time_end_idx" ON "entity_temporal_metadata" (UPPER("transaction_time"));
+
+CREATE INDEX
+  "entity_temporal_metadata_entity_edition_id_idx" ON "entity_temporal_metadata" ("entity_edition_id");
+
+CREATE INDEX
+  "ontology_temporal_metadata_transaction_time_start_idx" ON "ontology_temporal_metadata" (LOWER("transaction_time"));
+
+CREATE INDEX
+  "ontology_temporal_metadata_transaction_time_end_idx" ON "ontology_temporal_metadata" (UPPER("transaction_time"));
@@ -0,0 +1,502 @@
+use graph::store::{AccountStore, AsClient, EntityStore};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{EntityEditionId, EntityId, EntityMetadata, EntityProperties, EntityUuid},
+        link::{EntityLinkOrder, LinkData},
+    },
+    provenance::{OwnedById, RecordCreatedById, TransferredById},
+};
+use type_system::url::{BaseUrl, VersionedUrl};
+use uuid::Uuid;
+
+use crate::DatabaseTestWrapper;
+
+/// Returns the temporal rows of the entity as edition and whether the row is current in
+/// transaction time.
+async fn temporal_rows(
+    database: &DatabaseTestWrapper,
+    entity_id: EntityId,
+) -> Vec<(EntityEditionId, bool)> {
+    database
+        .connection
+        .as_client()
+        .query(
+            r"
+                SELECT entity_edition_id, upper_inf(transaction_time)
+                FROM entity_temporal_metadata
+                WHERE owned_by_id = $1 AND entity_uuid = $2
+                ORDER BY lower(transaction_time);
+            ",
+            &[&entity_id.owned_by_id, &entity_id.entity_uuid],
+        )
+        .await
+        .expect("could not read temporal rows")
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect()
+}
+
+async fn insert_account(database: &mut DatabaseTestWrapper) -> AccountId {
+    let account_id = AccountId::new(Uuid::new_v4());
+    database
+        .connection
+        .insert_account_id(account_id)
+        .await
+        .expect("could not insert account id");
+    account_id
+}
+
+async fn create_entity(
+    database: &mut DatabaseTestWrapper,
+    owned_by_id: AccountId,
+    entity_uuid: Option<EntityUuid>,
+    entity_type_id: &VersionedUrl,
+) -> EntityMetadata {
+    database
+        .connection
+        .create_entity(
+            OwnedById::new(owned_by_id),
+            entity_uuid,
+            None,
+            RecordCreatedById::new(owned_by_id),
+            false,
+            entity_type_id.clone(),
+            EntityProperties::empty(),
+            None,
+        )
+        .await
+        .expect("could not create entity")
+}
+
+#[tokio::test]
+async fn transfer_moves_entities_and_links() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let friend_of_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let alice_id = api
+        .create_entity(alice, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+    let bob_id = api
+        .create_entity(bob, person_type_id, None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+    let link_id = api
+        .create_link_entity(
+            EntityProperties::empty(),
+            friend_of_type_id.clone(),
+            None,
+            alice_id,
+            bob_id,
+        )
+        .await
+        .expect("could not create link")
+        .record_id()
+        .entity_id;
+
+    let from_owned_by_id = OwnedById::new(api.account_id);
+    let to_owned_by_id = OwnedById::new(api.insert_account().await);
+    let transfer = api
+        .transfer_ownership(to_owned_by_id)
+        .await
+        .expect("could not transfer ownership");
+    assert_eq!(transfer.entities, 3);
+    assert_eq!(transfer.ontology_types, 5);
+
+    let moved = |entity_id: EntityId| EntityId {
+        owned_by_id: to_owned_by_id,
+        entity_uuid: entity_id.entity_uuid,
+    };
+    assert!(
+        api.get_entities(alice_id)
+            .await
+            .expect("could not get entities")
+            .is_empty(),
+        "the entity should not be current at the previous entity ID"
+    );
+    let moved_alice = api
+        .get_latest_entity(moved(alice_id))
+        .await
+        .expect("could not get entity");
+    assert_eq!(
+        moved_alice.properties,
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity")
+    );
+
+    let target = api
+        .get_link_entity_target(moved(alice_id), friend_of_type_id)
+        .await
+        .expect("could not get link target");
+    assert_eq!(target.metadata.record_id().entity_id, moved(bob_id));
+
+    let records = api
+        .get_ownership_transfers(from_owned_by_id)
+        .await
+        .expect("could not get ownership transfers");
+    assert_eq!(
+        records,
+        api.get_ownership_transfers(to_owned_by_id)
+            .await
+            .expect("could not get ownership transfers")
+    );
+    let [record] = records.as_slice() else {
+        panic!("expected exactly one ownership transfer, got {records:?}");
+    };
+    assert_eq!(record.from_owned_by_id, from_owned_by_id);
+    assert_eq!(record.to_owned_by_id, to_owned_by_id);
+    assert_eq!(record.transferred_at, transfer.transferred_at);
+    assert_eq!(record.ontology_type_ids.len(), 5);
+    let mut entity_uuids = vec![
+        alice_id.entity_uuid,
+        bob_id.entity_uuid,
+        link_id.entity_uuid,
+    ];
+    entity_uuids.sort_by_key(|entity_uuid| entity_uuid.as_uuid());
+    assert_eq!(record.entity_uuids, entity_uuids);
+}
+
+#[tokio::test]
+async fn transfer_repoints_links_of_other_owners() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+    let bob = serde_json::from_str(entity::PERSON_BOB_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let person_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let friend_of_type_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/entity-type/friend-of/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let alice_id = api
+        .create_entity(alice, person_type_id.clone(), None)
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+
+    // The link and its source are owned by a third account, which is not part of the transfer
+    let third_owned_by_id = OwnedById::new(api.insert_account().await);
+    let bob_id = api
+        .store
+        .create_entity(
+            third_owned_by_id,
+            None,
+            None,
+            RecordCreatedById::new(third_owned_by_id.as_account_id()),
+            false,
+            person_type_id,
+            bob,
+            None,
+        )
+        .await
+        .expect("could not create entity")
+        .record_id()
+        .entity_id;
+    let link = api
+        .store
+        .create_entity(
+            third_owned_by_id,
+            None,
+            None,
+            RecordCreatedById::new(third_owned_by_id.as_account_id()),
+            false,
+            friend_of_type_id.clone(),
+            EntityProperties::empty(),
+            Some(LinkData {
+                left_entity_id: bob_id,
+                right_entity_id: alice_id,
+                order: EntityLinkOrder {
+                    left_to_right: None,
+                    right_to_left: None,
+                },
+            }),
+        )
+        .await
+        .expect("could not create link")
+        .record_id();
+
+    let to_owned_by_id = OwnedById::new(api.insert_account().await);
+    let transfer = api
+        .transfer_ownership(to_owned_by_id)
+        .await
+        .expect("could not transfer ownership");
+    assert_eq!(transfer.entities, 1);
+
+    let target = api
+        .get_link_entity_target(bob_id, friend_of_type_id)
+        .await
+        .expect("could not get link target");
+    assert_eq!(target.metadata.record_id().entity_id, EntityId {
+        owned_by_id: to_owned_by_id,
+        entity_uuid: alice_id.entity_uuid,
+    });
+
+    // The existing edition of the link is re-pointed instead of being closed
+    let rows = api
+        .store
+        .as_client()
+        .query(
+            r"
+                SELECT entity_edition_id, upper_inf(transaction_time)
+                FROM entity_temporal_metadata
+                WHERE owned_by_id = $1 AND entity_uuid = $2;
+            ",
+            &[&link.entity_id.owned_by_id, &link.entity_id.entity_uuid],
+        )
+        .await
+        .expect("could not read temporal rows")
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect::<Vec<(EntityEditionId, bool)>>();
+    assert_eq!(rows, [(link.edition_id, true)]);
+}
+
+#[tokio::test]
+async fn transfer_keeps_history_at_previous_entity_id() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    // The history is only kept for editions created before the transfer, so every write is
+    // committed in its own transaction.
+    let (from_account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let to_account_id = insert_account(&mut database).await;
+    let created = create_entity(&mut database, from_account_id, None, &entity_type_id).await;
+    let entity_id = created.record_id().entity_id;
+
+    database
+        .connection
+        .transfer_ownership(
+            OwnedById::new(from_account_id),
+            OwnedById::new(to_account_id),
+            TransferredById::new(from_account_id),
+        )
+        .await
+        .expect("could not transfer ownership");
+
+    let edition_id = created.record_id().edition_id;
+    assert_eq!(temporal_rows(&database, entity_id).await, [(
+        edition_id, false
+    )]);
+    assert_eq!(
+        temporal_rows(&database, EntityId {
+            owned_by_id: OwnedById::new(to_account_id),
+            entity_uuid: entity_id.entity_uuid,
+        })
+        .await,
+        [(edition_id, true)]
+    );
+}
+
+#[tokio::test]
+async fn transfer_back_to_previous_owner() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (first_account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let second_account_id = insert_account(&mut database).await;
+    let created = create_entity(&mut database, first_account_id, None, &entity_type_id).await;
+    let entity_id = created.record_id().entity_id;
+
+    for (from, to) in [
+        (first_account_id, second_account_id),
+        (second_account_id, first_account_id),
+    ] {
+        database
+            .connection
+            .transfer_ownership(
+                OwnedById::new(from),
+                OwnedById::new(to),
+                TransferredById::new(from),
+            )
+            .await
+            .expect("could not transfer ownership");
+    }
+
+    let edition_id = created.record_id().edition_id;
+    assert_eq!(temporal_rows(&database, entity_id).await, [
+        (edition_id, false),
+        (edition_id, true)
+    ]);
+
+    let transfers = database
+        .connection
+        .get_ownership_transfers(OwnedById::new(first_account_id))
+        .await
+        .expect("could not get ownership transfers");
+    assert_eq!(
+        transfers
+            .iter()
+            .map(|transfer| (transfer.from_owned_by_id, transfer.to_owned_by_id))
+            .collect::<Vec<_>>(),
+        [
+            (
+                OwnedById::new(first_account_id),
+                OwnedById::new(second_account_id)
+            ),
+            (
+                OwnedById::new(second_account_id),
+                OwnedById::new(first_account_id)
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn transfer_conflicts_with_entity_of_receiver() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (from_account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let to_account_id = insert_account(&mut database).await;
+    let created = create_entity(&mut database, from_account_id, None, &entity_type_id).await;
+    let entity_id = created.record_id().entity_id;
+    create_entity(
+        &mut database,
+        to_account_id,
+        Some(entity_id.entity_uuid),
+        &entity_type_id,
+    )
+    .await;
+
+    database
+        .connection
+        .transfer_ownership(
+            OwnedById::new(from_account_id),
+            OwnedById::new(to_account_id),
+            TransferredById::new(from_account_id),
+        )
+        .await
+        .expect_err("the entity UUID is already used by the receiving account");
+
+    assert_eq!(temporal_rows(&database, entity_id).await, [(
+        created.record_id().edition_id,
+        true
+    )]);
+    assert!(
+        database
+            .connection
+            .get_ownership_transfers(OwnedById::new(from_account_id))
+            .await
+            .expect("could not get ownership transfers")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn changing_owner_does_not_create_entity_version() {
+    let alice = serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let created = api
+        .create_entity(
+            alice,
+            VersionedUrl {
+                base_url: BaseUrl::new(
+                    "https://blockprotocol.org/@alice/types/entity-type/person/".to_owned(),
+                )
+                .expect("couldn't construct Base URL"),
+                version: 1,
+            },
+            None,
+        )
+        .await
+        .expect("could not create entity");
+    let entity_id = created.record_id().entity_id;
+    let owned_by_id = OwnedById::new(api.insert_account().await);
+
+    // Updating any other column of a temporal row creates a new version of the entity, changing
+    // the owner only moves the row to the new entity ID.
+    let client = api.store.as_client();
+    client
+        .execute(
+            "INSERT INTO entity_ids (owned_by_id, entity_uuid) VALUES ($1, $2);",
+            &[&owned_by_id, &entity_id.entity_uuid],
+        )
+        .await
+        .expect("could not insert entity ID");
+    client
+        .execute(
+            r"
+                UPDATE entity_temporal_metadata
+                SET owned_by_id = $1
+                WHERE owned_by_id = $2 AND entity_uuid = $3;
+            ",
+            &[&owned_by_id, &entity_id.owned_by_id, &entity_id.entity_uuid],
+        )
+        .await
+        .expect("could not change the owner");
+    let rows: i64 = client
+        .query_one(
+            "SELECT count(*) FROM entity_temporal_metadata WHERE entity_uuid = $1;",
+            &[&entity_id.entity_uuid],
+        )
+        .await
+        .expect("could not count temporal rows")
+        .get(0);
+    assert_eq!(rows, 1);
+
+    let moved = api
+        .get_latest_entity(EntityId {
+            owned_by_id,
+            entity_uuid: entity_id.entity_uuid,
+        })
+        .await
+        .expect("could not get entity");
+    assert_eq!(
+        moved.metadata.record_id().edition_id,
+        created.record_id().edition_id
+    );
+}
@@ -0,0 +1,166 @@
+use std::time::Duration;
+
+use graph::store::{ChangeFeedQuery, ChangeFeedStore, ChangeKind, ChangedRecord, EntityStore};
+use graph_types::{
+    knowledge::{
+        entity::{EntityId, EntityMetadata, EntityProperties, EntityRecordId},
+        link::EntityLinkOrder,
+    },
+    provenance::{OwnedById, RecordCreatedById},
+};
+use temporal_versioning::{ClosedTemporalBound, Timestamp, TransactionTime};
+
+use crate::DatabaseTestWrapper;
+
+/// Reads the changes of `entity_id` from the change feed until `expected` changes are returned.
+///
+/// Changes are only returned after all concurrently running transactions have finished, so the
+/// change feed is polled until the changes are visible.
+async fn entity_changes(
+    database: &DatabaseTestWrapper,
+    since: Timestamp<TransactionTime>,
+    entity_id: EntityId,
+    expected: usize,
+) -> Vec<(ChangeKind, EntityRecordId, Timestamp<TransactionTime>)> {
+    for _ in 0..600 {
+        let mut changes = Vec::new();
+        let mut query = ChangeFeedQuery {
+            since,
+            until: None,
+            resume_token: None,
+            limit: 2,
+        };
+        loop {
+            let page = database
+                .connection
+                .get_changes(&query)
+                .await
+                .expect("could not read change feed");
+            changes.extend(
+                page.events
+                    .into_iter()
+                    .filter_map(|event| match event.record {
+                        ChangedRecord::Entity(record_id) if record_id.entity_id == entity_id => {
+                            Some((event.kind, record_id, event.transaction_time))
+                        }
+                        _ => None,
+                    }),
+            );
+            if !page.has_more {
+                break;
+            }
+            query.resume_token = page.resume_token;
+        }
+
+        if changes.len() >= expected {
+            return changes;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("the change feed did not return {expected} changes of the entity");
+}
+
+#[tokio::test]
+async fn entity_changes_include_closed_editions() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    // The change feed only contains committed changes, so every write is committed in its own
+    // transaction.
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+
+    let created = database
+        .connection
+        .create_entity(
+            OwnedById::new(account_id),
+            None,
+            None,
+            RecordCreatedById::new(account_id),
+            false,
+            entity_type_id.clone(),
+            EntityProperties::empty(),
+            None,
+        )
+        .await
+        .expect("could not create entity");
+    let entity_id = created.record_id().entity_id;
+
+    let link_order = EntityLinkOrder {
+        left_to_right: None,
+        right_to_left: None,
+    };
+    let updated = database
+        .connection
+        .update_entity(
+            entity_id,
+            None,
+            RecordCreatedById::new(account_id),
+            false,
+            entity_type_id.clone(),
+            EntityProperties::empty(),
+            link_order,
+        )
+        .await
+        .expect("could not update entity");
+    let archived = database
+        .connection
+        .update_entity(
+            entity_id,
+            None,
+            RecordCreatedById::new(account_id),
+            true,
+            entity_type_id,
+            EntityProperties::empty(),
+            link_order,
+        )
+        .await
+        .expect("could not archive entity");
+
+    let written_at = |metadata: &EntityMetadata| {
+        let ClosedTemporalBound::Inclusive(transaction_time) =
+            metadata.temporal_versioning().transaction_time.start();
+        *transaction_time
+    };
+
+    let mut changes = entity_changes(&database, written_at(&created), entity_id, 5).await;
+    assert!(
+        changes
+            .windows(2)
+            .all(|changes| changes[0].2 <= changes[1].2),
+        "changes are not ordered by transaction time"
+    );
+
+    // Changes at the same transaction time are ordered by an unspecified sequence id
+    let mut expected = vec![
+        (
+            ChangeKind::Created,
+            created.record_id(),
+            written_at(&created),
+        ),
+        (
+            ChangeKind::Updated,
+            updated.record_id(),
+            written_at(&updated),
+        ),
+        (
+            ChangeKind::Closed,
+            created.record_id(),
+            written_at(&updated),
+        ),
+        (
+            ChangeKind::Archived,
+            archived.record_id(),
+            written_at(&archived),
+        ),
+        (
+            ChangeKind::Closed,
+            updated.record_id(),
+            written_at(&archived),
+        ),
+    ];
+    let order = |change: &(ChangeKind, EntityRecordId, Timestamp<TransactionTime>)| {
+        (change.2, change.1.edition_id)
+    };
+    changes.sort_by_key(order);
+    expected.sort_by_key(order);
+    assert_eq!(changes, expected);
+}
@@ -2,7 +2,7 @@ use graph::store::{
     error::{OntologyTypeIsNotOwned, OntologyVersionDoesNotExist, VersionedUrlAlreadyExists},
     BaseUrlAlreadyExists,
 };
-use type_system::{repr, DataType};
+use type_system::{repr, url::VersionedUrl, DataType};
 
 use crate::DatabaseTestWrapper;
 
@@ -249,3 +249,112 @@ async fn update_external_with_owned() {
         "wrong error, expected `OntologyTypeIsNotOwned`, got {report:?}"
     );
 }
+
+fn data_type_id(json: &str) -> VersionedUrl {
+    let data_type_repr: repr::DataType =
+        serde_json::from_str(json).expect("could not parse data type representation");
+    DataType::try_from(data_type_repr)
+        .expect("could not parse data type")
+        .id()
+        .clone()
+}
+
+#[tokio::test]
+async fn deprecate() {
+    let text_id = data_type_id(graph_test_data::data_type::TEXT_V1);
+    let number_id = data_type_id(graph_test_data::data_type::NUMBER_V1);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [
+                graph_test_data::data_type::TEXT_V1,
+                graph_test_data::data_type::NUMBER_V1,
+            ],
+            [],
+            [],
+        )
+        .await
+        .expect("could not seed database");
+
+    let deprecation = api
+        .deprecate_data_type(&text_id, Some(&number_id))
+        .await
+        .expect("could not deprecate data type");
+    assert_eq!(deprecation.successor.as_ref(), Some(&number_id));
+    assert_eq!(
+        api.get_data_type(&text_id)
+            .await
+            .expect("could not get data type")
+            .metadata
+            .deprecation,
+        Some(deprecation)
+    );
+    assert_eq!(
+        api.get_data_type(&number_id)
+            .await
+            .expect("could not get data type")
+            .metadata
+            .deprecation,
+        None
+    );
+
+    // Deprecating again replaces the previous deprecation
+    let deprecation = api
+        .deprecate_data_type(&text_id, None)
+        .await
+        .expect("could not deprecate data type");
+    assert_eq!(deprecation.successor, None);
+    assert_eq!(
+        api.get_data_type(&text_id)
+            .await
+            .expect("could not get data type")
+            .metadata
+            .deprecation,
+        Some(deprecation)
+    );
+}
+
+#[tokio::test]
+async fn deprecate_with_invalid_successor() {
+    let text_id = data_type_id(graph_test_data::data_type::TEXT_V1);
+    let boolean_id = data_type_id(graph_test_data::data_type::BOOLEAN_V1);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([graph_test_data::data_type::TEXT_V1], [], [])
+        .await
+        .expect("could not seed database");
+
+    let report = api
+        .deprecate_data_type(&text_id, Some(&boolean_id))
+        .await
+        .expect_err("could deprecate data type with a missing successor");
+    assert!(
+        report.contains::<OntologyVersionDoesNotExist>(),
+        "wrong error, expected `OntologyVersionDoesNotExist`, got {report:?}"
+    );
+
+    api.deprecate_data_type(&text_id, Some(&text_id))
+        .await
+        .expect_err("could deprecate data type with itself as successor");
+
+    assert_eq!(
+        api.get_data_type(&text_id)
+            .await
+            .expect("could not get data type")
+            .metadata
+            .deprecation,
+        None
+    );
+
+    // This aborts the transaction, so it has to be checked last
+    let report = api
+        .deprecate_data_type(&boolean_id, None)
+        .await
+        .expect_err("could deprecate missing data type");
+    assert!(
+        report.contains::<OntologyVersionDoesNotExist>(),
+        "wrong error, expected `OntologyVersionDoesNotExist`, got {report:?}"
+    );
+}
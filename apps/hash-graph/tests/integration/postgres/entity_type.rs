@@ -1,5 +1,19 @@
+use std::{collections::HashSet, str::FromStr};
+
+use graph::{
+    ontology::{
+        compatibility::OntologyTypeChangeKind, EntityTypeQueryPath, OntologyDependencyGraph,
+        OntologyGraphFormat,
+    },
+    store::{
+        query::{Filter, FilterExpression},
+        OntologyVersionDoesNotExist,
+    },
+};
 use graph_test_data::{data_type, entity_type, property_type};
-use type_system::{repr, EntityType};
+use graph_types::{knowledge::entity::EntityProperties, ontology::EntityTypeWithMetadata};
+use serde_json::json;
+use type_system::{repr, url::VersionedUrl, EntityType};
 
 use crate::DatabaseTestWrapper;
 
@@ -99,3 +113,307 @@ async fn update() {
     assert_eq!(page_et_v1, returned_page_et_v1.schema);
     assert_eq!(page_et_v2, returned_page_et_v2.schema);
 }
+
+/// Creates the `version` of the fan entity type, which optionally has a name.
+fn fan_entity_type(version: u32, title: &str, required: &[&str]) -> EntityType {
+    let mut schema = json!({
+        "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+        "kind": "entityType",
+        "$id": format!("https://example.com/@alice/types/entity-type/fan/v/{version}"),
+        "type": "object",
+        "title": title,
+        "properties": {
+            "https://blockprotocol.org/@alice/types/property-type/name/": {
+                "$ref": "https://blockprotocol.org/@alice/types/property-type/name/v/1"
+            }
+        }
+    });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+
+    let entity_type_repr: repr::EntityType =
+        serde_json::from_value(schema).expect("could not parse entity type representation");
+    EntityType::try_from(entity_type_repr).expect("could not parse entity type")
+}
+
+#[tokio::test]
+async fn check_update_of_parent_entity_type() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [])
+        .await
+        .expect("could not seed database");
+
+    let fan = fan_entity_type(1, "Fan", &[]);
+    let child_fan_repr: repr::EntityType = serde_json::from_value(json!({
+        "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+        "kind": "entityType",
+        "$id": "https://example.com/@alice/types/entity-type/child-fan/v/1",
+        "type": "object",
+        "title": "Child Fan",
+        "allOf": [{ "$ref": fan.id().to_string() }],
+        "properties": {}
+    }))
+    .expect("could not parse entity type representation");
+    let child_fan = EntityType::try_from(child_fan_repr).expect("could not parse entity type");
+
+    api.create_entity_type(fan.clone())
+        .await
+        .expect("could not create entity type");
+    api.create_entity_type(child_fan.clone())
+        .await
+        .expect("could not create entity type");
+    api.create_entity(EntityProperties::empty(), child_fan.id().clone(), None)
+        .await
+        .expect("could not create entity");
+
+    let additive = api
+        .check_entity_type_update(fan.id(), &fan_entity_type(2, "Film Fan", &[]))
+        .await
+        .expect("could not check entity type update");
+    assert!(!additive.is_breaking(), "{additive:#?}");
+    assert_eq!(additive.changes.len(), 1);
+    assert_eq!(additive.changes[0].kind, OntologyTypeChangeKind::Additive);
+    assert_eq!(additive.invalid_entities, 0);
+
+    let breaking = api
+        .check_entity_type_update(
+            fan.id(),
+            &fan_entity_type(2, "Fan", &[
+                "https://blockprotocol.org/@alice/types/property-type/name/",
+            ]),
+        )
+        .await
+        .expect("could not check entity type update");
+    assert!(breaking.is_breaking());
+    assert_eq!(breaking.changes.len(), 1, "{breaking:#?}");
+    assert_eq!(breaking.changes[0].kind, OntologyTypeChangeKind::Breaking);
+    assert_eq!(
+        breaking.invalid_entities, 1,
+        "the entity of the child entity type should be invalid"
+    );
+}
+
+#[tokio::test]
+async fn check_update_of_missing_entity_type() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [])
+        .await
+        .expect("could not seed database");
+
+    let fan = fan_entity_type(1, "Fan", &[]);
+    let report = api
+        .check_entity_type_update(fan.id(), &fan_entity_type(2, "Fan", &[]))
+        .await
+        .expect_err("the entity type should not exist");
+    assert!(report.contains::<OntologyVersionDoesNotExist>());
+}
+
+fn parse_entity_type(json: &str) -> EntityType {
+    let entity_type_repr: repr::EntityType =
+        serde_json::from_str(json).expect("could not parse entity type representation");
+    EntityType::try_from(entity_type_repr).expect("could not parse entity type")
+}
+
+#[tokio::test]
+async fn filter_deprecated() {
+    let person = parse_entity_type(entity_type::PERSON_V1);
+    let organization = parse_entity_type(entity_type::ORGANIZATION_V1);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+            entity_type::ORGANIZATION_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    api.deprecate_entity_type(organization.id(), Some(person.id()))
+        .await
+        .expect("could not deprecate entity type");
+
+    let ids = |entity_types: Vec<EntityTypeWithMetadata>| {
+        entity_types
+            .into_iter()
+            .map(|entity_type| entity_type.schema.id().clone())
+            .collect::<HashSet<_>>()
+    };
+    let deprecated_by_id = || Some(FilterExpression::Path(EntityTypeQueryPath::DeprecatedById));
+
+    let current = ids(api
+        .get_entity_types_by_filter(&Filter::Equal(deprecated_by_id(), None))
+        .await
+        .expect("could not read entity types"));
+    assert!(current.contains(person.id()));
+    assert!(!current.contains(organization.id()));
+
+    let deprecated = ids(api
+        .get_entity_types_by_filter(&Filter::NotEqual(deprecated_by_id(), None))
+        .await
+        .expect("could not read entity types"));
+    assert_eq!(deprecated, HashSet::from([organization.id().clone()]));
+
+    let organization = api
+        .get_entity_type(organization.id())
+        .await
+        .expect("could not get entity type");
+    assert_eq!(
+        organization
+            .metadata
+            .deprecation
+            .expect("entity type should be deprecated")
+            .successor
+            .as_ref(),
+        Some(person.id())
+    );
+}
+
+#[tokio::test]
+async fn deprecation_warnings() {
+    let person = parse_entity_type(entity_type::PERSON_V1);
+    let text_id =
+        VersionedUrl::from_str("https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1")
+            .expect("could not parse data type ID");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    assert!(
+        api.reference_deprecation_warnings(&person)
+            .await
+            .expect("could not collect warnings")
+            .is_empty()
+    );
+    assert!(
+        api.entity_type_deprecation_warnings(person.id())
+            .await
+            .expect("could not collect warnings")
+            .is_empty()
+    );
+
+    // The data type is only referenced by the property type of the entity type
+    api.deprecate_data_type(&text_id, None)
+        .await
+        .expect("could not deprecate data type");
+    assert_eq!(
+        api.reference_deprecation_warnings(&person)
+            .await
+            .expect("could not collect warnings"),
+        [format!("The data type {text_id} is deprecated")]
+    );
+
+    api.deprecate_entity_type(person.id(), None)
+        .await
+        .expect("could not deprecate entity type");
+    assert_eq!(
+        api.entity_type_deprecation_warnings(person.id())
+            .await
+            .expect("could not collect warnings"),
+        [format!("The entity type {} is deprecated", person.id())]
+    );
+    // The entity type is the destination of its own link
+    assert_eq!(
+        api.reference_deprecation_warnings(&person)
+            .await
+            .expect("could not collect warnings")
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn resolve() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let person_type_id =
+        VersionedUrl::from_str("https://blockprotocol.org/@alice/types/entity-type/person/v/1")
+            .expect("couldn't construct Versioned URL");
+    let resolved = api
+        .get_resolved_entity_type(&person_type_id)
+        .await
+        .expect("could not resolve entity type");
+
+    assert_eq!(resolved.entity_type_id, person_type_id);
+    assert!(resolved.inherits_from.is_empty());
+    let data_type: serde_json::Value =
+        serde_json::from_str(data_type::TEXT_V1).expect("could not parse data type");
+    assert_eq!(
+        resolved.schema["properties"]["https://blockprotocol.org/@alice/types/property-type/name/"]
+            ["oneOf"][0]["$id"],
+        data_type["$id"]
+    );
+    assert_eq!(
+        resolved.schema["links"]
+            ["https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1"]["items"],
+        json!({
+            "oneOf": [{ "$ref": "https://blockprotocol.org/@alice/types/entity-type/person/v/1" }]
+        })
+    );
+
+    api.get_resolved_entity_type(
+        &VersionedUrl::from_str("https://blockprotocol.org/@alice/types/entity-type/person/v/2")
+            .expect("couldn't construct Versioned URL"),
+    )
+    .await
+    .expect_err("the entity type does not exist");
+}
+
+#[tokio::test]
+async fn dependency_graph() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let api = database
+        .seed([data_type::TEXT_V1], [property_type::NAME_V1], [
+            entity_type::LINK_V1,
+            entity_type::link::FRIEND_OF_V1,
+            entity_type::PERSON_V1,
+        ])
+        .await
+        .expect("could not seed database");
+
+    let person_type_id =
+        VersionedUrl::from_str("https://blockprotocol.org/@alice/types/entity-type/person/v/1")
+            .expect("couldn't construct Versioned URL");
+    let graph = OntologyDependencyGraph::read(&api.store, &[person_type_id])
+        .await
+        .expect("could not read dependency graph")
+        .render(OntologyGraphFormat::Dot);
+
+    for line in [
+        r#""https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1" [label="Friend of\nhttps://blockprotocol.org/@alice/types/entity-type/friend-of/v/1\nlink type, owned", shape=hexagon, style=solid];"#,
+        r#""https://blockprotocol.org/@alice/types/entity-type/person/v/1" -> "https://blockprotocol.org/@alice/types/property-type/name/v/1" [label="CONSTRAINS_PROPERTIES_ON"];"#,
+        r#""https://blockprotocol.org/@alice/types/entity-type/person/v/1" -> "https://blockprotocol.org/@alice/types/entity-type/friend-of/v/1" [label="CONSTRAINS_LINKS_ON"];"#,
+        r#""https://blockprotocol.org/@alice/types/property-type/name/v/1" -> "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1" [label="CONSTRAINS_VALUES_ON"];"#,
+    ] {
+        assert!(
+            graph.lines().any(|rendered| rendered.trim() == line),
+            "`{line}` is missing in:\n{graph}"
+        );
+    }
+
+    OntologyDependencyGraph::read(&api.store, &[VersionedUrl::from_str(
+        "https://blockprotocol.org/@alice/types/entity-type/person/v/2",
+    )
+    .expect("couldn't construct Versioned URL")])
+    .await
+    .expect_err("the entity type does not exist");
+}
@@ -0,0 +1,106 @@
+use std::str::FromStr;
+
+use graph_test_data::{data_type, property_type};
+use serde_json::{json, Value};
+use type_system::{repr, url::VersionedUrl};
+
+use crate::DatabaseTestWrapper;
+
+fn id(schema: &Value) -> VersionedUrl {
+    VersionedUrl::from_str(schema["$id"].as_str().expect("`$id` should be a string"))
+        .expect("could not parse `$id`")
+}
+
+/// Returns the schema of `json` with the version of its `$id` replaced by `version`.
+fn with_version(json: &str, version: u32) -> Value {
+    let mut schema: Value = serde_json::from_str(json).expect("could not parse schema");
+    let mut ontology_type_id = id(&schema);
+    ontology_type_id.version = version;
+    schema["$id"] = json!(ontology_type_id.to_string());
+    schema
+}
+
+#[tokio::test]
+async fn import_multiple_versions_of_new_type() {
+    let object_v1 = with_version(data_type::OBJECT_V1, 1);
+    let object_v2 = with_version(data_type::OBJECT_V2, 2);
+    let object_v10 = with_version(data_type::OBJECT_V1, 10);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+
+    let summary = api
+        .import_ontology_types([object_v10.clone(), object_v2.clone(), object_v1.clone()])
+        .await
+        .expect("could not import ontology types");
+    assert_eq!(summary.created, [id(&object_v1)]);
+    assert_eq!(summary.updated, [id(&object_v2), id(&object_v10)]);
+    assert!(summary.unchanged.is_empty());
+
+    for schema in [&object_v1, &object_v2, &object_v10] {
+        api.get_data_type(&id(schema))
+            .await
+            .expect("could not get data type");
+    }
+}
+
+#[tokio::test]
+async fn import_later_version_of_stored_type() {
+    let object_v2 = with_version(data_type::OBJECT_V2, 2);
+    let object_v5 = with_version(data_type::OBJECT_V1, 5);
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([data_type::OBJECT_V1], [], [])
+        .await
+        .expect("could not seed database");
+    api.import_ontology_types([object_v2.clone()])
+        .await
+        .expect("could not import ontology types");
+
+    let summary = api
+        .import_ontology_types([object_v2.clone(), object_v5.clone()])
+        .await
+        .expect("could not import ontology types");
+    assert!(summary.created.is_empty());
+    assert_eq!(summary.updated, [id(&object_v5)]);
+    assert_eq!(summary.unchanged, [id(&object_v2)]);
+
+    let data_type = api
+        .get_data_type(&id(&object_v5))
+        .await
+        .expect("could not get data type");
+    assert_eq!(data_type.schema.id(), &id(&object_v5));
+}
+
+#[tokio::test]
+async fn import_versions_with_references() {
+    let text = with_version(data_type::TEXT_V1, 1);
+    let name_v1 = with_version(property_type::NAME_V1, 1);
+    let mut name_v2 = with_version(property_type::NAME_V1, 2);
+    name_v2["title"] = json!("Full Name");
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed([], [], [])
+        .await
+        .expect("could not seed database");
+
+    let summary = api
+        .import_ontology_types([name_v2.clone(), name_v1.clone(), text.clone()])
+        .await
+        .expect("could not import ontology types");
+    assert_eq!(summary.created, [id(&text), id(&name_v1)]);
+    assert_eq!(summary.updated, [id(&name_v2)]);
+
+    let property_type = api
+        .get_property_type(&id(&name_v2))
+        .await
+        .expect("could not get property type");
+    let schema = serde_json::to_value(repr::PropertyType::from(property_type.schema))
+        .expect("could not serialize property type");
+    assert_eq!(schema["title"], "Full Name");
+}
@@ -6,23 +6,33 @@
     clippy::unwrap_used
 )]
 
+mod account;
+mod change_feed;
 mod data_type;
 mod entity;
 mod entity_type;
+mod import;
 mod links;
 mod property_type;
+mod snapshot;
 
-use std::{borrow::Cow, str::FromStr};
+use std::{borrow::Cow, slice, str::FromStr};
 
 use error_stack::Result;
 use graph::{
     knowledge::EntityQueryPath,
-    ontology::EntityTypeQueryPath,
+    ontology::{
+        self,
+        compatibility::{self, OntologyTypeCompatibility},
+        EntityTypeQueryPath, ImportedOntologyType, OntologyImportSummary, OntologyTypeImport,
+        OntologyTypeUsage, ResolvedEntityType,
+    },
     store::{
+        crud::Read,
         query::{Filter, FilterExpression, Parameter},
         AccountStore, ConflictBehavior, DataTypeStore, DatabaseConnectionInfo, DatabaseType,
-        EntityStore, EntityTypeStore, InsertionError, PostgresStore, PostgresStorePool,
-        PropertyTypeStore, QueryError, StorePool, UpdateError,
+        EntityStore, EntityTypeStore, InsertionError, OwnershipTransfer, OwnershipTransferRecord,
+        PostgresStore, PostgresStorePool, PropertyTypeStore, QueryError, StorePool, UpdateError,
     },
     subgraph::{
         edges::{EdgeDirection, GraphResolveDepths, KnowledgeGraphEdgeKind, SharedEdgeKind},
@@ -44,10 +54,13 @@ use graph_types::{
     },
     ontology::{
         DataTypeWithMetadata, EntityTypeMetadata, EntityTypeWithMetadata, OntologyElementMetadata,
-        OntologyTypeVersion, PartialCustomEntityTypeMetadata, PartialCustomOntologyMetadata,
-        PartialEntityTypeMetadata, PartialOntologyElementMetadata, PropertyTypeWithMetadata,
+        OntologyTypeDeprecation, OntologyTypeVersion, PartialCustomEntityTypeMetadata,
+        PartialCustomOntologyMetadata, PartialEntityTypeMetadata, PartialOntologyElementMetadata,
+        PropertyTypeWithMetadata,
+    },
+    provenance::{
+        DeprecatedById, OwnedById, ProvenanceMetadata, RecordCreatedById, TransferredById,
     },
-    provenance::{OwnedById, ProvenanceMetadata, RecordCreatedById},
 };
 use temporal_versioning::{DecisionTime, LimitedTemporalBound, TemporalBound, Timestamp};
 use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
@@ -56,7 +69,7 @@ use type_system::{repr, url::VersionedUrl, DataType, EntityType, PropertyType};
 use uuid::Uuid;
 
 pub struct DatabaseTestWrapper {
-    _pool: PostgresStorePool<NoTls>,
+    pool: PostgresStorePool<NoTls>,
     connection: <PostgresStorePool<NoTls> as StorePool>::Store<'static>,
 }
 
@@ -95,10 +108,42 @@ impl DatabaseTestWrapper {
             .await
             .expect("could not acquire a database connection");
 
-        Self {
-            _pool: pool,
-            connection,
-        }
+        Self { pool, connection }
+    }
+
+    /// Inserts a new account and a [`unique_entity_type`] owned by it.
+    ///
+    /// Unlike [`seed`], every write is committed in its own transaction, so the records are
+    /// visible to other connections.
+    ///
+    /// [`seed`]: Self::seed
+    pub async fn insert_unique_entity_type(&mut self) -> (AccountId, VersionedUrl) {
+        let account_id = AccountId::new(Uuid::new_v4());
+        self.connection
+            .insert_account_id(account_id)
+            .await
+            .expect("could not insert account id");
+
+        let entity_type = unique_entity_type();
+        let entity_type_id = entity_type.id().clone();
+        self.connection
+            .create_entity_type(entity_type, PartialEntityTypeMetadata {
+                record_id: entity_type_id.clone().into(),
+                custom: PartialCustomEntityTypeMetadata {
+                    common: PartialCustomOntologyMetadata::Owned {
+                        provenance: ProvenanceMetadata {
+                            record_created_by_id: RecordCreatedById::new(account_id),
+                            record_archived_by_id: None,
+                        },
+                        owned_by_id: OwnedById::new(account_id),
+                    },
+                    label_property: None,
+                },
+            })
+            .await
+            .expect("could not create entity type");
+
+        (account_id, entity_type_id)
     }
 
     pub async fn seed<D, P, E>(
@@ -212,6 +257,22 @@ fn generate_decision_time() -> Timestamp<DecisionTime> {
     .expect("could not parse timestamp")
 }
 
+/// Creates an entity type without any properties with a unique URL.
+///
+/// Tests, which commit their changes, use unique URLs to not interfere with other tests.
+fn unique_entity_type() -> EntityType {
+    let entity_type_repr: repr::EntityType = serde_json::from_value(serde_json::json!({
+        "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+        "kind": "entityType",
+        "$id": format!("https://example.com/{}/types/entity-type/thing/v/1", Uuid::new_v4()),
+        "type": "object",
+        "title": "Thing",
+        "properties": {}
+    }))
+    .expect("could not parse entity type representation");
+    EntityType::try_from(entity_type_repr).expect("could not parse entity type")
+}
+
 // TODO: Add get_all_* methods
 impl DatabaseApi<'_> {
     pub async fn create_owned_data_type(
@@ -336,6 +397,14 @@ impl DatabaseApi<'_> {
             .await
     }
 
+    pub async fn check_property_type_update(
+        &self,
+        type_to_update: &VersionedUrl,
+        property_type: &PropertyType,
+    ) -> Result<OntologyTypeCompatibility, QueryError> {
+        compatibility::check_property_type_update(&self.store, type_to_update, property_type).await
+    }
+
     pub async fn create_entity_type(
         &mut self,
         entity_type: EntityType,
@@ -390,6 +459,94 @@ impl DatabaseApi<'_> {
             .await
     }
 
+    pub async fn check_entity_type_update(
+        &self,
+        type_to_update: &VersionedUrl,
+        entity_type: &EntityType,
+    ) -> Result<OntologyTypeCompatibility, QueryError> {
+        compatibility::check_entity_type_update(&self.store, type_to_update, entity_type).await
+    }
+
+    pub async fn deprecate_data_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.store
+            .deprecate_data_type(id, successor, DeprecatedById::new(self.account_id))
+            .await
+    }
+
+    pub async fn deprecate_entity_type(
+        &mut self,
+        id: &VersionedUrl,
+        successor: Option<&VersionedUrl>,
+    ) -> Result<OntologyTypeDeprecation, UpdateError> {
+        self.store
+            .deprecate_entity_type(id, successor, DeprecatedById::new(self.account_id))
+            .await
+    }
+
+    pub async fn get_entity_types_by_filter(
+        &self,
+        filter: &Filter<'_, EntityTypeWithMetadata>,
+    ) -> Result<Vec<EntityTypeWithMetadata>, QueryError> {
+        Read::<EntityTypeWithMetadata>::read_vec(&self.store, filter, None).await
+    }
+
+    pub async fn get_data_type_usage(
+        &self,
+        data_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        self.store.get_data_type_usage(data_type_id).await
+    }
+
+    pub async fn get_property_type_usage(
+        &self,
+        property_type_id: &VersionedUrl,
+    ) -> Result<OntologyTypeUsage, QueryError> {
+        self.store.get_property_type_usage(property_type_id).await
+    }
+
+    pub async fn get_resolved_entity_type(
+        &self,
+        entity_type_id: &VersionedUrl,
+    ) -> Result<ResolvedEntityType, QueryError> {
+        self.store.get_resolved_entity_type(entity_type_id).await
+    }
+
+    pub async fn entity_type_deprecation_warnings(
+        &self,
+        entity_type_id: &VersionedUrl,
+    ) -> Result<Vec<String>, QueryError> {
+        ontology::entity_type_deprecation_warnings(&self.store, entity_type_id).await
+    }
+
+    pub async fn reference_deprecation_warnings(
+        &self,
+        entity_type: &EntityType,
+    ) -> Result<Vec<String>, QueryError> {
+        ontology::reference_deprecation_warnings(&self.store, slice::from_ref(entity_type)).await
+    }
+
+    pub async fn import_ontology_types(
+        &mut self,
+        schemas: impl IntoIterator<Item = serde_json::Value>,
+    ) -> Result<OntologyImportSummary, InsertionError> {
+        let import = OntologyTypeImport::new(schemas.into_iter().map(|schema| {
+            ImportedOntologyType::from_value(schema).expect("could not parse ontology type")
+        }))
+        .expect("could not order ontology types");
+
+        self.store
+            .import_ontology_types(
+                import,
+                OwnedById::new(self.account_id),
+                RecordCreatedById::new(self.account_id),
+            )
+            .await
+    }
+
     pub async fn create_entity(
         &mut self,
         properties: EntityProperties,
@@ -681,6 +838,35 @@ impl DatabaseApi<'_> {
             )
             .await
     }
+
+    pub async fn insert_account(&mut self) -> AccountId {
+        let account_id = AccountId::new(Uuid::new_v4());
+        self.store
+            .insert_account_id(account_id)
+            .await
+            .expect("could not insert account id");
+        account_id
+    }
+
+    pub async fn transfer_ownership(
+        &mut self,
+        to_owned_by_id: OwnedById,
+    ) -> Result<OwnershipTransfer, UpdateError> {
+        self.store
+            .transfer_ownership(
+                OwnedById::new(self.account_id),
+                to_owned_by_id,
+                TransferredById::new(self.account_id),
+            )
+            .await
+    }
+
+    pub async fn get_ownership_transfers(
+        &self,
+        owned_by_id: OwnedById,
+    ) -> Result<Vec<OwnershipTransferRecord>, QueryError> {
+        self.store.get_ownership_transfers(owned_by_id).await
+    }
 }
 
 #[tokio::test]
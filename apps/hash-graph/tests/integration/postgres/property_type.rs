@@ -1,5 +1,14 @@
-use graph_test_data::{data_type, property_type};
-use type_system::{repr, PropertyType};
+use std::str::FromStr;
+
+use graph::ontology::{compatibility::OntologyTypeChangeKind, OwnerUsage};
+use graph_test_data::{data_type, entity, entity_type, property_type};
+use graph_types::{knowledge::entity::EntityProperties, provenance::OwnedById};
+use serde_json::json;
+use type_system::{
+    repr,
+    url::{BaseUrl, VersionedUrl},
+    EntityType, PropertyType,
+};
 
 use crate::DatabaseTestWrapper;
 
@@ -88,3 +97,286 @@ async fn update() {
     assert_eq!(user_id_pt_v1, returned_user_id_pt_v1.schema);
     assert_eq!(user_id_pt_v2, returned_user_id_pt_v2.schema);
 }
+
+fn parse_property_type(schema: serde_json::Value) -> PropertyType {
+    let property_type_repr: repr::PropertyType =
+        serde_json::from_value(schema).expect("could not parse property type representation");
+    PropertyType::try_from(property_type_repr).expect("could not parse property type")
+}
+
+/// Creates the second version of the favorite film property type with the given values.
+fn favorite_film_v2(one_of: serde_json::Value) -> PropertyType {
+    let mut schema: serde_json::Value = serde_json::from_str(property_type::FAVORITE_FILM_V1)
+        .expect("could not parse property type representation");
+    schema["$id"] = json!("https://blockprotocol.org/@alice/types/property-type/favorite-film/v/2");
+    schema["oneOf"] = one_of;
+    parse_property_type(schema)
+}
+
+#[tokio::test]
+async fn check_update_of_nested_property_type() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1, data_type::NUMBER_V1],
+            [
+                property_type::FAVORITE_FILM_V1,
+                property_type::FAVORITE_SONG_V1,
+                property_type::HOBBY_V1,
+                property_type::INTERESTS_V1,
+            ],
+            [],
+        )
+        .await
+        .expect("could not seed database");
+
+    let fan_id = VersionedUrl {
+        base_url: BaseUrl::new("https://example.com/@alice/types/entity-type/fan/".to_owned())
+            .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    let child_fan_id = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://example.com/@alice/types/entity-type/child-fan/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+    for schema in [
+        json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+            "kind": "entityType",
+            "$id": fan_id.to_string(),
+            "type": "object",
+            "title": "Fan",
+            "properties": {
+                "https://blockprotocol.org/@alice/types/property-type/interests/": {
+                    "$ref": "https://blockprotocol.org/@alice/types/property-type/interests/v/1"
+                }
+            }
+        }),
+        json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+            "kind": "entityType",
+            "$id": child_fan_id.to_string(),
+            "type": "object",
+            "title": "Child Fan",
+            "allOf": [{ "$ref": fan_id.to_string() }],
+            "properties": {}
+        }),
+    ] {
+        let entity_type_repr: repr::EntityType =
+            serde_json::from_value(schema).expect("could not parse entity type representation");
+        api.create_entity_type(
+            EntityType::try_from(entity_type_repr).expect("could not parse entity type"),
+        )
+        .await
+        .expect("could not create entity type");
+    }
+
+    // The favorite film is only referenced by the interests of the parent entity type.
+    api.create_entity(
+        serde_json::from_value(json!({
+            "https://blockprotocol.org/@alice/types/property-type/interests/": {
+                "https://blockprotocol.org/@alice/types/property-type/favorite-film/": "Ghostbusters"
+            }
+        }))
+        .expect("could not parse entity properties"),
+        child_fan_id,
+        None,
+    )
+    .await
+    .expect("could not create entity");
+
+    let favorite_film_v1 = VersionedUrl {
+        base_url: BaseUrl::new(
+            "https://blockprotocol.org/@alice/types/property-type/favorite-film/".to_owned(),
+        )
+        .expect("couldn't construct Base URL"),
+        version: 1,
+    };
+
+    let additive = api
+        .check_property_type_update(
+            &favorite_film_v1,
+            &favorite_film_v2(json!([
+                { "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1" },
+                { "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/number/v/1" }
+            ])),
+        )
+        .await
+        .expect("could not check property type update");
+    assert!(!additive.is_breaking(), "{additive:#?}");
+    assert_eq!(additive.changes.len(), 1);
+    assert_eq!(additive.changes[0].kind, OntologyTypeChangeKind::Additive);
+    assert_eq!(additive.invalid_entities, 0);
+
+    let breaking = api
+        .check_property_type_update(
+            &favorite_film_v1,
+            &favorite_film_v2(json!([
+                { "$ref": "https://blockprotocol.org/@blockprotocol/types/data-type/number/v/1" }
+            ])),
+        )
+        .await
+        .expect("could not check property type update");
+    assert!(breaking.is_breaking());
+    assert!(
+        breaking
+            .changes
+            .iter()
+            .any(|change| change.kind == OntologyTypeChangeKind::Breaking),
+        "{breaking:#?}"
+    );
+    assert_eq!(
+        breaking.invalid_entities, 1,
+        "the entity of the child entity type should be invalid"
+    );
+}
+
+#[tokio::test]
+async fn usage() {
+    let contact_et = EntityType::try_from(
+        serde_json::from_value::<repr::EntityType>(json!({
+            "$schema": "https://blockprotocol.org/types/modules/graph/0.3/schema/entity-type",
+            "kind": "entityType",
+            "$id": "https://blockprotocol.org/@alice/types/entity-type/contact/v/1",
+            "type": "object",
+            "title": "Contact",
+            "properties": {
+                "https://blockprotocol.org/@alice/types/property-type/contact-information/": {
+                    "$ref": "https://blockprotocol.org/@alice/types/property-type/contact-information/v/1"
+                }
+            }
+        }))
+        .expect("could not parse entity type representation"),
+    )
+    .expect("could not parse entity type");
+    let url = |url: &str| VersionedUrl::from_str(url).expect("couldn't construct Versioned URL");
+    let text_id = url("https://blockprotocol.org/@blockprotocol/types/data-type/text/v/1");
+    let name_id = url("https://blockprotocol.org/@alice/types/property-type/name/v/1");
+    let email_id = url("https://blockprotocol.org/@alice/types/property-type/email/v/1");
+    let contact_information_id =
+        url("https://blockprotocol.org/@alice/types/property-type/contact-information/v/1");
+    let person_id = url("https://blockprotocol.org/@alice/types/entity-type/person/v/1");
+    let contact_id = contact_et.id().clone();
+
+    let mut database = DatabaseTestWrapper::new().await;
+    let mut api = database
+        .seed(
+            [data_type::TEXT_V1],
+            [
+                property_type::NAME_V1,
+                property_type::EMAIL_V1,
+                property_type::PHONE_NUMBER_V1,
+                property_type::CONTACT_INFORMATION_V1,
+            ],
+            [
+                entity_type::LINK_V1,
+                entity_type::link::FRIEND_OF_V1,
+                entity_type::PERSON_V1,
+            ],
+        )
+        .await
+        .expect("could not seed database");
+    api.create_entity_type(contact_et)
+        .await
+        .expect("could not create entity type");
+
+    api.create_entity(
+        serde_json::from_str(entity::PERSON_ALICE_V1).expect("could not parse entity"),
+        person_id.clone(),
+        None,
+    )
+    .await
+    .expect("could not create entity");
+    // Entities without a value for the property type are not counted
+    api.create_entity(EntityProperties::empty(), person_id.clone(), None)
+        .await
+        .expect("could not create entity");
+    api.create_entity(
+        serde_json::from_value(json!({
+            "https://blockprotocol.org/@alice/types/property-type/contact-information/": {
+                "https://blockprotocol.org/@alice/types/property-type/email/": "alice@example.com"
+            }
+        }))
+        .expect("could not parse entity"),
+        contact_id.clone(),
+        None,
+    )
+    .await
+    .expect("could not create entity");
+    let owned_by_id = OwnedById::new(api.account_id);
+
+    let name_usage = api
+        .get_property_type_usage(&name_id)
+        .await
+        .expect("could not get property type usage");
+    assert!(name_usage.property_types.is_empty());
+    let [person] = name_usage.entity_types.as_slice() else {
+        panic!("expected exactly one entity type, got {name_usage:?}");
+    };
+    assert_eq!(person.entity_type_id, person_id);
+    assert!(person.direct);
+    assert!(!person.inherited);
+    assert_eq!(person.entities, 1);
+    assert_eq!(name_usage.owners, [OwnerUsage {
+        owned_by_id,
+        entities: 1
+    }]);
+
+    let email_usage = api
+        .get_property_type_usage(&email_id)
+        .await
+        .expect("could not get property type usage");
+    assert_eq!(
+        email_usage
+            .property_types
+            .iter()
+            .map(|property_type| (&property_type.property_type_id, property_type.depth))
+            .collect::<Vec<_>>(),
+        [(&contact_information_id, 1)]
+    );
+    let [contact] = email_usage.entity_types.as_slice() else {
+        panic!("expected exactly one entity type, got {email_usage:?}");
+    };
+    assert_eq!(contact.entity_type_id, contact_id);
+    assert!(
+        !contact.direct,
+        "email is nested in the contact information"
+    );
+    assert_eq!(contact.entities, 1);
+
+    let text_usage = api
+        .get_data_type_usage(&text_id)
+        .await
+        .expect("could not get data type usage");
+    assert_eq!(
+        text_usage
+            .property_types
+            .iter()
+            .find(|property_type| property_type.property_type_id == contact_information_id)
+            .map(|property_type| property_type.depth),
+        Some(2)
+    );
+    assert_eq!(
+        text_usage
+            .entity_types
+            .iter()
+            .map(|entity_type| (
+                &entity_type.entity_type_id,
+                entity_type.direct,
+                entity_type.entities
+            ))
+            .collect::<Vec<_>>(),
+        [(&contact_id, false, 1), (&person_id, true, 1)]
+    );
+    assert_eq!(text_usage.owners, [OwnerUsage {
+        owned_by_id,
+        entities: 2
+    }]);
+
+    api.get_data_type_usage(&name_id)
+        .await
+        .expect_err("a property type is not a data type");
+}
@@ -0,0 +1,903 @@
+use std::num::NonZeroUsize;
+
+use error_stack::{Report, Result};
+use futures::{stream, TryStreamExt};
+use graph::{
+    snapshot::{
+        entity::EntitySnapshotRecord, SnapshotConflictBehavior, SnapshotDumpError,
+        SnapshotDumpFilter, SnapshotDumpSettings, SnapshotEntry, SnapshotRestoreError,
+        SnapshotRestoreMode, SnapshotRestoreReport, SnapshotRestoreSettings, SnapshotStore,
+    },
+    store::{AccountStore, AsClient, EntityStore, OwnershipTransferRecord},
+};
+use graph_types::{
+    account::AccountId,
+    knowledge::{
+        entity::{EntityEditionId, EntityMetadata, EntityProperties, EntityRecordId, EntityUuid},
+        link::EntityLinkOrder,
+    },
+    provenance::{OwnedById, RecordCreatedById, TransferredById},
+};
+use temporal_versioning::{ClosedTemporalBound, OpenTemporalBound, Timestamp, TransactionTime};
+use type_system::url::VersionedUrl;
+use uuid::Uuid;
+
+use crate::DatabaseTestWrapper;
+
+async fn create_entity(
+    database: &mut DatabaseTestWrapper,
+    account_id: AccountId,
+    entity_type_id: &VersionedUrl,
+) -> EntityMetadata {
+    database
+        .connection
+        .create_entity(
+            OwnedById::new(account_id),
+            None,
+            None,
+            RecordCreatedById::new(account_id),
+            false,
+            entity_type_id.clone(),
+            EntityProperties::empty(),
+            None,
+        )
+        .await
+        .expect("could not create entity")
+}
+
+fn written_at(metadata: &EntityMetadata) -> Timestamp<TransactionTime> {
+    let ClosedTemporalBound::Inclusive(transaction_time) =
+        metadata.temporal_versioning().transaction_time.start();
+    *transaction_time
+}
+
+async fn dump(
+    database: &DatabaseTestWrapper,
+    settings: SnapshotDumpSettings,
+    owners: &[Uuid],
+) -> Vec<SnapshotEntry> {
+    SnapshotStore::new(&database.pool)
+        .dump_snapshot(settings, SnapshotDumpFilter::default().owned_by(owners))
+        .try_collect()
+        .await
+        .expect("could not dump snapshot")
+}
+
+async fn update_entity(
+    database: &mut DatabaseTestWrapper,
+    account_id: AccountId,
+    entity: &EntityMetadata,
+) -> EntityMetadata {
+    database
+        .connection
+        .update_entity(
+            entity.record_id().entity_id,
+            None,
+            RecordCreatedById::new(account_id),
+            false,
+            entity.entity_type_id().clone(),
+            EntityProperties::empty(),
+            EntityLinkOrder {
+                left_to_right: None,
+                right_to_left: None,
+            },
+        )
+        .await
+        .expect("could not update entity")
+}
+
+async fn restore(
+    database: &DatabaseTestWrapper,
+    entries: Vec<SnapshotEntry>,
+    settings: SnapshotRestoreSettings,
+) -> Result<SnapshotRestoreReport, SnapshotRestoreError> {
+    SnapshotStore::new(&database.pool)
+        .restore_snapshot(
+            stream::iter(entries.into_iter().map(Ok::<_, Report<SnapshotDumpError>>)),
+            settings,
+        )
+        .await
+}
+
+/// Returns the records of the entities in the snapshot, sorted by their edition and start of the
+/// transaction time.
+fn entity_records(entries: &[SnapshotEntry]) -> Vec<EntitySnapshotRecord> {
+    let mut entities = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            SnapshotEntry::Entity(entity) => Some(entity.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    entities.sort_by_key(|entity| {
+        let ClosedTemporalBound::Inclusive(start) = entity
+            .metadata
+            .temporal_versioning
+            .as_ref()
+            .expect("temporal versioning is missing")
+            .transaction_time
+            .start();
+        (entity.metadata.record_id.edition_id, *start)
+    });
+    entities
+}
+
+/// Returns if the store contains the edition or the entity type of the edition.
+async fn contains_edition(database: &DatabaseTestWrapper, record_id: EntityRecordId) -> bool {
+    database
+        .connection
+        .as_client()
+        .query_one(
+            r"
+                SELECT
+                    EXISTS (SELECT 1 FROM entity_editions WHERE entity_edition_id = $1)
+                    OR EXISTS (SELECT 1 FROM entity_is_of_type WHERE entity_edition_id = $1);
+            ",
+            &[&record_id.edition_id],
+        )
+        .await
+        .expect("could not read entity edition")
+        .get(0)
+}
+
+#[tokio::test]
+async fn incremental_dump_contains_changed_editions() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    // The snapshot is read over separate connections, so every write is committed in its own
+    // transaction.
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let unchanged = create_entity(&mut database, account_id, &entity_type_id).await;
+    let superseded = create_entity(&mut database, account_id, &entity_type_id).await;
+    let created = create_entity(&mut database, account_id, &entity_type_id).await;
+    let since = written_at(&created);
+
+    let updated = update_entity(&mut database, account_id, &superseded).await;
+
+    let entries = dump(
+        &database,
+        SnapshotDumpSettings {
+            since: Some(since),
+            parallelism: NonZeroUsize::new(1),
+            ..SnapshotDumpSettings::default()
+        },
+        &[account_id.as_uuid()],
+    )
+    .await;
+
+    assert!(
+        !entries
+            .iter()
+            .any(|entry| matches!(entry, SnapshotEntry::EntityType(_))),
+        "the entity type was created before the incremental snapshot"
+    );
+
+    let entities = entries
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SnapshotEntry::Entity(entity) => Some(entity),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let transaction_times = |record_id| {
+        entities
+            .iter()
+            .filter(move |entity: &&EntitySnapshotRecord| entity.metadata.record_id == record_id)
+            .map(|entity| {
+                entity
+                    .metadata
+                    .temporal_versioning
+                    .as_ref()
+                    .expect("temporal versioning is missing")
+                    .transaction_time
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert!(
+        transaction_times(unchanged.record_id()).is_empty(),
+        "the unchanged entity was created before the incremental snapshot"
+    );
+    assert!(!transaction_times(created.record_id()).is_empty());
+    assert!(!transaction_times(updated.record_id()).is_empty());
+    assert!(
+        transaction_times(superseded.record_id())
+            .iter()
+            .any(|transaction_time| transaction_time.end()
+                == &OpenTemporalBound::Exclusive(written_at(&updated))),
+        "the superseded edition was closed after the incremental snapshot started"
+    );
+    for entity in &entities {
+        let transaction_time = entity
+            .metadata
+            .temporal_versioning
+            .as_ref()
+            .expect("temporal versioning is missing")
+            .transaction_time;
+        let ClosedTemporalBound::Inclusive(start) = transaction_time.start();
+        let closed_since = matches!(
+            transaction_time.end(),
+            OpenTemporalBound::Exclusive(end) if *end >= since
+        );
+        assert!(
+            *start >= since || closed_since,
+            "{} was neither created nor closed since the incremental snapshot started",
+            entity.metadata.record_id.entity_id
+        );
+    }
+}
+
+#[tokio::test]
+async fn full_dump_contains_all_editions() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let first = create_entity(&mut database, account_id, &entity_type_id).await;
+    let second = create_entity(&mut database, account_id, &entity_type_id).await;
+
+    let entries = dump(
+        &database,
+        SnapshotDumpSettings {
+            parallelism: NonZeroUsize::new(1),
+            ..SnapshotDumpSettings::default()
+        },
+        &[account_id.as_uuid()],
+    )
+    .await;
+
+    assert_eq!(
+        entries
+            .iter()
+            .filter(|entry| matches!(entry, SnapshotEntry::EntityType(_)))
+            .count(),
+        1
+    );
+    let mut record_ids = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            SnapshotEntry::Entity(entity) => Some(entity.metadata.record_id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    record_ids.sort_by_key(|record_id| record_id.edition_id);
+    let mut expected = vec![first.record_id(), second.record_id()];
+    expected.sort_by_key(|record_id| record_id.edition_id);
+    assert_eq!(record_ids, expected);
+}
+
+/// Dumps a snapshot containing a single entity, which is updated in the store afterwards.
+///
+/// Returns the snapshot, the created edition contained in the snapshot and the edition, which
+/// superseded it in the store.
+async fn conflicting_snapshot(
+    database: &mut DatabaseTestWrapper,
+) -> (
+    Vec<SnapshotEntry>,
+    AccountId,
+    EntityMetadata,
+    EntityMetadata,
+) {
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let created = create_entity(database, account_id, &entity_type_id).await;
+
+    let entries = dump(
+        database,
+        SnapshotDumpSettings {
+            parallelism: NonZeroUsize::new(1),
+            ..SnapshotDumpSettings::default()
+        },
+        &[account_id.as_uuid()],
+    )
+    .await;
+
+    let updated = update_entity(database, account_id, &created).await;
+
+    (entries, account_id, created, updated)
+}
+
+fn merge(on_conflict: SnapshotConflictBehavior) -> SnapshotRestoreSettings {
+    SnapshotRestoreSettings {
+        mode: SnapshotRestoreMode::Merge(on_conflict),
+        ..SnapshotRestoreSettings::default()
+    }
+}
+
+#[tokio::test]
+async fn merge_identical_snapshot() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    create_entity(&mut database, account_id, &entity_type_id).await;
+
+    let settings = SnapshotDumpSettings {
+        parallelism: NonZeroUsize::new(1),
+        ..SnapshotDumpSettings::default()
+    };
+    let entries = dump(&database, settings, &[account_id.as_uuid()]).await;
+    let report = restore(
+        &database,
+        entries.clone(),
+        merge(SnapshotConflictBehavior::Fail),
+    )
+    .await
+    .expect("could not merge snapshot");
+
+    assert_eq!(report.entity_editions.skipped, 1);
+    assert_eq!(report.entity_editions.inserted, 0);
+    assert_eq!(report.conflicting(), 0);
+    assert_eq!(
+        entity_records(&dump(&database, settings, &[account_id.as_uuid()]).await),
+        entity_records(&entries)
+    );
+}
+
+#[tokio::test]
+async fn merge_conflicting_snapshot_fails() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (entries, account_id, ..) = conflicting_snapshot(&mut database).await;
+
+    let settings = SnapshotDumpSettings {
+        parallelism: NonZeroUsize::new(1),
+        ..SnapshotDumpSettings::default()
+    };
+    let before = dump(&database, settings, &[account_id.as_uuid()]).await;
+
+    let error = restore(&database, entries, merge(SnapshotConflictBehavior::Fail))
+        .await
+        .expect_err("merging a conflicting snapshot should fail");
+    assert!(matches!(
+        error.current_context(),
+        SnapshotRestoreError::Conflict
+    ));
+
+    assert_eq!(
+        entity_records(&dump(&database, settings, &[account_id.as_uuid()]).await),
+        entity_records(&before)
+    );
+}
+
+#[tokio::test]
+async fn merge_conflicting_snapshot_skips() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (entries, account_id, created, updated) = conflicting_snapshot(&mut database).await;
+
+    let settings = SnapshotDumpSettings {
+        parallelism: NonZeroUsize::new(1),
+        ..SnapshotDumpSettings::default()
+    };
+    let before = dump(&database, settings, &[account_id.as_uuid()]).await;
+
+    let report = restore(&database, entries, merge(SnapshotConflictBehavior::Skip))
+        .await
+        .expect("could not merge snapshot");
+    assert_eq!(report.entity_editions.conflicting, 1);
+    assert_eq!(report.entity_editions.inserted, 0);
+
+    assert_eq!(
+        entity_records(&dump(&database, settings, &[account_id.as_uuid()]).await),
+        entity_records(&before)
+    );
+    assert!(contains_edition(&database, created.record_id()).await);
+    assert!(contains_edition(&database, updated.record_id()).await);
+}
+
+#[tokio::test]
+async fn merge_conflicting_snapshot_overwrites() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (entries, account_id, created, updated) = conflicting_snapshot(&mut database).await;
+
+    let report = restore(
+        &database,
+        entries.clone(),
+        merge(SnapshotConflictBehavior::Overwrite),
+    )
+    .await
+    .expect("could not merge snapshot");
+    assert_eq!(report.entity_editions.conflicting, 1);
+
+    // The update is replaced by the edition in the snapshot, so the superseding edition is removed
+    // from the store.
+    let after = dump(
+        &database,
+        SnapshotDumpSettings {
+            parallelism: NonZeroUsize::new(1),
+            ..SnapshotDumpSettings::default()
+        },
+        &[account_id.as_uuid()],
+    )
+    .await;
+    assert_eq!(entity_records(&after), entity_records(&entries));
+    assert!(contains_edition(&database, created.record_id()).await);
+    assert!(!contains_edition(&database, updated.record_id()).await);
+}
+
+/// Moves the entities of the snapshot to new entity UUIDs and editions, so the snapshot can be
+/// merged into the store it was dumped from.
+///
+/// The manifest is removed as it does not match the changed entities anymore.
+fn with_new_entities(entries: Vec<SnapshotEntry>) -> Vec<SnapshotEntry> {
+    entries
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SnapshotEntry::Snapshot(mut metadata) => {
+                metadata.custom.has_manifest = false;
+                Some(SnapshotEntry::Snapshot(metadata))
+            }
+            SnapshotEntry::Manifest(_) => None,
+            SnapshotEntry::Entity(mut entity) => {
+                entity.metadata.record_id.entity_id.entity_uuid = EntityUuid::new(Uuid::new_v4());
+                entity.metadata.record_id.edition_id = EntityEditionId::new(Uuid::new_v4());
+                Some(SnapshotEntry::Entity(entity))
+            }
+            entry => Some(entry),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn resume_interrupted_restore() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    for _ in 0..3 {
+        create_entity(&mut database, account_id, &entity_type_id).await;
+    }
+
+    let entries = with_new_entities(
+        dump(
+            &database,
+            SnapshotDumpSettings {
+                parallelism: NonZeroUsize::new(1),
+                ..SnapshotDumpSettings::default()
+            },
+            &[account_id.as_uuid()],
+        )
+        .await,
+    );
+    let record_ids = entity_records(&entries)
+        .into_iter()
+        .map(|entity| entity.metadata.record_id)
+        .collect::<Vec<_>>();
+    assert_eq!(record_ids.len(), 3);
+
+    let settings = SnapshotRestoreSettings {
+        checkpoint_interval: NonZeroUsize::new(1),
+        ..merge(SnapshotConflictBehavior::Fail)
+    };
+
+    // The snapshot stream fails in the middle of the snapshot after some checkpoints were
+    // committed.
+    let store = SnapshotStore::new(&database.pool);
+    let interrupt = |entries: Vec<SnapshotEntry>| {
+        let interrupted_at = entries.len() / 2;
+        store.restore_snapshot(
+            stream::iter(
+                entries
+                    .into_iter()
+                    .take(interrupted_at)
+                    .map(Ok)
+                    .chain([Err(Report::new(SnapshotDumpError::Read))]),
+            ),
+            settings,
+        )
+    };
+    interrupt(entries.clone())
+        .await
+        .expect_err("the interrupted restore should fail");
+    for record_id in &record_ids {
+        assert!(
+            !contains_edition(&database, *record_id).await,
+            "the interrupted restore wrote into the store"
+        );
+    }
+
+    let error = restore(&database, entries.clone(), settings)
+        .await
+        .expect_err("restoring without resuming the interrupted restore should fail");
+    assert!(matches!(
+        error.current_context(),
+        SnapshotRestoreError::InterruptedRestore
+    ));
+
+    database
+        .connection
+        .as_client()
+        .execute("SELECT pg_advisory_lock(hashtext('snapshot_restore'));", &[
+        ])
+        .await
+        .expect("could not lock staging tables");
+    let error = restore(&database, entries.clone(), SnapshotRestoreSettings {
+        resume: true,
+        ..settings
+    })
+    .await
+    .expect_err("restoring while another restore is in progress should fail");
+    assert!(matches!(
+        error.current_context(),
+        SnapshotRestoreError::Locked
+    ));
+    database
+        .connection
+        .as_client()
+        .execute(
+            "SELECT pg_advisory_unlock(hashtext('snapshot_restore'));",
+            &[],
+        )
+        .await
+        .expect("could not unlock staging tables");
+
+    let report = restore(&database, entries.clone(), SnapshotRestoreSettings {
+        resume: true,
+        ..settings
+    })
+    .await
+    .expect("could not resume restore");
+    assert_eq!(report.entity_editions.inserted, 3);
+    for record_id in record_ids {
+        assert!(contains_edition(&database, record_id).await);
+    }
+
+    // A discarded checkpoint restores the snapshot from the start
+    let entries = with_new_entities(entries);
+    interrupt(entries.clone())
+        .await
+        .expect_err("the interrupted restore should fail");
+    let report = restore(&database, entries, SnapshotRestoreSettings {
+        discard_checkpoint: true,
+        ..settings
+    })
+    .await
+    .expect("could not restore after discarding the checkpoint");
+    assert_eq!(report.entity_editions.inserted, 3);
+
+    let staging_exists: bool = database
+        .connection
+        .as_client()
+        .query_one(
+            "SELECT to_regnamespace('snapshot_restore') IS NOT NULL;",
+            &[],
+        )
+        .await
+        .expect("could not read staging schema")
+        .get(0);
+    assert!(!staging_exists, "the staging tables were not dropped");
+}
+
+#[tokio::test]
+async fn ownership_transfers_are_dumped_and_merged() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (from_account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let to_account_id = AccountId::new(Uuid::new_v4());
+    database
+        .connection
+        .insert_account_id(to_account_id)
+        .await
+        .expect("could not insert account id");
+    let created = create_entity(&mut database, from_account_id, &entity_type_id).await;
+    database
+        .connection
+        .transfer_ownership(
+            OwnedById::new(from_account_id),
+            OwnedById::new(to_account_id),
+            TransferredById::new(from_account_id),
+        )
+        .await
+        .expect("could not transfer ownership");
+
+    let settings = SnapshotDumpSettings {
+        since: Some(written_at(&created)),
+        parallelism: NonZeroUsize::new(1),
+        ..SnapshotDumpSettings::default()
+    };
+    assert!(
+        !dump(&database, settings, &[to_account_id.as_uuid()])
+            .await
+            .iter()
+            .any(|entry| matches!(entry, SnapshotEntry::OwnershipTransfer(_))),
+        "transfers should only be dumped if all records are selected"
+    );
+
+    let entries: Vec<SnapshotEntry> = SnapshotStore::new(&database.pool)
+        .dump_snapshot(settings, SnapshotDumpFilter::default())
+        .try_collect()
+        .await
+        .expect("could not dump snapshot");
+    let transfers = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            SnapshotEntry::OwnershipTransfer(transfer)
+                if transfer.from_owned_by_id == OwnedById::new(from_account_id) =>
+            {
+                Some(transfer.clone())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let [transfer] = transfers.as_slice() else {
+        panic!("expected exactly one ownership transfer, got {transfers:?}");
+    };
+    assert_eq!(transfer.ontology_type_ids, [entity_type_id]);
+    assert_eq!(transfer.entity_uuids, [created
+        .record_id()
+        .entity_id
+        .entity_uuid]);
+
+    // The transfer is restored into the store it was dumped from, so it is skipped, while the same
+    // transfer with a new ID is inserted.
+    let Some(SnapshotEntry::Snapshot(mut metadata)) = entries.into_iter().next() else {
+        panic!("the snapshot does not start with its metadata");
+    };
+    metadata.custom.since = None;
+    metadata.custom.has_manifest = false;
+    let copied = OwnershipTransferRecord {
+        transfer_id: Uuid::new_v4(),
+        ..transfer.clone()
+    };
+    let report = restore(
+        &database,
+        vec![
+            SnapshotEntry::Snapshot(metadata),
+            SnapshotEntry::OwnershipTransfer(transfer.clone()),
+            SnapshotEntry::OwnershipTransfer(copied.clone()),
+        ],
+        merge(SnapshotConflictBehavior::Fail),
+    )
+    .await
+    .expect("could not merge snapshot");
+    assert_eq!(report.ownership_transfers.skipped, 1);
+    assert_eq!(report.ownership_transfers.inserted, 1);
+
+    let stored = database
+        .connection
+        .get_ownership_transfers(OwnedById::new(from_account_id))
+        .await
+        .expect("could not get ownership transfers");
+    assert!(stored.contains(transfer));
+    assert!(stored.contains(&copied));
+}
+
+#[tokio::test]
+async fn pinned_dump_contains_visible_editions() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    let created = create_entity(&mut database, account_id, &entity_type_id).await;
+    let updated = update_entity(&mut database, account_id, &created).await;
+
+    // Pinned before the update, the superseded edition is still current in transaction time.
+    let entities = entity_records(
+        &dump(
+            &database,
+            SnapshotDumpSettings {
+                transaction_time: Some(written_at(&created)),
+                parallelism: NonZeroUsize::new(1),
+                ..SnapshotDumpSettings::default()
+            },
+            &[account_id.as_uuid()],
+        )
+        .await,
+    );
+    let [entity] = entities.as_slice() else {
+        panic!("expected exactly one entity, got {entities:?}");
+    };
+    assert_eq!(entity.metadata.record_id, created.record_id());
+    assert_eq!(
+        entity
+            .metadata
+            .temporal_versioning
+            .as_ref()
+            .expect("temporal versioning is missing")
+            .transaction_time
+            .end(),
+        &OpenTemporalBound::Unbounded
+    );
+
+    // Pinned at the update, the superseded edition is no longer valid in decision time.
+    let ClosedTemporalBound::Inclusive(decision_time) =
+        updated.temporal_versioning().decision_time.start();
+    let entities = entity_records(
+        &dump(
+            &database,
+            SnapshotDumpSettings {
+                transaction_time: Some(written_at(&updated)),
+                decision_time: Some(*decision_time),
+                parallelism: NonZeroUsize::new(1),
+                ..SnapshotDumpSettings::default()
+            },
+            &[account_id.as_uuid()],
+        )
+        .await,
+    );
+    assert_eq!(
+        entities
+            .iter()
+            .map(|entity| entity.metadata.record_id)
+            .collect::<Vec<_>>(),
+        [updated.record_id()]
+    );
+}
+
+#[tokio::test]
+async fn parallel_dump_equals_sequential_dump() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    for _ in 0..8 {
+        let created = create_entity(&mut database, account_id, &entity_type_id).await;
+        update_entity(&mut database, account_id, &created).await;
+    }
+
+    let [sequential, parallel] = [1, 4].map(|parallelism| SnapshotDumpSettings {
+        parallelism: NonZeroUsize::new(parallelism),
+        ..SnapshotDumpSettings::default()
+    });
+    let sequential = dump(&database, sequential, &[account_id.as_uuid()]).await;
+    let parallel = dump(&database, parallel, &[account_id.as_uuid()]).await;
+
+    let mut edition_ids = entity_records(&parallel)
+        .iter()
+        .map(|entity| entity.metadata.record_id.edition_id)
+        .collect::<Vec<_>>();
+    edition_ids.dedup();
+    assert_eq!(edition_ids.len(), 16);
+    assert_eq!(entity_records(&parallel), entity_records(&sequential));
+    assert_eq!(
+        parallel
+            .iter()
+            .filter(|entry| matches!(entry, SnapshotEntry::EntityType(_)))
+            .collect::<Vec<_>>(),
+        sequential
+            .iter()
+            .filter(|entry| matches!(entry, SnapshotEntry::EntityType(_)))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn dump_of_entity_types_selects_their_entities() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    let (account_id, selected_type_id) = database.insert_unique_entity_type().await;
+    let (_, other_type_id) = database.insert_unique_entity_type().await;
+    let selected = create_entity(&mut database, account_id, &selected_type_id).await;
+    create_entity(&mut database, account_id, &other_type_id).await;
+
+    let entries: Vec<SnapshotEntry> = SnapshotStore::new(&database.pool)
+        .dump_snapshot(
+            SnapshotDumpSettings {
+                parallelism: NonZeroUsize::new(1),
+                ..SnapshotDumpSettings::default()
+            },
+            SnapshotDumpFilter::default().of_entity_types(&[selected_type_id.clone()]),
+        )
+        .try_collect()
+        .await
+        .expect("could not dump snapshot");
+
+    assert!(!entries.iter().any(|entry| matches!(
+        entry,
+        SnapshotEntry::DataType(_) | SnapshotEntry::PropertyType(_)
+    )));
+    assert_eq!(
+        entries
+            .iter()
+            .filter_map(|entry| match entry {
+                SnapshotEntry::EntityType(entity_type) =>
+                    Some(VersionedUrl::from(entity_type.metadata.record_id.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        [selected_type_id]
+    );
+    assert_eq!(
+        entity_records(&entries)
+            .iter()
+            .map(|entity| entity.metadata.record_id)
+            .collect::<Vec<_>>(),
+        [selected.record_id()]
+    );
+}
+
+#[tokio::test]
+async fn dump_includes_dependencies_of_selected_records() {
+    let mut database = DatabaseTestWrapper::new().await;
+
+    // The entity type is owned by another account than the entity, so it's only selected as a
+    // dependency of the entity.
+    let (_, entity_type_id) = database.insert_unique_entity_type().await;
+    let account_id = AccountId::new(Uuid::new_v4());
+    database
+        .connection
+        .insert_account_id(account_id)
+        .await
+        .expect("could not insert account id");
+    let created = create_entity(&mut database, account_id, &entity_type_id).await;
+
+    let entity_type_ids = |entries: &[SnapshotEntry]| {
+        entries
+            .iter()
+            .filter_map(|entry| match entry {
+                SnapshotEntry::EntityType(entity_type) => {
+                    Some(VersionedUrl::from(entity_type.metadata.record_id.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let settings = SnapshotDumpSettings {
+        parallelism: NonZeroUsize::new(1),
+        ..SnapshotDumpSettings::default()
+    };
+    let owners = [account_id.as_uuid()];
+
+    let entries = dump(&database, settings, &owners).await;
+    assert!(entity_type_ids(&entries).is_empty());
+
+    let entries: Vec<SnapshotEntry> = SnapshotStore::new(&database.pool)
+        .dump_snapshot(settings, SnapshotDumpFilter {
+            include_dependencies: true,
+            ..SnapshotDumpFilter::default().owned_by(&owners)
+        })
+        .try_collect()
+        .await
+        .expect("could not dump snapshot");
+    assert_eq!(entity_type_ids(&entries), [entity_type_id]);
+    assert_eq!(
+        entity_records(&entries)
+            .iter()
+            .map(|entity| entity.metadata.record_id)
+            .collect::<Vec<_>>(),
+        [created.record_id()]
+    );
+}
+
+#[tokio::test]
+async fn restore_verifies_manifest() {
+    let mut database = DatabaseTestWrapper::new().await;
+    let (account_id, entity_type_id) = database.insert_unique_entity_type().await;
+    create_entity(&mut database, account_id, &entity_type_id).await;
+
+    let entries = dump(
+        &database,
+        SnapshotDumpSettings {
+            parallelism: NonZeroUsize::new(1),
+            ..SnapshotDumpSettings::default()
+        },
+        &[account_id.as_uuid()],
+    )
+    .await;
+    let Some(SnapshotEntry::Manifest(manifest)) = entries.last() else {
+        panic!("the snapshot does not end with a manifest");
+    };
+    assert_eq!(manifest.records.entity_types, 1);
+    assert_eq!(manifest.records.entities, 1);
+
+    let without_manifest = entries[..entries.len() - 1].to_vec();
+    let without_entity = entries
+        .iter()
+        .filter(|entry| !matches!(entry, SnapshotEntry::Entity(_)))
+        .cloned()
+        .collect();
+    let mut corrupted = entries.clone();
+    if let Some(SnapshotEntry::Manifest(manifest)) = corrupted.last_mut() {
+        manifest.checksum = "00".to_owned();
+    }
+
+    for (entries, expected) in [
+        (without_manifest, SnapshotRestoreError::Truncated),
+        (without_entity, SnapshotRestoreError::Truncated),
+        (corrupted, SnapshotRestoreError::Corrupted),
+    ] {
+        let report = restore(&database, entries, merge(SnapshotConflictBehavior::Fail))
+            .await
+            .expect_err("the snapshot does not match its manifest");
+        assert_eq!(
+            report.current_context().to_string(),
+            expected.to_string(),
+            "{report:?}"
+        );
+    }
+
+    restore(&database, entries, merge(SnapshotConflictBehavior::Fail))
+        .await
+        .expect("could not restore snapshot matching its manifest");
+}